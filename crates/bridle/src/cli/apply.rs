@@ -0,0 +1,61 @@
+//! CLI apply command implementation.
+
+use std::path::Path;
+
+use color_eyre::eyre::{Result, eyre};
+
+use crate::cli::output::{ResolvedFormat, output_list};
+use crate::cli::plan;
+use crate::config::BridleConfig;
+use crate::install::spec::{ProfileApply, ProfileSpec, apply_spec};
+use crate::install::updater::SourceCache;
+
+pub fn run(spec_path: &Path, dry_run: bool, format: ResolvedFormat) -> Result<()> {
+    let spec = ProfileSpec::load(spec_path)?;
+    let profiles_dir = BridleConfig::profiles_dir()?;
+
+    let Some(results) = plan::run(dry_run, format, || -> Result<Vec<ProfileApply>> {
+        let results = apply_spec(&spec, &profiles_dir, &mut SourceCache::new())?;
+        let targets: Vec<_> = results.iter().map(|r| r.target.clone()).collect();
+        crate::cli::sync::refresh_lockfile(&targets);
+        Ok(results)
+    })?
+    else {
+        return Ok(());
+    };
+
+    let failed = results.iter().any(|r| !r.errors.is_empty());
+
+    output_list(&results, format, |results| {
+        for result in results {
+            print_profile_apply(result);
+        }
+    });
+
+    if failed {
+        return Err(eyre!("Some components failed to apply"));
+    }
+    Ok(())
+}
+
+fn print_profile_apply(result: &ProfileApply) {
+    eprintln!("{}/{}:", result.target.harness, result.target.profile);
+    for item in &result.installed {
+        eprintln!("  + {}", item);
+    }
+    for item in &result.skipped {
+        eprintln!("  ~ Skipped {}", item);
+    }
+    for error in &result.errors {
+        eprintln!("  ! Error applying {}: {}", error.skill, error.error);
+    }
+    for component in &result.issues {
+        eprintln!("  ? {} {}:", component.component_type, component.name);
+        for issue in &component.issues {
+            match issue.code {
+                Some(code) => eprintln!("      [{}] {}", code, issue.message),
+                None => eprintln!("      {}", issue.message),
+            }
+        }
+    }
+}
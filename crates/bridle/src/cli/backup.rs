@@ -0,0 +1,132 @@
+//! CLI backup command implementation.
+
+use color_eyre::eyre::{Result, eyre};
+
+use crate::cli::output::{ResolvedFormat, output, output_list};
+use crate::cli::plan;
+use crate::cli::profile::resolve_harness;
+use crate::config::{BackupInfo, BackupRetention, BridleConfig, ProfileManager};
+
+fn get_manager() -> Result<ProfileManager> {
+    Ok(ProfileManager::new(BridleConfig::profiles_dir()?))
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+fn print_backup_line(backup: &BackupInfo) {
+    let created = backup
+        .created
+        .map(|c| c.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_else(|| "-".to_string());
+    println!(
+        "  {:<24} {:<19} {:<10} {:>4} files  {}",
+        backup.id,
+        created,
+        backup.kind.as_str(),
+        backup.files,
+        format_size(backup.size)
+    );
+}
+
+pub fn list(harness_name: &str, format: ResolvedFormat) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let backups = get_manager()?.list_backups(&harness)?;
+
+    output_list(&backups, format, |backups| {
+        if backups.is_empty() {
+            println!("No backups for {}", harness_name);
+            return;
+        }
+        println!("Backups for {}:", harness_name);
+        for backup in backups {
+            print_backup_line(backup);
+        }
+    });
+    Ok(())
+}
+
+pub fn show(harness_name: &str, id: &str, format: ResolvedFormat) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let details = get_manager()?.show_backup(&harness, id)?;
+
+    output(&details, format, |d| {
+        println!("Backup {} ({})", d.info.id, d.info.harness_id);
+        println!("Path: {}", d.info.path.display());
+        if let Some(created) = d.info.created {
+            println!("Created: {}", created.format("%Y-%m-%d %H:%M:%S"));
+        }
+        println!("{} files, {}", d.info.files, format_size(d.info.size));
+        for (path, size) in &d.entries {
+            println!("  {:<50} {}", path, format_size(*size));
+        }
+    });
+    Ok(())
+}
+
+pub fn restore(harness_name: &str, id: &str, dry_run: bool, format: ResolvedFormat) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let manager = get_manager()?;
+    let Some(restore) = plan::run(dry_run, format, || manager.restore_backup(&harness, id))? else {
+        return Ok(());
+    };
+
+    output(&restore, format, |r| {
+        println!("Restored backup {} for {}", r.restored, r.harness_id);
+        if let Some(undo) = &r.undo {
+            println!(
+                "Previous config saved as {} (undo with: bridle backup restore {} {})",
+                undo, harness_name, undo
+            );
+        }
+    });
+    Ok(())
+}
+
+pub fn prune(
+    harness_name: &str,
+    overrides: BackupRetention,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let configured = BridleConfig::load().unwrap_or_default().backups;
+    let retention = BackupRetention {
+        keep: overrides.keep.or(configured.keep),
+        max_age_days: overrides.max_age_days.or(configured.max_age_days),
+        max_size_mb: overrides.max_size_mb.or(configured.max_size_mb),
+    };
+    if retention.is_unlimited() {
+        return Err(eyre!(
+            "No retention policy: pass --keep, --max-age-days or --max-size-mb, \
+             or set backup_keep, backup_max_age_days or backup_max_size_mb with `bridle config set`"
+        ));
+    }
+
+    let pruned = get_manager()?.prune_backups(&harness, &retention, dry_run)?;
+
+    output_list(&pruned, format, |pruned| {
+        if pruned.is_empty() {
+            println!("Nothing to prune for {}", harness_name);
+            return;
+        }
+        let verb = if dry_run { "Would remove" } else { "Removed" };
+        println!("{} {} backup(s) for {}:", verb, pruned.len(), harness_name);
+        for backup in pruned {
+            print_backup_line(backup);
+        }
+    });
+    Ok(())
+}
@@ -1,11 +1,19 @@
 //! CLI subcommand definitions.
 
-use clap::Subcommand;
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+
+use crate::lock::LockMode;
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Show status of all harnesses.
-    Status,
+    Status {
+        /// Also show project-scope resources (defaults to the enclosing git repository).
+        #[arg(long, value_name = "PATH", num_args = 0..=1)]
+        project: Option<Option<PathBuf>>,
+    },
 
     /// Initialize bridle configuration.
     Init,
@@ -14,6 +22,44 @@ pub enum Commands {
     #[command(subcommand)]
     Profile(ProfileCommands),
 
+    /// Switch every harness in a workspace to its profile in one step.
+    Use {
+        /// Workspace name.
+        workspace: String,
+    },
+
+    /// Define, list and remove workspaces (named sets of profiles across harnesses).
+    #[command(subcommand)]
+    Workspace(WorkspaceCommands),
+
+    /// Run a harness with a profile without switching to it globally:
+    /// `exec <harness> <profile> -- [args]`.
+    Exec {
+        /// Harness to run.
+        harness: String,
+        /// Profile to run it with.
+        profile: String,
+        /// Arguments passed to the harness, after `--`.
+        #[arg(last = true)]
+        args: Vec<String>,
+    },
+
+    /// Print a shell snippet that switches profiles from `.bridle` files on `cd`.
+    Hook {
+        /// Shell to emit the snippet for.
+        shell: crate::cli::hook::Shell,
+    },
+
+    /// Show the profiles the nearest `.bridle` file selects, and switch with --apply.
+    Resolve {
+        /// Directory to resolve for (defaults to the current directory).
+        dir: Option<PathBuf>,
+        /// Switch to the selected profiles, restoring the previous ones when
+        /// no `.bridle` file applies any more.
+        #[arg(long)]
+        apply: bool,
+    },
+
     /// Launch terminal UI.
     Tui,
 
@@ -21,13 +67,80 @@ pub enum Commands {
     #[command(subcommand)]
     Config(ConfigCommands),
 
-    /// Install skills from a GitHub repository.
+    /// List, inspect, restore and prune config backups.
+    #[command(subcommand)]
+    Backup(BackupCommands),
+
+    /// Manage and test MCP servers in profiles.
+    #[command(subcommand)]
+    Mcp(McpCommands),
+
+    /// Add, list and remove plugin marketplaces to install from by name.
+    #[command(subcommand)]
+    Marketplace(MarketplaceCommands),
+
+    /// Install skills from a GitHub repository, git remote, archive or local directory.
     Install {
-        /// GitHub repository URL or owner/repo shorthand.
-        source: String,
+        /// owner/repo shorthand, GitHub URL, git+https:// or git+ssh:// remote
+        /// (append #ref to pin), .zip/.tar.gz URL, file:// URL, local path, or
+        /// registry:<server-id>[@version] from the MCP registry, or an added
+        /// marketplace as <marketplace> or <plugin>@<marketplace>.
+        #[arg(required_unless_present = "locked")]
+        source: Option<String>,
         /// Force overwrite existing skills.
         #[arg(long, short)]
         force: bool,
+        /// Install exactly what bridle.lock records (optionally only from SOURCE).
+        #[arg(long)]
+        locked: bool,
+        /// Install into a project's harness directories instead of profiles
+        /// (defaults to the enclosing git repository).
+        #[arg(long, value_name = "PATH", num_args = 0..=1, conflicts_with = "locked")]
+        project: Option<Option<PathBuf>>,
+        #[command(flatten)]
+        select: InstallArgs,
+    },
+
+    /// Search the MCP registry for servers.
+    Search {
+        /// Text to match against server names and descriptions.
+        query: String,
+    },
+
+    /// Reinstall every component in bridle.lock at its pinned revision.
+    Sync {
+        /// Path to the lockfile (defaults to ./bridle.lock, then the config directory).
+        #[arg(long)]
+        lockfile: Option<PathBuf>,
+    },
+
+    /// Re-fetch installed components from their sources and apply upstream changes.
+    Update {
+        /// Harness name (defaults to every harness with an active profile).
+        harness: Option<String>,
+        /// Profile name (defaults to the active profile).
+        profile: Option<String>,
+        /// Update every profile instead of only active ones.
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Create or update profiles from a declarative spec file.
+    Apply {
+        /// Path to the spec file.
+        #[arg(default_value = "bridle.toml")]
+        spec: PathBuf,
+    },
+
+    /// Check profiles and live configs for broken or incompatible components.
+    Doctor {
+        /// Harness name (defaults to every harness).
+        harness: Option<String>,
+        /// Profile name (defaults to every profile plus the live config).
+        profile: Option<String>,
+        /// Repair issues that have a mechanical fix.
+        #[arg(long)]
+        fix: bool,
     },
 
     /// Uninstall components from a profile.
@@ -35,10 +148,93 @@ pub enum Commands {
         /// Harness name (claude-code, opencode, goose, amp-code, crush, copilot-cli).
         harness: String,
         /// Profile name.
-        profile: String,
+        #[arg(required_unless_present = "project")]
+        profile: Option<String>,
+        /// Uninstall from a project instead of a profile (defaults to the enclosing git repository).
+        #[arg(long, value_name = "PATH", num_args = 0..=1, conflicts_with = "profile")]
+        project: Option<Option<PathBuf>>,
     },
 }
 
+impl Commands {
+    /// Whether the command can run with `--dry-run`: every command that
+    /// changes files, except `exec` and `profile edit`, which hand the files
+    /// to another program, and `profile export`, which only writes the
+    /// archive.
+    pub fn supports_dry_run(&self) -> bool {
+        match self {
+            Commands::Tui
+            | Commands::Status { .. }
+            | Commands::Search { .. }
+            | Commands::Exec { .. }
+            | Commands::Hook { .. }
+            | Commands::Resolve { apply: false, .. } => false,
+            Commands::Profile(command) => !matches!(
+                command,
+                ProfileCommands::List { .. }
+                    | ProfileCommands::Show { .. }
+                    | ProfileCommands::Diff { .. }
+                    | ProfileCommands::Edit { .. }
+                    | ProfileCommands::Export { .. }
+            ),
+            Commands::Config(command) => matches!(command, ConfigCommands::Set { .. }),
+            Commands::Backup(command) => !matches!(
+                command,
+                BackupCommands::List { .. } | BackupCommands::Show { .. }
+            ),
+            Commands::Marketplace(command) => !matches!(command, MarketplaceCommands::List),
+            Commands::Workspace(command) => !matches!(command, WorkspaceCommands::List),
+            Commands::Mcp(command) => {
+                !matches!(command, McpCommands::List { .. } | McpCommands::Test { .. })
+            }
+            Commands::Doctor { fix, .. } => *fix,
+            _ => true,
+        }
+    }
+
+    /// How the command holds the state lock, if at all. The TUI locks around
+    /// each change instead, `profile edit` leaves the files to the user's
+    /// editor, `resolve` and `exec` lock only while they change something,
+    /// and `hook` touches no state.
+    pub fn lock_mode(&self, dry_run: bool) -> Option<LockMode> {
+        let read_only = match self {
+            Commands::Tui
+            | Commands::Profile(ProfileCommands::Edit { .. })
+            | Commands::Resolve { .. }
+            | Commands::Exec { .. }
+            | Commands::Hook { .. } => return None,
+            _ if dry_run => true,
+            Commands::Status { .. } | Commands::Search { .. } => true,
+            Commands::Profile(command) => matches!(
+                command,
+                ProfileCommands::List { .. }
+                    | ProfileCommands::Show { .. }
+                    | ProfileCommands::Diff { .. }
+                    | ProfileCommands::Export { .. }
+            ),
+            Commands::Config(command) => matches!(command, ConfigCommands::Get { .. }),
+            Commands::Backup(command) => {
+                matches!(
+                    command,
+                    BackupCommands::List { .. } | BackupCommands::Show { .. }
+                )
+            }
+            Commands::Marketplace(command) => matches!(command, MarketplaceCommands::List),
+            Commands::Workspace(command) => matches!(command, WorkspaceCommands::List),
+            Commands::Mcp(command) => {
+                matches!(command, McpCommands::List { .. } | McpCommands::Test { .. })
+            }
+            Commands::Doctor { fix, .. } => !fix,
+            _ => false,
+        };
+        Some(if read_only {
+            LockMode::Shared
+        } else {
+            LockMode::Exclusive
+        })
+    }
+}
+
 #[derive(Subcommand, Debug)]
 pub enum ConfigCommands {
     /// Set a configuration value.
@@ -56,6 +252,229 @@ pub enum ConfigCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum MarketplaceCommands {
+    /// Add a marketplace (a source with .claude-plugin/marketplace.json).
+    Add {
+        /// owner/repo shorthand, URL or local path of the marketplace.
+        source: String,
+        /// Name to refer to it by (defaults to the marketplace's own name).
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// List added marketplaces.
+    List,
+
+    /// Remove an added marketplace.
+    Remove {
+        /// Marketplace name.
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum WorkspaceCommands {
+    /// Create or replace a workspace.
+    Set {
+        /// Workspace name.
+        name: String,
+        /// HARNESS=PROFILE pairs (defaults to the active profile of every harness).
+        #[arg(value_name = "HARNESS=PROFILE")]
+        profiles: Vec<String>,
+    },
+
+    /// List workspaces and their profiles.
+    List,
+
+    /// Remove a workspace (its profiles are kept).
+    Remove {
+        /// Workspace name.
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum McpCommands {
+    /// List the MCP servers in a profile.
+    List {
+        /// Harness name, or `all`.
+        harness: String,
+        /// Profile name.
+        profile: String,
+    },
+
+    /// Add an MCP server: `mcp add <harness> <profile> <name> -- <command> [args]`.
+    Add {
+        /// Harness name, or `all` for every harness with this profile.
+        harness: String,
+        /// Profile name.
+        profile: String,
+        /// Server name.
+        name: String,
+        #[command(flatten)]
+        server: McpServerArgs,
+        /// Replace an existing server with the same name.
+        #[arg(long, short)]
+        force: bool,
+    },
+
+    /// Remove an MCP server.
+    Rm {
+        /// Harness name, or `all`.
+        harness: String,
+        /// Profile name.
+        profile: String,
+        /// Server name.
+        name: String,
+    },
+
+    /// Enable a disabled MCP server.
+    Enable {
+        /// Harness name, or `all`.
+        harness: String,
+        /// Profile name.
+        profile: String,
+        /// Server name.
+        name: String,
+    },
+
+    /// Disable an MCP server without removing it.
+    Disable {
+        /// Harness name, or `all`.
+        harness: String,
+        /// Profile name.
+        profile: String,
+        /// Server name.
+        name: String,
+    },
+
+    /// Rename an MCP server.
+    Mv {
+        /// Harness name, or `all`.
+        harness: String,
+        /// Profile name.
+        profile: String,
+        /// Current server name.
+        from: String,
+        /// New server name.
+        to: String,
+    },
+
+    /// Start or connect to MCP servers and run the initialize handshake.
+    Test {
+        /// Harness name.
+        harness: String,
+        /// Profile name.
+        profile: String,
+        /// Only test this server.
+        server: Option<String>,
+    },
+}
+
+/// Component and target selection for `bridle install` without prompts.
+///
+/// Any of these flags makes the install non-interactive.
+#[derive(Args, Debug, Default)]
+pub struct InstallArgs {
+    /// Install this skill (repeatable or comma-separated).
+    #[arg(long = "skill", value_name = "NAME", value_delimiter = ',')]
+    pub skills: Vec<String>,
+    /// Install this agent (repeatable or comma-separated).
+    #[arg(long = "agent", value_name = "NAME", value_delimiter = ',')]
+    pub agents: Vec<String>,
+    /// Install this command (repeatable or comma-separated).
+    #[arg(long = "command", value_name = "NAME", value_delimiter = ',')]
+    pub commands: Vec<String>,
+    /// Install this MCP server (repeatable or comma-separated).
+    #[arg(long = "mcp", value_name = "NAME", value_delimiter = ',')]
+    pub mcps: Vec<String>,
+    /// Install this hook set (repeatable or comma-separated).
+    #[arg(long = "hook", value_name = "NAME", value_delimiter = ',')]
+    pub hooks: Vec<String>,
+    /// Install every discovered component.
+    #[arg(long, conflicts_with_all = ["skills", "agents", "commands", "mcps", "hooks"])]
+    pub all: bool,
+    /// Target harness (repeatable; defaults to every harness with profiles).
+    #[arg(long = "harness", value_name = "ID", value_delimiter = ',')]
+    pub harnesses: Vec<String>,
+    /// Target profile (repeatable; defaults to each harness's active profile).
+    #[arg(long = "profile", value_name = "NAME", value_delimiter = ',')]
+    pub profiles: Vec<String>,
+    /// Accept confirmations, including running hook commands.
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+}
+
+/// How to reach a server added with `bridle mcp add`.
+#[derive(Args, Debug)]
+pub struct McpServerArgs {
+    /// Connect to a remote server at this URL instead of running a command.
+    #[arg(long)]
+    pub url: Option<String>,
+    /// Remote transport.
+    #[arg(long, requires = "url", value_parser = ["http", "sse"])]
+    pub transport: Option<String>,
+    /// HTTP header for remote servers (repeatable).
+    #[arg(long = "header", value_name = "KEY=VALUE", requires = "url")]
+    pub headers: Vec<String>,
+    /// Environment variable with a literal value (repeatable).
+    #[arg(long = "env", value_name = "KEY=VALUE", conflicts_with = "url")]
+    pub env: Vec<String>,
+    /// Environment variable read from the user's environment at launch (repeatable).
+    #[arg(long = "env-ref", value_name = "KEY=VAR", conflicts_with = "url")]
+    pub env_refs: Vec<String>,
+    /// Working directory for the command.
+    #[arg(long, conflicts_with = "url")]
+    pub cwd: Option<PathBuf>,
+    /// Connection timeout in milliseconds.
+    #[arg(long)]
+    pub timeout_ms: Option<u64>,
+    /// Command and arguments to run, after `--`.
+    #[arg(last = true, required_unless_present = "url", conflicts_with = "url")]
+    pub command: Vec<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BackupCommands {
+    /// List backups for a harness, newest first.
+    List {
+        /// Harness name.
+        harness: String,
+    },
+
+    /// Show the files in a backup.
+    Show {
+        /// Harness name.
+        harness: String,
+        /// Backup id (as shown by `backup list`).
+        id: String,
+    },
+
+    /// Restore a backup into the live config (snapshots the current config first).
+    Restore {
+        /// Harness name.
+        harness: String,
+        /// Backup id (as shown by `backup list`).
+        id: String,
+    },
+
+    /// Delete backups outside the retention policy.
+    Prune {
+        /// Harness name.
+        harness: String,
+        /// Keep at most this many backups (overrides backup_keep).
+        #[arg(long)]
+        keep: Option<usize>,
+        /// Delete backups older than this many days (overrides backup_max_age_days).
+        #[arg(long)]
+        max_age_days: Option<u64>,
+        /// Cap total backup size in megabytes (overrides backup_max_size_mb).
+        #[arg(long)]
+        max_size_mb: Option<u64>,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum ProfileCommands {
     /// List profiles for a harness.
@@ -81,6 +500,9 @@ pub enum ProfileCommands {
         /// Copy current harness config to the new profile.
         #[arg(long)]
         from_current: bool,
+        /// Base profile to inherit from (repeatable; later bases win).
+        #[arg(long, value_name = "PROFILE", conflicts_with = "from_current")]
+        extends: Vec<String>,
     },
 
     /// Delete a profile.
@@ -97,6 +519,19 @@ pub enum ProfileCommands {
         harness: String,
         /// Profile name.
         name: String,
+        /// Copy the profile into a project instead of the global config
+        /// (defaults to the enclosing git repository).
+        #[arg(long, value_name = "PATH", num_args = 0..=1)]
+        project: Option<Option<PathBuf>>,
+        /// Save live edits made since activation into the current profile.
+        #[arg(long, conflicts_with_all = ["discard_drift", "fork_drift", "project"])]
+        keep_drift: bool,
+        /// Drop live edits made since activation.
+        #[arg(long, conflicts_with_all = ["fork_drift", "project"])]
+        discard_drift: bool,
+        /// Save live edits made since activation as a new profile.
+        #[arg(long, value_name = "NAME", conflicts_with = "project")]
+        fork_drift: Option<String>,
     },
 
     /// Edit a profile with $EDITOR.
@@ -115,5 +550,46 @@ pub enum ProfileCommands {
         name: String,
         /// Second profile name (optional, defaults to current config).
         other: Option<String>,
+        /// Show a raw file-level `diff -u` instead of the semantic diff.
+        #[arg(long)]
+        raw: bool,
+    },
+
+    /// Convert a profile to another harness, reporting what was dropped or degraded.
+    Convert {
+        /// Harness the profile belongs to.
+        from_harness: String,
+        /// Profile name.
+        name: String,
+        /// Harness to convert to.
+        to_harness: String,
+        /// Name of the new profile (defaults to the same name).
+        new_name: Option<String>,
+    },
+
+    /// Export a profile to a portable archive, with MCP secrets replaced by
+    /// environment variable references.
+    Export {
+        /// Harness name.
+        harness: String,
+        /// Profile name.
+        name: String,
+        /// Archive to write (defaults to `<name>.bridle`).
+        file: Option<PathBuf>,
+        /// Overwrite the archive if it already exists.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Create a profile from an archive written by `profile export`.
+    Import {
+        /// Archive to read.
+        file: PathBuf,
+        /// Name of the new profile (defaults to the exported name).
+        #[arg(long)]
+        name: Option<String>,
+        /// Import without asking, even if the profile's hooks run commands.
+        #[arg(long, short = 'y')]
+        yes: bool,
     },
 }
@@ -1,14 +1,27 @@
 use harness_locate::{Harness, HarnessKind};
 
+use crate::cli::output::ResolvedFormat;
+use crate::cli::plan;
 use crate::config::BridleConfig;
 use crate::error::{Error, Result};
 use crate::harness::HarnessConfig;
+use crate::plan::fs;
 
-pub fn set_config(key: &str, value: &str) -> Result<()> {
-    match key {
+pub fn set_config(key: &str, value: &str, dry_run: bool, format: ResolvedFormat) -> Result<()> {
+    let Some(setting) = plan::run(dry_run, format, || match key {
         "profile_marker" => set_profile_marker(value),
+        "backup_keep" | "backup_max_age_days" | "backup_max_size_mb" => {
+            set_backup_retention(key, value)
+        }
+        "registry_url" => set_registry_url(value),
         _ => Err(Error::UnknownSetting(key.to_string())),
-    }
+    })?
+    else {
+        return Ok(());
+    };
+
+    println!("{}", setting);
+    Ok(())
 }
 
 pub fn get_config(key: &str) -> Result<()> {
@@ -16,12 +29,16 @@ pub fn get_config(key: &str) -> Result<()> {
 
     match key {
         "profile_marker" => println!("{}", config.profile_marker),
+        "backup_keep" => println!("{}", display_limit(config.backups.keep)),
+        "backup_max_age_days" => println!("{}", display_limit(config.backups.max_age_days)),
+        "backup_max_size_mb" => println!("{}", display_limit(config.backups.max_size_mb)),
+        "registry_url" => println!("{}", config.registry_url.as_deref().unwrap_or("default")),
         _ => return Err(Error::UnknownSetting(key.to_string())),
     }
     Ok(())
 }
 
-fn set_profile_marker(value: &str) -> Result<()> {
+fn set_profile_marker(value: &str) -> Result<String> {
     let enabled = match value.to_lowercase().as_str() {
         "true" | "1" | "yes" | "on" => true,
         "false" | "0" | "no" | "off" => false,
@@ -36,8 +53,54 @@ fn set_profile_marker(value: &str) -> Result<()> {
         cleanup_all_marker_files();
     }
 
-    println!("profile_marker = {}", enabled);
-    Ok(())
+    Ok(format!("profile_marker = {}", enabled))
+}
+
+fn display_limit<T: std::fmt::Display>(limit: Option<T>) -> String {
+    limit
+        .map(|l| l.to_string())
+        .unwrap_or_else(|| "none".to_string())
+}
+
+/// Sets a retention limit; `none`/`off` removes it.
+fn set_backup_retention(key: &str, value: &str) -> Result<String> {
+    let limit = match value.to_lowercase().as_str() {
+        "none" | "off" | "" => None,
+        v => Some(
+            v.parse::<u64>()
+                .map_err(|_| Error::InvalidValue(value.to_string()))?,
+        ),
+    };
+
+    let mut config = BridleConfig::load().unwrap_or_default();
+    match key {
+        "backup_keep" => config.backups.keep = limit.map(|l| l as usize),
+        "backup_max_age_days" => config.backups.max_age_days = limit,
+        _ => config.backups.max_size_mb = limit,
+    }
+    config.save()?;
+
+    Ok(format!("{} = {}", key, display_limit(limit)))
+}
+
+/// Points registry lookups at another server; `default` restores the official one.
+fn set_registry_url(value: &str) -> Result<String> {
+    let url = match value {
+        "" | "default" | "none" => None,
+        v if v.starts_with("http://") || v.starts_with("https://") => {
+            Some(v.trim_end_matches('/').to_string())
+        }
+        _ => return Err(Error::InvalidValue(value.to_string())),
+    };
+
+    let mut config = BridleConfig::load().unwrap_or_default();
+    config.registry_url = url;
+    config.save()?;
+
+    Ok(format!(
+        "registry_url = {}",
+        config.registry_url.as_deref().unwrap_or("default")
+    ))
 }
 
 fn cleanup_all_marker_files() {
@@ -46,7 +109,7 @@ fn cleanup_all_marker_files() {
         let Ok(config_dir) = harness.config_dir() else {
             continue;
         };
-        let Ok(entries) = std::fs::read_dir(&config_dir) else {
+        let Ok(entries) = fs::read_dir(&config_dir) else {
             continue;
         };
         for entry in entries.flatten() {
@@ -55,7 +118,7 @@ fn cleanup_all_marker_files() {
                 continue;
             };
             if name.starts_with("BRIDLE_PROFILE_") {
-                let _ = std::fs::remove_file(entry.path());
+                let _ = fs::remove_file(entry.path());
             }
         }
     }
@@ -0,0 +1,121 @@
+//! CLI doctor command implementation.
+
+use color_eyre::eyre::{Result, eyre};
+use colored::Colorize;
+use harness_locate::{Harness, HarnessKind, Severity};
+
+use crate::cli::output::{ResolvedFormat, output_list};
+use crate::cli::plan;
+use crate::cli::profile::resolve_harness;
+use crate::config::{BridleConfig, ProfileManager};
+use crate::install::doctor::{DoctorReport, Finding, check_live, check_profile};
+
+pub fn run(
+    harness: Option<&str>,
+    profile: Option<&str>,
+    fix: bool,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let profiles_dir = BridleConfig::profiles_dir()?;
+    let manager = ProfileManager::new(profiles_dir.clone());
+
+    let kinds: Vec<HarnessKind> = match harness {
+        Some(name) => vec![resolve_harness(name)?.kind()],
+        None => HarnessKind::ALL.to_vec(),
+    };
+
+    let Some(reports) = plan::run(dry_run, format, || -> Result<Vec<DoctorReport>> {
+        let mut reports = Vec::new();
+        for kind in kinds {
+            if let Some(profile) = profile {
+                reports.push(check_profile(kind, profile, &profiles_dir, fix)?);
+                continue;
+            }
+            for name in manager.list_profiles(&Harness::new(kind))? {
+                reports.push(check_profile(kind, name.as_str(), &profiles_dir, fix)?);
+            }
+            if let Some(report) = check_live(kind, fix)? {
+                reports.push(report);
+            }
+        }
+        Ok(reports)
+    })?
+    else {
+        return Ok(());
+    };
+
+    let errors: usize = reports.iter().map(|r| r.count(Severity::Error)).sum();
+
+    output_list(&reports, format, |reports| {
+        if reports.is_empty() {
+            println!("No profiles or harness configs to check");
+            return;
+        }
+        for report in reports {
+            print_report(report);
+        }
+        print_summary(reports, fix);
+    });
+
+    if errors > 0 {
+        return Err(eyre!("{} error(s) found", errors));
+    }
+    Ok(())
+}
+
+fn print_report(report: &DoctorReport) {
+    let location = match &report.profile {
+        Some(profile) => format!("{}/{}", report.harness, profile),
+        None => format!("{} (live)", report.harness),
+    };
+    println!(
+        "{} {}",
+        location.bold(),
+        report.path.display().to_string().dimmed()
+    );
+    if report.findings.is_empty() {
+        println!("  {}", "No issues".green());
+    }
+    for finding in &report.findings {
+        print_finding(finding);
+    }
+}
+
+fn print_finding(finding: &Finding) {
+    let issue = &finding.issue;
+    let marker = if finding.fixed {
+        "fixed".green()
+    } else {
+        match issue.severity {
+            Severity::Error => "error".red(),
+            Severity::Warning => "warn".yellow(),
+        }
+    };
+    let code = issue.code.map(|c| format!(" [{}]", c)).unwrap_or_default();
+    let hint = if finding.fixable && !finding.fixed {
+        " (fixable)".dimmed().to_string()
+    } else {
+        String::new()
+    };
+    println!(
+        "  {:<5} {} {}: {}{}{}",
+        marker, finding.component_type, finding.name, issue.message, code, hint
+    );
+}
+
+fn print_summary(reports: &[DoctorReport], fix: bool) {
+    let errors: usize = reports.iter().map(|r| r.count(Severity::Error)).sum();
+    let warnings: usize = reports.iter().map(|r| r.count(Severity::Warning)).sum();
+    let findings = || reports.iter().flat_map(|r| &r.findings);
+    let fixed = findings().filter(|f| f.fixed).count();
+    let fixable = findings().filter(|f| f.fixable && !f.fixed).count();
+
+    println!();
+    println!("{} error(s), {} warning(s)", errors, warnings);
+    if fix && fixed > 0 {
+        println!("Fixed {} issue(s)", fixed);
+    } else if fixable > 0 {
+        println!("{} issue(s) can be fixed with --fix", fixable);
+    }
+}
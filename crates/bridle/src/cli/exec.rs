@@ -0,0 +1,196 @@
+//! CLI exec command implementation.
+
+use std::path::PathBuf;
+use std::process::{Command, ExitStatus};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use color_eyre::eyre::{Result, eyre};
+use harness_locate::Harness;
+
+use crate::cli::output::ResolvedFormat;
+use crate::cli::plan;
+use crate::cli::profile::resolve_harness;
+use crate::cli::workspace::switch_each;
+use crate::config::{BridleConfig, ProfileManager, ProfileName};
+use crate::error::Error;
+use crate::harness::{HarnessConfig, config_redirect};
+use crate::lock::{LockMode, StateLock};
+
+/// Run a harness with a profile without switching to it globally.
+///
+/// The profile is laid out in `exec/<harness>/<profile>/` in the bridle
+/// config dir and the harness is pointed at it through its config dir
+/// variable. The copy is refreshed from the profile on every run; edits made
+/// while the harness runs stay in the copy. When that variable is
+/// `XDG_CONFIG_HOME`, the rest of the user's config dir is linked in beside
+/// the copy so the programs the harness starts still find theirs. A harness that cannot be
+/// redirected is switched to the profile for as long as it runs and switched
+/// back afterwards, unless another profile was switched to in the meantime.
+///
+/// Exits with the harness's exit code.
+pub fn exec(harness_name: &str, profile_name: &str, args: &[String], wait: bool) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let name = ProfileName::new(profile_name)
+        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+    let manager = ProfileManager::new(BridleConfig::profiles_dir()?);
+    if !manager.profile_exists(&harness, &name) {
+        return Err(Error::ProfileNotFound(profile_name.to_string()).into());
+    }
+
+    let binary = find_binary(&harness)?.ok_or_else(|| {
+        eyre!(
+            "{} is not installed: none of {} found in PATH",
+            harness.id(),
+            harness.kind().binary_names().join(", ")
+        )
+    })?;
+    let mut command = Command::new(&binary);
+    command.args(args);
+
+    let status = match config_redirect(harness.kind()) {
+        Some(redirect) => {
+            let root = exec_dir(&harness, &name)?;
+            {
+                let _lock = StateLock::acquire(LockMode::Exclusive, wait)?;
+                manager.materialize_profile(&harness, &name, &redirect.config_dir(&root))?;
+                redirect.link_shared(&root)?;
+            }
+            command.env(redirect.var, &root);
+            run(command)?
+        }
+        None => switch_and_restore(&manager, &harness, &name, command, wait)?,
+    };
+    std::process::exit(status.code().unwrap_or(1));
+}
+
+fn find_binary(harness: &Harness) -> Result<Option<PathBuf>> {
+    for name in harness.kind().binary_names() {
+        if let Some(path) = harness_locate::find_binary(name)? {
+            return Ok(Some(path));
+        }
+    }
+    Ok(None)
+}
+
+fn exec_dir(harness: &Harness, name: &ProfileName) -> Result<PathBuf> {
+    Ok(BridleConfig::config_dir()?
+        .join("exec")
+        .join(harness.id())
+        .join(name.as_str()))
+}
+
+/// Replace bridle with the harness, so signals and the terminal go straight
+/// to it.
+#[cfg(unix)]
+fn run(mut command: Command) -> Result<ExitStatus> {
+    use std::os::unix::process::CommandExt;
+
+    let err = command.exec();
+    Err(eyre!(
+        "Failed to run {}: {}",
+        command.get_program().display(),
+        err
+    ))
+}
+
+#[cfg(not(unix))]
+fn run(command: Command) -> Result<ExitStatus> {
+    run_guarded(command)
+}
+
+/// Run the harness to completion. Interrupts meant for the harness (Ctrl-C,
+/// a closed terminal) must not end bridle before it has cleaned up, so they
+/// are ignored until the harness exits.
+fn run_guarded(mut command: Command) -> Result<ExitStatus> {
+    let ignored = Arc::new(AtomicBool::new(false));
+    let mut signals = signal_hook::consts::TERM_SIGNALS.to_vec();
+    #[cfg(unix)]
+    signals.push(signal_hook::consts::SIGHUP);
+    let ids = signals
+        .iter()
+        .map(|&signal| signal_hook::flag::register(signal, Arc::clone(&ignored)))
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    let status = command
+        .status()
+        .map_err(|e| eyre!("Failed to run {}: {}", command.get_program().display(), e));
+
+    for id in ids {
+        signal_hook::low_level::unregister(id);
+    }
+    status
+}
+
+/// Switch to the profile, run the harness, and switch back to the profile
+/// that was active before, even if the harness fails to start.
+///
+/// The state lock is held for each switch but not while the harness runs,
+/// so other bridle commands are not blocked for the whole session. If the
+/// harness was switched to another profile meanwhile, that switch is kept.
+fn switch_and_restore(
+    manager: &ProfileManager,
+    harness: &Harness,
+    name: &ProfileName,
+    command: Command,
+    wait: bool,
+) -> Result<ExitStatus> {
+    let lock = StateLock::acquire(LockMode::Exclusive, wait)?;
+
+    let config = BridleConfig::load()?;
+    let previous = config.active_profile_for(harness.id()).ok_or_else(|| {
+        eyre!(
+            "{} cannot be pointed at another config dir, so bridle exec has to switch it \
+             and back, but it has no active profile to switch back to. Switch to a profile first.",
+            harness.id()
+        )
+    })?;
+    let previous =
+        ProfileName::new(previous).map_err(|_| Error::InvalidProfileName(previous.to_string()))?;
+    if previous.as_str() == name.as_str() {
+        drop(lock);
+        return run_guarded(command);
+    }
+
+    switch(manager, harness, name)?;
+    drop(lock);
+    eprintln!(
+        "{} cannot be pointed at another config dir; switched to {} until it exits",
+        harness.id(),
+        name.as_str()
+    );
+    let status = run_guarded(command);
+
+    // Always wait here: giving up would leave the harness on the exec'd profile
+    let _lock = StateLock::acquire(LockMode::Exclusive, true)?;
+    let active = BridleConfig::load()?
+        .active_profile_for(harness.id())
+        .map(String::from);
+    if active.as_deref() != Some(name.as_str()) {
+        eprintln!(
+            "{} was switched to {} while it ran; not switching back to {}",
+            harness.id(),
+            active.as_deref().unwrap_or("no profile"),
+            previous.as_str()
+        );
+        return status;
+    }
+    switch(manager, harness, &previous).map_err(|e| {
+        eyre!(
+            "{} was left on profile {}; switching back to {} failed: {}",
+            harness.id(),
+            name.as_str(),
+            previous.as_str(),
+            e
+        )
+    })?;
+    status
+}
+
+fn switch(manager: &ProfileManager, harness: &Harness, name: &ProfileName) -> Result<()> {
+    let targets = [(Harness::new(harness.kind()), name.clone())];
+    plan::run(false, ResolvedFormat::Text, || {
+        switch_each(manager, &targets)
+    })?;
+    Ok(())
+}
@@ -0,0 +1,169 @@
+//! CLI hook and resolve command implementation.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use clap::ValueEnum;
+use color_eyre::eyre::{Result, eyre};
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::cli::output::{ResolvedFormat, output};
+use crate::cli::plan;
+use crate::cli::workspace::{resolve_profile, switch_each};
+use crate::config::{AutoSwitch, AutoSwitchState, BridleConfig, DirMarker, ProfileManager};
+use crate::lock::{LockMode, StateLock};
+
+/// Shells `bridle hook` can emit a snippet for.
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+const BASH_HOOK: &str = r#"_bridle_hook() {
+  local previous_exit_status=$?
+  if [[ "$PWD" != "${_BRIDLE_LAST_PWD-}" ]]; then
+    _BRIDLE_LAST_PWD="$PWD"
+    command bridle --no-wait resolve --apply
+  fi
+  return $previous_exit_status
+}
+if [[ ";${PROMPT_COMMAND[*]:-};" != *";_bridle_hook;"* ]]; then
+  PROMPT_COMMAND="_bridle_hook${PROMPT_COMMAND:+;$PROMPT_COMMAND}"
+fi
+"#;
+
+const ZSH_HOOK: &str = r#"_bridle_hook() {
+  command bridle --no-wait resolve --apply
+}
+autoload -Uz add-zsh-hook
+add-zsh-hook chpwd _bridle_hook
+_bridle_hook
+"#;
+
+const FISH_HOOK: &str = r#"function __bridle_hook --on-variable PWD --description 'Switch bridle profiles for .bridle files'
+    command bridle --no-wait resolve --apply
+end
+__bridle_hook
+"#;
+
+/// Print the snippet that runs `bridle resolve --apply` whenever the shell
+/// changes directory.
+pub fn print_hook(shell: Shell) {
+    print!("{}", hook_script(shell));
+}
+
+fn hook_script(shell: Shell) -> &'static str {
+    match shell {
+        Shell::Bash => BASH_HOOK,
+        Shell::Zsh => ZSH_HOOK,
+        Shell::Fish => FISH_HOOK,
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Resolution {
+    /// The `.bridle` file in effect, if any.
+    pub marker: Option<PathBuf>,
+    /// Profile each affected harness should be on.
+    pub profiles: BTreeMap<String, String>,
+    /// Harnesses that are not on their profile yet.
+    pub switches: BTreeMap<String, String>,
+    pub applied: bool,
+}
+
+/// Decide which profiles `dir` calls for and, with `apply`, switch to them.
+///
+/// Only reads `config.toml`, the auto-switch state and the `.bridle` files on
+/// the way up from `dir`; when the active profiles already match, nothing is
+/// locked, planned or written, so running it on every `cd` is cheap.
+pub fn resolve(
+    dir: Option<&Path>,
+    apply: bool,
+    wait: bool,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let dir = match dir {
+        Some(dir) => std::fs::canonicalize(dir)
+            .map_err(|e| eyre!("Cannot resolve {}: {}", dir.display(), e))?,
+        None => std::env::current_dir()?,
+    };
+    let marker = DirMarker::find(&dir)?;
+
+    let state = AutoSwitchState::load()?;
+    let mut decided = AutoSwitch::resolve(&BridleConfig::load()?.active, marker.as_ref(), &state);
+    let mut applied = false;
+
+    if apply && !decided.is_noop(&state) {
+        let _lock = StateLock::acquire(LockMode::Exclusive, wait)?;
+        // Decide again now that no other bridle process can switch under us
+        let state = AutoSwitchState::load()?;
+        decided = AutoSwitch::resolve(&BridleConfig::load()?.active, marker.as_ref(), &state);
+
+        if !decided.is_noop(&state) {
+            let manager = ProfileManager::new(BridleConfig::profiles_dir()?);
+            let targets = decided
+                .switches
+                .iter()
+                .map(|(harness, profile)| resolve_profile(&manager, harness, profile))
+                .collect::<Result<Vec<_>>>()?;
+
+            let switched = plan::run(dry_run, format, || {
+                let switched = switch_each(&manager, &targets)?;
+                decided.state.save()?;
+                Ok::<_, crate::error::Error>(switched)
+            })
+            .map_err(|e| eyre!("Profiles for {} not switched: {e}", dir.display()))?;
+            if switched.is_none() {
+                return Ok(());
+            }
+            applied = true;
+        }
+    }
+
+    let resolution = Resolution {
+        marker: marker.map(|m| m.path),
+        profiles: decided.targets,
+        switches: decided.switches,
+        applied,
+    };
+    output(&resolution, format, |resolution| {
+        if apply {
+            // Stay quiet on every `cd` that changes nothing
+            for (harness, profile) in &resolution.switches {
+                println!("bridle: {} → {}", harness, profile.bold());
+            }
+            return;
+        }
+        match &resolution.marker {
+            Some(marker) => println!("Marker: {}", marker.display()),
+            None => println!("No {} file applies here", crate::config::DIR_MARKER_FILE),
+        }
+        for (harness, profile) in &resolution.profiles {
+            if resolution.switches.contains_key(harness) {
+                println!("  {}: {} {}", harness, profile, "(switch needed)".yellow());
+            } else {
+                println!("  {}: {}", harness, profile);
+            }
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hooks_run_resolve_without_waiting_for_the_lock() {
+        for shell in Shell::value_variants() {
+            assert!(
+                hook_script(*shell).contains("command bridle --no-wait resolve --apply"),
+                "{shell:?}"
+            );
+        }
+    }
+}
@@ -2,10 +2,13 @@
 
 use harness_locate::{Harness, HarnessKind};
 
+use crate::cli::output::ResolvedFormat;
+use crate::cli::plan;
 use crate::config::{BridleConfig, ProfileManager};
 use crate::error::Result;
+use crate::plan::fs;
 
-pub fn run_init() -> Result<()> {
+pub fn run_init(dry_run: bool, format: ResolvedFormat) -> Result<()> {
     let config_dir = BridleConfig::config_dir()?;
     let config_path = BridleConfig::config_path()?;
 
@@ -14,18 +17,24 @@ pub fn run_init() -> Result<()> {
         return Ok(());
     }
 
-    std::fs::create_dir_all(&config_dir)?;
+    let initialized = plan::run(dry_run, format, || -> Result<()> {
+        fs::create_dir_all(&config_dir)?;
 
-    let profiles_dir = config_dir.join("profiles");
-    std::fs::create_dir_all(&profiles_dir)?;
+        let profiles_dir = config_dir.join("profiles");
+        fs::create_dir_all(&profiles_dir)?;
 
-    let config = BridleConfig::default();
-    config.save()?;
+        let config = BridleConfig::default();
+        config.save()?;
 
-    let manager = ProfileManager::new(profiles_dir);
-    for kind in HarnessKind::ALL {
-        let harness = Harness::new(*kind);
-        let _ = manager.create_from_current_if_missing(&harness);
+        let manager = ProfileManager::new(profiles_dir);
+        for kind in HarnessKind::ALL {
+            let harness = Harness::new(*kind);
+            let _ = manager.create_from_current_if_missing(&harness);
+        }
+        Ok(())
+    })?;
+    if initialized.is_none() {
+        return Ok(());
     }
 
     println!("Initialized bridle at {}", config_dir.display());
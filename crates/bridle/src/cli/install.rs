@@ -1,23 +1,38 @@
 //! CLI install command implementation.
 
 use std::io::IsTerminal;
+use std::path::Path;
 
 use color_eyre::eyre::{Result, eyre};
 use colored::Colorize;
 use dialoguer_multiselect::theme::ColorfulTheme;
-use dialoguer_multiselect::{GroupMultiSelect, ItemState};
+use dialoguer_multiselect::{Confirm, GroupMultiSelect, Input, ItemState, MultiSelect};
 
-use harness_locate::{Harness, HarnessKind, Scope, Severity, validate_agent_for_harness};
+use harness_locate::{EnvValue, Harness, HarnessKind, Scope, Severity, validate_agent_for_harness};
 
-use crate::config::{BridleConfig, ProfileManager};
+use crate::cli::InstallArgs;
+use crate::cli::output::{ResolvedFormat, output};
+use crate::cli::plan::print_plan_text;
+use crate::config::{BridleConfig, ProfileManager, ProfileName};
 use crate::harness::HarnessConfig;
 use crate::install::discovery::{DiscoveryError, discover_skills};
-use crate::install::installer::{install_agent, install_command, install_skills};
-use crate::install::mcp_installer::{McpInstallOutcome, install_mcp};
+use crate::install::hook_installer::{hook_commands, install_hook_with_source, supports_hooks};
+use crate::install::installer::{
+    InstallError, InstallOutcome, install_agent_with_source, install_command_with_source,
+    install_skill_with_source,
+};
+use crate::install::marketplace;
+use crate::install::mcp_installer::{McpInstallOutcome, install_mcp_with_source};
+use crate::install::project::{ProjectComponent, ProjectError, install_to_project};
+use crate::install::registry::{
+    RegistrySource, discovery_from_entry, env_descriptions, fetch_entry, registry_client,
+};
 use crate::install::{
-    AgentInfo, CommandInfo, DiscoveryResult, InstallOptions, InstallTarget, SkillInfo,
-    parse_harness_kind,
+    AgentInfo, CommandInfo, ComponentType, DiscoveryResult, HookInfo, InstallFailure,
+    InstallOptions, InstallReport, InstallSkip, InstallSuccess, InstallTarget, PluginInfo,
+    SkillInfo, SkipReason, SourceInfo, parse_harness_kind,
 };
+use crate::plan;
 use harness_locate::McpServer;
 use std::collections::HashMap;
 
@@ -93,6 +108,7 @@ struct SelectedComponents {
     mcp_servers: HashMap<String, McpServer>,
     agents: Vec<AgentInfo>,
     commands: Vec<CommandInfo>,
+    hooks: Vec<HookInfo>,
 }
 
 impl SelectedComponents {
@@ -101,25 +117,131 @@ impl SelectedComponents {
             && self.mcp_servers.is_empty()
             && self.agents.is_empty()
             && self.commands.is_empty()
+            && self.hooks.is_empty()
     }
 }
 
-pub fn run(source: &str, force: bool) -> Result<()> {
-    if !std::io::stdin().is_terminal() {
+/// Exit status when some components failed to install.
+pub const EXIT_PARTIAL_FAILURE: i32 = 2;
+/// Exit status when nothing failed but some components were skipped because
+/// they were already installed (pass `--force` to overwrite).
+pub const EXIT_ALREADY_INSTALLED: i32 = 3;
+
+/// Process exit status for a finished install.
+pub fn exit_code(report: &InstallReport) -> i32 {
+    if !report.errors.is_empty() {
+        EXIT_PARTIAL_FAILURE
+    } else if report
+        .skipped
+        .iter()
+        .any(|s| matches!(s.reason, SkipReason::AlreadyExists))
+    {
+        EXIT_ALREADY_INSTALLED
+    } else {
+        0
+    }
+}
+
+/// Whether any flag asks for a prompt-free install.
+fn is_non_interactive(args: &InstallArgs) -> bool {
+    names_components(args)
+        || args.all
+        || !args.harnesses.is_empty()
+        || !args.profiles.is_empty()
+        || args.yes
+}
+
+fn names_components(args: &InstallArgs) -> bool {
+    !(args.skills.is_empty()
+        && args.agents.is_empty()
+        && args.commands.is_empty()
+        && args.mcps.is_empty()
+        && args.hooks.is_empty())
+}
+
+/// Discover components in `source`, select them and their targets (by
+/// prompting, or from `args`) and install them. Returns the process exit
+/// status.
+pub fn run(
+    source: &str,
+    force: bool,
+    project: Option<&Path>,
+    args: &InstallArgs,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<i32> {
+    let interactive = !(is_non_interactive(args) || dry_run);
+    if interactive && !std::io::stdin().is_terminal() {
         return Err(eyre!(
-            "Interactive mode requires a terminal. Use --help for non-interactive options."
+            "Interactive mode requires a terminal. Pass --all or --skill/--agent/--command/--mcp/--hook \
+             with --harness/--profile to install without prompts."
         ));
     }
 
+    let subscribed = if is_local_path(source) {
+        None
+    } else {
+        marketplace::resolve(source)
+    };
+    let (source, wanted_plugin) = match subscribed {
+        Some((subscription, plugin)) => {
+            eprintln!(
+                "Using marketplace {} ({})",
+                subscription.name, subscription.source
+            );
+            (subscription.source, plugin)
+        }
+        None => (source.to_string(), None),
+    };
+    let source = source.as_str();
     let url = normalize_source(source);
 
-    eprintln!("Discovering components from {}...", url);
+    let registry_source = RegistrySource::parse(source);
+    let (mut discovery, env_help) = match &registry_source {
+        Some(registry) => {
+            eprintln!("Looking up {} in the MCP registry...", registry.id);
+            let entry = fetch_entry(&registry_client(), registry)
+                .map_err(|e| eyre!("Failed to fetch {} from the MCP registry: {}", registry, e))?;
+            (
+                discovery_from_entry(&entry, registry),
+                Some(env_descriptions(&entry)),
+            )
+        }
+        None => {
+            eprintln!("Discovering components from {}...", url);
+            let discovery = discover_skills(&url).map_err(|e| match e {
+                DiscoveryError::InvalidUrl(msg) => eyre!("Invalid source: {}", msg),
+                DiscoveryError::FetchError(e) => eyre!("Failed to fetch repository: {}", e),
+                DiscoveryError::NoSkillsFound => {
+                    eyre!("No installable components found in repository")
+                }
+            })?;
+            (discovery, None)
+        }
+    };
 
-    let discovery = discover_skills(&url).map_err(|e| match e {
-        DiscoveryError::InvalidUrl(msg) => eyre!("Invalid URL: {}", msg),
-        DiscoveryError::FetchError(e) => eyre!("Failed to fetch repository: {}", e),
-        DiscoveryError::NoSkillsFound => eyre!("No installable components found in repository"),
-    })?;
+    if let Some(wanted) = &wanted_plugin {
+        if !discovery.plugins.iter().any(|p| &p.name == wanted) {
+            return Err(eyre!(
+                "No plugin named '{}' in {}",
+                wanted,
+                discovery.source
+            ));
+        }
+        discovery.retain_plugins(|p| &p.name == wanted, false);
+    } else if interactive && discovery.plugins.len() > 1 {
+        eprintln!(
+            "Found {} plugin(s) in {}",
+            discovery.plugins.len(),
+            discovery.source
+        );
+        let chosen = select_plugins(&discovery.plugins)?;
+        if chosen.is_empty() {
+            eprintln!("No plugins selected");
+            return Ok(0);
+        }
+        discovery.retain_plugins(|p| chosen.contains(&p.name), true);
+    }
 
     // Build summary of what was found
     let mut found_parts = Vec::new();
@@ -135,108 +257,182 @@ pub fn run(source: &str, force: bool) -> Result<()> {
     if !discovery.commands.is_empty() {
         found_parts.push(format!("{} command(s)", discovery.commands.len()));
     }
+    if !discovery.hooks.is_empty() {
+        found_parts.push(format!("{} hook set(s)", discovery.hooks.len()));
+    }
 
     if found_parts.is_empty() {
         eprintln!("No installable components found in {}", url);
-        return Ok(());
+        return Ok(0);
     }
 
-    eprintln!(
-        "Found {} from {}/{}",
-        found_parts.join(", "),
-        discovery.source.owner,
-        discovery.source.repo
-    );
+    eprintln!("Found {} from {}", found_parts.join(", "), discovery.source);
 
-    let selected = select_components(&discovery)?;
+    let mut selected = if interactive {
+        select_components(&discovery)?
+    } else {
+        select_named(&discovery, args)?
+    };
 
     if selected.is_empty() {
         eprintln!("No components selected");
-        return Ok(());
+        return Ok(0);
     }
 
-    let targets = select_targets(&selected)?;
+    if interactive && let Some(env_help) = &env_help {
+        prompt_env_refs(&mut selected.mcp_servers, env_help)?;
+    }
+
+    if !selected.hooks.is_empty() {
+        let confirmed = if interactive {
+            confirm_hooks(&selected.hooks)?
+        } else if args.yes {
+            show_hook_commands(&selected.hooks);
+            true
+        } else {
+            eprintln!(
+                "~ Skipping {} hook set(s) - pass --yes to install hook commands",
+                selected.hooks.len()
+            );
+            false
+        };
+        if !confirmed {
+            selected.hooks.clear();
+            if selected.is_empty() {
+                eprintln!("No components selected");
+                return Ok(0);
+            }
+        }
+    }
+
+    if let Some(root) = project {
+        return install_into_project(
+            &selected,
+            &discovery,
+            root,
+            force,
+            args,
+            interactive,
+            dry_run,
+        );
+    }
+
+    let targets = if interactive {
+        select_targets(&selected)?
+    } else {
+        resolve_targets(&args.harnesses, &args.profiles)?
+    };
 
     if targets.is_empty() {
         eprintln!("No targets selected");
-        return Ok(());
+        return Ok(0);
     }
 
     let options = InstallOptions { force };
+    let (mut report, plan) =
+        plan::record(|| install_to_targets(&selected, &discovery, &targets, &options, dry_run));
 
-    for target in &targets {
-        eprintln!("\nInstalling to {}/{}...", target.harness, target.profile);
+    if dry_run {
+        report.plan = Some(plan);
+    } else {
+        crate::cli::plan::apply(&plan)?;
+        crate::cli::sync::refresh_lockfile(&targets);
+    }
 
-        // Install skills
-        if !selected.skills.is_empty() {
-            let report = install_skills(&selected.skills, target, &options);
+    output(&report, format, |report| {
+        let verb = if report.dry_run {
+            "Would install"
+        } else {
+            "Installed"
+        };
+        eprintln!(
+            "\n{} {}, skipped {}, failed {}",
+            verb,
+            report.installed.len(),
+            report.skipped.len(),
+            report.errors.len()
+        );
+        if let Some(plan) = &report.plan {
+            eprintln!();
+            print_plan_text(plan);
+        }
+    });
+    Ok(exit_code(&report))
+}
 
-            for success in &report.installed {
-                eprintln!("  + Installed skill: {}", success.skill);
-            }
-            for skip in &report.skipped {
-                eprintln!("  = Skipped skill: {} (already exists)", skip.skill);
-            }
-            for error in &report.errors {
-                eprintln!(
-                    "  ! Error installing skill {}: {}",
-                    error.skill, error.error
-                );
-            }
+/// Install every selected component into each target profile, printing
+/// progress as it goes. `dry_run` only changes the wording; run inside
+/// [`plan::record`] to keep the changes from reaching the disk.
+fn install_to_targets(
+    selected: &SelectedComponents,
+    discovery: &DiscoveryResult,
+    targets: &[InstallTarget],
+    options: &InstallOptions,
+    dry_run: bool,
+) -> InstallReport {
+    let mut report = InstallReport {
+        dry_run,
+        ..Default::default()
+    };
+    let mut servers: Vec<_> = selected.mcp_servers.iter().collect();
+    servers.sort_by(|a, b| a.0.cmp(b.0));
+
+    for target in targets {
+        eprintln!("\nInstalling to {}/{}...", target.harness, target.profile);
+        let kind = parse_harness_kind(&target.harness);
+        let step =
+            |report: &mut InstallReport,
+             component_type: ComponentType,
+             name: &str,
+             install: &dyn Fn(&SourceInfo) -> Result<InstallOutcome, InstallError>| {
+                let outcome = install(&discovery.source_for(component_type, name));
+                record(report, component_type, name, target, outcome);
+            };
+
+        for skill in &selected.skills {
+            step(&mut report, ComponentType::Skill, &skill.name, &|source| {
+                install_skill_with_source(skill, target, options, Some(source))
+            });
         }
 
-        // Install agents
         if !selected.agents.is_empty() && !harness_supports_agents(&target.harness) {
             eprintln!(
                 "  ~ Skipping {} agent(s) - not supported by {}",
                 selected.agents.len(),
                 target.harness
             );
+            skip_unsupported(&mut report, selected.agents.iter().map(|a| &a.name), target);
         } else {
             for agent in &selected.agents {
-                match install_agent(agent, target, &options) {
-                    Ok(crate::install::installer::InstallOutcome::Installed(success)) => {
-                        eprintln!("  + Installed agent: {}", success.skill);
-                    }
-                    Ok(crate::install::installer::InstallOutcome::Skipped(skip)) => {
-                        eprintln!("  = Skipped agent: {} (already exists)", skip.skill);
-                    }
-                    Err(e) => {
-                        eprintln!("  ! Error installing agent {}: {}", agent.name, e);
-                    }
-                }
+                step(&mut report, ComponentType::Agent, &agent.name, &|source| {
+                    install_agent_with_source(agent, target, options, Some(source))
+                });
             }
         }
 
-        // Install commands
         if !selected.commands.is_empty() && !harness_supports_commands(&target.harness) {
             eprintln!(
                 "  ~ Skipping {} command(s) - not supported by {}",
                 selected.commands.len(),
                 target.harness
             );
+            skip_unsupported(
+                &mut report,
+                selected.commands.iter().map(|c| &c.name),
+                target,
+            );
         } else {
             for cmd in &selected.commands {
-                match install_command(cmd, target, &options) {
-                    Ok(crate::install::installer::InstallOutcome::Installed(success)) => {
-                        eprintln!("  + Installed command: {}", success.skill);
-                    }
-                    Ok(crate::install::installer::InstallOutcome::Skipped(skip)) => {
-                        eprintln!("  = Skipped command: {} (already exists)", skip.skill);
-                    }
-                    Err(e) => {
-                        eprintln!("  ! Error installing command {}: {}", cmd.name, e);
-                    }
-                }
+                step(&mut report, ComponentType::Command, &cmd.name, &|source| {
+                    install_command_with_source(cmd, target, options, Some(source))
+                });
             }
         }
 
-        // Install MCP servers
-        if !selected.mcp_servers.is_empty() && harness_supports_mcp(&target.harness) {
-            let harness_kind = parse_harness_kind(&target.harness);
-            for (name, server) in &selected.mcp_servers {
+        if !servers.is_empty() && harness_supports_mcp(&target.harness) {
+            for (name, server) in &servers {
                 // Check transport compatibility before attempting installation
-                if let Some(kind) = harness_kind
+                if let Some(kind) = kind
                     && !is_mcp_compatible(server, kind)
                 {
                     let transport = match server {
@@ -248,27 +444,472 @@ pub fn run(source: &str, force: bool) -> Result<()> {
                         "  ~ Skipping MCP server: {} ({} transport not supported by {})",
                         name, transport, target.harness
                     );
+                    skip_unsupported(&mut report, [*name], target);
                     continue;
                 }
-                match install_mcp(name, server, target, &options) {
-                    Ok(McpInstallOutcome::Installed(success)) => {
-                        eprintln!("  + Installed MCP server: {}", success.name);
-                    }
-                    Ok(McpInstallOutcome::Skipped(skip)) => {
-                        eprintln!("  = Skipped MCP server: {} ({:?})", skip.name, skip.reason);
+                step(&mut report, ComponentType::Mcp, name, &|source| {
+                    install_mcp_with_source(name, server, target, options, Some(source)).map(
+                        |outcome| match outcome {
+                            McpInstallOutcome::Installed(success) => {
+                                InstallOutcome::Installed(InstallSuccess {
+                                    skill: success.name,
+                                    target: success.target,
+                                    profile_path: success.profile_path,
+                                    harness_path: success.harness_path,
+                                })
+                            }
+                            McpInstallOutcome::Skipped(skip) => {
+                                InstallOutcome::Skipped(InstallSkip {
+                                    skill: skip.name,
+                                    target: skip.target,
+                                    reason: skip.reason,
+                                })
+                            }
+                        },
+                    )
+                });
+            }
+        } else if !servers.is_empty() {
+            eprintln!("  ~ Skipping MCP servers (harness does not support MCP)");
+            skip_unsupported(&mut report, servers.iter().map(|(n, _)| *n), target);
+        }
+
+        if !selected.hooks.is_empty() && !kind.is_some_and(supports_hooks) {
+            eprintln!(
+                "  ~ Skipping {} hook set(s) - not supported by {}",
+                selected.hooks.len(),
+                target.harness
+            );
+            skip_unsupported(&mut report, selected.hooks.iter().map(|h| &h.name), target);
+        } else {
+            for hook in &selected.hooks {
+                step(&mut report, ComponentType::Hook, &hook.name, &|source| {
+                    install_hook_with_source(hook, target, options, Some(source))
+                });
+            }
+        }
+    }
+
+    report
+}
+
+/// Print and record the outcome of installing one component.
+fn record(
+    report: &mut InstallReport,
+    component_type: ComponentType,
+    name: &str,
+    target: &InstallTarget,
+    outcome: Result<InstallOutcome, InstallError>,
+) {
+    let label = component_label(component_type);
+    let verb = if report.dry_run {
+        "Would install"
+    } else {
+        "Installed"
+    };
+    match outcome {
+        Ok(InstallOutcome::Installed(success)) => {
+            eprintln!("  + {} {}: {}", verb, label, success.skill);
+            report.installed.push(success);
+        }
+        Ok(InstallOutcome::Skipped(skip)) => {
+            eprintln!("  = Skipped {}: {} (already installed)", label, skip.skill);
+            report.skipped.push(skip);
+        }
+        Err(e) => {
+            eprintln!("  ! Error installing {} {}: {}", label, name, e);
+            report.errors.push(InstallFailure {
+                skill: name.to_string(),
+                target: target.clone(),
+                error: e.to_string(),
+            });
+        }
+    }
+}
+
+fn skip_unsupported<'a>(
+    report: &mut InstallReport,
+    names: impl IntoIterator<Item = &'a String>,
+    target: &InstallTarget,
+) {
+    report
+        .skipped
+        .extend(names.into_iter().map(|name| InstallSkip {
+            skill: name.clone(),
+            target: target.clone(),
+            reason: SkipReason::Unsupported,
+        }));
+}
+
+fn component_label(component_type: ComponentType) -> &'static str {
+    match component_type {
+        ComponentType::Skill => "skill",
+        ComponentType::Agent => "agent",
+        ComponentType::Command => "command",
+        ComponentType::Mcp => "MCP server",
+        ComponentType::Hook => "hooks",
+    }
+}
+
+/// Install selected components into a project's harness directories.
+fn install_into_project(
+    selected: &SelectedComponents,
+    discovery: &DiscoveryResult,
+    root: &Path,
+    force: bool,
+    args: &InstallArgs,
+    interactive: bool,
+    dry_run: bool,
+) -> Result<i32> {
+    let kinds = if interactive {
+        select_project_harnesses(root)?
+    } else if args.harnesses.is_empty() {
+        detected_project_harnesses(root)
+    } else {
+        args.harnesses
+            .iter()
+            .map(|id| parse_harness_kind(id).ok_or_else(|| eyre!("Unknown harness: {}", id)))
+            .collect::<Result<_>>()?
+    };
+    if kinds.is_empty() {
+        eprintln!("No harnesses selected");
+        return Ok(0);
+    }
+
+    let options = InstallOptions { force };
+    if !selected.hooks.is_empty() {
+        eprintln!(
+            "~ Skipping {} hook set(s) - hooks are only installed into profiles",
+            selected.hooks.len()
+        );
+    }
+    let mut servers: Vec<_> = selected.mcp_servers.iter().collect();
+    servers.sort_by(|a, b| a.0.cmp(b.0));
+    let (mut failed, mut existing) = (false, false);
+
+    let verb = if dry_run {
+        "Would install"
+    } else {
+        "Installed"
+    };
+    let ((), plan) = plan::record(|| {
+        for kind in kinds {
+            let harness = Harness::new(kind);
+            let harness_id = harness.id();
+            eprintln!("\nInstalling to {} in {}...", harness_id, root.display());
+
+            let components = selected
+                .skills
+                .iter()
+                .map(ProjectComponent::Skill)
+                .chain(selected.agents.iter().map(ProjectComponent::Agent))
+                .chain(selected.commands.iter().map(ProjectComponent::Command))
+                .chain(servers.iter().map(|(n, s)| ProjectComponent::Mcp(n, s)));
+
+            for component in components {
+                let label = component.component_type().dir_name().trim_end_matches('s');
+                let name = component.name();
+                if let ProjectComponent::Mcp(_, server) = component
+                    && !is_mcp_compatible(server, kind)
+                {
+                    eprintln!(
+                        "  ~ Skipping MCP server: {} (not supported by {})",
+                        name, harness_id
+                    );
+                    continue;
+                }
+                let source = discovery.source_for(component.component_type(), name);
+                match install_to_project(component, kind, root, &options, &source) {
+                    Ok(Some(_)) => eprintln!("  + {} {}: {}", verb, label, name),
+                    Ok(None) => {
+                        existing = true;
+                        eprintln!("  = Skipped {}: {} (already exists)", label, name)
                     }
+                    Err(ProjectError::Unsupported { .. }) => eprintln!(
+                        "  ~ Skipping {}: {} - no project location in {}",
+                        label, name, harness_id
+                    ),
                     Err(e) => {
-                        eprintln!("  ! Error installing MCP server {}: {}", name, e);
+                        failed = true;
+                        eprintln!("  ! Error installing {} {}: {}", label, name, e)
                     }
                 }
             }
-        } else if !selected.mcp_servers.is_empty() {
-            eprintln!("  ~ Skipping MCP servers (harness does not support MCP)");
         }
+    });
+    if dry_run {
+        eprintln!();
+        print_plan_text(&plan);
+    } else {
+        crate::cli::plan::apply(&plan)?;
     }
 
     eprintln!("\nDone!");
-    Ok(())
+    Ok(if failed {
+        EXIT_PARTIAL_FAILURE
+    } else if existing {
+        EXIT_ALREADY_INSTALLED
+    } else {
+        0
+    })
+}
+
+/// Installed harnesses already configured in the project.
+fn detected_project_harnesses(root: &Path) -> Vec<HarnessKind> {
+    let scope = Scope::Project(root.to_path_buf());
+    HarnessKind::ALL
+        .iter()
+        .copied()
+        .filter(|kind| {
+            let harness = Harness::new(*kind);
+            harness.is_installed()
+                && harness
+                    .config(&scope)
+                    .is_ok_and(|dir| dir != root && dir.exists())
+        })
+        .collect()
+}
+
+/// Components named by `--skill`/`--agent`/... flags, or everything
+/// discovered when none are given (or with `--all`).
+fn select_named(discovery: &DiscoveryResult, args: &InstallArgs) -> Result<SelectedComponents> {
+    if args.all || !names_components(args) {
+        return Ok(SelectedComponents {
+            skills: discovery.skills.clone(),
+            mcp_servers: discovery.mcp_servers.clone(),
+            agents: discovery.agents.clone(),
+            commands: discovery.commands.clone(),
+            hooks: discovery.hooks.clone(),
+        });
+    }
+
+    let mut missing = Vec::new();
+    let mut pick = |label: &str, wanted: &[String], available: Vec<&str>| -> Vec<usize> {
+        wanted
+            .iter()
+            .filter_map(|name| {
+                let found = available.iter().position(|n| n == name);
+                if found.is_none() {
+                    missing.push(format!("{} '{}'", label, name));
+                }
+                found
+            })
+            .collect()
+    };
+
+    let skills = pick(
+        "skill",
+        &args.skills,
+        discovery.skills.iter().map(|s| s.name.as_str()).collect(),
+    );
+    let agents = pick(
+        "agent",
+        &args.agents,
+        discovery.agents.iter().map(|a| a.name.as_str()).collect(),
+    );
+    let commands = pick(
+        "command",
+        &args.commands,
+        discovery.commands.iter().map(|c| c.name.as_str()).collect(),
+    );
+    let hooks = pick(
+        "hook",
+        &args.hooks,
+        discovery.hooks.iter().map(|h| h.name.as_str()).collect(),
+    );
+    let mcp_names: Vec<&String> = discovery.mcp_servers.keys().collect();
+    let mcps = pick(
+        "MCP server",
+        &args.mcps,
+        mcp_names.iter().map(|n| n.as_str()).collect(),
+    );
+
+    if !missing.is_empty() {
+        return Err(eyre!(
+            "Not found in {}: {}",
+            discovery.source,
+            missing.join(", ")
+        ));
+    }
+
+    Ok(SelectedComponents {
+        skills: skills
+            .into_iter()
+            .map(|i| discovery.skills[i].clone())
+            .collect(),
+        mcp_servers: mcps
+            .into_iter()
+            .map(|i| {
+                let name = mcp_names[i];
+                (name.clone(), discovery.mcp_servers[name].clone())
+            })
+            .collect(),
+        agents: agents
+            .into_iter()
+            .map(|i| discovery.agents[i].clone())
+            .collect(),
+        commands: commands
+            .into_iter()
+            .map(|i| discovery.commands[i].clone())
+            .collect(),
+        hooks: hooks
+            .into_iter()
+            .map(|i| discovery.hooks[i].clone())
+            .collect(),
+    })
+}
+
+/// Target profiles from `--harness`/`--profile`.
+fn resolve_targets(harnesses: &[String], profiles: &[String]) -> Result<Vec<InstallTarget>> {
+    let config = BridleConfig::load()?;
+    let manager = ProfileManager::new(BridleConfig::profiles_dir()?);
+
+    let kinds: Vec<HarnessKind> = if harnesses.is_empty() {
+        HarnessKind::ALL.to_vec()
+    } else {
+        harnesses
+            .iter()
+            .map(|id| parse_harness_kind(id).ok_or_else(|| eyre!("Unknown harness: {}", id)))
+            .collect::<Result<_>>()?
+    };
+
+    let mut available = Vec::new();
+    for kind in kinds {
+        let Ok(harness) = Harness::locate(kind) else {
+            if !harnesses.is_empty() {
+                return Err(eyre!(
+                    "Harness {} is not installed",
+                    Harness::new(kind).id()
+                ));
+            }
+            continue;
+        };
+        let existing = manager.list_profiles(&harness).unwrap_or_default();
+        let active = config.active_profile_for(harness.id()).map(String::from);
+        available.push((harness.id().to_string(), existing, active));
+    }
+
+    targets_from(&available, !harnesses.is_empty(), profiles)
+}
+
+/// Pick targets from `(harness, profiles, active profile)` triples: the
+/// named profiles where they exist, otherwise each harness's active profile.
+/// Harnesses named explicitly must have every requested profile.
+fn targets_from(
+    available: &[(String, Vec<ProfileName>, Option<String>)],
+    explicit: bool,
+    profiles: &[String],
+) -> Result<Vec<InstallTarget>> {
+    let mut targets = Vec::new();
+    for (harness, existing, active) in available {
+        let wanted: Vec<&str> = if profiles.is_empty() {
+            match active {
+                Some(active) => vec![active.as_str()],
+                None if explicit => {
+                    return Err(eyre!("{} has no active profile; pass --profile", harness));
+                }
+                None => continue,
+            }
+        } else {
+            profiles.iter().map(String::as_str).collect()
+        };
+        for name in wanted {
+            match existing.iter().find(|p| p.as_str() == name) {
+                Some(profile) => targets.push(InstallTarget {
+                    harness: harness.clone(),
+                    profile: profile.clone(),
+                }),
+                None if explicit => {
+                    return Err(eyre!("Profile '{}' not found for {}", name, harness));
+                }
+                None => {}
+            }
+        }
+    }
+
+    if targets.is_empty() {
+        return Err(eyre!(
+            "No matching profiles found. Pass --harness and --profile, or create one with: \
+             bridle profile create <harness> <name>"
+        ));
+    }
+    Ok(targets)
+}
+
+/// Pick harnesses to install into; those already configured in the project
+/// are preselected.
+fn select_project_harnesses(root: &Path) -> Result<Vec<HarnessKind>> {
+    let scope = Scope::Project(root.to_path_buf());
+    let kinds: Vec<HarnessKind> = HarnessKind::ALL
+        .iter()
+        .copied()
+        .filter(|kind| Harness::new(*kind).is_installed())
+        .collect();
+    if kinds.is_empty() {
+        return Err(eyre!("No installed harnesses found"));
+    }
+
+    let detected: Vec<bool> = kinds
+        .iter()
+        .map(|kind| {
+            Harness::new(*kind)
+                .config(&scope)
+                .is_ok_and(|dir| dir != root && dir.exists())
+        })
+        .collect();
+    let labels: Vec<String> = kinds
+        .iter()
+        .zip(&detected)
+        .map(|(kind, detected)| {
+            let harness = Harness::new(*kind);
+            let id = harness.id();
+            if *detected {
+                format!("{} (in project)", id)
+            } else {
+                id.to_string()
+            }
+        })
+        .collect();
+
+    let Some(selected) = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Select harnesses to install into (Esc to cancel)")
+        .items(&labels)
+        .defaults(&detected)
+        .interact_opt()?
+    else {
+        return Ok(Vec::new());
+    };
+
+    Ok(selected.into_iter().map(|i| kinds[i]).collect())
+}
+
+/// Pick which of a marketplace's plugins to browse; their components are
+/// offered (all preselected) in the component selector that follows.
+fn select_plugins(plugins: &[PluginInfo]) -> Result<Vec<String>> {
+    let labels: Vec<String> = plugins
+        .iter()
+        .map(|plugin| {
+            let mut label = plugin.name.bold().to_string();
+            if let Some(description) = &plugin.description {
+                label.push_str(&format!(" - {}", description));
+            }
+            label.push_str(&format!(" {}", format!("({})", plugin.summary()).dimmed()));
+            label
+        })
+        .collect();
+
+    let Some(selected) = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Select plugins to install (Esc to cancel)")
+        .items(&labels)
+        .interact_opt()?
+    else {
+        return Ok(Vec::new());
+    };
+
+    Ok(selected
+        .into_iter()
+        .map(|i| plugins[i].name.clone())
+        .collect())
 }
 
 /// Select components to install using grouped multi-select UI
@@ -276,36 +917,72 @@ fn select_components(discovery: &DiscoveryResult) -> Result<SelectedComponents>
     // Build groups for each non-empty category
     let mut groups: Vec<(&str, Vec<String>, Vec<usize>)> = Vec::new();
 
+    let label = |component_type: ComponentType, name: &str| match discovery
+        .plugin_of(component_type, name)
+    {
+        Some(plugin) if discovery.plugins.len() > 1 => {
+            format!("{} {}", name, format!("({})", plugin.name).dimmed())
+        }
+        _ => name.to_string(),
+    };
+
     if !discovery.skills.is_empty() {
-        let names: Vec<String> = discovery.skills.iter().map(|s| s.name.clone()).collect();
+        let names: Vec<String> = discovery
+            .skills
+            .iter()
+            .map(|s| label(ComponentType::Skill, &s.name))
+            .collect();
         let indices: Vec<usize> = (0..discovery.skills.len()).collect();
         groups.push(("Skills", names, indices));
     }
 
     if !discovery.mcp_servers.is_empty() {
-        let names: Vec<String> = discovery.mcp_servers.keys().cloned().collect();
+        let names: Vec<String> = discovery
+            .mcp_servers
+            .keys()
+            .map(|n| label(ComponentType::Mcp, n))
+            .collect();
         let indices: Vec<usize> = (0..discovery.mcp_servers.len()).collect();
         groups.push(("MCP Servers", names, indices));
     }
 
     if !discovery.agents.is_empty() {
-        let names: Vec<String> = discovery.agents.iter().map(|a| a.name.clone()).collect();
+        let names: Vec<String> = discovery
+            .agents
+            .iter()
+            .map(|a| label(ComponentType::Agent, &a.name))
+            .collect();
         let indices: Vec<usize> = (0..discovery.agents.len()).collect();
         groups.push(("Agents", names, indices));
     }
 
     if !discovery.commands.is_empty() {
-        let names: Vec<String> = discovery.commands.iter().map(|c| c.name.clone()).collect();
+        let names: Vec<String> = discovery
+            .commands
+            .iter()
+            .map(|c| label(ComponentType::Command, &c.name))
+            .collect();
         let indices: Vec<usize> = (0..discovery.commands.len()).collect();
         groups.push(("Commands", names, indices));
     }
 
+    if !discovery.hooks.is_empty() {
+        let names: Vec<String> = discovery
+            .hooks
+            .iter()
+            .map(|h| label(ComponentType::Hook, &h.name))
+            .collect();
+        let indices: Vec<usize> = (0..discovery.hooks.len()).collect();
+        groups.push(("Hooks", names, indices));
+    }
+
     if groups.is_empty() {
         return Ok(SelectedComponents {
             skills: Vec::new(),
             mcp_servers: HashMap::new(),
             agents: Vec::new(),
             commands: Vec::new(),
+            hooks: Vec::new(),
         });
     }
 
@@ -332,6 +1009,7 @@ fn select_components(discovery: &DiscoveryResult) -> Result<SelectedComponents>
             mcp_servers: HashMap::new(),
             agents: Vec::new(),
             commands: Vec::new(),
+            hooks: Vec::new(),
         });
     };
 
@@ -341,6 +1019,7 @@ fn select_components(discovery: &DiscoveryResult) -> Result<SelectedComponents>
         mcp_servers: HashMap::new(),
         agents: Vec::new(),
         commands: Vec::new(),
+        hooks: Vec::new(),
     };
 
     for (group_idx, selected_indices) in selections.iter().enumerate() {
@@ -368,6 +1047,11 @@ fn select_components(discovery: &DiscoveryResult) -> Result<SelectedComponents>
                     selected.commands.push(discovery.commands[idx].clone());
                 }
             }
+            "Hooks" => {
+                for &idx in selected_indices {
+                    selected.hooks.push(discovery.hooks[idx].clone());
+                }
+            }
             _ => {}
         }
     }
@@ -375,8 +1059,75 @@ fn select_components(discovery: &DiscoveryResult) -> Result<SelectedComponents>
     Ok(selected)
 }
 
+/// Shows every command the selected hooks would run and asks for
+/// confirmation, since hooks execute them without further prompting.
+fn confirm_hooks(hooks: &[HookInfo]) -> Result<bool> {
+    show_hook_commands(hooks);
+    Ok(Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Install these hooks?")
+        .default(false)
+        .interact_opt()?
+        .unwrap_or(false))
+}
+
+fn show_hook_commands(hooks: &[HookInfo]) {
+    eprintln!("\nHooks run these commands automatically:");
+    for hook in hooks {
+        eprintln!("  {}", hook.name.bold());
+        for line in hook_commands(hook) {
+            eprintln!("    {}", line);
+        }
+    }
+}
+
+/// Asks which of the user's environment variables feeds each variable a
+/// registry server needs.
+///
+/// Answers are recorded as `EnvValue::EnvRef`, so secrets never end up in
+/// harness configs or profiles.
+fn prompt_env_refs(
+    servers: &mut HashMap<String, McpServer>,
+    descriptions: &HashMap<String, String>,
+) -> Result<()> {
+    let mut names: Vec<String> = servers.keys().cloned().collect();
+    names.sort();
+    for name in names {
+        let Some(McpServer::Stdio(server)) = servers.get_mut(&name) else {
+            continue;
+        };
+        let mut keys: Vec<String> = server
+            .env
+            .iter()
+            .filter(|(_, value)| value.is_env_ref())
+            .map(|(key, _)| key.clone())
+            .collect();
+        keys.sort();
+        if keys.is_empty() {
+            continue;
+        }
+
+        eprintln!("\n{} needs environment variables:", name.bold());
+        for key in keys {
+            if let Some(description) = descriptions.get(&key).filter(|d| !d.is_empty()) {
+                eprintln!("  {}: {}", key, description);
+            }
+            let var: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Read {} from environment variable", key))
+                .default(key.clone())
+                .interact_text()?;
+            if std::env::var_os(&var).is_none() {
+                eprintln!("  ~ {} is not set in this shell", var);
+            }
+            server.env.insert(key, EnvValue::env(var));
+        }
+    }
+    Ok(())
+}
+
 fn normalize_source(source: &str) -> String {
-    if source.starts_with("http://") || source.starts_with("https://") {
+    if is_local_path(source) {
+        absolute_local_path(source)
+    } else if source.starts_with("http://") || source.starts_with("https://") {
         source.to_string()
     } else if source.contains('/') && !source.contains(':') {
         format!("https://github.com/{}", source)
@@ -385,6 +1136,40 @@ fn normalize_source(source: &str) -> String {
     }
 }
 
+/// Whether a source refers to the local filesystem rather than GitHub shorthand.
+///
+/// Only an explicit `./`, `../`, `/`, `~` or `file://` prefix counts, so a
+/// directory that happens to be named like `owner/repo` never shadows the
+/// repository.
+fn is_local_path(source: &str) -> bool {
+    matches!(source, "." | "..")
+        || source.starts_with("./")
+        || source.starts_with("../")
+        || source.starts_with('/')
+        || source.starts_with('~')
+        || source.starts_with("file://")
+}
+
+/// Resolves a local source to its canonical absolute path, so the manifest
+/// and lockfile don't record a path relative to wherever `install` ran.
+///
+/// Paths that don't exist are returned unchanged for discovery to report.
+fn absolute_local_path(source: &str) -> String {
+    let path = source.strip_prefix("file://").unwrap_or(source);
+    let path = match path.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            match harness_locate::platform::home_dir() {
+                Ok(home) => home.join(rest.trim_start_matches('/')),
+                Err(_) => return source.to_string(),
+            }
+        }
+        _ => Path::new(path).to_path_buf(),
+    };
+    std::fs::canonicalize(path)
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| source.to_string())
+}
+
 fn select_targets(selected: &SelectedComponents) -> Result<Vec<InstallTarget>> {
     let config = BridleConfig::load()?;
     let profiles_dir = BridleConfig::profiles_dir()?;
@@ -419,10 +1204,12 @@ fn select_targets(selected: &SelectedComponents) -> Result<Vec<InstallTarget>> {
         let supports_skills = harness_supports_skills(harness_id);
         let supports_agents = harness_supports_agents(harness_id);
         let supports_commands = harness_supports_commands(harness_id);
+        let hooks_supported = supports_hooks(*kind);
 
         let can_install_skills = supports_skills && !selected.skills.is_empty();
         let can_install_agents = supports_agents && !selected.agents.is_empty();
         let can_install_commands = supports_commands && !selected.commands.is_empty();
+        let can_install_hooks = hooks_supported && !selected.hooks.is_empty();
         let incompatible_mcp_count = count_incompatible_mcps(&selected.mcp_servers, *kind);
         let compatible_mcp_count = selected.mcp_servers.len() - incompatible_mcp_count;
         let can_install_mcp = compatible_mcp_count > 0;
@@ -434,6 +1221,7 @@ fn select_targets(selected: &SelectedComponents) -> Result<Vec<InstallTarget>> {
         let can_install_anything = can_install_skills
             || can_install_agents
             || can_install_commands
+            || can_install_hooks
             || (can_install_mcp && !claude_mcp_in_dev);
 
         let mut skipped: Vec<&str> = Vec::new();
@@ -443,6 +1231,9 @@ fn select_targets(selected: &SelectedComponents) -> Result<Vec<InstallTarget>> {
         if !selected.commands.is_empty() && !supports_commands {
             skipped.push("commands");
         }
+        if !selected.hooks.is_empty() && !hooks_supported {
+            skipped.push("hooks");
+        }
 
         let incompatible_agent_count = if supports_agents && !selected.agents.is_empty() {
             count_incompatible_agents(&selected.agents, *kind)
@@ -561,6 +1352,7 @@ fn select_targets(selected: &SelectedComponents) -> Result<Vec<InstallTarget>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::install::fixtures::{self, skill};
 
     #[test]
     fn normalize_source_handles_shorthand() {
@@ -581,4 +1373,135 @@ mod tests {
         let url = "http://example.com/repo";
         assert_eq!(normalize_source(url), url);
     }
+
+    #[test]
+    fn normalize_source_makes_local_paths_absolute() {
+        let here = std::env::current_dir().unwrap().canonicalize().unwrap();
+        let cli = here.join("src/cli").display().to_string();
+        assert_eq!(normalize_source("./src/cli"), cli);
+        assert_eq!(normalize_source("./src/../src/cli"), cli);
+        assert_eq!(normalize_source(&format!("file://{cli}")), cli);
+        assert_eq!(normalize_source("."), here.display().to_string());
+
+        // Missing paths are left for discovery to report
+        assert_eq!(normalize_source("./my/skills"), "./my/skills");
+        assert_eq!(normalize_source("/srv/skills"), "/srv/skills");
+    }
+
+    #[test]
+    fn local_paths_need_an_explicit_prefix() {
+        // `src/cli` exists relative to the crate dir tests run in, but it
+        // is still read as GitHub shorthand
+        assert!(std::path::Path::new("src/cli").is_dir());
+        assert!(!is_local_path("src/cli"));
+        assert_eq!(normalize_source("src/cli"), "https://github.com/src/cli");
+
+        for source in [
+            ".",
+            "./src/cli",
+            "../skills",
+            "/srv/skills",
+            "~/skills",
+            "file:///srv/skills",
+        ] {
+            assert!(is_local_path(source), "{source}");
+        }
+        assert!(!is_local_path("file:skills"));
+    }
+
+    #[test]
+    fn normalize_source_preserves_git_and_file_urls() {
+        for source in [
+            "git+https://gitlab.com/team/skills.git#v1",
+            "git+ssh://git@gitlab.com/team/skills.git",
+            "git@gitlab.com:team/skills.git",
+            "file:///srv/skills",
+        ] {
+            assert_eq!(normalize_source(source), source);
+        }
+    }
+
+    fn discovery() -> DiscoveryResult {
+        fixtures::discovery(vec![skill("alpha", ""), skill("beta", "")])
+    }
+
+    #[test]
+    fn select_named_picks_flagged_components() {
+        let args = InstallArgs {
+            skills: vec!["beta".to_string()],
+            ..Default::default()
+        };
+        let selected = select_named(&discovery(), &args).unwrap();
+        let names: Vec<&str> = selected.skills.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["beta"]);
+
+        let all = select_named(&discovery(), &InstallArgs::default()).unwrap();
+        assert_eq!(all.skills.len(), 2);
+    }
+
+    #[test]
+    fn select_named_rejects_unknown_names() {
+        let args = InstallArgs {
+            skills: vec!["alpha".to_string(), "gamma".to_string()],
+            mcps: vec!["db".to_string()],
+            ..Default::default()
+        };
+        let Err(err) = select_named(&discovery(), &args) else {
+            panic!("unknown names should be rejected");
+        };
+        let err = err.to_string();
+        assert!(err.contains("skill 'gamma'"), "{}", err);
+        assert!(err.contains("MCP server 'db'"), "{}", err);
+    }
+
+    #[test]
+    fn targets_default_to_active_profiles() {
+        let profile = |name: &str| ProfileName::new(name).unwrap();
+        let available = vec![
+            (
+                "claude-code".to_string(),
+                vec![profile("work"), profile("home")],
+                Some("work".to_string()),
+            ),
+            ("opencode".to_string(), vec![profile("home")], None),
+        ];
+
+        let targets = targets_from(&available, false, &[]).unwrap();
+        assert_eq!(targets.len(), 1);
+        assert_eq!(targets[0].profile.as_str(), "work");
+
+        let targets = targets_from(&available, false, &["home".to_string()]).unwrap();
+        let harnesses: Vec<&str> = targets.iter().map(|t| t.harness.as_str()).collect();
+        assert_eq!(harnesses, vec!["claude-code", "opencode"]);
+
+        assert!(targets_from(&available, true, &[]).is_err());
+        assert!(targets_from(&available[..1], true, &["work".to_string()]).is_ok());
+        assert!(targets_from(&available[..1], true, &["play".to_string()]).is_err());
+    }
+
+    #[test]
+    fn exit_code_reflects_failures_and_skips() {
+        let target = InstallTarget {
+            harness: "claude-code".to_string(),
+            profile: ProfileName::new("work").unwrap(),
+        };
+        let skip = |reason| InstallSkip {
+            skill: "alpha".to_string(),
+            target: target.clone(),
+            reason,
+        };
+
+        let mut report = InstallReport::default();
+        assert_eq!(exit_code(&report), 0);
+        report.skipped.push(skip(SkipReason::Unsupported));
+        assert_eq!(exit_code(&report), 0);
+        report.skipped.push(skip(SkipReason::AlreadyExists));
+        assert_eq!(exit_code(&report), EXIT_ALREADY_INSTALLED);
+        report.errors.push(InstallFailure {
+            skill: "beta".to_string(),
+            target: target.clone(),
+            error: "boom".to_string(),
+        });
+        assert_eq!(exit_code(&report), EXIT_PARTIAL_FAILURE);
+    }
 }
@@ -0,0 +1,62 @@
+//! CLI marketplace command implementation.
+
+use color_eyre::eyre::Result;
+use colored::Colorize;
+
+use crate::cli::output::{ResolvedFormat, output, output_list};
+use crate::cli::plan;
+use crate::install::marketplace;
+
+pub fn add(source: &str, name: Option<&str>, dry_run: bool, format: ResolvedFormat) -> Result<()> {
+    let Some((subscription, plugins)) = plan::run(dry_run, format, || -> Result<_> {
+        Ok(marketplace::subscribe(source, name)?)
+    })?
+    else {
+        return Ok(());
+    };
+    output(&subscription, format, |subscription| {
+        println!(
+            "Added marketplace {} ({}, {} plugin(s))",
+            subscription.name.bold(),
+            subscription.source,
+            plugins
+        );
+        println!(
+            "Install with: bridle install {} or bridle install <plugin>@{}",
+            subscription.name, subscription.name
+        );
+    });
+    Ok(())
+}
+
+pub fn list(format: ResolvedFormat) -> Result<()> {
+    let subscriptions = marketplace::subscriptions();
+    output_list(&subscriptions, format, |subscriptions| {
+        if subscriptions.is_empty() {
+            println!("No marketplaces added");
+            println!("Add one with: bridle marketplace add <source>");
+            return;
+        }
+        for subscription in subscriptions {
+            println!(
+                "{}  {}",
+                subscription.name.bold(),
+                subscription.source.dimmed()
+            );
+        }
+    });
+    Ok(())
+}
+
+pub fn remove(name: &str, dry_run: bool, format: ResolvedFormat) -> Result<()> {
+    let Some(removed) = plan::run(dry_run, format, || -> Result<_> {
+        Ok(marketplace::unsubscribe(name)?)
+    })?
+    else {
+        return Ok(());
+    };
+    output(&removed, format, |removed| {
+        println!("Removed marketplace {}", removed.name.bold());
+    });
+    Ok(())
+}
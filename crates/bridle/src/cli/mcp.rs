@@ -0,0 +1,613 @@
+//! CLI mcp command implementation.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use color_eyre::eyre::{Result, eyre};
+use colored::Colorize;
+use harness_locate::{
+    EnvValue, Harness, HarnessKind, HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer,
+};
+use serde::Serialize;
+
+use crate::cli::McpServerArgs;
+use crate::cli::output::{ResolvedFormat, output_list};
+use crate::cli::plan;
+use crate::cli::profile::resolve_harness;
+use crate::config::BridleConfig;
+use crate::harness::HarnessConfig;
+use crate::install::mcp_edit::{
+    DISABLED_FILE, McpEditError, McpEntry, ToggleMode, add_server, list_servers, remove_server,
+    rename_server, set_server_enabled,
+};
+use crate::install::mcp_probe::{ProbeReport, probe_profile};
+
+/// Servers of one harness's profile, for `mcp list`.
+#[derive(Debug, Serialize)]
+struct ProfileServers {
+    harness: String,
+    profile: String,
+    servers: Vec<McpEntry>,
+}
+
+/// Outcome of an edit on one harness.
+#[derive(Debug, Serialize)]
+struct EditResult {
+    harness: String,
+    profile: String,
+    name: String,
+    /// New name, for `mv`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mode: Option<ToggleMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Harnesses named by a `<harness|all>` argument.
+///
+/// `all` means every harness that has a profile with this name.
+fn target_kinds(
+    harness_name: &str,
+    profile: &str,
+    profiles_dir: &Path,
+) -> Result<Vec<HarnessKind>> {
+    if harness_name != "all" {
+        return Ok(vec![resolve_harness(harness_name)?.kind()]);
+    }
+    let kinds: Vec<HarnessKind> = HarnessKind::ALL
+        .iter()
+        .copied()
+        .filter(|kind| {
+            let harness = Harness::new(*kind);
+            profiles_dir.join(harness.id()).join(profile).is_dir()
+        })
+        .collect();
+    if kinds.is_empty() {
+        return Err(eyre!("No harness has a profile named '{}'", profile));
+    }
+    Ok(kinds)
+}
+
+/// Applies an edit to each target harness.
+///
+/// With a single harness any error is returned as is. With `all`, harnesses
+/// that don't have the server are skipped and other errors are reported per
+/// harness; the command fails only if no harness was edited.
+fn edit_each(
+    harness_name: &str,
+    profile: &str,
+    profiles_dir: &Path,
+    name: &str,
+    mut edit: impl FnMut(HarnessKind) -> std::result::Result<Option<ToggleMode>, McpEditError>,
+) -> Result<Vec<EditResult>> {
+    let all = harness_name == "all";
+    let mut results = Vec::new();
+    for kind in target_kinds(harness_name, profile, profiles_dir)? {
+        let harness = Harness::new(kind);
+        let (mode, error) = match edit(kind) {
+            Ok(mode) => (mode, None),
+            Err(e) if !all => return Err(e.into()),
+            Err(McpEditError::NotFound { .. }) => continue,
+            Err(e) => (None, Some(e.to_string())),
+        };
+        results.push(EditResult {
+            harness: harness.id().to_string(),
+            profile: profile.to_string(),
+            name: name.to_string(),
+            to: None,
+            mode,
+            error,
+        });
+    }
+    if results.is_empty() {
+        return Err(eyre!(
+            "MCP server '{}' not found in any '{}' profile",
+            name,
+            profile
+        ));
+    }
+    Ok(results)
+}
+
+fn report_edits(results: &[EditResult], format: ResolvedFormat, verb: &str) -> Result<()> {
+    let failed = results.iter().filter(|r| r.error.is_some()).count();
+    output_list(results, format, |results| {
+        for result in results {
+            let location = format!("{}/{}", result.harness, result.profile);
+            let name = match &result.to {
+                Some(to) => format!("{} → {}", result.name, to),
+                None => result.name.clone(),
+            };
+            match (&result.error, result.mode) {
+                (Some(error), _) => println!("  {} {}: {}", "✗".red(), location, error),
+                (None, Some(ToggleMode::Unchanged)) => {
+                    println!("  {} already {} in {}", name, verb, location)
+                }
+                (None, Some(ToggleMode::Emulated)) => println!(
+                    "  {} {} {} in {} (via {})",
+                    "✓".green(),
+                    capitalize(verb),
+                    name,
+                    location,
+                    DISABLED_FILE
+                ),
+                (None, _) => println!(
+                    "  {} {} {} in {}",
+                    "✓".green(),
+                    capitalize(verb),
+                    name,
+                    location
+                ),
+            }
+        }
+    });
+    if failed == results.len() {
+        return Err(eyre!("No profile was changed"));
+    }
+    Ok(())
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn parse_pairs(values: &[String], flag: &str) -> Result<Vec<(String, String)>> {
+    values
+        .iter()
+        .map(|value| {
+            value
+                .split_once('=')
+                .filter(|(key, _)| !key.is_empty())
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .ok_or_else(|| eyre!("{} expects KEY=VALUE, got '{}'", flag, value))
+        })
+        .collect()
+}
+
+fn server_from_args(args: &McpServerArgs) -> Result<McpServer> {
+    if let Some(url) = &args.url {
+        let headers: HashMap<String, EnvValue> = parse_pairs(&args.headers, "--header")?
+            .into_iter()
+            .map(|(key, value)| (key, EnvValue::plain(value)))
+            .collect();
+        return Ok(match args.transport.as_deref() {
+            Some("sse") => McpServer::Sse(SseMcpServer {
+                url: url.clone(),
+                headers,
+                enabled: true,
+                timeout_ms: args.timeout_ms,
+            }),
+            _ => McpServer::Http(HttpMcpServer {
+                url: url.clone(),
+                headers,
+                oauth: None,
+                enabled: true,
+                timeout_ms: args.timeout_ms,
+            }),
+        });
+    }
+
+    let Some((command, command_args)) = args.command.split_first() else {
+        return Err(eyre!("Give a command after `--` or a --url"));
+    };
+    let mut env: HashMap<String, EnvValue> = parse_pairs(&args.env, "--env")?
+        .into_iter()
+        .map(|(key, value)| (key, EnvValue::plain(value)))
+        .collect();
+    for (key, var) in parse_pairs(&args.env_refs, "--env-ref")? {
+        env.insert(key, EnvValue::env(var));
+    }
+    Ok(McpServer::Stdio(StdioMcpServer {
+        command: command.clone(),
+        args: command_args.to_vec(),
+        env,
+        cwd: args.cwd.clone(),
+        enabled: true,
+        timeout_ms: args.timeout_ms,
+    }))
+}
+
+pub fn list(harness_name: &str, profile: &str, format: ResolvedFormat) -> Result<()> {
+    let profiles_dir = BridleConfig::profiles_dir()?;
+    let mut profiles = Vec::new();
+    for kind in target_kinds(harness_name, profile, &profiles_dir)? {
+        let harness = Harness::new(kind);
+        profiles.push(ProfileServers {
+            harness: harness.id().to_string(),
+            profile: profile.to_string(),
+            servers: list_servers(kind, profile, &profiles_dir)?,
+        });
+    }
+
+    output_list(&profiles, format, |profiles| {
+        for entry in profiles {
+            println!("{}/{}:", entry.harness, entry.profile);
+            if entry.servers.is_empty() {
+                println!("  (no MCP servers)");
+            }
+            for server in &entry.servers {
+                let state = if server.enabled {
+                    "enabled".green()
+                } else {
+                    "disabled".yellow()
+                };
+                match &server.error {
+                    Some(error) => println!("  {:<20} {}", server.name, error.red()),
+                    None => println!(
+                        "  {:<20} {:<6} {:<8} {}",
+                        server.name, server.transport, state, server.target
+                    ),
+                }
+            }
+        }
+    });
+    Ok(())
+}
+
+pub fn add(
+    harness_name: &str,
+    profile: &str,
+    name: &str,
+    args: &McpServerArgs,
+    force: bool,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let server = server_from_args(args)?;
+    let profiles_dir = BridleConfig::profiles_dir()?;
+    let Some(results) = plan::run(dry_run, format, || {
+        edit_each(harness_name, profile, &profiles_dir, name, |kind| {
+            add_server(kind, profile, &profiles_dir, name, &server, force).map(|_| None)
+        })
+    })?
+    else {
+        return Ok(());
+    };
+    report_edits(&results, format, "added")
+}
+
+pub fn remove(
+    harness_name: &str,
+    profile: &str,
+    name: &str,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let profiles_dir = BridleConfig::profiles_dir()?;
+    let Some(results) = plan::run(dry_run, format, || {
+        edit_each(harness_name, profile, &profiles_dir, name, |kind| {
+            remove_server(kind, profile, &profiles_dir, name).map(|_| None)
+        })
+    })?
+    else {
+        return Ok(());
+    };
+    report_edits(&results, format, "removed")
+}
+
+pub fn set_enabled(
+    harness_name: &str,
+    profile: &str,
+    name: &str,
+    enabled: bool,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let profiles_dir = BridleConfig::profiles_dir()?;
+    let Some(results) = plan::run(dry_run, format, || {
+        edit_each(harness_name, profile, &profiles_dir, name, |kind| {
+            set_server_enabled(kind, profile, &profiles_dir, name, enabled).map(Some)
+        })
+    })?
+    else {
+        return Ok(());
+    };
+    report_edits(
+        &results,
+        format,
+        if enabled { "enabled" } else { "disabled" },
+    )
+}
+
+pub fn rename(
+    harness_name: &str,
+    profile: &str,
+    from: &str,
+    to: &str,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let profiles_dir = BridleConfig::profiles_dir()?;
+    let Some(mut results) = plan::run(dry_run, format, || {
+        edit_each(harness_name, profile, &profiles_dir, from, |kind| {
+            rename_server(kind, profile, &profiles_dir, from, to).map(|_| None)
+        })
+    })?
+    else {
+        return Ok(());
+    };
+    for result in &mut results {
+        result.to = Some(to.to_string());
+    }
+    report_edits(&results, format, "renamed")
+}
+
+pub fn test(
+    harness_name: &str,
+    profile: &str,
+    server: Option<&str>,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let reports = probe_profile(
+        harness.kind(),
+        profile,
+        &BridleConfig::profiles_dir()?,
+        server,
+    )?;
+    let failed = reports.iter().filter(|r| !r.ok).count();
+
+    output_list(&reports, format, |reports| {
+        if reports.is_empty() {
+            println!("No MCP servers in {}/{}", harness_name, profile);
+            return;
+        }
+        for report in reports {
+            print_report(report);
+        }
+        println!();
+        println!("{} passed, {} failed", reports.len() - failed, failed);
+    });
+
+    if failed > 0 {
+        return Err(eyre!("{} MCP server(s) failed", failed));
+    }
+    Ok(())
+}
+
+fn print_report(report: &ProbeReport) {
+    let disabled = if report.enabled { "" } else { " [disabled]" };
+    if !report.ok {
+        println!(
+            "{} {} ({}){}",
+            "✗".red(),
+            report.server.bold(),
+            report.transport,
+            disabled
+        );
+        if let Some(error) = &report.error {
+            println!("    {}", error.red());
+        }
+        if let Some(stderr) = &report.stderr {
+            println!("    stderr:");
+            for line in stderr.lines() {
+                println!("      {}", line.dimmed());
+            }
+        }
+        return;
+    }
+
+    let server_info = report
+        .server_info
+        .as_ref()
+        .map(|info| match &info.version {
+            Some(version) => format!("  {} {}", info.name, version),
+            None => format!("  {}", info.name),
+        })
+        .unwrap_or_default();
+    println!(
+        "{} {} ({}){}  {}ms  protocol {}{}",
+        "✓".green(),
+        report.server.bold(),
+        report.transport,
+        disabled,
+        report.latency_ms.unwrap_or(report.total_ms),
+        report.protocol_version.as_deref().unwrap_or("?"),
+        server_info
+    );
+    for (label, items) in [
+        ("tools", &report.tools),
+        ("resources", &report.resources),
+        ("prompts", &report.prompts),
+    ] {
+        if !items.is_empty() {
+            println!("    {} ({}): {}", label, items.len(), items.join(", "));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::install::mcp_config::read_mcp_config;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn args(command: &[&str]) -> McpServerArgs {
+        McpServerArgs {
+            url: None,
+            transport: None,
+            headers: Vec::new(),
+            env: Vec::new(),
+            env_refs: Vec::new(),
+            cwd: None,
+            timeout_ms: None,
+            command: command.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn profiles(temp: &TempDir, harnesses: &[&str]) -> std::path::PathBuf {
+        let profiles = temp.path().join("profiles");
+        for harness in harnesses {
+            fs::create_dir_all(profiles.join(harness).join("work")).unwrap();
+        }
+        profiles
+    }
+
+    #[test]
+    fn stdio_server_from_args() {
+        let mut args = args(&["npx", "-y", "server"]);
+        args.env = vec!["LOG=debug".to_string(), "EMPTY=".to_string()];
+        args.env_refs = vec!["TOKEN=GITHUB_TOKEN".to_string()];
+        args.timeout_ms = Some(5000);
+
+        let McpServer::Stdio(server) = server_from_args(&args).unwrap() else {
+            panic!("expected a stdio server");
+        };
+        assert_eq!(server.command, "npx");
+        assert_eq!(server.args, ["-y", "server"]);
+        assert_eq!(server.env["LOG"], EnvValue::plain("debug"));
+        assert_eq!(server.env["EMPTY"], EnvValue::plain(""));
+        assert_eq!(server.env["TOKEN"], EnvValue::env("GITHUB_TOKEN"));
+        assert_eq!(server.timeout_ms, Some(5000));
+    }
+
+    #[test]
+    fn remote_server_from_args() {
+        let mut args = args(&[]);
+        args.url = Some("https://mcp.example".to_string());
+        args.headers = vec!["Accept=application/json".to_string()];
+        let McpServer::Http(server) = server_from_args(&args).unwrap() else {
+            panic!("expected an http server");
+        };
+        assert_eq!(server.url, "https://mcp.example");
+        assert_eq!(
+            server.headers["Accept"],
+            EnvValue::plain("application/json")
+        );
+
+        args.transport = Some("sse".to_string());
+        assert!(matches!(server_from_args(&args), Ok(McpServer::Sse(_))));
+    }
+
+    #[test]
+    fn server_args_need_a_command_and_key_value_pairs() {
+        let err = server_from_args(&args(&[])).unwrap_err();
+        assert!(err.to_string().contains("--url"), "{err}");
+
+        let mut bad = args(&["server"]);
+        bad.env = vec!["NO_EQUALS".to_string()];
+        let err = server_from_args(&bad).unwrap_err();
+        assert!(err.to_string().contains("--env expects KEY=VALUE"), "{err}");
+
+        bad.env = vec!["=value".to_string()];
+        assert!(server_from_args(&bad).is_err());
+    }
+
+    #[test]
+    fn all_targets_every_harness_with_the_profile() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profiles(&temp, &["claude-code", "opencode"]);
+
+        let kinds = target_kinds("all", "work", &profiles).unwrap();
+        assert_eq!(kinds.len(), 2);
+        assert!(kinds.contains(&HarnessKind::ClaudeCode));
+        assert!(kinds.contains(&HarnessKind::OpenCode));
+        assert!(target_kinds("all", "missing", &profiles).is_err());
+    }
+
+    #[test]
+    fn edits_on_all_skip_harnesses_without_the_server() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profiles(&temp, &["claude-code", "opencode"]);
+        let server = server_from_args(&args(&["fs-server"])).unwrap();
+        add_server(
+            HarnessKind::ClaudeCode,
+            "work",
+            &profiles,
+            "fs",
+            &server,
+            false,
+        )
+        .unwrap();
+
+        let results = edit_each("all", "work", &profiles, "fs", |kind| {
+            remove_server(kind, "work", &profiles, "fs").map(|_| None)
+        })
+        .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].harness, "claude-code");
+        assert!(results[0].error.is_none());
+
+        let err = edit_each("all", "work", &profiles, "fs", |kind| {
+            remove_server(kind, "work", &profiles, "fs").map(|_| None)
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("not found in any"), "{err}");
+    }
+
+    #[test]
+    fn edits_on_one_harness_return_its_error() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profiles(&temp, &["claude-code"]);
+        let err = edit_each("claude-code", "work", &profiles, "fs", |kind| {
+            set_server_enabled(kind, "work", &profiles, "fs", false).map(Some)
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("not found"), "{err}");
+    }
+
+    #[test]
+    fn edits_on_all_report_per_harness_failures() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profiles(&temp, &["claude-code", "opencode"]);
+        let server = server_from_args(&args(&["fs-server"])).unwrap();
+        add_server(
+            HarnessKind::OpenCode,
+            "work",
+            &profiles,
+            "fs",
+            &server,
+            false,
+        )
+        .unwrap();
+
+        // Adding without --force fails where the server exists and succeeds
+        // elsewhere
+        let results = edit_each("all", "work", &profiles, "fs", |kind| {
+            add_server(kind, "work", &profiles, "fs", &server, false).map(|_| None)
+        })
+        .unwrap();
+        let failed: Vec<&str> = results
+            .iter()
+            .filter(|r| r.error.is_some())
+            .map(|r| r.harness.as_str())
+            .collect();
+        assert_eq!(failed, ["opencode"]);
+        assert!(report_edits(&results, ResolvedFormat::Json, "added").is_ok());
+
+        let results = edit_each("all", "work", &profiles, "fs", |kind| {
+            add_server(kind, "work", &profiles, "fs", &server, false).map(|_| None)
+        })
+        .unwrap();
+        assert!(report_edits(&results, ResolvedFormat::Json, "added").is_err());
+    }
+
+    #[test]
+    fn dry_run_edits_write_nothing() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profiles(&temp, &["claude-code"]);
+        let server = server_from_args(&args(&["fs-server"])).unwrap();
+
+        let (result, plan) = crate::plan::record(|| {
+            edit_each("claude-code", "work", &profiles, "fs", |kind| {
+                add_server(kind, "work", &profiles, "fs", &server, false).map(|_| None)
+            })
+        });
+        result.unwrap();
+        assert!(!plan.is_empty());
+        let config = profiles.join("claude-code/work/.mcp.json");
+        assert!(!config.exists());
+
+        plan.apply_with_journal(&temp.path().join("journal.json"))
+            .unwrap();
+        let servers = read_mcp_config(HarnessKind::ClaudeCode, &config).unwrap();
+        assert_eq!(servers["fs"]["command"], "fs-server");
+    }
+}
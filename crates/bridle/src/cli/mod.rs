@@ -1,13 +1,28 @@
 //! CLI module for bridle.
 
+pub mod apply;
+pub mod backup;
 mod commands;
 pub mod config_cmd;
+pub mod doctor;
+pub mod exec;
+pub mod hook;
 pub mod init;
 pub mod install;
+pub mod marketplace;
+pub mod mcp;
 pub mod output;
+pub mod plan;
 pub mod profile;
+pub mod search;
 pub mod status;
+pub mod sync;
 pub mod tui;
 pub mod uninstall;
+pub mod update;
+pub mod workspace;
 
-pub use commands::{Commands, ConfigCommands, ProfileCommands};
+pub use commands::{
+    BackupCommands, Commands, ConfigCommands, InstallArgs, MarketplaceCommands, McpCommands,
+    McpServerArgs, ProfileCommands, WorkspaceCommands,
+};
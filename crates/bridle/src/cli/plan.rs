@@ -0,0 +1,101 @@
+//! Rendering and running planned changes for `--dry-run`.
+
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+
+use crate::cli::output::{ResolvedFormat, output};
+use crate::config::{BridleConfig, ProfileManager};
+use crate::plan::{self, Action, EntryKind, Plan, PlanError};
+
+/// Record the file changes made by `f`, then print them when `dry_run` is
+/// set or apply them otherwise. Returns `None` for a dry run.
+pub fn run<T, E: From<PlanError>>(
+    dry_run: bool,
+    format: ResolvedFormat,
+    f: impl FnOnce() -> Result<T, E>,
+) -> Result<Option<T>, E> {
+    let (result, plan) = plan::record(f);
+    let value = result?;
+    if dry_run {
+        print_plan(&plan, format);
+        return Ok(None);
+    }
+    apply(&plan)?;
+    Ok(Some(value))
+}
+
+/// Apply a plan recorded outside [`run`]. Live harness files it writes are
+/// folded into the drift baseline of the active profile, since bridle
+/// made those changes itself.
+pub fn apply(plan: &Plan) -> Result<(), PlanError> {
+    plan.apply()?;
+    if let Ok(profiles_dir) = BridleConfig::profiles_dir() {
+        let paths: Vec<PathBuf> = plan.changes.iter().map(|c| c.path.clone()).collect();
+        if let Err(e) = ProfileManager::new(profiles_dir).refresh_fingerprints(&paths) {
+            eprintln!("Warning: failed to update drift baseline: {e}");
+        }
+    }
+    Ok(())
+}
+
+fn print_plan(plan: &Plan, format: ResolvedFormat) {
+    output(plan, format, print_plan_text);
+}
+
+pub fn print_plan_text(plan: &Plan) {
+    if plan.is_empty() {
+        println!("No changes");
+        return;
+    }
+
+    println!(
+        "Plan: {} to create, {} to overwrite, {} to delete",
+        plan.count(Action::Create),
+        plan.count(Action::Overwrite),
+        plan.count(Action::Delete)
+    );
+
+    // Files copied into a backup are summarised under "Backups"
+    let in_backup = |path: &Path| plan.backups.iter().any(|b| path.starts_with(b));
+    for change in plan.changes.iter().filter(|c| !in_backup(&c.path)) {
+        let path = match change.kind {
+            EntryKind::Dir => format!("{}/", change.path.display()),
+            _ => change.path.display().to_string(),
+        };
+        let marker = match change.action {
+            Action::Create => "+".green(),
+            Action::Overwrite => "~".yellow(),
+            Action::Delete => "-".red(),
+        };
+        println!("  {marker} {path}");
+    }
+
+    if !plan.mcp.is_empty() {
+        println!("MCP servers:");
+        for change in &plan.mcp {
+            println!("  {} ({})", change.config.display(), change.harness);
+            for name in &change.added {
+                println!("    {} {}", "+".green(), name);
+            }
+            for name in &change.changed {
+                println!("    {} {}", "~".yellow(), name);
+            }
+            for name in &change.removed {
+                println!("    {} {}", "-".red(), name);
+            }
+        }
+    }
+
+    if !plan.backups.is_empty() {
+        println!("Backups:");
+        for backup in &plan.backups {
+            let files = plan
+                .changes
+                .iter()
+                .filter(|c| c.kind != EntryKind::Dir && c.path.starts_with(backup))
+                .count();
+            println!("  {} ({} files)", backup.display(), files);
+        }
+    }
+}
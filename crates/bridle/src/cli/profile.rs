@@ -1,11 +1,21 @@
+use std::io::IsTerminal;
+use std::path::Path;
+
+use dialoguer_multiselect::theme::ColorfulTheme;
+use dialoguer_multiselect::{Confirm, Input, Select};
+
 use harness_locate::{Harness, HarnessKind, InstallationStatus};
 use serde::Serialize;
 
 use crate::cli::output::{ResolvedFormat, output, output_list};
-use crate::config::{BridleConfig, ProfileManager, ProfileName};
-use crate::display::{ProfileNode, SectionKind, nodes_to_text, profile_to_nodes};
+use crate::cli::plan;
+use crate::config::{BridleConfig, DriftAction, ProfileManager, ProfileName};
+use crate::display::{ProfileNode, SectionKind, diff_to_text, nodes_to_text, profile_to_nodes};
 use crate::error::{Error, Result};
 use crate::harness::HarnessConfig;
+use crate::install::InstallTarget;
+use crate::install::convert::ConversionReport;
+use crate::install::project;
 
 #[derive(Serialize)]
 struct ProfileListEntry {
@@ -99,7 +109,13 @@ fn print_profile_text(info: &crate::config::ProfileInfo, harness: &harness_locat
     print!("{}", nodes_to_text(&nodes));
 }
 
-pub fn create_profile(harness_name: &str, profile_name: &str) -> Result<()> {
+pub fn create_profile(
+    harness_name: &str,
+    profile_name: &str,
+    extends: &[String],
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
     let harness = resolve_harness(harness_name)?;
 
     let status = harness
@@ -121,13 +137,34 @@ pub fn create_profile(harness_name: &str, profile_name: &str) -> Result<()> {
         .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
     let manager = get_manager()?;
 
-    let path = manager.create_profile(&harness, &name)?;
+    let bases = extends
+        .iter()
+        .map(|base| ProfileName::new(base).map_err(|_| Error::InvalidProfileName(base.clone())))
+        .collect::<Result<Vec<_>>>()?;
+
+    let Some(path) = plan::run(dry_run, format, || -> Result<_> {
+        let path = manager.create_profile(&harness, &name)?;
+        manager.set_profile_extends(&harness, &name, &bases)?;
+        Ok(path)
+    })?
+    else {
+        return Ok(());
+    };
+
     println!("Created profile: {}", name.as_str());
     println!("Path: {}", path.display());
+    if !bases.is_empty() {
+        println!("Extends: {}", extends.join(", "));
+    }
     Ok(())
 }
 
-pub fn create_profile_from_current(harness_name: &str, profile_name: &str) -> Result<()> {
+pub fn create_profile_from_current(
+    harness_name: &str,
+    profile_name: &str,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
     let harness = resolve_harness(harness_name)?;
 
     let status = harness
@@ -149,19 +186,33 @@ pub fn create_profile_from_current(harness_name: &str, profile_name: &str) -> Re
         .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
     let manager = get_manager()?;
 
-    let path = manager.create_from_current_with_resources(&harness, Some(&harness), &name)?;
+    let Some(path) = plan::run(dry_run, format, || {
+        manager.create_from_current_with_resources(&harness, Some(&harness), &name)
+    })?
+    else {
+        return Ok(());
+    };
+
     println!("Created profile from current config: {}", name.as_str());
     println!("Path: {}", path.display());
     Ok(())
 }
 
-pub fn delete_profile(harness_name: &str, profile_name: &str) -> Result<()> {
+pub fn delete_profile(
+    harness_name: &str,
+    profile_name: &str,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
     let harness = resolve_harness(harness_name)?;
     let name = ProfileName::new(profile_name)
         .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
     let manager = get_manager()?;
 
-    manager.delete_profile(&harness, &name)?;
+    if plan::run(dry_run, format, || manager.delete_profile(&harness, &name))?.is_none() {
+        return Ok(());
+    }
+
     println!("Deleted profile: {}", name.as_str());
     Ok(())
 }
@@ -210,6 +261,26 @@ pub fn diff_profiles(
     harness_name: &str,
     profile_name: &str,
     other_name: Option<&str>,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let name = ProfileName::new(profile_name)
+        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+    let other = other_name
+        .map(|o| ProfileName::new(o).map_err(|_| Error::InvalidProfileName(o.to_string())))
+        .transpose()?;
+    let manager = get_manager()?;
+
+    let diff = manager.diff_profiles(&harness, &name, other.as_ref())?;
+    output(&diff, format, |d| print!("{}", diff_to_text(d)));
+    Ok(())
+}
+
+/// File-level `diff -u` of the two profile directories.
+pub fn diff_profiles_raw(
+    harness_name: &str,
+    profile_name: &str,
+    other_name: Option<&str>,
 ) -> Result<()> {
     let harness = resolve_harness(harness_name)?;
     let name = ProfileName::new(profile_name)
@@ -247,7 +318,96 @@ pub fn diff_profiles(
     Ok(())
 }
 
-pub fn switch_profile(harness_name: &str, profile_name: &str) -> Result<()> {
+/// Maps the `--keep-drift`/`--discard-drift`/`--fork-drift` flags to an action.
+pub fn drift_action_from_flags(
+    keep: bool,
+    discard: bool,
+    fork: Option<&str>,
+) -> Result<Option<DriftAction>> {
+    if let Some(fork) = fork {
+        let name =
+            ProfileName::new(fork).map_err(|_| Error::InvalidProfileName(fork.to_string()))?;
+        return Ok(Some(DriftAction::Fork(name)));
+    }
+    Ok(if discard {
+        Some(DriftAction::Discard)
+    } else if keep {
+        Some(DriftAction::Keep)
+    } else {
+        None
+    })
+}
+
+/// Reports drift in the active profile and asks what to do with it, keeping
+/// the edits when there is no terminal to ask on.
+fn prompt_drift_action(manager: &ProfileManager, harness: &Harness) -> Result<DriftAction> {
+    let Some(drift) = manager.detect_drift(harness)? else {
+        return Ok(DriftAction::Keep);
+    };
+    if drift.is_empty() {
+        return Ok(DriftAction::Keep);
+    }
+
+    println!(
+        "Live config changed since '{}' was activated ({}):",
+        drift.profile,
+        drift.summary()
+    );
+    for path in &drift.modified {
+        println!("  ~ {}", path);
+    }
+    for path in &drift.added {
+        println!("  + {}", path);
+    }
+    for path in &drift.removed {
+        println!("  - {}", path);
+    }
+
+    if !std::io::stdin().is_terminal() {
+        println!(
+            "Keeping changes in '{}' (use --discard-drift or --fork-drift <NAME> to change this)",
+            drift.profile
+        );
+        return Ok(DriftAction::Keep);
+    }
+
+    let items = [
+        format!("Keep changes in '{}'", drift.profile),
+        "Discard changes".to_string(),
+        "Fork changes into a new profile".to_string(),
+    ];
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("What should happen to these changes?")
+        .items(&items)
+        .default(0)
+        .interact()
+        .map_err(|e| Error::Command(e.to_string()))?;
+
+    match choice {
+        0 => Ok(DriftAction::Keep),
+        1 => Ok(DriftAction::Discard),
+        _ => {
+            let fork: String = Input::with_theme(&ColorfulTheme::default())
+                .with_prompt("New profile name")
+                .interact_text()
+                .map_err(|e| Error::Command(e.to_string()))?;
+            let name =
+                ProfileName::new(&fork).map_err(|_| Error::InvalidProfileName(fork.clone()))?;
+            if manager.profile_exists(harness, &name) {
+                return Err(Error::ProfileExists(fork));
+            }
+            Ok(DriftAction::Fork(name))
+        }
+    }
+}
+
+pub fn switch_profile(
+    harness_name: &str,
+    profile_name: &str,
+    drift: Option<DriftAction>,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
     let harness = resolve_harness(harness_name)?;
     let name = ProfileName::new(profile_name)
         .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
@@ -259,17 +419,325 @@ pub fn switch_profile(harness_name: &str, profile_name: &str) -> Result<()> {
 
     let harness_id = harness.id();
 
-    match manager.backup_current(&harness) {
-        Ok(backup_path) => {
-            println!("Backed up current config to: {}", backup_path.display());
+    let is_active = BridleConfig::load()
+        .ok()
+        .and_then(|c| c.active_profile_for(harness_id).map(|a| a == name.as_str()))
+        .unwrap_or(false);
+    // Switching to the active profile changes nothing, drift included
+    let drifted = !is_active
+        && manager
+            .detect_drift(&harness)?
+            .is_some_and(|drift| !drift.is_empty());
+    let drift = match drift {
+        Some(action) => action,
+        None if is_active => DriftAction::Keep,
+        None => prompt_drift_action(&manager, &harness)?,
+    };
+
+    let Some(backup) = plan::run(dry_run, format, || -> Result<_> {
+        let backup = manager.backup_current(&harness);
+        if backup.is_ok() {
+            let _ = manager.apply_backup_retention(&harness);
         }
-        Err(e) => {
-            println!("Warning: Could not backup current config: {e}");
+        manager.switch_profile_with_drift(&harness, Some(&harness), &name, &drift)?;
+        Ok(backup)
+    })?
+    else {
+        return Ok(());
+    };
+
+    match backup {
+        Ok(backup_path) => println!("Backed up current config to: {}", backup_path.display()),
+        Err(e) => println!("Warning: Could not backup current config: {e}"),
+    }
+    if drifted {
+        match &drift {
+            DriftAction::Discard => println!("Discarded live changes"),
+            DriftAction::Fork(fork) => {
+                println!("Saved live changes as profile: {}", fork.as_str())
+            }
+            DriftAction::Keep => {}
         }
     }
-
-    manager.switch_profile_with_resources(&harness, Some(&harness), &name)?;
     println!("Switched to profile: {}", name.as_str());
     println!("Harness: {harness_id}");
     Ok(())
 }
+
+/// Copy a profile into a project's harness directories, leaving the global
+/// config and active profile untouched.
+pub fn switch_project_profile(
+    harness_name: &str,
+    profile_name: &str,
+    root: &Path,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let name = ProfileName::new(profile_name)
+        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+    let profile_dir = BridleConfig::profiles_dir()?
+        .join(harness.id())
+        .join(name.as_str());
+    if !profile_dir.exists() {
+        return Err(Error::ProfileNotFound(profile_name.to_string()));
+    }
+
+    let Some(switch) = plan::run(dry_run, format, || {
+        project::switch_project_profile(harness.kind(), root, name.as_str(), &profile_dir)
+            .map_err(|e| Error::Command(e.to_string()))
+    })?
+    else {
+        return Ok(());
+    };
+
+    output(&switch, format, |s| {
+        for removed in &s.removed {
+            println!("  - Removed {}", removed);
+        }
+        for installed in &s.installed {
+            println!("  + Installed {}", installed);
+        }
+        for skipped in &s.skipped {
+            println!("  ~ Skipped {}", skipped);
+        }
+        println!("Switched project to profile: {}", s.profile);
+        println!("Harness: {}", s.harness);
+        println!("Project: {}", s.root.display());
+    });
+    Ok(())
+}
+
+pub fn convert_profile(
+    from_harness: &str,
+    profile_name: &str,
+    to_harness: &str,
+    new_name: Option<&str>,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let from = resolve_harness(from_harness)?;
+    let to = resolve_harness(to_harness)?;
+    let name = ProfileName::new(profile_name)
+        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+    let new_name = new_name.unwrap_or(profile_name);
+    let target_name =
+        ProfileName::new(new_name).map_err(|_| Error::InvalidProfileName(new_name.to_string()))?;
+
+    let profiles_dir = BridleConfig::profiles_dir()?;
+    let Some(report) = plan::run(dry_run, format, || {
+        crate::install::convert::convert_profile(
+            &InstallTarget {
+                harness: from.id().to_string(),
+                profile: name,
+            },
+            from.kind(),
+            &InstallTarget {
+                harness: to.id().to_string(),
+                profile: target_name,
+            },
+            to.kind(),
+            &profiles_dir,
+        )
+        .map_err(|e| Error::Command(e.to_string()))
+    })?
+    else {
+        return Ok(());
+    };
+
+    output(&report, format, print_conversion_report);
+    Ok(())
+}
+
+fn print_conversion_report(report: &ConversionReport) {
+    println!(
+        "Converted {}/{} -> {}/{}",
+        report.from.harness, report.from.profile, report.to.harness, report.to.profile
+    );
+    for component in &report.converted {
+        if component.issues.is_empty() {
+            println!("  + {} {}", component.component_type, component.name);
+        } else {
+            println!("  ~ {} {}", component.component_type, component.name);
+        }
+        print_issues(&component.issues);
+    }
+    for component in &report.dropped {
+        println!(
+            "  - {} {} (dropped)",
+            component.component_type, component.name
+        );
+        print_issues(&component.issues);
+    }
+}
+
+fn print_issues(issues: &[harness_locate::ValidationIssue]) {
+    for issue in issues {
+        match issue.code {
+            Some(code) => println!("      [{}] {}", code, issue.message),
+            None => println!("      {}", issue.message),
+        }
+    }
+}
+
+pub fn export_profile(
+    harness_name: &str,
+    profile_name: &str,
+    file: Option<&Path>,
+    force: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let harness = resolve_harness(harness_name)?;
+    let name = ProfileName::new(profile_name)
+        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+    let default_file = format!("{}.bridle", name.as_str());
+    let file = file.unwrap_or(Path::new(&default_file));
+
+    let manager = ProfileManager::new(BridleConfig::profiles_dir()?);
+    let report = manager.export_profile(&harness, &name, file, force)?;
+
+    output(&report, format, |report| {
+        println!(
+            "Exported {}/{} to {} ({} files)",
+            report.manifest.harness,
+            report.manifest.profile,
+            report.path.display(),
+            report.files
+        );
+        if !report.manifest.env.is_empty() {
+            println!("Reads from the environment:");
+            for var in &report.manifest.env {
+                println!("  {} ({})", var.name, var.servers.join(", "));
+            }
+        }
+        for server in &report.dropped_servers {
+            println!(
+                "  - mcp {} (dropped: its secrets could not be replaced)",
+                server
+            );
+        }
+        for link in &report.skipped_links {
+            println!("  - {} (left out: symlink)", link.display());
+        }
+    });
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct ImportReport {
+    harness: String,
+    profile: String,
+    path: std::path::PathBuf,
+    files: usize,
+    /// Variables the profile reads that are not set in this shell.
+    missing_env: Vec<String>,
+}
+
+pub fn import_profile(
+    file: &Path,
+    new_name: Option<&str>,
+    yes: bool,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let archive = crate::config::ProfileArchive::read(file)?;
+    let profile_name = new_name.unwrap_or(&archive.manifest.profile);
+    let name = ProfileName::new(profile_name)
+        .map_err(|_| Error::InvalidProfileName(profile_name.to_string()))?;
+    let manager = ProfileManager::new(BridleConfig::profiles_dir()?);
+    if manager.profile_exists(&archive.harness, &name) {
+        return Err(Error::ProfileExists(name.as_str().to_string()));
+    }
+
+    let hooks = archive.hook_commands();
+    if !hooks.is_empty() {
+        eprintln!("\nThe profile's hooks run these commands automatically:");
+        for line in &hooks {
+            eprintln!("  {}", line);
+        }
+        if !(yes || dry_run || confirm_hooks()?) {
+            return Err(Error::Command(
+                "Not imported; pass --yes to import a profile with hooks".to_string(),
+            ));
+        }
+    }
+
+    let renames = if dry_run {
+        Default::default()
+    } else {
+        prompt_missing_env(&archive)?
+    };
+    let Some(path) = plan::run(dry_run, format, || {
+        manager.import_profile(&archive, &name, &renames)
+    })?
+    else {
+        return Ok(());
+    };
+
+    let missing_env = archive.missing_env(&renames);
+    let report = ImportReport {
+        harness: archive.harness.id().to_string(),
+        profile: name.as_str().to_string(),
+        path,
+        files: archive.file_count(),
+        missing_env,
+    };
+    output(&report, format, |report| {
+        println!(
+            "Imported {}/{} ({} files)",
+            report.harness, report.profile, report.files
+        );
+        if !report.missing_env.is_empty() {
+            println!("Set these before switching to it:");
+            for var in &report.missing_env {
+                println!("  {}", var);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Asks whether to import hooks that were just listed. Without a terminal
+/// the answer is no.
+fn confirm_hooks() -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    Ok(Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Import this profile with these hooks?")
+        .default(false)
+        .interact_opt()
+        .map_err(|e| Error::Command(e.to_string()))?
+        .unwrap_or(false))
+}
+
+/// Asks which of the user's environment variables feeds each variable the
+/// archive reads that is not set in this shell. Goose reads each variable
+/// under its own name, so there is nothing to ask.
+fn prompt_missing_env(
+    archive: &crate::config::ProfileArchive,
+) -> Result<std::collections::BTreeMap<String, String>> {
+    let mut renames = std::collections::BTreeMap::new();
+    if !std::io::stdin().is_terminal() || archive.harness.kind() == HarnessKind::Goose {
+        return Ok(renames);
+    }
+    for var in &archive.manifest.env {
+        if std::env::var_os(&var.name).is_some() {
+            continue;
+        }
+        eprintln!(
+            "{} ({}) is not set in this shell",
+            var.name,
+            var.servers.join(", ")
+        );
+        let answer: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Read {} from environment variable", var.name))
+            .default(var.name.clone())
+            .interact_text()
+            .map_err(|e| Error::Command(e.to_string()))?;
+        if answer != var.name {
+            renames.insert(var.name.clone(), answer);
+        }
+    }
+    Ok(renames)
+}
@@ -0,0 +1,55 @@
+//! CLI search command implementation.
+
+use color_eyre::eyre::{Result, eyre};
+use colored::Colorize;
+
+use crate::cli::output::{ResolvedFormat, output_list};
+use crate::install::registry::registry_client;
+
+pub fn run(query: &str, format: ResolvedFormat) -> Result<()> {
+    let client = registry_client();
+    let entries = client
+        .search(query)
+        .map_err(|e| eyre!("Registry search at {} failed: {}", client.base_url(), e))?;
+
+    output_list(&entries, format, |entries| {
+        if entries.is_empty() {
+            println!("No MCP servers matching '{}'", query);
+            return;
+        }
+        for entry in entries {
+            let version = entry
+                .version
+                .as_deref()
+                .map(|v| format!(" {}", v))
+                .unwrap_or_default();
+            println!("{}{}", entry.id.bold(), version.dimmed());
+            if let Some(description) = &entry.description {
+                println!("  {}", description);
+            }
+            for pkg in &entry.packages {
+                let version = pkg
+                    .version
+                    .as_deref()
+                    .map(|v| format!("@{}", v))
+                    .unwrap_or_default();
+                println!("  package  {}:{}{}", pkg.registry, pkg.name, version);
+                if !pkg.environment_variables.is_empty() {
+                    let mut vars: Vec<&str> = pkg
+                        .environment_variables
+                        .keys()
+                        .map(String::as_str)
+                        .collect();
+                    vars.sort();
+                    println!("           env: {}", vars.join(", "));
+                }
+            }
+            for remote in &entry.remotes {
+                println!("  remote   {} {}", remote.transport_type, remote.url);
+            }
+        }
+        println!();
+        println!("Install with: bridle install registry:<id>[@version]");
+    });
+    Ok(())
+}
@@ -1,13 +1,28 @@
+use std::path::{Path, PathBuf};
+
 use harness_locate::{Harness, HarnessKind, InstallationStatus, Scope};
 use serde::Serialize;
 
 use crate::cli::output::{ResolvedFormat, output};
-use crate::config::BridleConfig;
+use crate::cli::profile::resolve_harness;
+use crate::config::{BridleConfig, Drift, ProfileManager};
+use crate::install::project::{ProjectResources, scan_project};
 
 #[derive(Debug, Serialize)]
 pub struct StatusOutput {
     pub harnesses: Vec<HarnessStatus>,
     pub active_profiles: Vec<ActiveProfile>,
+    /// Workspace whose profiles are all active, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_workspace: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<ProjectStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectStatus {
+    pub root: PathBuf,
+    pub harnesses: Vec<ProjectResources>,
 }
 
 #[derive(Debug, Serialize)]
@@ -22,9 +37,12 @@ pub struct HarnessStatus {
 pub struct ActiveProfile {
     pub harness: String,
     pub profile: String,
+    /// Live edits made since the profile was activated, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drift: Option<Drift>,
 }
 
-pub fn display_status(format: ResolvedFormat) {
+pub fn display_status(format: ResolvedFormat, project: Option<&Path>) {
     let harnesses: Vec<HarnessStatus> = HarnessKind::ALL
         .iter()
         .map(|kind| {
@@ -52,7 +70,14 @@ pub fn display_status(format: ResolvedFormat) {
         })
         .collect();
 
-    let active_profiles: Vec<ActiveProfile> = BridleConfig::load()
+    let manager = BridleConfig::profiles_dir().ok().map(ProfileManager::new);
+    let config = BridleConfig::load();
+    let active_workspace = config
+        .as_ref()
+        .ok()
+        .and_then(|c| c.active_workspace())
+        .map(String::from);
+    let active_profiles: Vec<ActiveProfile> = config
         .map(|config| {
             config
                 .active
@@ -60,14 +85,32 @@ pub fn display_status(format: ResolvedFormat) {
                 .map(|(harness, profile)| ActiveProfile {
                     harness: harness.clone(),
                     profile: profile.clone(),
+                    drift: manager
+                        .as_ref()
+                        .and_then(|m| {
+                            let harness = resolve_harness(harness).ok()?;
+                            m.detect_drift(&harness).ok().flatten()
+                        })
+                        .filter(|d| !d.is_empty()),
                 })
                 .collect()
         })
         .unwrap_or_default();
 
+    let project = project.map(|root| ProjectStatus {
+        root: root.to_path_buf(),
+        harnesses: HarnessKind::ALL
+            .iter()
+            .map(|kind| scan_project(*kind, root))
+            .filter(|r| !r.is_empty() || r.profile.is_some())
+            .collect(),
+    });
+
     let status = StatusOutput {
         harnesses,
         active_profiles,
+        active_workspace,
+        project,
     };
 
     output(&status, format, |s| {
@@ -80,10 +123,44 @@ pub fn display_status(format: ResolvedFormat) {
         }
 
         if !s.active_profiles.is_empty() {
-            println!("\nActive Profiles:");
+            match &s.active_workspace {
+                Some(workspace) => println!("\nActive Profiles (workspace {workspace}):"),
+                None => println!("\nActive Profiles:"),
+            }
             for ap in &s.active_profiles {
-                println!("  {}: {}", ap.harness, ap.profile);
+                match &ap.drift {
+                    Some(drift) => println!(
+                        "  {}: {} (drifted: {})",
+                        ap.harness,
+                        ap.profile,
+                        drift.summary()
+                    ),
+                    None => println!("  {}: {}", ap.harness, ap.profile),
+                }
+            }
+        }
+
+        if let Some(project) = &s.project {
+            println!("\nProject ({}):", project.root.display());
+            if project.harnesses.is_empty() {
+                println!("  No project-scope resources");
+            }
+            for r in &project.harnesses {
+                match &r.profile {
+                    Some(profile) => println!("  {}: {}", r.harness, profile),
+                    None => println!("  {}", r.harness),
+                }
+                print_names("Skills", &r.skills);
+                print_names("Agents", &r.agents);
+                print_names("Commands", &r.commands);
+                print_names("MCP Servers", &r.mcp_servers);
             }
         }
     });
 }
+
+fn print_names(label: &str, names: &[String]) {
+    if !names.is_empty() {
+        println!("    {}: {}", label, names.join(", "));
+    }
+}
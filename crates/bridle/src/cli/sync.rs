@@ -0,0 +1,105 @@
+//! CLI sync command and lockfile maintenance.
+
+use std::path::Path;
+
+use color_eyre::eyre::{Result, eyre};
+
+use crate::cli::output::{ResolvedFormat, output};
+use crate::cli::plan;
+use crate::config::BridleConfig;
+use crate::install::lockfile::{Lockfile, install_verified, verify_locked};
+use crate::install::updater::SourceCache;
+use crate::install::{InstallReport, InstallTarget};
+
+/// Reproduce installs from the lockfile. With `source`, only components
+/// locked from that source are synced (`bridle install --locked <source>`).
+pub fn run(
+    lockfile: Option<&Path>,
+    source: Option<&str>,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let path = match lockfile {
+        Some(path) => path.to_path_buf(),
+        None => Lockfile::default_path()?,
+    };
+    if !path.exists() {
+        return Err(eyre!("Lockfile not found: {}", path.display()));
+    }
+
+    let lock = Lockfile::load(&path)?;
+    let mut cache = SourceCache::new();
+    let verified = verify_locked(
+        &lock,
+        |c| source.is_none_or(|s| c.source == s || c.resolved == s),
+        &mut cache,
+    )?;
+
+    if verified.is_empty() {
+        return Err(match source {
+            Some(source) => eyre!("No components locked from {}", source),
+            None => eyre!("No components in {}", path.display()),
+        });
+    }
+
+    let profiles_dir = BridleConfig::profiles_dir()?;
+    let Some(report) = plan::run(dry_run, format, || -> Result<_> {
+        Ok(install_verified(&verified, &profiles_dir))
+    })?
+    else {
+        return Ok(());
+    };
+    let failed = !report.errors.is_empty();
+
+    output(&report, format, print_report);
+
+    if failed {
+        return Err(eyre!("Some components failed to sync"));
+    }
+    Ok(())
+}
+
+fn print_report(report: &InstallReport) {
+    for success in &report.installed {
+        eprintln!(
+            "  + Synced {} to {}/{}",
+            success.skill, success.target.harness, success.target.profile
+        );
+    }
+    for error in &report.errors {
+        eprintln!(
+            "  ! Error syncing {} to {}/{}: {}",
+            error.skill, error.target.harness, error.target.profile, error.error
+        );
+    }
+}
+
+/// Record the current manifest contents of each target in the lockfile.
+///
+/// Failures are reported as warnings; the install itself already succeeded.
+pub fn refresh_lockfile(targets: &[InstallTarget]) {
+    if let Err(e) = try_refresh_lockfile(targets) {
+        eprintln!("Warning: failed to update lockfile: {}", e);
+    }
+}
+
+fn try_refresh_lockfile(targets: &[InstallTarget]) -> Result<()> {
+    plan::run(false, ResolvedFormat::Text, || record_targets(targets))?;
+    Ok(())
+}
+
+fn record_targets(targets: &[InstallTarget]) -> Result<()> {
+    let path = Lockfile::default_path()?;
+    let profiles_dir = BridleConfig::profiles_dir()?;
+    let mut lock = Lockfile::load(&path)?;
+
+    for target in targets {
+        let profile_dir = profiles_dir
+            .join(&target.harness)
+            .join(target.profile.as_str());
+        lock.record_profile(target, &profile_dir)?;
+    }
+
+    lock.save(&path)?;
+    Ok(())
+}
@@ -7,13 +7,17 @@ use color_eyre::eyre::{Result, eyre};
 use dialoguer_multiselect::MultiSelect;
 use dialoguer_multiselect::theme::ColorfulTheme;
 
+use crate::cli::output::ResolvedFormat;
+use crate::cli::plan;
 use crate::cli::profile::resolve_harness;
 use crate::config::BridleConfig;
 use crate::harness::HarnessConfig;
+use crate::install::manifest::{InstallManifest, manifest_path};
+use crate::install::project::{ProjectManifest, uninstall_from_project};
 use crate::install::uninstaller::uninstall_components;
 use crate::install::{ComponentType, InstallTarget};
 
-pub fn run(harness: &str, profile: &str) -> Result<()> {
+pub fn run(harness: &str, profile: &str, dry_run: bool, format: ResolvedFormat) -> Result<()> {
     if !std::io::stdin().is_terminal() {
         return Err(eyre!("Interactive mode requires a terminal."));
     }
@@ -67,7 +71,12 @@ pub fn run(harness: &str, profile: &str) -> Result<()> {
 
     eprintln!("\nUninstalling from {}/{}...", harness_id, profile);
 
-    let report = uninstall_components(&selected_components, &target);
+    let Some(report) = plan::run(dry_run, format, || -> Result<_> {
+        Ok(uninstall_components(&selected_components, &target))
+    })?
+    else {
+        return Ok(());
+    };
 
     for success in &report.removed {
         eprintln!(
@@ -83,6 +92,89 @@ pub fn run(harness: &str, profile: &str) -> Result<()> {
         );
     }
 
+    crate::cli::sync::refresh_lockfile(std::slice::from_ref(&target));
+
+    eprintln!("\nDone!");
+    Ok(())
+}
+
+/// Interactively remove bridle-installed components from a project.
+pub fn run_project(
+    harness: &str,
+    root: &Path,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    if !std::io::stdin().is_terminal() {
+        return Err(eyre!("Interactive mode requires a terminal."));
+    }
+
+    let harness_obj = resolve_harness(harness)?;
+    let harness_id = harness_obj.id();
+
+    let manifest = ProjectManifest::load(root)?;
+    let components: Vec<(String, ComponentType)> = manifest
+        .state(harness_id)
+        .map(|s| {
+            s.manifest
+                .entries
+                .iter()
+                .map(|e| (e.name.clone(), e.component_type))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if components.is_empty() {
+        eprintln!(
+            "No components installed by bridle for {} in {}",
+            harness_id,
+            root.display()
+        );
+        return Ok(());
+    }
+
+    let component_labels: Vec<String> = components
+        .iter()
+        .map(|(name, comp_type)| format!("{:?}: {}", comp_type, name))
+        .collect();
+
+    let Some(selected_indices) = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Select components to uninstall (Esc to cancel)")
+        .items(&component_labels)
+        .interact_opt()?
+    else {
+        eprintln!("Cancelled");
+        return Ok(());
+    };
+
+    if selected_indices.is_empty() {
+        eprintln!("No components selected");
+        return Ok(());
+    }
+
+    eprintln!("\nUninstalling from {} ({})...", root.display(), harness_id);
+
+    let Some(results) = plan::run(dry_run, format, || -> Result<_> {
+        Ok(selected_indices
+            .iter()
+            .map(|&i| {
+                let (name, comp_type) = &components[i];
+                let result = uninstall_from_project(harness_obj.kind(), root, *comp_type, name);
+                (name, comp_type, result)
+            })
+            .collect::<Vec<_>>())
+    })?
+    else {
+        return Ok(());
+    };
+
+    for (name, comp_type, result) in results {
+        match result {
+            Ok(()) => eprintln!("  - Removed: {} ({})", name, comp_type.dir_name()),
+            Err(e) => eprintln!("  ! Error removing {}: {}", name, e),
+        }
+    }
+
     eprintln!("\nDone!");
     Ok(())
 }
@@ -112,6 +204,16 @@ fn list_installed_components(profile_path: &Path) -> Result<Vec<(String, Compone
         }
     }
 
+    // MCP servers and hooks live inside harness config files, so only tracked
+    // ones are listed
+    if let Ok(manifest) = InstallManifest::load(&manifest_path(profile_path)) {
+        for comp_type in [ComponentType::Mcp, ComponentType::Hook] {
+            for entry in manifest.components_of(comp_type) {
+                components.push((entry.name.clone(), comp_type));
+            }
+        }
+    }
+
     Ok(components)
 }
 
@@ -120,6 +222,27 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn list_installed_components_includes_tracked_mcps() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("skills/my-skill")).unwrap();
+        std::fs::write(
+            manifest_path(temp.path()),
+            r#"{"entries": [{"component_type": "mcp", "name": "filesystem",
+                "source": {"owner": "o", "repo": "r", "git_ref": null},
+                "installed_at": "2025-01-02T00:00:00Z"}]}"#,
+        )
+        .unwrap();
+
+        let components = list_installed_components(temp.path()).unwrap();
+        assert_eq!(components.len(), 2);
+        assert!(
+            components
+                .iter()
+                .any(|(n, t)| n == "filesystem" && matches!(t, ComponentType::Mcp))
+        );
+    }
+
     #[test]
     fn resolve_harness_canonicalizes_copilot_alias() {
         let harness = resolve_harness("copilot").unwrap();
@@ -0,0 +1,251 @@
+//! CLI update command implementation.
+
+use std::path::Path;
+
+use color_eyre::eyre::{Result, eyre};
+use serde::Serialize;
+
+use crate::cli::output::{ResolvedFormat, output_list};
+use crate::cli::plan;
+use crate::cli::profile::resolve_harness;
+use crate::config::{BridleConfig, ProfileName};
+use crate::harness::HarnessConfig;
+use crate::install::manifest::manifest_path;
+use crate::install::updater::{ChangeKind, SourceCache, UpdatePlan, apply_update, plan_update};
+use crate::install::{InstallFailure, InstallSuccess, InstallTarget};
+
+#[derive(Debug, Serialize)]
+pub struct ProfileUpdate {
+    #[serde(flatten)]
+    pub plan: UpdatePlan,
+    pub updated: Vec<InstallSuccess>,
+    pub errors: Vec<InstallFailure>,
+}
+
+impl ProfileUpdate {
+    /// Whether a source could not be fetched or a component failed to install.
+    pub fn failed(&self) -> bool {
+        !self.plan.failures.is_empty() || !self.errors.is_empty()
+    }
+}
+
+pub fn run(
+    harness: Option<&str>,
+    profile: Option<&str>,
+    all: bool,
+    dry_run: bool,
+    format: ResolvedFormat,
+) -> Result<()> {
+    let profiles_dir = BridleConfig::profiles_dir()?;
+    let targets = resolve_targets(harness, profile, all, &profiles_dir)?;
+
+    let Some(results) = plan::run(dry_run, format, || -> Result<Vec<ProfileUpdate>> {
+        let mut cache = SourceCache::new();
+        let mut results = Vec::new();
+
+        for target in targets {
+            let plan = plan_update(&target, &mut cache)?;
+            let report = apply_update(&plan);
+
+            results.push(ProfileUpdate {
+                plan,
+                updated: report.installed,
+                errors: report.errors,
+            });
+        }
+
+        let updated: Vec<InstallTarget> = results
+            .iter()
+            .filter(|r| !r.updated.is_empty())
+            .map(|r| r.plan.target.clone())
+            .collect();
+        if !updated.is_empty() {
+            crate::cli::sync::refresh_lockfile(&updated);
+        }
+        Ok(results)
+    })?
+    else {
+        return Ok(());
+    };
+
+    let failed = results.iter().any(ProfileUpdate::failed);
+
+    output_list(&results, format, |results| {
+        if results.is_empty() {
+            eprintln!("No profiles with installed components to update");
+        }
+        for result in results {
+            print_profile_update(result);
+        }
+    });
+
+    if failed {
+        return Err(eyre!("Some sources or components failed to update"));
+    }
+    Ok(())
+}
+
+fn print_profile_update(result: &ProfileUpdate) {
+    let plan = &result.plan;
+    eprintln!("{}/{}:", plan.target.harness, plan.target.profile);
+
+    for failure in &plan.failures {
+        eprintln!("  ! Failed to fetch {}: {}", failure.source, failure.error);
+    }
+
+    let mut unchanged = 0;
+    for change in &plan.components {
+        let kind = format!("{:?}", change.component_type).to_lowercase();
+        match change.change {
+            ChangeKind::Unchanged => unchanged += 1,
+            ChangeKind::Modified => {
+                eprintln!("  ~ Changed {}: {} ({})", kind, change.name, change.source)
+            }
+            ChangeKind::Missing => {
+                eprintln!(
+                    "  + Restoring {}: {} ({})",
+                    kind, change.name, change.source
+                )
+            }
+            ChangeKind::RemovedUpstream => eprintln!(
+                "  ? {} {} no longer in {} (kept)",
+                kind, change.name, change.source
+            ),
+        }
+    }
+
+    for success in &result.updated {
+        eprintln!("  + Updated: {}", success.skill);
+    }
+    for error in &result.errors {
+        eprintln!("  ! Error updating {}: {}", error.skill, error.error);
+    }
+
+    if unchanged > 0 {
+        eprintln!("  = {} component(s) up to date", unchanged);
+    }
+}
+
+/// Resolve which profiles to update.
+///
+/// With a harness and profile, only that profile. With only a harness, its
+/// active profile (or all of its profiles with `--all`). With neither, every
+/// active profile (or every profile with `--all`).
+fn resolve_targets(
+    harness: Option<&str>,
+    profile: Option<&str>,
+    all: bool,
+    profiles_dir: &Path,
+) -> Result<Vec<InstallTarget>> {
+    let config = BridleConfig::load()?;
+
+    let harness_ids: Vec<String> = match harness {
+        Some(name) => vec![resolve_harness(name)?.id().to_string()],
+        None if all => list_dirs(profiles_dir),
+        None => {
+            let mut ids: Vec<String> = config.active.keys().cloned().collect();
+            ids.sort();
+            ids
+        }
+    };
+
+    let mut targets = Vec::new();
+    for harness_id in harness_ids {
+        let profile_names: Vec<String> = match profile {
+            Some(name) => {
+                if !profiles_dir.join(&harness_id).join(name).exists() {
+                    return Err(eyre!("Profile not found: {}/{}", harness_id, name));
+                }
+                vec![name.to_string()]
+            }
+            None if all => list_dirs(&profiles_dir.join(&harness_id)),
+            None => match config.active_profile_for(&harness_id) {
+                Some(active) => vec![active.to_string()],
+                None if harness.is_some() => {
+                    return Err(eyre!(
+                        "No active profile for {}; pass a profile name or --all",
+                        harness_id
+                    ));
+                }
+                None => Vec::new(),
+            },
+        };
+
+        for name in profile_names {
+            let profile_dir = profiles_dir.join(&harness_id).join(&name);
+            if profile.is_none() && !manifest_path(&profile_dir).exists() {
+                continue;
+            }
+            targets.push(InstallTarget {
+                harness: harness_id.clone(),
+                profile: ProfileName::new(&name)?,
+            });
+        }
+    }
+
+    Ok(targets)
+}
+
+fn list_dirs(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| e.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .filter_map(|e| e.file_name().to_str().map(String::from))
+        .collect();
+    names.sort();
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::install::fixtures::{skill, source};
+    use crate::install::installer::install_skill_to_dir_with_source;
+    use crate::install::updater::plan_update_in_dir;
+    use crate::install::{DiscoveryError, InstallOptions};
+    use tempfile::TempDir;
+
+    #[test]
+    fn unreachable_source_fails_the_update() {
+        let temp = TempDir::new().unwrap();
+        let profiles_dir = temp.path().join("profiles");
+        std::fs::create_dir_all(profiles_dir.join("claude-code/work")).unwrap();
+        let target = InstallTarget {
+            harness: "claude-code".to_string(),
+            profile: ProfileName::new("work").unwrap(),
+        };
+        install_skill_to_dir_with_source(
+            &skill("review", "v1"),
+            &target,
+            &InstallOptions::default(),
+            &profiles_dir,
+            Some(&source()),
+        )
+        .unwrap();
+
+        let mut cache = SourceCache::new();
+        cache.insert(&source().location(), Err(DiscoveryError::NoSkillsFound));
+        let plan = plan_update_in_dir(&target, &profiles_dir, &mut cache).unwrap();
+        let update = ProfileUpdate {
+            plan,
+            updated: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        assert_eq!(update.plan.failures.len(), 1);
+        assert!(update.failed());
+    }
+
+    #[test]
+    fn list_dirs_returns_sorted_directories_only() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("zeta")).unwrap();
+        std::fs::create_dir_all(temp.path().join("alpha")).unwrap();
+        std::fs::write(temp.path().join("file.txt"), "x").unwrap();
+
+        assert_eq!(list_dirs(temp.path()), vec!["alpha", "zeta"]);
+        assert!(list_dirs(&temp.path().join("missing")).is_empty());
+    }
+}
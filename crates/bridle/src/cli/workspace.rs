@@ -0,0 +1,222 @@
+//! CLI workspace command implementation.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use color_eyre::eyre::{Result, eyre};
+use colored::Colorize;
+use harness_locate::Harness;
+use serde::Serialize;
+
+use crate::cli::output::{ResolvedFormat, output, output_list};
+use crate::cli::plan;
+use crate::cli::profile::resolve_harness;
+use crate::config::{BridleConfig, DriftAction, ProfileManager, ProfileName};
+use crate::error::Error;
+use crate::harness::HarnessConfig;
+
+#[derive(Debug, Serialize)]
+pub struct Workspace {
+    pub name: String,
+    pub active: bool,
+    /// Profile per harness id.
+    pub profiles: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkspaceSwitch {
+    pub workspace: String,
+    pub profiles: Vec<SwitchedProfile>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SwitchedProfile {
+    pub harness: String,
+    pub profile: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backup: Option<PathBuf>,
+}
+
+/// Switch every harness in the workspace to its profile.
+///
+/// All switches are recorded into one plan and applied as a single journaled
+/// change: if any harness fails to switch, no harness is changed. Live edits
+/// to the outgoing profiles are kept, as with `profile switch --keep-drift`.
+pub fn use_workspace(name: &str, dry_run: bool, format: ResolvedFormat) -> Result<()> {
+    let config = BridleConfig::load()?;
+    let workspace = config
+        .workspace(name)
+        .ok_or_else(|| eyre!("Workspace not found: {name}"))?;
+    let manager = ProfileManager::new(BridleConfig::profiles_dir()?);
+
+    // Resolve everything up front so a typo fails before anything is planned
+    let targets = workspace
+        .iter()
+        .map(|(harness_id, profile)| resolve_profile(&manager, harness_id, profile))
+        .collect::<Result<Vec<_>>>()?;
+
+    let Some(profiles) = plan::run(dry_run, format, || {
+        switch_each(&manager, &targets).map_err(|e| eyre!("Workspace {name} not switched: {e}"))
+    })?
+    else {
+        return Ok(());
+    };
+
+    let switch = WorkspaceSwitch {
+        workspace: name.to_string(),
+        profiles,
+    };
+    output(&switch, format, |switch| {
+        println!("Switched to workspace: {}", switch.workspace.bold());
+        for profile in &switch.profiles {
+            println!("  {}: {}", profile.harness, profile.profile);
+        }
+    });
+    Ok(())
+}
+
+/// Create or replace a workspace from `HARNESS=PROFILE` pairs, or from the
+/// active profiles when none are given.
+pub fn set(name: &str, pairs: &[String], dry_run: bool, format: ResolvedFormat) -> Result<()> {
+    if ProfileName::new(name).is_err() {
+        return Err(eyre!("Invalid workspace name: {name}"));
+    }
+    let mut config = BridleConfig::load()?;
+    let manager = ProfileManager::new(BridleConfig::profiles_dir()?);
+
+    let requested: Vec<(String, String)> = if pairs.is_empty() {
+        config
+            .active
+            .iter()
+            .map(|(harness, profile)| (harness.clone(), profile.clone()))
+            .collect()
+    } else {
+        pairs
+            .iter()
+            .map(|pair| {
+                pair.split_once('=')
+                    .map(|(harness, profile)| (harness.to_string(), profile.to_string()))
+                    .ok_or_else(|| eyre!("Expected HARNESS=PROFILE, got: {pair}"))
+            })
+            .collect::<Result<_>>()?
+    };
+    if requested.is_empty() {
+        return Err(eyre!(
+            "No active profiles to save; pass HARNESS=PROFILE pairs instead"
+        ));
+    }
+
+    let mut profiles = BTreeMap::new();
+    for (harness, profile) in &requested {
+        let (harness, profile) = resolve_profile(&manager, harness, profile)?;
+        profiles.insert(harness.id().to_string(), profile.as_str().to_string());
+    }
+    config.workspaces.insert(name.to_string(), profiles);
+    if plan::run(dry_run, format, || -> Result<_> { Ok(config.save()?) })?.is_none() {
+        return Ok(());
+    }
+
+    let workspace = workspace_entry(&config, name);
+    output(&workspace, format, |workspace| {
+        println!("Saved workspace: {}", workspace.name.bold());
+        print_profiles(&workspace.profiles);
+    });
+    Ok(())
+}
+
+pub fn list(format: ResolvedFormat) -> Result<()> {
+    let config = BridleConfig::load()?;
+    let workspaces: Vec<Workspace> = config
+        .workspaces
+        .keys()
+        .map(|name| workspace_entry(&config, name))
+        .collect();
+    output_list(&workspaces, format, |workspaces| {
+        if workspaces.is_empty() {
+            println!("No workspaces defined");
+            println!("Define one with: bridle workspace set <name> [HARNESS=PROFILE...]");
+            return;
+        }
+        for workspace in workspaces {
+            if workspace.active {
+                println!("{} {}", workspace.name.bold(), "(active)".green());
+            } else {
+                println!("{}", workspace.name.bold());
+            }
+            print_profiles(&workspace.profiles);
+        }
+    });
+    Ok(())
+}
+
+pub fn remove(name: &str, dry_run: bool, format: ResolvedFormat) -> Result<()> {
+    let mut config = BridleConfig::load()?;
+    let workspace = workspace_entry(&config, name);
+    if config.workspaces.remove(name).is_none() {
+        return Err(eyre!("Workspace not found: {name}"));
+    }
+    if plan::run(dry_run, format, || -> Result<_> { Ok(config.save()?) })?.is_none() {
+        return Ok(());
+    }
+    output(&workspace, format, |workspace| {
+        println!("Removed workspace {}", workspace.name.bold());
+    });
+    Ok(())
+}
+
+/// Back up each harness and switch it to its profile, keeping live edits in
+/// the outgoing profiles. Run inside [`plan::run`] so that the switches
+/// are applied together.
+pub(crate) fn switch_each(
+    manager: &ProfileManager,
+    targets: &[(Harness, ProfileName)],
+) -> Result<Vec<SwitchedProfile>, Error> {
+    targets
+        .iter()
+        .map(|(harness, profile)| {
+            let backup = manager.backup_current(harness).ok();
+            if backup.is_some() {
+                let _ = manager.apply_backup_retention(harness);
+            }
+            manager.switch_profile_with_drift(
+                harness,
+                Some(harness),
+                profile,
+                &DriftAction::Keep,
+            )?;
+            Ok(SwitchedProfile {
+                harness: harness.id().to_string(),
+                profile: profile.as_str().to_string(),
+                backup,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn resolve_profile(
+    manager: &ProfileManager,
+    harness_id: &str,
+    profile: &str,
+) -> Result<(Harness, ProfileName)> {
+    let harness = resolve_harness(harness_id)?;
+    let name =
+        ProfileName::new(profile).map_err(|_| Error::InvalidProfileName(profile.to_string()))?;
+    if !manager.profile_exists(&harness, &name) {
+        return Err(Error::ProfileNotFound(format!("{}/{}", harness.id(), profile)).into());
+    }
+    Ok((harness, name))
+}
+
+fn workspace_entry(config: &BridleConfig, name: &str) -> Workspace {
+    Workspace {
+        name: name.to_string(),
+        active: config.active_workspace() == Some(name),
+        profiles: config.workspace(name).cloned().unwrap_or_default(),
+    }
+}
+
+fn print_profiles(profiles: &BTreeMap<String, String>) {
+    for (harness, profile) in profiles {
+        println!("  {harness}: {profile}");
+    }
+}
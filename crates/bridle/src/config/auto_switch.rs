@@ -0,0 +1,271 @@
+//! Directory-based profile selection for `bridle hook` and `bridle resolve`.
+//!
+//! A `.bridle` file names a profile per harness for the directory it sits in
+//! and everything below it. Entering such a tree switches those harnesses;
+//! leaving it switches them back to the profiles that were active before,
+//! which are remembered in `auto-switch.toml` in the bridle config dir.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::plan::fs;
+
+/// Name of the marker file looked up from the current directory upwards.
+pub const DIR_MARKER_FILE: &str = ".bridle";
+
+const STATE_FILE: &str = "auto-switch.toml";
+
+/// A `.bridle` file: `harness = "profile"` lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirMarker {
+    pub path: PathBuf,
+    pub profiles: BTreeMap<String, String>,
+}
+
+impl DirMarker {
+    /// The nearest marker in `dir` or one of its ancestors.
+    pub fn find(dir: &Path) -> Result<Option<Self>> {
+        for ancestor in dir.ancestors() {
+            let path = ancestor.join(DIR_MARKER_FILE);
+            if std::fs::metadata(&path).is_ok_and(|meta| meta.is_file()) {
+                return Self::load(&path).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        let profiles = toml::from_str(&content)
+            .map_err(|e| Error::Config(format!("{}: {}", path.display(), e)))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            profiles,
+        })
+    }
+}
+
+/// What the shell hook last applied.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoSwitchState {
+    /// Marker whose profiles are in effect.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<PathBuf>,
+    /// Profile to switch back to per harness when leaving the marker's tree.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub restore: BTreeMap<String, String>,
+}
+
+impl AutoSwitchState {
+    pub fn path() -> Result<PathBuf> {
+        super::BridleConfig::config_dir().map(|dir| dir.join(STATE_FILE))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Ok(toml::from_str(&content)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Save the state, or remove the file once nothing is left to restore.
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if *self == Self::default() {
+            if fs::exists(&path) {
+                fs::remove_file(&path)?;
+            }
+            return Ok(());
+        }
+        let content = toml::to_string_pretty(self).map_err(|e| Error::Config(e.to_string()))?;
+        fs::write(&path, content)?;
+        Ok(())
+    }
+}
+
+/// The profiles a directory calls for, decided against the active profiles.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutoSwitch {
+    /// Profile each affected harness should end up on.
+    pub targets: BTreeMap<String, String>,
+    /// Harnesses whose target is not already active.
+    pub switches: BTreeMap<String, String>,
+    /// State to save once the switches are made.
+    pub state: AutoSwitchState,
+}
+
+impl AutoSwitch {
+    /// Decide what entering (or staying in) the tree of `marker` means.
+    ///
+    /// Harnesses named by the marker go to its profiles, remembering what was
+    /// active before the first marker took effect. Harnesses remembered from
+    /// an earlier marker but not named by this one go back to that profile.
+    pub fn resolve(
+        active: &HashMap<String, String>,
+        marker: Option<&DirMarker>,
+        state: &AutoSwitchState,
+    ) -> Self {
+        let wanted = marker.map(|m| m.profiles.clone()).unwrap_or_default();
+
+        let mut targets: BTreeMap<String, String> = state
+            .restore
+            .iter()
+            .filter(|(harness, _)| !wanted.contains_key(*harness))
+            .map(|(harness, profile)| (harness.clone(), profile.clone()))
+            .collect();
+        targets.extend(wanted.clone());
+
+        let restore = wanted
+            .keys()
+            .filter_map(|harness| {
+                state
+                    .restore
+                    .get(harness)
+                    .or_else(|| active.get(harness))
+                    .map(|profile| (harness.clone(), profile.clone()))
+            })
+            .collect();
+
+        let switches = targets
+            .iter()
+            .filter(|(harness, profile)| active.get(*harness) != Some(*profile))
+            .map(|(harness, profile)| (harness.clone(), profile.clone()))
+            .collect();
+
+        Self {
+            targets,
+            switches,
+            state: AutoSwitchState {
+                marker: marker.map(|m| m.path.clone()),
+                restore,
+            },
+        }
+    }
+
+    /// Whether applying would neither switch a profile nor change the state.
+    pub fn is_noop(&self, state: &AutoSwitchState) -> bool {
+        self.switches.is_empty() && self.state == *state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn map(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn active(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        map(pairs).into_iter().collect()
+    }
+
+    fn marker(path: &str, pairs: &[(&str, &str)]) -> DirMarker {
+        DirMarker {
+            path: PathBuf::from(path),
+            profiles: map(pairs),
+        }
+    }
+
+    #[test]
+    fn find_uses_the_nearest_marker() {
+        let temp = TempDir::new().unwrap();
+        let nested = temp.path().join("repo/src/deep");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(temp.path().join(".bridle"), "claude-code = \"home\"\n").unwrap();
+        std::fs::write(
+            temp.path().join("repo/.bridle"),
+            "claude-code = \"work\"\nopencode = \"work\"\n",
+        )
+        .unwrap();
+
+        let found = DirMarker::find(&nested).unwrap().unwrap();
+        assert_eq!(found.path, temp.path().join("repo/.bridle"));
+        assert_eq!(
+            found.profiles,
+            map(&[("claude-code", "work"), ("opencode", "work")])
+        );
+    }
+
+    #[test]
+    fn find_reports_the_marker_it_cannot_parse() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join(".bridle"), "claude-code = [").unwrap();
+
+        let err = DirMarker::find(temp.path()).unwrap_err();
+        assert!(err.to_string().contains(".bridle"), "{err}");
+    }
+
+    #[test]
+    fn entering_switches_and_remembers_the_previous_profiles() {
+        let active = active(&[("claude-code", "personal")]);
+        let work = marker(
+            "/repo/.bridle",
+            &[("claude-code", "work"), ("opencode", "work")],
+        );
+
+        let entered = AutoSwitch::resolve(&active, Some(&work), &AutoSwitchState::default());
+        assert_eq!(
+            entered.switches,
+            map(&[("claude-code", "work"), ("opencode", "work")])
+        );
+        // opencode had no active profile, so there is nothing to go back to
+        assert_eq!(entered.state.restore, map(&[("claude-code", "personal")]));
+        assert_eq!(entered.state.marker, Some(PathBuf::from("/repo/.bridle")));
+    }
+
+    #[test]
+    fn staying_inside_is_a_noop() {
+        let active = active(&[("claude-code", "work")]);
+        let work = marker("/repo/.bridle", &[("claude-code", "work")]);
+        let state = AutoSwitchState {
+            marker: Some(PathBuf::from("/repo/.bridle")),
+            restore: map(&[("claude-code", "personal")]),
+        };
+
+        assert!(AutoSwitch::resolve(&active, Some(&work), &state).is_noop(&state));
+        assert!(
+            AutoSwitch::resolve(&active, None, &AutoSwitchState::default())
+                .is_noop(&AutoSwitchState::default())
+        );
+    }
+
+    #[test]
+    fn leaving_restores_the_previous_profiles() {
+        let active = active(&[("claude-code", "work"), ("opencode", "work")]);
+        let state = AutoSwitchState {
+            marker: Some(PathBuf::from("/repo/.bridle")),
+            restore: map(&[("claude-code", "personal")]),
+        };
+
+        let left = AutoSwitch::resolve(&active, None, &state);
+        assert_eq!(left.switches, map(&[("claude-code", "personal")]));
+        assert_eq!(left.state, AutoSwitchState::default());
+    }
+
+    #[test]
+    fn moving_between_markers_keeps_the_original_profiles() {
+        let active = active(&[("claude-code", "work"), ("goose", "work")]);
+        let state = AutoSwitchState {
+            marker: Some(PathBuf::from("/work/.bridle")),
+            restore: map(&[("claude-code", "personal"), ("goose", "personal")]),
+        };
+        let oss = marker("/oss/.bridle", &[("claude-code", "oss")]);
+
+        let moved = AutoSwitch::resolve(&active, Some(&oss), &state);
+        assert_eq!(
+            moved.switches,
+            map(&[("claude-code", "oss"), ("goose", "personal")])
+        );
+        assert_eq!(moved.state.restore, map(&[("claude-code", "personal")]));
+    }
+}
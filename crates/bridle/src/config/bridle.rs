@@ -1,7 +1,8 @@
 //! Bridle's own configuration file handling.
 
+use crate::plan::fs;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 /// User preference for TUI view mode.
@@ -26,6 +27,36 @@ pub struct TuiConfig {
     pub view: ViewPreference,
 }
 
+/// Retention policy for `backups/<harness>/`, applied by `bridle backup prune`
+/// and after each new backup. Unset limits are not enforced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub struct BackupRetention {
+    /// Keep at most this many backups per harness.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keep: Option<usize>,
+    /// Delete backups older than this many days.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_days: Option<u64>,
+    /// Delete the oldest backups once a harness's backups exceed this many megabytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size_mb: Option<u64>,
+}
+
+impl BackupRetention {
+    pub fn is_unlimited(&self) -> bool {
+        self.keep.is_none() && self.max_age_days.is_none() && self.max_size_mb.is_none()
+    }
+}
+
+/// A plugin marketplace subscribed with `bridle marketplace add`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarketplaceSubscription {
+    /// Name used with `bridle install <name>` and `<plugin>@<name>`.
+    pub name: String,
+    /// Source the marketplace is fetched from (same forms as `bridle install`).
+    pub source: String,
+}
+
 /// Bridle's configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct BridleConfig {
@@ -54,6 +85,24 @@ pub struct BridleConfig {
     /// Default harness to show when TUI opens.
     #[serde(default)]
     pub default_harness: Option<String>,
+
+    /// Backup retention limits.
+    #[serde(default)]
+    pub backups: BackupRetention,
+
+    /// Base URL of the MCP registry used by `search` and `registry:` installs.
+    /// Defaults to the official registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry_url: Option<String>,
+
+    /// Subscribed plugin marketplaces.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub marketplaces: Vec<MarketplaceSubscription>,
+
+    /// Named sets of profiles switched together with `bridle use`
+    /// (workspace -> harness_id -> profile_name).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub workspaces: BTreeMap<String, BTreeMap<String, String>>,
 }
 
 impl BridleConfig {
@@ -81,8 +130,8 @@ impl BridleConfig {
     /// Load configuration from the default location.
     pub fn load() -> crate::error::Result<Self> {
         let path = Self::config_path()?;
-        if path.exists() {
-            let content = std::fs::read_to_string(&path)?;
+        if fs::exists(&path) {
+            let content = fs::read_to_string(&path)?;
             let config: Self = toml::from_str(&content)?;
             Ok(config)
         } else {
@@ -117,7 +166,7 @@ impl BridleConfig {
         let path = Self::config_path()?;
         let content =
             toml::to_string_pretty(self).map_err(|e| crate::error::Error::Config(e.to_string()))?;
-        std::fs::write(&path, content)?;
+        fs::write(&path, content)?;
         Ok(())
     }
 
@@ -152,4 +201,76 @@ impl BridleConfig {
     pub fn set_default_harness(&mut self, harness_id: Option<&str>) {
         self.default_harness = harness_id.map(String::from);
     }
+
+    /// Get the profiles of a workspace (harness_id -> profile_name).
+    pub fn workspace(&self, name: &str) -> Option<&BTreeMap<String, String>> {
+        self.workspaces.get(name)
+    }
+
+    /// The workspace whose profiles are all active, if any.
+    ///
+    /// Derived from the active profiles rather than stored, so switching a
+    /// single harness away from it ends the workspace.
+    pub fn active_workspace(&self) -> Option<&str> {
+        self.workspaces
+            .iter()
+            .find(|(_, profiles)| {
+                !profiles.is_empty()
+                    && profiles
+                        .iter()
+                        .all(|(harness, profile)| self.active_profile_for(harness) == Some(profile))
+            })
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_workspace() -> BridleConfig {
+        toml::from_str(
+            r#"
+            [active]
+            claude-code = "work"
+            opencode = "work"
+
+            [workspaces.work]
+            claude-code = "work"
+            opencode = "work"
+
+            [workspaces.oss]
+            claude-code = "oss"
+            "#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn workspace_is_active_while_all_its_profiles_are() {
+        let mut config = config_with_workspace();
+        assert_eq!(config.active_workspace(), Some("work"));
+
+        config.set_active_profile("opencode", "personal");
+        assert_eq!(config.active_workspace(), None);
+
+        config.set_active_profile("claude-code", "oss");
+        assert_eq!(config.active_workspace(), Some("oss"));
+    }
+
+    #[test]
+    fn workspaces_round_trip_through_toml() {
+        let config = config_with_workspace();
+        let saved: BridleConfig =
+            toml::from_str(&toml::to_string_pretty(&config).unwrap()).unwrap();
+        assert_eq!(saved.workspaces, config.workspaces);
+        assert_eq!(
+            saved
+                .workspace("work")
+                .unwrap()
+                .get("opencode")
+                .map(String::as_str),
+            Some("work")
+        );
+    }
 }
@@ -0,0 +1,797 @@
+//! Portable profile archives for `bridle profile export` and `import`.
+//!
+//! An archive is a zip holding [`ARCHIVE_MANIFEST`] and the profile's files
+//! under `profile/`. Exports are self-contained: inherited resources are
+//! folded in, session data and credential files are left out, and every
+//! MCP env value, header and OAuth client secret that looks like a secret
+//! is replaced by an environment variable reference listed in the manifest.
+//! Servers with a secret in a command argument or URL are left out, since
+//! those cannot read from the environment portably. Symlinks are left out
+//! too. Other literal values are kept as they are.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use harness_locate::{EnvValue, Harness, HarnessKind, McpServer};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use super::ProfileManager;
+use super::files::is_excluded;
+use super::materialize::MATERIALIZED_FILE;
+use crate::config::profile_name::ProfileName;
+use crate::error::{Error, Result};
+use crate::harness::HarnessConfig;
+use crate::install::hook_installer::{SETTINGS_FILE, settings_hook_commands, supports_hooks};
+use crate::install::manifest::{InstallManifest, manifest_path};
+use crate::install::mcp_config::{
+    McpConfigError, native_fragment, read_mcp_config, remove_mcp_config, write_mcp_config,
+};
+use crate::install::mcp_edit::DISABLED_FILE;
+use crate::install::mcp_installer::get_profile_config_path;
+use crate::install::types::ComponentType;
+use crate::plan::{self, fs};
+
+/// Manifest at the root of every archive.
+pub const ARCHIVE_MANIFEST: &str = "bridle-profile.toml";
+
+const PROFILE_PREFIX: &str = "profile/";
+const ARCHIVE_FORMAT: u32 = 1;
+
+/// Largest file an archive may hold when imported.
+const MAX_ENTRY_SIZE: u64 = 10 * 1024 * 1024;
+/// Most an archive's files may add up to when imported.
+const MAX_ARCHIVE_SIZE: u64 = 100 * 1024 * 1024;
+
+/// Files that hold credentials rather than configuration.
+const CREDENTIAL_FILES: &[&str] = &[".credentials.json", "secrets.yaml", ".env"];
+
+/// Env var and header names containing one of these (upper-cased, `-` as
+/// `_`) hold secrets.
+const SECRET_NAMES: &[&str] = &[
+    "TOKEN",
+    "SECRET",
+    "PASSW",
+    "KEY",
+    "AUTH",
+    "CREDENTIAL",
+    "COOKIE",
+];
+
+/// Prefixes of well-known credential formats, caught whatever they are named.
+const SECRET_PREFIXES: &[&str] = &[
+    "ghp_",
+    "gho_",
+    "ghs_",
+    "github_pat_",
+    "glpat-",
+    "sk-",
+    "xoxb-",
+    "xoxp-",
+    "AKIA",
+    "Bearer ",
+    "Basic ",
+];
+
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    #[error("{path} is not a bridle profile archive: {reason}")]
+    Invalid { path: PathBuf, reason: String },
+
+    #[error(
+        "{path} uses archive format {format}, newer than this bridle supports ({ARCHIVE_FORMAT}); upgrade bridle to import it"
+    )]
+    UnsupportedFormat { path: PathBuf, format: u32 },
+
+    #[error("{path}: {entry} exceeds the size limit: {size} bytes > {limit} bytes")]
+    SizeLimit {
+        path: PathBuf,
+        entry: String,
+        size: u64,
+        limit: u64,
+    },
+
+    #[error("{path} already exists; pass --force to overwrite it")]
+    Exists { path: PathBuf },
+
+    #[error("Failed to read {path}: {source}")]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Failed to write {path}: {source}")]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: zip::result::ZipError,
+    },
+}
+
+/// What an archive contains, stored as [`ARCHIVE_MANIFEST`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveManifest {
+    pub format: u32,
+    /// Harness id, e.g. `claude-code`.
+    pub harness: String,
+    pub profile: String,
+    pub bridle_version: String,
+    /// Environment variables the profile's MCP servers read.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env: Vec<EnvPlaceholder>,
+}
+
+/// An environment variable that stands in for a value left out of an archive.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnvPlaceholder {
+    pub name: String,
+    /// MCP servers that read it.
+    pub servers: Vec<String>,
+}
+
+/// Outcome of [`ProfileManager::export_profile`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportReport {
+    pub path: PathBuf,
+    pub manifest: ArchiveManifest,
+    pub files: usize,
+    /// MCP servers left out because their secrets could not be replaced.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub dropped_servers: Vec<String>,
+    /// Symlinks left out, relative to the profile; they can point anywhere.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub skipped_links: Vec<PathBuf>,
+}
+
+/// A read and validated archive.
+#[derive(Debug)]
+pub struct ProfileArchive {
+    pub manifest: ArchiveManifest,
+    pub harness: Harness,
+    /// Files relative to the profile directory.
+    files: Vec<(PathBuf, Vec<u8>)>,
+}
+
+/// Where a value sits in an MCP server.
+enum Field<'a> {
+    Env(&'a str),
+    Header(&'a str),
+    ClientSecret,
+}
+
+/// Environment variables chosen while scrubbing an export.
+#[derive(Default)]
+struct Placeholders {
+    /// Literal each placeholder replaced, so equal values share a variable.
+    values: BTreeMap<String, String>,
+    servers: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Placeholders {
+    /// The variable that replaces `literal`: named after its key, then
+    /// prefixed with the server, then numbered, until it names no other value.
+    fn replace(&mut self, server: &str, field: Field<'_>, literal: &str) -> String {
+        let base = match field {
+            Field::Env(key) => env_name(key),
+            Field::Header(key) => env_name(&format!("{server}_{key}")),
+            Field::ClientSecret => env_name(&format!("{server}_client_secret")),
+        };
+        let prefixed = env_name(&format!("{server}_{base}"));
+        let var = [base.clone(), prefixed]
+            .into_iter()
+            .chain((2..).map(|n| format!("{base}_{n}")))
+            .find(|var| self.values.get(var).is_none_or(|v| v == literal))
+            .unwrap_or(base);
+        self.values.insert(var.clone(), literal.to_string());
+        self.reference(server, &var);
+        var
+    }
+
+    fn reference(&mut self, server: &str, var: &str) {
+        self.servers
+            .entry(var.to_string())
+            .or_default()
+            .insert(server.to_string());
+    }
+
+    fn into_env(self) -> Vec<EnvPlaceholder> {
+        self.servers
+            .into_iter()
+            .map(|(name, servers)| EnvPlaceholder {
+                name,
+                servers: servers.into_iter().collect(),
+            })
+            .collect()
+    }
+}
+
+impl ProfileManager {
+    /// Writes a profile to a zip archive at `dest`, with secrets replaced by
+    /// environment variable references.
+    ///
+    /// # Errors
+    /// Returns [`ArchiveError::Exists`] if `dest` exists and `force` is not set.
+    pub fn export_profile(
+        &self,
+        harness: &Harness,
+        name: &ProfileName,
+        dest: &Path,
+        force: bool,
+    ) -> Result<ExportReport> {
+        if !force && fs::exists(dest) {
+            return Err(ArchiveError::Exists {
+                path: dest.to_path_buf(),
+            }
+            .into());
+        }
+
+        // The profile is laid out and scrubbed in a plan that is never
+        // applied, so the staging dir only exists in memory
+        let staging = std::env::temp_dir().join(format!(
+            "bridle-export-{}-{}",
+            std::process::id(),
+            chrono::Local::now().format("%Y%m%d%H%M%S%3f")
+        ));
+        let (archive, _) = plan::record(|| self.export_from(harness, name, &staging, dest));
+        let (report, bytes) = archive?;
+
+        fs::write(dest, bytes)?;
+        Ok(report)
+    }
+
+    fn export_from(
+        &self,
+        harness: &Harness,
+        name: &ProfileName,
+        staging: &Path,
+        dest: &Path,
+    ) -> Result<(ExportReport, Vec<u8>)> {
+        self.materialize_profile(harness, name, staging)?;
+
+        let mut placeholders = Placeholders::default();
+        let dropped = rewrite_mcp_servers(harness, staging, |server, native| {
+            scrub_server(harness, server, native, &mut placeholders)
+        })?;
+
+        let manifest = ArchiveManifest {
+            format: ARCHIVE_FORMAT,
+            harness: HarnessConfig::id(harness).to_string(),
+            profile: name.as_str().to_string(),
+            bridle_version: env!("CARGO_PKG_VERSION").to_string(),
+            env: placeholders.into_env(),
+        };
+        let mut files = Vec::new();
+        let mut links = Vec::new();
+        collect_files(staging, Path::new(""), &mut files, &mut links)?;
+        let bytes = archive_bytes(staging, &files, &manifest, dest)?;
+        let report = ExportReport {
+            path: dest.to_path_buf(),
+            manifest,
+            files: files.len(),
+            dropped_servers: dropped,
+            skipped_links: links,
+        };
+        Ok((report, bytes))
+    }
+
+    /// Creates profile `name` from an archive, pointing the references to
+    /// each variable in `renames` at another variable.
+    ///
+    /// # Errors
+    /// Returns [`Error::ProfileExists`] if the profile exists.
+    pub fn import_profile(
+        &self,
+        archive: &ProfileArchive,
+        name: &ProfileName,
+        renames: &BTreeMap<String, String>,
+    ) -> Result<PathBuf> {
+        let harness = &archive.harness;
+        let (path, plan) = plan::record(|| {
+            let path = self.create_profile(harness, name)?;
+            for (relative, content) in &archive.files {
+                let dest = path.join(relative);
+                if let Some(parent) = dest.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&dest, content)?;
+            }
+            if !renames.is_empty() {
+                rewrite_mcp_servers(harness, &path, |server, native| {
+                    Some(rename_refs(harness, server, native, renames))
+                })?;
+            }
+            Ok::<_, Error>(path)
+        });
+        let path = path?;
+        plan.apply()?;
+        Ok(path)
+    }
+}
+
+impl ProfileArchive {
+    /// Reads an archive, checking its manifest, that every file stays
+    /// inside the profile and that the files are not too large to unpack.
+    pub fn read(path: &Path) -> Result<Self> {
+        let invalid = |reason: String| ArchiveError::Invalid {
+            path: path.to_path_buf(),
+            reason,
+        };
+        let size_limit = |entry: &str, size: u64, limit: u64| ArchiveError::SizeLimit {
+            path: path.to_path_buf(),
+            entry: entry.to_string(),
+            size,
+            limit,
+        };
+        let file = std::fs::File::open(path).map_err(|source| ArchiveError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let mut zip = ZipArchive::new(file).map_err(|e| invalid(e.to_string()))?;
+
+        let mut manifest = None;
+        let mut files = Vec::new();
+        let mut total = 0;
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i).map_err(|e| invalid(e.to_string()))?;
+            if entry.is_dir() {
+                continue;
+            }
+            let name = entry.name().to_string();
+            if entry.size() > MAX_ENTRY_SIZE {
+                return Err(size_limit(&name, entry.size(), MAX_ENTRY_SIZE).into());
+            }
+            // The recorded size is not trusted; stop reading just past the limit
+            let mut content = Vec::new();
+            (&mut entry)
+                .take(MAX_ENTRY_SIZE + 1)
+                .read_to_end(&mut content)
+                .map_err(|e| invalid(format!("{name}: {e}")))?;
+            let size = content.len() as u64;
+            if size > MAX_ENTRY_SIZE {
+                return Err(size_limit(&name, size, MAX_ENTRY_SIZE).into());
+            }
+            total += size;
+            if total > MAX_ARCHIVE_SIZE {
+                return Err(size_limit("its files", total, MAX_ARCHIVE_SIZE).into());
+            }
+
+            if name == ARCHIVE_MANIFEST {
+                let text = String::from_utf8(content)
+                    .map_err(|_| invalid(format!("{ARCHIVE_MANIFEST} is not UTF-8")))?;
+                manifest = Some(
+                    toml::from_str::<ArchiveManifest>(&text)
+                        .map_err(|e| invalid(format!("{ARCHIVE_MANIFEST}: {e}")))?,
+                );
+                continue;
+            }
+            let relative = name
+                .strip_prefix(PROFILE_PREFIX)
+                .map(PathBuf::from)
+                .filter(|p| is_safe_relative(p))
+                .ok_or_else(|| invalid(format!("unexpected entry {name}")))?;
+            files.push((relative, content));
+        }
+
+        let manifest = manifest.ok_or_else(|| invalid(format!("no {ARCHIVE_MANIFEST}")))?;
+        if manifest.format > ARCHIVE_FORMAT {
+            return Err(ArchiveError::UnsupportedFormat {
+                path: path.to_path_buf(),
+                format: manifest.format,
+            }
+            .into());
+        }
+        let harness = HarnessKind::ALL
+            .iter()
+            .map(|kind| Harness::new(*kind))
+            .find(|harness| HarnessConfig::id(harness) == manifest.harness)
+            .ok_or_else(|| invalid(format!("unknown harness {}", manifest.harness)))?;
+        Ok(Self {
+            manifest,
+            harness,
+            files,
+        })
+    }
+
+    pub fn file_count(&self) -> usize {
+        self.files.len()
+    }
+
+    /// One line per command the hooks in the archived settings run, for
+    /// review before importing.
+    pub fn hook_commands(&self) -> Vec<String> {
+        if !supports_hooks(self.harness.kind()) {
+            return Vec::new();
+        }
+        self.files
+            .iter()
+            .find(|(path, _)| path == Path::new(SETTINGS_FILE))
+            .and_then(|(_, content)| serde_json::from_slice::<Value>(content).ok())
+            .map(|settings| settings_hook_commands(&settings["hooks"]))
+            .unwrap_or_default()
+    }
+
+    /// Variables the imported profile would read that are not set in this
+    /// shell, after applying `renames`.
+    pub fn missing_env(&self, renames: &BTreeMap<String, String>) -> Vec<String> {
+        self.manifest
+            .env
+            .iter()
+            .map(|var| renames.get(&var.name).unwrap_or(&var.name))
+            .filter(|var| std::env::var_os(var).is_none())
+            .cloned()
+            .collect()
+    }
+}
+
+/// Passes every MCP server in a profile directory through `rewrite`,
+/// including disabled ones and the fragments recorded in its install
+/// manifest. Servers `rewrite` returns `None` for are removed, and their
+/// names returned.
+fn rewrite_mcp_servers(
+    harness: &Harness,
+    dir: &Path,
+    mut rewrite: impl FnMut(&str, &Value) -> Option<Value>,
+) -> Result<Vec<String>> {
+    let kind = harness.kind();
+    let mut dropped = Vec::new();
+    // Native config of every server that is kept, for the install manifest
+    let mut kept = HashMap::new();
+
+    let config_path = get_profile_config_path(dir, kind);
+    let servers = read_mcp_config(kind, &config_path).map_err(mcp_error)?;
+    let mut names: Vec<&String> = servers.keys().collect();
+    names.sort();
+    let mut changed = HashMap::new();
+    for name in names {
+        let native = &servers[name];
+        match rewrite(name, native) {
+            Some(rewritten) if rewritten == *native => {
+                kept.insert(name.clone(), rewritten);
+            }
+            Some(rewritten) => {
+                remove_mcp_config(kind, &config_path, name).map_err(mcp_error)?;
+                changed.insert(name.clone(), rewritten.clone());
+                kept.insert(name.clone(), rewritten);
+            }
+            None => {
+                remove_mcp_config(kind, &config_path, name).map_err(mcp_error)?;
+                dropped.push(name.clone());
+            }
+        }
+    }
+    if !changed.is_empty() {
+        write_mcp_config(kind, &config_path, &changed).map_err(mcp_error)?;
+    }
+
+    let stash_path = dir.join(DISABLED_FILE);
+    if fs::exists(&stash_path) {
+        let stash: BTreeMap<String, Value> =
+            serde_json::from_str(&fs::read_to_string(&stash_path)?)?;
+        let mut rewritten_stash = BTreeMap::new();
+        for (name, native) in stash {
+            match rewrite(&name, &native) {
+                Some(rewritten) => {
+                    kept.insert(name.clone(), rewritten.clone());
+                    rewritten_stash.insert(name, rewritten);
+                }
+                None => dropped.push(name),
+            }
+        }
+        fs::write(&stash_path, serde_json::to_string_pretty(&rewritten_stash)?)?;
+    }
+
+    let install_manifest = manifest_path(dir);
+    if fs::exists(&install_manifest) {
+        let mut manifest =
+            InstallManifest::load(&install_manifest).map_err(|e| Error::Config(e.to_string()))?;
+        for entry in &mut manifest.entries {
+            if entry.component_type == ComponentType::Mcp && entry.fragment.is_some() {
+                entry.fragment = kept
+                    .get(&entry.name)
+                    .map(|native| native_fragment(kind, &entry.name, native));
+            }
+        }
+        manifest
+            .save(&install_manifest)
+            .map_err(|e| Error::Config(e.to_string()))?;
+    }
+    Ok(dropped)
+}
+
+/// Replaces the literal values of one server with variable references, or
+/// returns `None` when that cannot be done.
+fn scrub_server(
+    harness: &Harness,
+    name: &str,
+    native: &Value,
+    placeholders: &mut Placeholders,
+) -> Option<Value> {
+    let server = harness.parse_mcp_server_config(name, native).ok();
+    if server.as_ref().is_some_and(has_inline_secret) {
+        return None;
+    }
+    if harness.kind() == HarnessKind::Goose {
+        return scrub_goose_server(name, native, placeholders);
+    }
+    let mut server = server?;
+    for_each_value(&mut server, |field, value| match value {
+        EnvValue::Plain(literal) if is_secret(&field, literal) => {
+            let var = placeholders.replace(name, field, &literal.clone());
+            *value = EnvValue::env(var);
+        }
+        EnvValue::Plain(_) => {}
+        EnvValue::EnvRef { env } => placeholders.reference(name, env),
+    });
+    let rewritten = harness.mcp_to_native(name, &server).ok()?;
+    Some(overlay(native, rewritten))
+}
+
+/// Goose has no syntax for reading a value from the environment inside its
+/// config; it reads the variables named in `env_keys` instead. Secret env
+/// values move there under their own name, and servers with secret headers
+/// are left out.
+fn scrub_goose_server(
+    name: &str,
+    native: &Value,
+    placeholders: &mut Placeholders,
+) -> Option<Value> {
+    let mut native = native.clone();
+    let object = native.as_object_mut()?;
+    if object
+        .get("headers")
+        .and_then(Value::as_object)
+        .is_some_and(|headers| {
+            headers.iter().any(|(key, value)| {
+                is_secret(&Field::Header(key), value.as_str().unwrap_or_default())
+            })
+        })
+    {
+        return None;
+    }
+
+    let mut keys: BTreeSet<String> = object
+        .get("env_keys")
+        .and_then(Value::as_array)
+        .map(|keys| {
+            keys.iter()
+                .filter_map(|key| key.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    if let Some(Value::Object(envs)) = object.remove("envs") {
+        let (secret, plain): (serde_json::Map<_, _>, _) =
+            envs.into_iter().partition(|(key, value)| {
+                is_secret(&Field::Env(key), value.as_str().unwrap_or_default())
+            });
+        keys.extend(secret.into_iter().map(|(key, _)| key));
+        if !plain.is_empty() {
+            object.insert("envs".to_string(), Value::Object(plain));
+        }
+    }
+    for key in &keys {
+        placeholders.reference(name, key);
+    }
+    if !keys.is_empty() {
+        object.insert("env_keys".to_string(), serde_json::json!(keys));
+    }
+    Some(native)
+}
+
+/// Points references to renamed variables at their new names.
+fn rename_refs(
+    harness: &Harness,
+    name: &str,
+    native: &Value,
+    renames: &BTreeMap<String, String>,
+) -> Value {
+    // Goose reads each env key from the variable of the same name
+    if harness.kind() == HarnessKind::Goose {
+        return native.clone();
+    }
+    let Ok(mut server) = harness.parse_mcp_server_config(name, native) else {
+        return native.clone();
+    };
+    let mut renamed = false;
+    for_each_value(&mut server, |_, value| {
+        if let EnvValue::EnvRef { env } = value
+            && let Some(new_name) = renames.get(env.as_str())
+        {
+            *value = EnvValue::env(new_name.clone());
+            renamed = true;
+        }
+    });
+    match harness.mcp_to_native(name, &server) {
+        Ok(rewritten) if renamed => overlay(native, rewritten),
+        _ => native.clone(),
+    }
+}
+
+fn for_each_value(server: &mut McpServer, mut visit: impl FnMut(Field<'_>, &mut EnvValue)) {
+    match server {
+        McpServer::Stdio(s) => {
+            for (key, value) in s.env.iter_mut() {
+                visit(Field::Env(key), value);
+            }
+        }
+        McpServer::Sse(s) => {
+            for (key, value) in s.headers.iter_mut() {
+                visit(Field::Header(key), value);
+            }
+        }
+        McpServer::Http(s) => {
+            for (key, value) in s.headers.iter_mut() {
+                visit(Field::Header(key), value);
+            }
+            if let Some(secret) = s.oauth.as_mut().and_then(|o| o.client_secret.as_mut()) {
+                visit(Field::ClientSecret, secret);
+            }
+        }
+    }
+}
+
+/// Whether a literal should be replaced by a variable reference on export.
+fn is_secret(field: &Field<'_>, literal: &str) -> bool {
+    let name = match field {
+        Field::ClientSecret => return true,
+        Field::Env(key) | Field::Header(key) => env_name(key),
+    };
+    SECRET_NAMES.iter().any(|word| name.contains(word))
+        || SECRET_PREFIXES
+            .iter()
+            .any(|prefix| literal.starts_with(prefix))
+}
+
+/// Whether a secret sits in a command argument or URL, where no harness-neutral
+/// variable reference can stand in for it.
+fn has_inline_secret(server: &McpServer) -> bool {
+    match server {
+        McpServer::Stdio(s) => args_have_secret(&s.args),
+        McpServer::Sse(s) => url_has_secret(&s.url),
+        McpServer::Http(s) => url_has_secret(&s.url),
+    }
+}
+
+/// `ghp_...`, `--token=...` or `--api-key ...`.
+fn args_have_secret(args: &[String]) -> bool {
+    args.iter().enumerate().any(|(i, arg)| {
+        let Some(flag) = arg.strip_prefix('-').map(|f| f.trim_start_matches('-')) else {
+            return is_secret(&Field::Env(""), arg);
+        };
+        match flag.split_once('=') {
+            Some((key, value)) => is_literal(value) && is_secret(&Field::Env(key), value),
+            None => {
+                is_secret(&Field::Env(flag), "")
+                    && args
+                        .get(i + 1)
+                        .is_some_and(|value| !value.starts_with('-') && is_literal(value))
+            }
+        }
+    })
+}
+
+/// A password in the userinfo, or a secret query parameter.
+fn url_has_secret(url: &str) -> bool {
+    let url = url.split('#').next().unwrap_or_default();
+    let (base, query) = url.split_once('?').unwrap_or((url, ""));
+    let password = base
+        .split_once("://")
+        .and_then(|(_, rest)| rest.split('/').next())
+        .and_then(|authority| authority.rsplit_once('@'))
+        .is_some_and(|(userinfo, _)| userinfo.contains(':'));
+    password
+        || query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .any(|(key, value)| is_literal(value) && is_secret(&Field::Env(key), value))
+}
+
+/// Not empty and not already a reference such as `${TOKEN}` or `{env:TOKEN}`.
+fn is_literal(value: &str) -> bool {
+    !value.is_empty() && !value.starts_with('$') && !value.contains("{env:")
+}
+
+/// `rewritten` on top of `native`, keeping settings bridle does not model.
+fn overlay(native: &Value, rewritten: Value) -> Value {
+    match (native, rewritten) {
+        (Value::Object(native), Value::Object(rewritten)) => {
+            let mut merged = native.clone();
+            merged.extend(rewritten);
+            Value::Object(merged)
+        }
+        (_, rewritten) => rewritten,
+    }
+}
+
+fn mcp_error(e: McpConfigError) -> Error {
+    Error::Config(e.to_string())
+}
+
+/// `api-key` -> `API_KEY`.
+fn env_name(raw: &str) -> String {
+    raw.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn is_safe_relative(path: &Path) -> bool {
+    path.components().next().is_some()
+        && path.components().all(|c| matches!(c, Component::Normal(_)))
+}
+
+/// Zips `files` of `dir` under `profile/` next to the manifest.
+fn archive_bytes(
+    dir: &Path,
+    files: &[PathBuf],
+    manifest: &ArchiveManifest,
+    dest: &Path,
+) -> Result<Vec<u8>> {
+    let zip_error = |source| ArchiveError::Write {
+        path: dest.to_path_buf(),
+        source,
+    };
+
+    let mut buffer = std::io::Cursor::new(Vec::new());
+    let mut zip = ZipWriter::new(&mut buffer);
+    let options = SimpleFileOptions::default();
+    let manifest = toml::to_string_pretty(manifest).map_err(|e| Error::Config(e.to_string()))?;
+    zip.start_file(ARCHIVE_MANIFEST, options)
+        .map_err(zip_error)?;
+    zip.write_all(manifest.as_bytes())?;
+    for relative in files {
+        let name = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        zip.start_file(format!("{PROFILE_PREFIX}{name}"), options)
+            .map_err(zip_error)?;
+        zip.write_all(&fs::read(dir.join(relative))?)?;
+    }
+    zip.finish().map_err(zip_error)?;
+
+    Ok(buffer.into_inner())
+}
+
+/// Files below `root` that belong in an archive. Symlinks are collected into
+/// `links` instead of being followed, so nothing outside the profile ends up
+/// in the archive.
+fn collect_files(
+    root: &Path,
+    relative: &Path,
+    files: &mut Vec<PathBuf>,
+    links: &mut Vec<PathBuf>,
+) -> Result<()> {
+    let mut entries = fs::read_dir(root.join(relative))?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        let name = entry.file_name();
+        let name_str = name.to_string_lossy();
+        if is_excluded(&name_str)
+            || CREDENTIAL_FILES.contains(&name_str.as_ref())
+            || name_str == MATERIALIZED_FILE
+        {
+            continue;
+        }
+        let path = relative.join(&name);
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            links.push(path);
+        } else if file_type.is_dir() {
+            collect_files(root, &path, files, links)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
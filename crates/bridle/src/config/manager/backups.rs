@@ -0,0 +1,265 @@
+//! Listing, restoring and pruning the backups under `backups/<harness>/`.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use serde::Serialize;
+
+use super::{ProfileManager, files};
+use crate::config::{BackupRetention, BridleConfig};
+use crate::error::{Error, Result};
+use crate::harness::HarnessConfig;
+use crate::plan::fs;
+
+/// Session-data snapshots, rotated separately by `backup_session_data`.
+const EXTRA_DIR: &str = "extra";
+const NO_PROFILE_DIR: &str = "no-profile";
+
+/// Where a backup came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackupKind {
+    /// Taken before a switch or restore (`<YYYYmmdd_HHMMSS>`).
+    Snapshot,
+    /// Left behind by a profile switch that failed to roll back
+    /// (`<YYYYmmdd_HHMMSS_mmm>_<pid>`).
+    Switch,
+    /// The harness config as it was before bridle first switched profiles.
+    NoProfile,
+}
+
+impl BackupKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BackupKind::Snapshot => "snapshot",
+            BackupKind::Switch => "switch",
+            BackupKind::NoProfile => "no-profile",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupInfo {
+    pub id: String,
+    pub harness_id: String,
+    pub kind: BackupKind,
+    pub path: PathBuf,
+    pub created: Option<DateTime<Local>>,
+    pub size: u64,
+    pub files: usize,
+}
+
+/// Contents of a single backup.
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupDetails {
+    #[serde(flatten)]
+    pub info: BackupInfo,
+    /// Paths relative to the backup root, with their sizes in bytes.
+    pub entries: Vec<(String, u64)>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupRestore {
+    pub harness_id: String,
+    pub restored: String,
+    /// Snapshot of the config that was replaced, restorable to undo.
+    pub undo: Option<String>,
+}
+
+impl ProfileManager {
+    fn harness_backups_dir(&self, harness: &dyn HarnessConfig) -> PathBuf {
+        self.backups_dir().join(harness.id())
+    }
+
+    /// Lists a harness's backups, newest first.
+    pub fn list_backups(&self, harness: &dyn HarnessConfig) -> Result<Vec<BackupInfo>> {
+        let dir = self.harness_backups_dir(harness);
+        if !fs::exists(&dir) {
+            return Ok(Vec::new());
+        }
+
+        let mut backups = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let Some(id) = entry.file_name().to_str().map(String::from) else {
+                continue;
+            };
+            if id == EXTRA_DIR {
+                continue;
+            }
+            backups.push(backup_info(harness.id(), &id, &entry.path())?);
+        }
+
+        backups.sort_by(|a, b| b.created.cmp(&a.created).then_with(|| b.id.cmp(&a.id)));
+        Ok(backups)
+    }
+
+    /// Returns a backup together with its file listing.
+    pub fn show_backup(&self, harness: &dyn HarnessConfig, id: &str) -> Result<BackupDetails> {
+        let info = self.find_backup(harness, id)?;
+        let mut entries = Vec::new();
+        walk_files(&info.path, &info.path, &mut entries)?;
+        entries.sort();
+        Ok(BackupDetails { info, entries })
+    }
+
+    fn find_backup(&self, harness: &dyn HarnessConfig, id: &str) -> Result<BackupInfo> {
+        let path = self.harness_backups_dir(harness).join(id);
+        if id == EXTRA_DIR || id.contains(['/', '\\']) || id == ".." || !fs::is_dir(&path) {
+            return Err(Error::BackupNotFound(id.to_string()));
+        }
+        backup_info(harness.id(), id, &path)
+    }
+
+    /// Restores a backup into the harness's live config.
+    ///
+    /// The current config is snapshotted first so the restore can itself be
+    /// undone, then the backup is switched in through the same
+    /// backup-wipe-copy path as a profile switch. The active profile is left
+    /// as is, so the restored files show up as drift against it.
+    pub fn restore_backup(&self, harness: &dyn HarnessConfig, id: &str) -> Result<BackupRestore> {
+        let backup = self.find_backup(harness, id)?;
+
+        let undo = match self.backup_current(harness) {
+            Ok(path) => path.file_name().map(|n| n.to_string_lossy().into_owned()),
+            Err(Error::NoConfigFound(_)) => None,
+            Err(e) => return Err(e),
+        };
+
+        let target_dir = harness.config_dir()?;
+        if !fs::exists(&target_dir) {
+            fs::create_dir_all(&target_dir)?;
+        }
+        files::switch_config_dir_safely(
+            &backup.path,
+            &target_dir,
+            &self.harness_backups_dir(harness),
+        )?;
+
+        if let Some(mcp_path) = harness.mcp_config_path()
+            && !mcp_path.starts_with(&target_dir)
+            && let Some(filename) = mcp_path.file_name()
+        {
+            let mcp_in_backup = backup.path.join(filename);
+            if fs::is_file(&mcp_in_backup) {
+                fs::copy(&mcp_in_backup, &mcp_path)?;
+                let _ = fs::remove_file(target_dir.join(filename));
+            }
+        }
+
+        let _ = self.apply_backup_retention(harness);
+
+        Ok(BackupRestore {
+            harness_id: harness.id().to_string(),
+            restored: backup.id,
+            undo,
+        })
+    }
+
+    /// Prunes with the retention policy from `config.toml`, if one is set.
+    pub fn apply_backup_retention(&self, harness: &dyn HarnessConfig) -> Result<Vec<BackupInfo>> {
+        let retention = BridleConfig::load().unwrap_or_default().backups;
+        if retention.is_unlimited() {
+            return Ok(Vec::new());
+        }
+        self.prune_backups(harness, &retention, false)
+    }
+
+    /// Deletes backups outside the retention policy and returns them.
+    ///
+    /// The `no-profile` snapshot is the only copy of the pre-bridle config
+    /// and is never pruned.
+    pub fn prune_backups(
+        &self,
+        harness: &dyn HarnessConfig,
+        retention: &BackupRetention,
+        dry_run: bool,
+    ) -> Result<Vec<BackupInfo>> {
+        let now = Local::now();
+        let max_age = retention
+            .max_age_days
+            .map(|days| chrono::Duration::days(days as i64));
+        let max_size = retention.max_size_mb.map(|mb| mb * 1024 * 1024);
+
+        let mut kept = 0usize;
+        let mut kept_size = 0u64;
+        let mut pruned = Vec::new();
+        for backup in self.list_backups(harness)? {
+            if backup.kind == BackupKind::NoProfile {
+                continue;
+            }
+
+            let over_count = retention.keep.is_some_and(|keep| kept >= keep);
+            let too_old = max_age
+                .zip(backup.created)
+                .is_some_and(|(age, created)| now - created > age);
+            let over_size = max_size.is_some_and(|max| kept_size + backup.size > max);
+
+            if over_count || too_old || over_size {
+                if !dry_run {
+                    fs::remove_dir_all(&backup.path)?;
+                }
+                pruned.push(backup);
+            } else {
+                kept += 1;
+                kept_size += backup.size;
+            }
+        }
+        Ok(pruned)
+    }
+}
+
+fn backup_info(harness_id: &str, id: &str, path: &Path) -> Result<BackupInfo> {
+    let kind = if id == NO_PROFILE_DIR {
+        BackupKind::NoProfile
+    } else if id.split('_').count() > 2 {
+        BackupKind::Switch
+    } else {
+        BackupKind::Snapshot
+    };
+
+    let created = id
+        .get(..15)
+        .and_then(|ts| NaiveDateTime::parse_from_str(ts, "%Y%m%d_%H%M%S").ok())
+        .and_then(|ts| Local.from_local_datetime(&ts).earliest())
+        .or_else(|| {
+            std::fs::metadata(path)
+                .and_then(|m| m.modified())
+                .ok()
+                .map(DateTime::<Local>::from)
+        });
+
+    let mut entries = Vec::new();
+    walk_files(path, path, &mut entries)?;
+
+    Ok(BackupInfo {
+        id: id.to_string(),
+        harness_id: harness_id.to_string(),
+        kind,
+        path: path.to_path_buf(),
+        created,
+        size: entries.iter().map(|(_, size)| size).sum(),
+        files: entries.len(),
+    })
+}
+
+fn walk_files(root: &Path, dir: &Path, out: &mut Vec<(String, u64)>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            walk_files(root, &path, out)?;
+        } else if file_type.is_file() {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            out.push((
+                relative.to_string_lossy().replace('\\', "/"),
+                fs::len(&path)?,
+            ));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,488 @@
+//! Semantic comparison of two profile directories.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use harness_locate::{Harness, Scope};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::ProfileManager;
+use super::extraction::{
+    extract_agents, extract_commands, extract_mcp_servers, extract_model, extract_plugins,
+    extract_rules_file, extract_skills, extract_theme,
+};
+use crate::config::jsonc::strip_jsonc_comments;
+use crate::config::profile_name::ProfileName;
+use crate::config::types::{McpServerInfo, ResourceSummary};
+use crate::error::{Error, Result};
+use crate::harness::HarnessConfig;
+
+/// Label used for the live harness config when no second profile is given.
+pub const CURRENT_CONFIG: &str = "current";
+
+/// Differences between two profiles, from `left` to `right`.
+///
+/// Added entries exist only in `right`, removed entries only in `left`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProfileDiff {
+    pub harness_id: String,
+    pub left: String,
+    pub right: String,
+    pub mcp_servers: McpDiff,
+    pub skills: SetDiff,
+    pub commands: SetDiff,
+    pub agents: SetDiff,
+    pub plugins: SetDiff,
+    /// Model, theme and rules file
+    pub settings: Vec<ValueChange>,
+    /// Key-level changes in top-level JSON/YAML config files, keyed as
+    /// `file:/json/pointer`. MCP server sections are covered by `mcp_servers`.
+    pub config: Vec<ValueChange>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct McpDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<McpServerChange>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct McpServerChange {
+    pub name: String,
+    pub fields: Vec<ValueChange>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SetDiff {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValueChange {
+    pub key: String,
+    pub left: Option<String>,
+    pub right: Option<String>,
+}
+
+impl SetDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl McpDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl ProfileDiff {
+    pub fn is_empty(&self) -> bool {
+        self.mcp_servers.is_empty()
+            && self.skills.is_empty()
+            && self.commands.is_empty()
+            && self.agents.is_empty()
+            && self.plugins.is_empty()
+            && self.settings.is_empty()
+            && self.config.is_empty()
+    }
+}
+
+impl ProfileManager {
+    /// Compare a profile with another profile, or with the live harness
+    /// config when `other` is `None`.
+    pub fn diff_profiles(
+        &self,
+        harness: &Harness,
+        name: &ProfileName,
+        other: Option<&ProfileName>,
+    ) -> Result<ProfileDiff> {
+        let left = self.profile_path(harness, name);
+        if !left.exists() {
+            return Err(Error::ProfileNotFound(name.as_str().to_string()));
+        }
+
+        let (right_label, right) = match other {
+            Some(other) => {
+                let path = self.profile_path(harness, other);
+                if !path.exists() {
+                    return Err(Error::ProfileNotFound(other.as_str().to_string()));
+                }
+                (other.as_str(), path)
+            }
+            None => (CURRENT_CONFIG, harness.config_dir()?),
+        };
+
+        Ok(diff_dirs(
+            harness,
+            (name.as_str(), &left),
+            (right_label, &right),
+        ))
+    }
+}
+
+/// Compare two directories holding configs for the same harness.
+pub fn diff_dirs(harness: &Harness, left: (&str, &Path), right: (&str, &Path)) -> ProfileDiff {
+    let (left_label, left) = left;
+    let (right_label, right) = right;
+
+    let mut settings = Vec::new();
+    push_change(
+        &mut settings,
+        "model",
+        extract_model(harness, left),
+        extract_model(harness, right),
+    );
+    push_change(
+        &mut settings,
+        "theme",
+        extract_theme(harness, left),
+        extract_theme(harness, right),
+    );
+    let rules_name = |dir: &Path| {
+        extract_rules_file(harness, dir)
+            .0
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+    };
+    push_change(&mut settings, "rules", rules_name(left), rules_name(right));
+
+    let (config, mcp_fields) = diff_config_files(harness, left, right);
+
+    ProfileDiff {
+        harness_id: harness.id().to_string(),
+        left: left_label.to_string(),
+        right: right_label.to_string(),
+        mcp_servers: diff_mcp(
+            &extract_mcp_servers(harness, left).unwrap_or_default(),
+            &extract_mcp_servers(harness, right).unwrap_or_default(),
+            mcp_fields,
+        ),
+        skills: diff_resources(
+            Some(extract_skills(harness, left).0),
+            Some(extract_skills(harness, right).0),
+        ),
+        commands: diff_resources(
+            Some(extract_commands(harness, left).0),
+            Some(extract_commands(harness, right).0),
+        ),
+        agents: diff_resources(
+            extract_agents(harness, left).0,
+            extract_agents(harness, right).0,
+        ),
+        plugins: diff_resources(
+            extract_plugins(harness, left).0,
+            extract_plugins(harness, right).0,
+        ),
+        settings,
+        config,
+    }
+}
+
+fn push_change(
+    changes: &mut Vec<ValueChange>,
+    key: &str,
+    left: Option<String>,
+    right: Option<String>,
+) {
+    if left != right {
+        changes.push(ValueChange {
+            key: key.to_string(),
+            left,
+            right,
+        });
+    }
+}
+
+fn diff_resources(left: Option<ResourceSummary>, right: Option<ResourceSummary>) -> SetDiff {
+    let left: BTreeSet<String> = left
+        .map(|s| s.items)
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    let right: BTreeSet<String> = right
+        .map(|s| s.items)
+        .unwrap_or_default()
+        .into_iter()
+        .collect();
+    SetDiff {
+        added: right.difference(&left).cloned().collect(),
+        removed: left.difference(&right).cloned().collect(),
+    }
+}
+
+/// Native field changes per MCP server name, taken from the raw config.
+type McpFieldChanges = BTreeMap<String, Vec<ValueChange>>;
+
+fn diff_mcp(
+    left: &[McpServerInfo],
+    right: &[McpServerInfo],
+    mut native: McpFieldChanges,
+) -> McpDiff {
+    let left: BTreeMap<&str, &McpServerInfo> = left.iter().map(|s| (s.name.as_str(), s)).collect();
+    let right: BTreeMap<&str, &McpServerInfo> =
+        right.iter().map(|s| (s.name.as_str(), s)).collect();
+
+    let mut diff = McpDiff::default();
+    for (name, server) in &left {
+        let Some(other) = right.get(name) else {
+            diff.removed.push(name.to_string());
+            continue;
+        };
+        // Prefer native fields; fall back to the extracted summary when the
+        // server lives outside the parsed config files
+        let mut fields = native.remove(*name).unwrap_or_default();
+        if fields.is_empty() {
+            push_change(
+                &mut fields,
+                "enabled",
+                Some(server.enabled.to_string()),
+                Some(other.enabled.to_string()),
+            );
+            push_change(
+                &mut fields,
+                "type",
+                server.server_type.clone(),
+                other.server_type.clone(),
+            );
+            push_change(
+                &mut fields,
+                "command",
+                server.command.clone(),
+                other.command.clone(),
+            );
+            push_change(
+                &mut fields,
+                "args",
+                server.args.as_ref().map(|a| a.join(" ")),
+                other.args.as_ref().map(|a| a.join(" ")),
+            );
+            push_change(&mut fields, "url", server.url.clone(), other.url.clone());
+        }
+        if !fields.is_empty() {
+            diff.changed.push(McpServerChange {
+                name: name.to_string(),
+                fields,
+            });
+        }
+    }
+    diff.added = right
+        .keys()
+        .filter(|name| !left.contains_key(*name))
+        .map(|name| name.to_string())
+        .collect();
+    diff
+}
+
+/// Key-level changes across config files. Keys under the harness's MCP
+/// section are split out per server instead.
+fn diff_config_files(
+    harness: &Harness,
+    left: &Path,
+    right: &Path,
+) -> (Vec<ValueChange>, McpFieldChanges) {
+    // The first segment of the MCP key path, e.g. `mcpServers` or `extensions`
+    let mcp_key = harness.mcp(&Scope::Global).ok().flatten().and_then(|r| {
+        r.key_path
+            .trim_start_matches('/')
+            .split('/')
+            .next()
+            .map(String::from)
+    });
+
+    let left_files = config_files(left);
+    let right_files = config_files(right);
+    let names: BTreeSet<&String> = left_files.keys().chain(right_files.keys()).collect();
+
+    let mut changes = Vec::new();
+    let mut mcp_changes = McpFieldChanges::new();
+    for name in names {
+        let empty = BTreeMap::new();
+        let l = left_files.get(name).unwrap_or(&empty);
+        let r = right_files.get(name).unwrap_or(&empty);
+        let keys: BTreeSet<&String> = l.keys().chain(r.keys()).collect();
+        for key in keys {
+            let mut segments = key.trim_start_matches('/').splitn(3, '/');
+            if mcp_key.is_some() && segments.next() == mcp_key.as_deref() {
+                if let Some(server) = segments.next() {
+                    let server = server.replace("~1", "/").replace("~0", "~");
+                    let field = segments.next().unwrap_or("(value)");
+                    push_change(
+                        mcp_changes.entry(server).or_default(),
+                        field,
+                        l.get(key).cloned(),
+                        r.get(key).cloned(),
+                    );
+                }
+                continue;
+            }
+            push_change(
+                &mut changes,
+                &format!("{}:{}", name, key),
+                l.get(key).cloned(),
+                r.get(key).cloned(),
+            );
+        }
+    }
+    (changes, mcp_changes)
+}
+
+/// Top-level JSON/JSONC/YAML files in a directory, flattened to
+/// pointer → compact value.
+fn config_files(dir: &Path) -> BTreeMap<String, BTreeMap<String, String>> {
+    let mut files = BTreeMap::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return files;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().and_then(|n| n.to_str()).map(String::from) else {
+            continue;
+        };
+        if !path.is_file() || name == ".bridle-manifest.json" {
+            continue;
+        }
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let value: Option<Value> = match path.extension().and_then(|e| e.to_str()) {
+            Some("json") | Some("jsonc") => {
+                serde_json::from_str(&strip_jsonc_comments(&content)).ok()
+            }
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&content).ok(),
+            _ => None,
+        };
+        if let Some(value) = value {
+            let mut flat = BTreeMap::new();
+            flatten(&value, String::new(), &mut flat);
+            files.insert(name, flat);
+        }
+    }
+    files
+}
+
+fn flatten(value: &Value, pointer: String, out: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                let escaped = key.replace('~', "~0").replace('/', "~1");
+                flatten(child, format!("{}/{}", pointer, escaped), out);
+            }
+        }
+        _ => {
+            let pointer = if pointer.is_empty() {
+                "/".to_string()
+            } else {
+                pointer
+            };
+            out.insert(pointer, value.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use harness_locate::HarnessKind;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn diff_reports_mcp_fields_resources_and_config_keys() {
+        let temp = TempDir::new().unwrap();
+        let left = temp.path().join("left");
+        let right = temp.path().join("right");
+        write(
+            &left,
+            "opencode.jsonc",
+            r#"{
+              // comment
+              "model": "a/one",
+              "theme": "dark",
+              "mcp": {
+                "fs": {"type": "local", "command": ["npx", "fs"], "enabled": true},
+                "old": {"type": "remote", "url": "https://old"}
+              }
+            }"#,
+        );
+        write(
+            &right,
+            "opencode.jsonc",
+            r#"{
+              "model": "a/two",
+              "theme": "dark",
+              "autoupdate": false,
+              "mcp": {
+                "fs": {"type": "local", "command": ["npx", "fs", "--ro"], "enabled": false},
+                "new": {"type": "remote", "url": "https://new"}
+              }
+            }"#,
+        );
+        write(&left, "skill/alpha/SKILL.md", "a");
+        write(&left, "skill/beta/SKILL.md", "b");
+        write(&right, "skill/beta/SKILL.md", "b");
+        write(&right, "skill/gamma/SKILL.md", "c");
+
+        let harness = Harness::new(HarnessKind::OpenCode);
+        let diff = diff_dirs(&harness, ("left", &left), ("right", &right));
+
+        assert_eq!(diff.mcp_servers.added, vec!["new"]);
+        assert_eq!(diff.mcp_servers.removed, vec!["old"]);
+        assert_eq!(diff.mcp_servers.changed.len(), 1);
+        let fs = &diff.mcp_servers.changed[0];
+        assert_eq!(fs.name, "fs");
+        let keys: Vec<&str> = fs.fields.iter().map(|f| f.key.as_str()).collect();
+        assert_eq!(keys, vec!["command", "enabled"]);
+        assert_eq!(
+            fs.fields[0].right.as_deref(),
+            Some(r#"["npx","fs","--ro"]"#)
+        );
+
+        assert_eq!(diff.skills.added, vec!["gamma"]);
+        assert_eq!(diff.skills.removed, vec!["alpha"]);
+
+        assert_eq!(
+            diff.settings,
+            vec![ValueChange {
+                key: "model".to_string(),
+                left: Some("a/one".to_string()),
+                right: Some("a/two".to_string()),
+            }]
+        );
+
+        let config_keys: Vec<&str> = diff.config.iter().map(|c| c.key.as_str()).collect();
+        assert_eq!(
+            config_keys,
+            vec!["opencode.jsonc:/autoupdate", "opencode.jsonc:/model"]
+        );
+    }
+
+    #[test]
+    fn identical_dirs_have_empty_diff() {
+        let temp = TempDir::new().unwrap();
+        let left = temp.path().join("left");
+        let right = temp.path().join("right");
+        write(
+            &left,
+            "settings.json",
+            r#"{"model": "opus", "env": {"A": "1"}}"#,
+        );
+        write(
+            &right,
+            "settings.json",
+            "{\n  \"env\": {\"A\": \"1\"},\n  \"model\": \"opus\"\n}",
+        );
+
+        let harness = Harness::new(HarnessKind::ClaudeCode);
+        let diff = diff_dirs(&harness, ("left", &left), ("right", &right));
+        assert!(diff.is_empty(), "{:?}", diff);
+    }
+}
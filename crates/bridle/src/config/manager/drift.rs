@@ -0,0 +1,271 @@
+//! Detection of out-of-band edits to a harness's live config.
+//!
+//! When a profile is activated, bridle records a fingerprint of the live
+//! config directory (and MCP file). Anything that rewrites those files
+//! afterwards — a harness self-update, a script, a manual edit — shows up as
+//! drift against that fingerprint, so a switch can ask what to do with it
+//! instead of silently folding it into the profile. Bridle's own writes to
+//! the live config update the fingerprint instead.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use harness_locate::{Harness, HarnessKind};
+use serde::{Deserialize, Serialize};
+
+use super::{MARKER_PREFIX, ProfileManager, files};
+use crate::config::BridleConfig;
+use crate::config::profile_name::ProfileName;
+use crate::error::Result;
+use crate::harness::HarnessConfig;
+use crate::install::manifest::content_digest;
+use crate::plan::fs;
+
+/// Digests of every tracked live file, keyed by path relative to the config dir.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fingerprint {
+    /// Profile that was active when the fingerprint was taken.
+    pub profile: String,
+    pub files: BTreeMap<String, String>,
+}
+
+/// Live files that changed since the active profile was activated.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Drift {
+    pub harness_id: String,
+    pub profile: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<String>,
+}
+
+impl Drift {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+
+    /// Short count summary such as `2 modified, 1 added`.
+    pub fn summary(&self) -> String {
+        [
+            (self.modified.len(), "modified"),
+            (self.added.len(), "added"),
+            (self.removed.len(), "removed"),
+        ]
+        .iter()
+        .filter(|(n, _)| *n > 0)
+        .map(|(n, label)| format!("{} {}", n, label))
+        .collect::<Vec<_>>()
+        .join(", ")
+    }
+}
+
+/// What to do with drifted live config when switching away from a profile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DriftAction {
+    /// Save the live config into the active profile (the historical behaviour).
+    Keep,
+    /// Leave the active profile as it was at activation.
+    Discard,
+    /// Save the live config as a new profile and leave the active one untouched.
+    Fork(ProfileName),
+}
+
+impl ProfileManager {
+    fn fingerprints_dir(&self) -> PathBuf {
+        self.profiles_dir
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_else(|| self.profiles_dir.clone())
+            .join("fingerprints")
+    }
+
+    fn fingerprint_path(&self, harness: &dyn HarnessConfig) -> PathBuf {
+        self.fingerprints_dir()
+            .join(format!("{}.json", harness.id()))
+    }
+
+    /// Digests the live config dir and MCP file of a harness.
+    pub fn live_fingerprint(
+        &self,
+        harness: &dyn HarnessConfig,
+        profile: &str,
+    ) -> Result<Fingerprint> {
+        let mut fingerprint = Fingerprint {
+            profile: profile.to_string(),
+            files: BTreeMap::new(),
+        };
+
+        let config_dir = harness.config_dir()?;
+        if fs::exists(&config_dir) {
+            digest_dir(&config_dir, &config_dir, &mut fingerprint.files)?;
+        }
+
+        if let Some(mcp_path) = harness.mcp_config_path()
+            && fs::is_file(&mcp_path)
+            && !mcp_path.starts_with(&config_dir)
+            && let Some(filename) = mcp_path.file_name()
+        {
+            let content = fs::read(&mcp_path)?;
+            fingerprint.files.insert(
+                filename.to_string_lossy().into_owned(),
+                content_digest(&content),
+            );
+        }
+
+        Ok(fingerprint)
+    }
+
+    /// Records the current live state as the baseline for `profile`.
+    pub fn record_fingerprint(&self, harness: &dyn HarnessConfig, profile: &str) -> Result<()> {
+        let fingerprint = self.live_fingerprint(harness, profile)?;
+        self.save_fingerprint(harness, &fingerprint)
+    }
+
+    fn save_fingerprint(
+        &self,
+        harness: &dyn HarnessConfig,
+        fingerprint: &Fingerprint,
+    ) -> Result<()> {
+        let path = self.fingerprint_path(harness);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(fingerprint)?)?;
+        Ok(())
+    }
+
+    /// Folds bridle's own writes to `paths` into the baseline of the active
+    /// profile, so they don't show up as drift. Other live files keep their
+    /// recorded digests, and edits to them are still reported.
+    pub fn refresh_fingerprint(
+        &self,
+        harness: &dyn HarnessConfig,
+        paths: &[PathBuf],
+    ) -> Result<()> {
+        let Some(mut recorded) = self.recorded_fingerprint(harness) else {
+            return Ok(());
+        };
+        let config = BridleConfig::load().unwrap_or_default();
+        if config.active_profile_for(harness.id()) != Some(recorded.profile.as_str()) {
+            return Ok(());
+        }
+
+        let written: Vec<String> = paths
+            .iter()
+            .filter_map(|path| live_key(harness, path))
+            .collect();
+        if written.is_empty() {
+            return Ok(());
+        }
+        let is_written = |key: &str| {
+            written.iter().any(|prefix| {
+                prefix.is_empty()
+                    || key == prefix
+                    || key
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+        };
+
+        let live = self.live_fingerprint(harness, &recorded.profile)?;
+        recorded.files.retain(|key, _| !is_written(key));
+        recorded
+            .files
+            .extend(live.files.into_iter().filter(|(key, _)| is_written(key)));
+        self.save_fingerprint(harness, &recorded)
+    }
+
+    /// [`refresh_fingerprint`](Self::refresh_fingerprint) for every harness
+    /// with an active profile.
+    pub fn refresh_fingerprints(&self, paths: &[PathBuf]) -> Result<()> {
+        let config = BridleConfig::load().unwrap_or_default();
+        for kind in HarnessKind::ALL {
+            let harness = Harness::new(*kind);
+            if config.active_profile_for(harness.id()).is_some() {
+                self.refresh_fingerprint(&harness, paths)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn recorded_fingerprint(&self, harness: &dyn HarnessConfig) -> Option<Fingerprint> {
+        let content = fs::read_to_string(self.fingerprint_path(harness)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Compares the live config against the fingerprint taken when the active
+    /// profile was activated.
+    ///
+    /// Returns `None` when there is no active profile or no fingerprint for
+    /// it (e.g. it was activated by an older bridle), and an empty [`Drift`]
+    /// when nothing changed.
+    pub fn detect_drift(&self, harness: &dyn HarnessConfig) -> Result<Option<Drift>> {
+        let config = BridleConfig::load().unwrap_or_default();
+        let Some(active) = config.active_profile_for(harness.id()) else {
+            return Ok(None);
+        };
+        let Some(recorded) = self.recorded_fingerprint(harness) else {
+            return Ok(None);
+        };
+        if recorded.profile != active {
+            return Ok(None);
+        }
+
+        let live = self.live_fingerprint(harness, active)?;
+        let mut drift = Drift {
+            harness_id: harness.id().to_string(),
+            profile: active.to_string(),
+            ..Default::default()
+        };
+        for (path, digest) in &live.files {
+            match recorded.files.get(path) {
+                None => drift.added.push(path.clone()),
+                Some(old) if old != digest => drift.modified.push(path.clone()),
+                Some(_) => {}
+            }
+        }
+        drift.removed = recorded
+            .files
+            .keys()
+            .filter(|path| !live.files.contains_key(*path))
+            .cloned()
+            .collect();
+
+        Ok(Some(drift))
+    }
+}
+
+/// Key of `path` in a fingerprint of `harness`, if it is a live file.
+fn live_key(harness: &dyn HarnessConfig, path: &Path) -> Option<String> {
+    let config_dir = harness.config_dir().ok()?;
+    if let Ok(relative) = path.strip_prefix(&config_dir) {
+        return Some(relative.to_string_lossy().replace('\\', "/"));
+    }
+    let mcp_path = harness.mcp_config_path()?;
+    (mcp_path == path)
+        .then(|| mcp_path.file_name())
+        .flatten()
+        .map(|name| name.to_string_lossy().into_owned())
+}
+
+fn digest_dir(root: &Path, dir: &Path, out: &mut BTreeMap<String, String>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let name = file_name.to_string_lossy();
+        if files::is_excluded(&name) || (dir == root && name.starts_with(MARKER_PREFIX)) {
+            continue;
+        }
+
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            digest_dir(root, &path, out)?;
+        } else if file_type.is_file() {
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            let key = relative.to_string_lossy().replace('\\', "/");
+            out.insert(key, content_digest(&fs::read(&path)?));
+        }
+    }
+    Ok(())
+}
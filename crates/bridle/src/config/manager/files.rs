@@ -6,6 +6,7 @@ use harness_locate::{Harness, HarnessKind, Scope};
 use crate::error::Result;
 use crate::harness::HarnessConfig;
 use crate::install::installer::{sanitize_name_for_opencode, transform_skill_for_opencode};
+use crate::plan::fs;
 
 const ALWAYS_EXCLUDED: &[&str] = &[
     ".git",
@@ -13,6 +14,7 @@ const ALWAYS_EXCLUDED: &[&str] = &[
     "Thumbs.db",
     "__pycache__",
     "node_modules",
+    super::PROFILE_META_FILE,
 ];
 
 const SESSION_DATA: &[&str] = &[
@@ -25,7 +27,7 @@ const SESSION_DATA: &[&str] = &[
     "history.jsonl",
 ];
 
-fn is_excluded(name: &str) -> bool {
+pub(super) fn is_excluded(name: &str) -> bool {
     ALWAYS_EXCLUDED.contains(&name) || SESSION_DATA.contains(&name)
 }
 
@@ -46,8 +48,8 @@ pub fn copy_config_files(
     let mut copied_files: HashSet<std::path::PathBuf> = HashSet::new();
 
     if source_is_live {
-        if config_dir.exists() {
-            for entry in std::fs::read_dir(&config_dir)? {
+        if fs::exists(&config_dir) {
+            for entry in fs::read_dir(&config_dir)? {
                 let entry = entry?;
                 let file_name = entry.file_name();
                 let name_str = file_name.to_string_lossy();
@@ -60,7 +62,7 @@ pub fn copy_config_files(
                 let dest = profile_path.join(&file_name);
 
                 if file_type.is_file() {
-                    std::fs::copy(entry.path(), &dest)?;
+                    fs::copy(entry.path(), &dest)?;
                     if let Ok(canonical) = entry.path().canonicalize() {
                         copied_files.insert(canonical);
                     }
@@ -77,24 +79,24 @@ pub fn copy_config_files(
                 .unwrap_or(false);
 
             if !dominated
-                && mcp_path.exists()
-                && mcp_path.is_file()
+                && fs::exists(&mcp_path)
+                && fs::is_file(&mcp_path)
                 && let Some(filename) = mcp_path.file_name()
             {
                 let dest = profile_path.join(filename);
-                std::fs::copy(&mcp_path, dest)?;
+                fs::copy(&mcp_path, dest)?;
             }
         }
     } else {
-        if !config_dir.exists() {
-            std::fs::create_dir_all(&config_dir)?;
+        if !fs::exists(&config_dir) {
+            fs::create_dir_all(&config_dir)?;
         }
 
         let mcp_filename = harness
             .mcp_config_path()
             .and_then(|p| p.file_name().map(|f| f.to_os_string()));
 
-        for entry in std::fs::read_dir(profile_path)? {
+        for entry in fs::read_dir(profile_path)? {
             let entry = entry?;
             if entry.file_type()?.is_file() {
                 let filename = entry.file_name();
@@ -103,12 +105,12 @@ pub fn copy_config_files(
                     && &filename == mcp_name
                     && let Some(mcp_path) = harness.mcp_config_path()
                 {
-                    std::fs::copy(entry.path(), &mcp_path)?;
+                    fs::copy(entry.path(), &mcp_path)?;
                     continue;
                 }
 
                 let dest = config_dir.join(&filename);
-                std::fs::copy(entry.path(), dest)?;
+                fs::copy(entry.path(), dest)?;
             }
         }
     }
@@ -117,11 +119,11 @@ pub fn copy_config_files(
 }
 
 pub fn copy_all_contents(src: &Path, dst: &Path) -> Result<()> {
-    if !src.exists() {
+    if !fs::exists(src) {
         return Ok(());
     }
-    std::fs::create_dir_all(dst)?;
-    for entry in std::fs::read_dir(src)? {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
         let entry = entry?;
         let file_name = entry.file_name();
         let name_str = file_name.to_string_lossy();
@@ -132,21 +134,38 @@ pub fn copy_all_contents(src: &Path, dst: &Path) -> Result<()> {
 
         let src_path = entry.path();
         let dst_path = dst.join(&file_name);
-        if entry.file_type()?.is_dir() {
+        let file_type = entry.file_type()?;
+        #[cfg(unix)]
+        if file_type.is_symlink() {
+            copy_symlink(&src_path, &dst_path);
+            continue;
+        }
+        if file_type.is_dir() {
             copy_dir_filtered(&src_path, &dst_path)?;
         } else {
-            std::fs::copy(&src_path, &dst_path)?;
+            fs::copy(&src_path, &dst_path)?;
         }
     }
     Ok(())
 }
 
+/// Recreates the symlink at `src` as `dst`, pointing at the same target.
+#[cfg(unix)]
+fn copy_symlink(src: &Path, dst: &Path) {
+    if let Ok(target) = fs::read_link(src) {
+        let _ = fs::remove_file(dst);
+        if let Err(e) = fs::symlink(&target, dst) {
+            eprintln!("Warning: Failed to create symlink {}: {}", dst.display(), e);
+        }
+    }
+}
+
 pub fn backup_session_data(config_dir: &Path, extra_dir: &Path) -> Result<()> {
-    if !config_dir.exists() {
+    if !fs::exists(config_dir) {
         return Ok(());
     }
 
-    let has_session_data = std::fs::read_dir(config_dir)?
+    let has_session_data = fs::read_dir(config_dir)?
         .filter_map(|e| e.ok())
         .any(|e| is_session_data(&e.file_name().to_string_lossy()));
 
@@ -156,9 +175,9 @@ pub fn backup_session_data(config_dir: &Path, extra_dir: &Path) -> Result<()> {
 
     let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
     let backup_path = extra_dir.join(&timestamp);
-    std::fs::create_dir_all(&backup_path)?;
+    fs::create_dir_all(&backup_path)?;
 
-    for entry in std::fs::read_dir(config_dir)? {
+    for entry in fs::read_dir(config_dir)? {
         let entry = entry?;
         let file_name = entry.file_name();
         let name_str = file_name.to_string_lossy();
@@ -173,7 +192,7 @@ pub fn backup_session_data(config_dir: &Path, extra_dir: &Path) -> Result<()> {
         if entry.file_type()?.is_dir() {
             copy_dir_recursive(&src_path, &dst_path)?;
         } else {
-            std::fs::copy(&src_path, &dst_path)?;
+            fs::copy(&src_path, &dst_path)?;
         }
     }
 
@@ -182,7 +201,7 @@ pub fn backup_session_data(config_dir: &Path, extra_dir: &Path) -> Result<()> {
 }
 
 fn rotate_extra_backups(extra_dir: &Path, max_keep: usize) {
-    let Ok(entries) = std::fs::read_dir(extra_dir) else {
+    let Ok(entries) = fs::read_dir(extra_dir) else {
         return;
     };
 
@@ -196,7 +215,7 @@ fn rotate_extra_backups(extra_dir: &Path, max_keep: usize) {
 
     if backups.len() > max_keep {
         for old_backup in backups.iter().take(backups.len() - max_keep) {
-            let _ = std::fs::remove_dir_all(old_backup);
+            let _ = fs::remove_dir_all(old_backup);
         }
     }
 }
@@ -218,7 +237,7 @@ pub fn switch_config_dir_safely(
     use crate::error::Error;
 
     // Precondition: profile must exist
-    if !profile_path.exists() {
+    if !fs::exists(profile_path) {
         return Err(Error::ProfileNotFound(profile_path.display().to_string()));
     }
 
@@ -226,16 +245,16 @@ pub fn switch_config_dir_safely(
     let timestamp = Local::now().format("%Y%m%d_%H%M%S_%3f").to_string();
     let backup_path = backup_dir.join(format!("{}_{}", timestamp, std::process::id()));
 
-    let has_backup = if config_dir.exists() && std::fs::read_dir(config_dir)?.next().is_some() {
-        std::fs::create_dir_all(&backup_path)?;
+    let has_backup = if fs::exists(config_dir) && fs::read_dir(config_dir)?.next().is_some() {
+        fs::create_dir_all(&backup_path)?;
         copy_all_contents(config_dir, &backup_path)?;
         true
     } else {
         false
     };
 
-    if config_dir.exists() {
-        for entry in std::fs::read_dir(config_dir)? {
+    if fs::exists(config_dir) {
+        for entry in fs::read_dir(config_dir)? {
             let entry = entry?;
             let file_name = entry.file_name();
             let name_str = file_name.to_string_lossy();
@@ -247,9 +266,9 @@ pub fn switch_config_dir_safely(
             let path = entry.path();
             let file_type = entry.file_type()?;
             if file_type.is_dir() {
-                std::fs::remove_dir_all(&path)?;
+                fs::remove_dir_all(&path)?;
             } else {
-                std::fs::remove_file(&path)?;
+                fs::remove_file(&path)?;
             }
         }
     }
@@ -261,7 +280,7 @@ pub fn switch_config_dir_safely(
         Ok(()) => {
             // Success: delete backup (best-effort)
             if has_backup {
-                let _ = std::fs::remove_dir_all(&backup_path);
+                let _ = fs::remove_dir_all(&backup_path);
             }
             Ok(())
         }
@@ -269,23 +288,19 @@ pub fn switch_config_dir_safely(
             eprintln!("Profile switch failed, restoring from backup...");
 
             // Wipe partial copy (best-effort, continue even if individual deletes fail)
-            if config_dir.exists() {
-                for entry in std::fs::read_dir(config_dir)
-                    .into_iter()
-                    .flatten()
-                    .flatten()
-                {
+            if fs::exists(config_dir) {
+                for entry in fs::read_dir(config_dir).into_iter().flatten().flatten() {
                     let path = entry.path();
                     let file_type = entry.file_type();
                     let _ = match file_type {
-                        Ok(ft) if ft.is_dir() => std::fs::remove_dir_all(&path),
-                        _ => std::fs::remove_file(&path),
+                        Ok(ft) if ft.is_dir() => fs::remove_dir_all(&path),
+                        _ => fs::remove_file(&path),
                     };
                 }
             }
 
             // Restore from backup if we have one
-            if has_backup && backup_path.exists() {
+            if has_backup && fs::exists(&backup_path) {
                 if let Err(restore_err) = copy_all_contents(&backup_path, config_dir) {
                     // Restore failed - keep backup, return compound error
                     return Err(Error::Config(format!(
@@ -295,7 +310,7 @@ pub fn switch_config_dir_safely(
                         backup_path.display()
                     )));
                 }
-                let _ = std::fs::remove_dir_all(&backup_path);
+                let _ = fs::remove_dir_all(&backup_path);
             }
 
             Err(e)
@@ -304,9 +319,9 @@ pub fn switch_config_dir_safely(
 }
 
 pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
-    std::fs::create_dir_all(dst)?;
+    fs::create_dir_all(dst)?;
 
-    for entry in std::fs::read_dir(src)? {
+    for entry in fs::read_dir(src)? {
         let entry = entry?;
         let src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
@@ -314,7 +329,7 @@ pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
         if entry.file_type()?.is_dir() {
             copy_dir_recursive(&src_path, &dst_path)?;
         } else {
-            std::fs::copy(&src_path, &dst_path)?;
+            fs::copy(&src_path, &dst_path)?;
         }
     }
 
@@ -324,9 +339,9 @@ pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
 /// Copy directory recursively, preserving symlinks and skipping excluded dirs.
 /// Continues on errors (logs warning) rather than aborting.
 pub fn copy_dir_filtered(src: &Path, dst: &Path) -> Result<()> {
-    std::fs::create_dir_all(dst)?;
+    fs::create_dir_all(dst)?;
 
-    for entry in std::fs::read_dir(src)? {
+    for entry in fs::read_dir(src)? {
         let entry = match entry {
             Ok(e) => e,
             Err(e) => {
@@ -348,16 +363,7 @@ pub fn copy_dir_filtered(src: &Path, dst: &Path) -> Result<()> {
 
         #[cfg(unix)]
         if file_type.is_symlink() {
-            if let Ok(target) = std::fs::read_link(&src_path) {
-                let _ = std::fs::remove_file(&dst_path);
-                if let Err(e) = std::os::unix::fs::symlink(&target, &dst_path) {
-                    eprintln!(
-                        "Warning: Failed to create symlink {}: {}",
-                        dst_path.display(),
-                        e
-                    );
-                }
-            }
+            copy_symlink(&src_path, &dst_path);
             continue;
         }
 
@@ -369,7 +375,7 @@ pub fn copy_dir_filtered(src: &Path, dst: &Path) -> Result<()> {
                     e
                 );
             }
-        } else if let Err(e) = std::fs::copy(&src_path, &dst_path) {
+        } else if let Err(e) = fs::copy(&src_path, &dst_path) {
             eprintln!("Warning: Failed to copy file {}: {}", src_path.display(), e);
         }
     }
@@ -385,17 +391,17 @@ pub const CANONICAL_SKILLS_DIR: &str = "skills";
 pub const CANONICAL_PLUGINS_DIR: &str = "plugins";
 
 fn copy_skills_for_opencode(src: &Path, dst: &Path) -> Result<()> {
-    if !src.exists() {
+    if !fs::exists(src) {
         return Ok(());
     }
 
-    std::fs::create_dir_all(dst)?;
+    fs::create_dir_all(dst)?;
 
-    for entry in std::fs::read_dir(src)? {
+    for entry in fs::read_dir(src)? {
         let entry = entry?;
         let src_path = entry.path();
 
-        if !src_path.is_dir() {
+        if !fs::is_dir(&src_path) {
             continue;
         }
 
@@ -403,27 +409,27 @@ fn copy_skills_for_opencode(src: &Path, dst: &Path) -> Result<()> {
         let sanitized_name = sanitize_name_for_opencode(&original_name);
         let dst_skill_dir = dst.join(&sanitized_name);
 
-        std::fs::create_dir_all(&dst_skill_dir)?;
+        fs::create_dir_all(&dst_skill_dir)?;
 
-        for skill_entry in std::fs::read_dir(&src_path)? {
+        for skill_entry in fs::read_dir(&src_path)? {
             let skill_entry = skill_entry?;
             let skill_src = skill_entry.path();
             let skill_dst = dst_skill_dir.join(skill_entry.file_name());
 
-            if skill_src.is_file() {
+            if fs::is_file(&skill_src) {
                 let is_skill_md = skill_entry
                     .file_name()
                     .to_string_lossy()
                     .eq_ignore_ascii_case("SKILL.md");
 
                 if is_skill_md {
-                    let content = std::fs::read_to_string(&skill_src)?;
+                    let content = fs::read_to_string(&skill_src)?;
                     let transformed = transform_skill_for_opencode(&content, &sanitized_name);
-                    std::fs::write(&skill_dst, transformed)?;
+                    fs::write(&skill_dst, transformed)?;
                 } else {
-                    std::fs::copy(&skill_src, &skill_dst)?;
+                    fs::copy(&skill_src, &skill_dst)?;
                 }
-            } else if skill_src.is_dir() {
+            } else if fs::is_dir(&skill_src) {
                 copy_dir_filtered(&skill_src, &skill_dst)?;
             }
         }
@@ -477,7 +483,7 @@ pub fn copy_resource_directories(
             (profile_resource.as_path(), harness_path.as_path())
         };
 
-        if src.exists() && src.is_dir() {
+        if fs::exists(src) && fs::is_dir(src) {
             let is_skills_to_opencode = !to_profile
                 && canonical_name == CANONICAL_SKILLS_DIR
                 && matches!(harness.kind(), HarnessKind::OpenCode);
@@ -0,0 +1,571 @@
+//! Profile inheritance.
+//!
+//! A profile can list base profiles of the same harness in
+//! [`PROFILE_META_FILE`]:
+//!
+//! ```toml
+//! extends = ["team-base", "rust"]
+//! ```
+//!
+//! At switch time the bases are layered under the profile. Skills, agents,
+//! commands and plugins are merged by entry name, MCP servers by server name,
+//! and other files (the rules file, settings) by file name. Conflicts resolve
+//! in this order, highest first:
+//!
+//! 1. the profile itself;
+//! 2. its bases, later entries in `extends` before earlier ones;
+//! 3. each base before the profiles it extends in turn.
+//!
+//! When live edits are saved back, resources identical to the inherited
+//! version are left out of the profile so later changes to a base still
+//! reach it.
+
+use std::collections::{BTreeSet, HashMap};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use harness_locate::{Harness, HarnessKind, Scope};
+use serde::{Deserialize, Serialize};
+
+use super::files::{
+    CANONICAL_AGENTS_DIR, CANONICAL_COMMANDS_DIR, CANONICAL_PLUGINS_DIR, CANONICAL_SKILLS_DIR,
+    copy_dir_filtered, is_excluded,
+};
+use super::{ProfileManager, extraction};
+use crate::config::profile_name::ProfileName;
+use crate::config::types::{InheritedResource, ProfileInfo, ResourceKind, ResourceSummary};
+use crate::error::{Error, Result};
+use crate::harness::HarnessConfig;
+use crate::install::mcp_config::{read_mcp_config, remove_mcp_config, write_mcp_config};
+use crate::plan::fs;
+
+/// Per-profile settings kept in the profile directory. Never copied into the
+/// live config.
+pub const PROFILE_META_FILE: &str = ".bridle-profile.toml";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProfileMeta {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    extends: Vec<String>,
+}
+
+/// A profile a switch layers in, and its directory.
+struct Layer {
+    name: ProfileName,
+    path: PathBuf,
+}
+
+impl ProfileManager {
+    /// Profiles `name` lists in `extends`, in the order written.
+    pub fn profile_extends(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+    ) -> Result<Vec<ProfileName>> {
+        read_meta(&self.profile_path(harness, name))?
+            .extends
+            .iter()
+            .map(|base| ProfileName::new(base).map_err(|_| Error::InvalidProfileName(base.clone())))
+            .collect()
+    }
+
+    /// Profiles that list `name` in `extends`.
+    pub fn extended_by(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+    ) -> Result<Vec<ProfileName>> {
+        let mut dependents = Vec::new();
+        for profile in self.list_profiles(harness)? {
+            let meta = read_meta(&self.profile_path(harness, &profile))?;
+            if meta.extends.iter().any(|base| base == name.as_str()) {
+                dependents.push(profile);
+            }
+        }
+        Ok(dependents)
+    }
+
+    /// Replace the profiles `name` extends.
+    pub fn set_profile_extends(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+        extends: &[ProfileName],
+    ) -> Result<()> {
+        let path = self.profile_path(harness, name);
+        let mut meta = read_meta(&path)?;
+        meta.extends = extends.iter().map(|p| p.as_str().to_string()).collect();
+        // Fails on a missing base or a cycle before anything is written
+        self.chain(harness, name, &mut Vec::new(), &mut Vec::new(), &meta)?;
+
+        let meta_path = path.join(PROFILE_META_FILE);
+        if meta.extends.is_empty() {
+            if fs::exists(&meta_path) {
+                fs::remove_file(&meta_path)?;
+            }
+            return Ok(());
+        }
+        let content = toml::to_string_pretty(&meta).map_err(|e| Error::Config(e.to_string()))?;
+        fs::write(&meta_path, content)?;
+        Ok(())
+    }
+
+    /// The bases of `name`, highest precedence first.
+    fn bases(&self, harness: &dyn HarnessConfig, name: &ProfileName) -> Result<Vec<Layer>> {
+        let meta = read_meta(&self.profile_path(harness, name))?;
+        let mut order = Vec::new();
+        self.chain(harness, name, &mut Vec::new(), &mut order, &meta)?;
+
+        // A base reached along several paths takes its lowest position, so
+        // it stays below every profile that extends it
+        let mut seen = BTreeSet::new();
+        let mut layers: Vec<Layer> = order
+            .into_iter()
+            .rev()
+            .filter(|layer: &Layer| seen.insert(layer.name.as_str().to_string()))
+            .collect();
+        layers.reverse();
+        layers.retain(|layer| layer.name.as_str() != name.as_str());
+        Ok(layers)
+    }
+
+    /// Push `name` and then its bases, highest precedence first.
+    fn chain(
+        &self,
+        harness: &dyn HarnessConfig,
+        name: &ProfileName,
+        stack: &mut Vec<String>,
+        order: &mut Vec<Layer>,
+        meta: &ProfileMeta,
+    ) -> Result<()> {
+        if stack.iter().any(|p| p == name.as_str()) {
+            stack.push(name.as_str().to_string());
+            return Err(Error::Config(format!(
+                "profile inheritance cycle: {}",
+                stack.join(" -> ")
+            )));
+        }
+        stack.push(name.as_str().to_string());
+        order.push(Layer {
+            name: name.clone(),
+            path: self.profile_path(harness, name),
+        });
+
+        for base in meta.extends.iter().rev() {
+            let base =
+                ProfileName::new(base).map_err(|_| Error::InvalidProfileName(base.clone()))?;
+            let base_path = self.profile_path(harness, &base);
+            if !fs::exists(&base_path) {
+                return Err(Error::ProfileNotFound(format!(
+                    "{} (extended by {})",
+                    base.as_str(),
+                    name.as_str()
+                )));
+            }
+            let base_meta = read_meta(&base_path)?;
+            self.chain(harness, &base, stack, order, &base_meta)?;
+        }
+        stack.pop();
+        Ok(())
+    }
+
+    /// Layer the bases of `name` into the live config at `target_dir`, after
+    /// the profile itself has been copied there.
+    pub(super) fn apply_inherited(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+        name: &ProfileName,
+        target_dir: &Path,
+    ) -> Result<()> {
+        let bases = self.bases(harness, name)?;
+        if bases.is_empty() {
+            return Ok(());
+        }
+
+        let groups = resource_dirs(harness_for_resources);
+        let mcp = mcp_target(harness, harness_for_resources);
+        let skip: Vec<OsString> = mcp.iter().map(|(_, _, f)| f.clone()).collect();
+        for base in &bases {
+            for unit in units(&base.path, &groups, &skip)? {
+                let dest = target_dir.join(&unit.relative);
+                if !fs::exists(&dest) {
+                    copy_unit(&base.path.join(&unit.relative), &dest)?;
+                }
+            }
+        }
+
+        if let Some((kind, mcp_path, filename)) = mcp {
+            let own = self.profile_path(harness, name).join(&filename);
+            let base_files: Vec<PathBuf> = bases
+                .iter()
+                .map(|b| b.path.join(&filename))
+                .filter(|p| fs::exists(p))
+                .collect();
+            if !fs::exists(&own)
+                && let Some(highest) = base_files.first()
+            {
+                fs::copy(highest, &mcp_path)?;
+            }
+            merge_mcp_servers(kind, &mcp_path, &base_files)?;
+        }
+        Ok(())
+    }
+
+    /// Drop resources from a freshly saved profile that are identical to
+    /// what its bases provide.
+    pub(super) fn strip_inherited(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+        name: &ProfileName,
+    ) -> Result<()> {
+        let bases = self.bases(harness, name)?;
+        if bases.is_empty() {
+            return Ok(());
+        }
+
+        let profile_path = self.profile_path(harness, name);
+        let groups = resource_dirs(harness_for_resources);
+        let mcp = mcp_target(harness, harness_for_resources);
+        let skip: Vec<OsString> = mcp.iter().map(|(_, _, f)| f.clone()).collect();
+        for unit in units(&profile_path, &groups, &skip)? {
+            let own = profile_path.join(&unit.relative);
+            let inherited = bases
+                .iter()
+                .map(|b| b.path.join(&unit.relative))
+                .find(|p| fs::exists(p));
+            if let Some(inherited) = inherited
+                && same_tree(&own, &inherited)?
+            {
+                remove_entry(&own)?;
+            }
+        }
+
+        if let Some((kind, _, filename)) = mcp {
+            let own_file = profile_path.join(&filename);
+            let own = read_mcp_config(kind, &own_file).map_err(mcp_error)?;
+            let inherited = inherited_servers(kind, bases.iter().map(|b| b.path.join(&filename)))?;
+            for (server, value) in &own {
+                if inherited.get(server) == Some(value) {
+                    remove_mcp_config(kind, &own_file, server).map_err(mcp_error)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Add the resources `info`'s profile inherits, noting where each comes
+    /// from.
+    pub(super) fn add_inherited(
+        &self,
+        harness: &Harness,
+        name: &ProfileName,
+        info: &mut ProfileInfo,
+    ) -> Result<()> {
+        info.extends = self
+            .profile_extends(harness, name)?
+            .iter()
+            .map(|p| p.as_str().to_string())
+            .collect();
+        let bases = self.bases(harness, name)?;
+        if bases.is_empty() {
+            return Ok(());
+        }
+
+        let mut provided = Provided::extract(harness, &self.profile_path(harness, name));
+        for base in &bases {
+            let from = base.name.as_str();
+            let base_resources = Provided::extract(harness, &base.path);
+
+            let servers = extraction::extract_mcp_servers(harness, &base.path).unwrap_or_default();
+            for server in servers {
+                if provided.mcp.insert(server.name.clone()) {
+                    info.inherit(ResourceKind::Mcp, &server.name, from);
+                    if !info.mcp_servers.iter().any(|s| s.name == server.name) {
+                        info.mcp_servers.push(server);
+                    }
+                }
+            }
+
+            let groups = [
+                (ResourceKind::Skill, &base_resources.skills),
+                (ResourceKind::Command, &base_resources.commands),
+                (ResourceKind::Plugin, &base_resources.plugins),
+                (ResourceKind::Agent, &base_resources.agents),
+            ];
+            for (kind, items) in groups {
+                for item in items {
+                    if provided.items(kind).insert(item.clone()) {
+                        info.inherit(kind, item, from);
+                        if let Some(summary) = info.summary_mut(kind) {
+                            summary.directory_exists = true;
+                            if !summary.items.contains(item) {
+                                summary.items.push(item.clone());
+                            }
+                        }
+                    }
+                }
+            }
+
+            if provided.rules.is_none()
+                && let Some(rules) = base_resources.rules
+            {
+                let file = rules
+                    .file_name()
+                    .map(|f| f.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                info.inherit(ResourceKind::Rules, &file, from);
+                info.rules_file.get_or_insert(rules.clone());
+                provided.rules = Some(rules);
+            }
+
+            if info.model.is_none() {
+                info.model = extraction::extract_model(harness, &base.path);
+            }
+            if info.theme.is_none() {
+                info.theme = extraction::extract_theme(harness, &base.path);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ProfileInfo {
+    fn inherit(&mut self, kind: ResourceKind, name: &str, from: &str) {
+        self.inherited.push(InheritedResource {
+            kind,
+            name: name.to_string(),
+            from: from.to_string(),
+        });
+    }
+
+    fn summary_mut(&mut self, kind: ResourceKind) -> Option<&mut ResourceSummary> {
+        match kind {
+            ResourceKind::Skill => Some(&mut self.skills),
+            ResourceKind::Command => Some(&mut self.commands),
+            ResourceKind::Plugin => Some(self.plugins.get_or_insert_with(Default::default)),
+            ResourceKind::Agent => Some(self.agents.get_or_insert_with(Default::default)),
+            _ => None,
+        }
+    }
+}
+
+/// Resource names a profile directory provides itself.
+#[derive(Default)]
+struct Provided {
+    mcp: BTreeSet<String>,
+    skills: BTreeSet<String>,
+    commands: BTreeSet<String>,
+    plugins: BTreeSet<String>,
+    agents: BTreeSet<String>,
+    rules: Option<PathBuf>,
+}
+
+impl Provided {
+    fn extract(harness: &Harness, path: &Path) -> Self {
+        let names = |summary: Option<ResourceSummary>| -> BTreeSet<String> {
+            summary
+                .map(|s| s.items.into_iter().collect())
+                .unwrap_or_default()
+        };
+        Self {
+            mcp: extraction::extract_mcp_servers(harness, path)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|s| s.name)
+                .collect(),
+            skills: names(Some(extraction::extract_skills(harness, path).0)),
+            commands: names(Some(extraction::extract_commands(harness, path).0)),
+            plugins: names(extraction::extract_plugins(harness, path).0),
+            agents: names(extraction::extract_agents(harness, path).0),
+            rules: extraction::extract_rules_file(harness, path).0,
+        }
+    }
+
+    fn items(&mut self, kind: ResourceKind) -> &mut BTreeSet<String> {
+        match kind {
+            ResourceKind::Skill => &mut self.skills,
+            ResourceKind::Command => &mut self.commands,
+            ResourceKind::Plugin => &mut self.plugins,
+            _ => &mut self.agents,
+        }
+    }
+}
+
+fn read_meta(profile_path: &Path) -> Result<ProfileMeta> {
+    let path = profile_path.join(PROFILE_META_FILE);
+    if !fs::exists(&path) {
+        return Ok(ProfileMeta::default());
+    }
+    let content = fs::read_to_string(&path)?;
+    toml::from_str(&content)
+        .map_err(|e| Error::Config(format!("invalid {}: {}", path.display(), e)))
+}
+
+/// Top-level directories whose entries are merged one by one: bridle's
+/// canonical resource dirs plus wherever the harness keeps them.
+fn resource_dirs(harness_for_resources: Option<&Harness>) -> BTreeSet<PathBuf> {
+    let mut dirs: BTreeSet<PathBuf> = [
+        CANONICAL_SKILLS_DIR,
+        CANONICAL_AGENTS_DIR,
+        CANONICAL_COMMANDS_DIR,
+        CANONICAL_PLUGINS_DIR,
+    ]
+    .into_iter()
+    .map(PathBuf::from)
+    .collect();
+
+    // Relative to the harness's own config dir, which `harness` may relocate
+    if let Some(resources) = harness_for_resources
+        && let Ok(config_dir) = resources.config(&Scope::Global)
+    {
+        let scope = Scope::Global;
+        let paths = [
+            resources.skills(&scope).ok().flatten().map(|r| r.path),
+            resources.agents(&scope).ok().flatten().map(|r| r.path),
+            resources.commands(&scope).ok().flatten().map(|r| r.path),
+            resources.plugins(&scope).ok().flatten().map(|r| r.path),
+        ];
+        dirs.extend(
+            paths
+                .into_iter()
+                .flatten()
+                .filter_map(|p| p.strip_prefix(&config_dir).ok().map(Path::to_path_buf))
+                .filter(|p| p.components().count() == 1),
+        );
+    }
+    dirs
+}
+
+/// The harness's MCP config: its kind, live path and file name in profiles.
+fn mcp_target(
+    harness: &dyn HarnessConfig,
+    harness_for_resources: Option<&Harness>,
+) -> Option<(HarnessKind, PathBuf, OsString)> {
+    let kind = harness_for_resources?.kind();
+    let path = harness.mcp_config_path()?;
+    let filename = path.file_name()?.to_os_string();
+    Some((kind, path, filename))
+}
+
+/// One mergeable entry of a profile, relative to the profile directory.
+struct Unit {
+    relative: PathBuf,
+}
+
+fn units(dir: &Path, groups: &BTreeSet<PathBuf>, skip: &[OsString]) -> Result<Vec<Unit>> {
+    let mut units = Vec::new();
+    if !fs::exists(dir) {
+        return Ok(units);
+    }
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        let name = file_name.to_string_lossy();
+        if is_excluded(&name) || skip.contains(&file_name) {
+            continue;
+        }
+        let relative = PathBuf::from(&file_name);
+        if groups.contains(&relative) && entry.file_type()?.is_dir() {
+            for child in fs::read_dir(dir.join(&relative))? {
+                let child = child?.file_name();
+                if !is_excluded(&child.to_string_lossy()) {
+                    units.push(Unit {
+                        relative: relative.join(child),
+                    });
+                }
+            }
+        } else {
+            units.push(Unit { relative });
+        }
+    }
+    Ok(units)
+}
+
+fn copy_unit(src: &Path, dest: &Path) -> Result<()> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::is_dir(src) {
+        copy_dir_filtered(src, dest)
+    } else {
+        fs::copy(src, dest)?;
+        Ok(())
+    }
+}
+
+pub(super) fn remove_entry(path: &Path) -> Result<()> {
+    if fs::is_dir(path) {
+        fs::remove_dir_all(path)?;
+    } else {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Whether two files or directory trees have the same contents.
+fn same_tree(a: &Path, b: &Path) -> Result<bool> {
+    match (fs::is_dir(a), fs::is_dir(b)) {
+        (true, true) => {
+            let names = |dir: &Path| -> Result<BTreeSet<OsString>> {
+                let mut names = BTreeSet::new();
+                for entry in fs::read_dir(dir)? {
+                    let name = entry?.file_name();
+                    if !is_excluded(&name.to_string_lossy()) {
+                        names.insert(name);
+                    }
+                }
+                Ok(names)
+            };
+            let entries = names(a)?;
+            if entries != names(b)? {
+                return Ok(false);
+            }
+            for name in entries {
+                if !same_tree(&a.join(&name), &b.join(&name))? {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        }
+        (false, false) => Ok(fs::read(a)? == fs::read(b)?),
+        _ => Ok(false),
+    }
+}
+
+/// Servers the files provide, where the first file listing a server wins.
+fn inherited_servers(
+    kind: HarnessKind,
+    files: impl Iterator<Item = PathBuf>,
+) -> Result<HashMap<String, serde_json::Value>> {
+    let mut servers = HashMap::new();
+    for file in files {
+        for (name, value) in read_mcp_config(kind, &file).map_err(mcp_error)? {
+            servers.entry(name).or_insert(value);
+        }
+    }
+    Ok(servers)
+}
+
+/// Add the servers in `base_files` (highest precedence first) that the
+/// config at `mcp_path` doesn't already declare.
+pub(super) fn merge_mcp_servers(
+    kind: HarnessKind,
+    mcp_path: &Path,
+    base_files: &[PathBuf],
+) -> Result<()> {
+    let present = read_mcp_config(kind, mcp_path).map_err(mcp_error)?;
+    let missing: HashMap<_, _> = inherited_servers(kind, base_files.iter().cloned())?
+        .into_iter()
+        .filter(|(name, _)| !present.contains_key(name))
+        .collect();
+    if !missing.is_empty() {
+        write_mcp_config(kind, mcp_path, &missing).map_err(mcp_error)?;
+    }
+    Ok(())
+}
+
+fn mcp_error(e: crate::install::mcp_config::McpConfigError) -> Error {
+    Error::Config(e.to_string())
+}
@@ -1,14 +1,16 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use chrono::Local;
 use harness_locate::Harness;
 
-use super::ProfileManager;
 use super::files;
+use super::{DriftAction, PROFILE_META_FILE, ProfileManager};
 use crate::config::BridleConfig;
 use crate::config::profile_name::ProfileName;
 use crate::error::{Error, Result};
 use crate::harness::HarnessConfig;
+use crate::install::manifest::manifest_path;
+use crate::plan::{self, fs};
 
 impl ProfileManager {
     pub fn backups_dir(&self) -> PathBuf {
@@ -21,10 +23,10 @@ impl ProfileManager {
 
     pub fn backup_current(&self, harness: &dyn HarnessConfig) -> Result<PathBuf> {
         let source_dir = harness.config_dir()?;
-        let has_config_dir = source_dir.exists();
+        let has_config_dir = fs::exists(&source_dir);
         let has_mcp = harness
             .mcp_config_path()
-            .map(|p| p.exists())
+            .map(|p| fs::exists(&p))
             .unwrap_or(false);
 
         if !has_config_dir && !has_mcp {
@@ -35,9 +37,20 @@ impl ProfileManager {
         }
 
         let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
-        let backup_path = self.backups_dir().join(harness.id()).join(&timestamp);
+        let mut backup_path = self.backups_dir().join(harness.id()).join(&timestamp);
+        // Two backups within the same second (e.g. restore right after a switch)
+        // must not merge into one directory.
+        let mut n = 1;
+        while fs::exists(&backup_path) {
+            backup_path = self
+                .backups_dir()
+                .join(harness.id())
+                .join(format!("{}-{}", timestamp, n));
+            n += 1;
+        }
 
-        std::fs::create_dir_all(&backup_path)?;
+        fs::create_dir_all(&backup_path)?;
+        plan::note_backup(&backup_path);
         files::copy_config_files(harness, true, &backup_path)?;
 
         let extra_dir = self.backups_dir().join(harness.id()).join("extra");
@@ -53,40 +66,85 @@ impl ProfileManager {
         name: &ProfileName,
     ) -> Result<()> {
         let profile_path = self.profile_path(harness, name);
-        if !profile_path.exists() {
+        if !fs::exists(&profile_path) {
             return Ok(());
         }
 
         let source_dir = harness.config_dir()?;
-        let has_config = source_dir.exists()
+        let has_config = fs::exists(&source_dir)
             || harness
                 .mcp_config_path()
-                .map(|p| p.exists())
+                .map(|p| fs::exists(&p))
                 .unwrap_or(false);
         if !has_config {
             return Ok(());
         }
 
-        for entry in std::fs::read_dir(&profile_path)? {
+        let installed = Self::read_install_manifest(&profile_path)?;
+        for entry in fs::read_dir(&profile_path)? {
             let entry = entry?;
+            if entry.file_name() == PROFILE_META_FILE {
+                continue;
+            }
             let path = entry.path();
-            if path.is_file() {
-                std::fs::remove_file(&path)?;
-            } else if path.is_dir() {
-                std::fs::remove_dir_all(&path)?;
+            if fs::is_file(&path) {
+                fs::remove_file(&path)?;
+            } else if fs::is_dir(&path) {
+                fs::remove_dir_all(&path)?;
             }
         }
 
-        files::copy_all_contents(&source_dir, &profile_path)?;
+        Self::copy_live_into(harness, harness_for_resources, &profile_path, installed)?;
+        self.strip_inherited(harness, harness_for_resources, name)?;
+
+        // The profile now matches the live config, so it is the new baseline.
+        self.record_fingerprint(harness, name.as_str())?;
+        Ok(())
+    }
+
+    /// Copies the live config into `dest`, with resource directories also
+    /// under their canonical names and `manifest` as its install manifest.
+    fn copy_live_into(
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+        dest: &Path,
+        manifest: Option<Vec<u8>>,
+    ) -> Result<()> {
+        files::copy_all_contents(&harness.config_dir()?, dest)?;
         if let Some(mcp_path) = harness.mcp_config_path()
-            && mcp_path.exists()
-            && mcp_path.is_file()
+            && fs::exists(&mcp_path)
+            && fs::is_file(&mcp_path)
             && let Some(filename) = mcp_path.file_name()
         {
-            let dest = profile_path.join(filename);
-            std::fs::copy(&mcp_path, dest)?;
+            fs::copy(&mcp_path, dest.join(filename))?;
+        }
+        if let Some(h) = harness_for_resources {
+            files::copy_resource_directories(h, true, dest)?;
+        }
+        Self::write_install_manifest(dest, manifest)
+    }
+
+    /// Reads a profile's install manifest before live contents are copied over it.
+    ///
+    /// Installs record themselves in the profile's manifest only, so the copy
+    /// the live config picked up when the profile was activated can be stale.
+    fn read_install_manifest(profile_path: &Path) -> Result<Option<Vec<u8>>> {
+        let path = manifest_path(profile_path);
+        if !fs::exists(&path) {
+            return Ok(None);
+        }
+        Ok(Some(fs::read(&path)?))
+    }
+
+    /// Puts back a manifest from [`Self::read_install_manifest`] in place of
+    /// whatever copy came with the live config.
+    fn write_install_manifest(dest: &Path, manifest: Option<Vec<u8>>) -> Result<()> {
+        let path = manifest_path(dest);
+        match manifest {
+            Some(bytes) => fs::write(&path, bytes)?,
+            None if fs::exists(&path) => fs::remove_file(&path)?,
+            None => {}
         }
-        let _ = harness_for_resources;
         Ok(())
     }
 
@@ -103,10 +161,42 @@ impl ProfileManager {
         harness: &dyn HarnessConfig,
         harness_for_resources: Option<&Harness>,
         name: &ProfileName,
+    ) -> Result<PathBuf> {
+        self.switch_profile_with_drift(harness, harness_for_resources, name, &DriftAction::Keep)
+    }
+
+    /// Switches profiles, handling live edits to the outgoing profile per `drift`.
+    ///
+    /// [`DriftAction::Keep`] saves the live config into the active profile,
+    /// [`DriftAction::Discard`] drops it, and [`DriftAction::Fork`] saves it as
+    /// a new profile instead. The live config is backed up in every case.
+    ///
+    /// Every step is planned first and then applied as one journaled change,
+    /// so a failure or crash part way leaves the previous profile in place.
+    pub fn switch_profile_with_drift(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+        name: &ProfileName,
+        drift: &DriftAction,
+    ) -> Result<PathBuf> {
+        let (target_dir, plan) =
+            plan::record(|| self.switch_steps(harness, harness_for_resources, name, drift));
+        let target_dir = target_dir?;
+        plan.apply()?;
+        Ok(target_dir)
+    }
+
+    fn switch_steps(
+        &self,
+        harness: &dyn HarnessConfig,
+        harness_for_resources: Option<&Harness>,
+        name: &ProfileName,
+        drift: &DriftAction,
     ) -> Result<PathBuf> {
         let profile_path = self.profile_path(harness, name);
 
-        if !profile_path.exists() {
+        if !fs::exists(&profile_path) {
             return Err(Error::ProfileNotFound(name.as_str().to_string()));
         }
 
@@ -126,7 +216,18 @@ impl ProfileManager {
             && let Ok(active_profile) = ProfileName::new(active_name)
             && active_profile.as_str() != name.as_str()
         {
-            self.save_to_profile(harness, harness_for_resources, &active_profile)?;
+            match drift {
+                DriftAction::Keep => {
+                    self.save_to_profile(harness, harness_for_resources, &active_profile)?
+                }
+                DriftAction::Discard => {}
+                DriftAction::Fork(fork) => {
+                    let installed =
+                        Self::read_install_manifest(&self.profile_path(harness, &active_profile))?;
+                    let fork_path = self.create_profile(harness, fork)?;
+                    Self::copy_live_into(harness, harness_for_resources, &fork_path, installed)?;
+                }
+            }
             true
         } else {
             false
@@ -136,15 +237,16 @@ impl ProfileManager {
 
         // If no active profile was saved, backup current state to "no-profile" folder
         // This preserves unknown files when switching for the first time
-        if !saved_to_profile && target_dir.exists() {
+        if !saved_to_profile && fs::exists(&target_dir) {
             let no_profile_backup = self.backups_dir().join(harness.id()).join("no-profile");
-            let _ = std::fs::remove_dir_all(&no_profile_backup);
-            std::fs::create_dir_all(&no_profile_backup)?;
+            let _ = fs::remove_dir_all(&no_profile_backup);
+            fs::create_dir_all(&no_profile_backup)?;
+            plan::note_backup(&no_profile_backup);
             files::copy_all_contents(&target_dir, &no_profile_backup)?;
         }
 
-        if !target_dir.exists() {
-            std::fs::create_dir_all(&target_dir)?;
+        if !fs::exists(&target_dir) {
+            fs::create_dir_all(&target_dir)?;
         }
 
         let backup_dir = self.backups_dir().join(harness.id());
@@ -153,13 +255,15 @@ impl ProfileManager {
         if let Some(mcp_path) = harness.mcp_config_path()
             && let Some(filename) = mcp_path.file_name()
         {
+            if let Some(resources) = harness_for_resources {
+                plan::note_mcp_config(&mcp_path, resources.kind());
+            }
             let mcp_in_profile = profile_path.join(filename);
-            if mcp_in_profile.exists() {
-                std::fs::copy(&mcp_in_profile, &mcp_path)?;
+            if fs::exists(&mcp_in_profile) {
+                fs::copy(&mcp_in_profile, &mcp_path)?;
             }
         }
-
-        let _ = harness_for_resources;
+        self.apply_inherited(harness, harness_for_resources, name, &target_dir)?;
 
         let mut config = BridleConfig::load().unwrap_or_default();
         config.set_active_profile(harness.id(), name.as_str());
@@ -169,6 +273,7 @@ impl ProfileManager {
         if config.profile_marker_enabled() {
             Self::create_marker_file(&target_dir, name.as_str())?;
         }
+        self.record_fingerprint(harness, name.as_str())?;
 
         Ok(target_dir)
     }
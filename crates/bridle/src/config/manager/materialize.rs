@@ -0,0 +1,140 @@
+//! Laying a profile out outside the harness's own config dir, for
+//! `bridle exec`.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use harness_locate::Harness;
+
+use super::ProfileManager;
+use super::files::{self, is_excluded};
+use super::inherit::remove_entry;
+use crate::config::profile_name::ProfileName;
+use crate::error::{Error, Result};
+use crate::harness::HarnessConfig;
+use crate::plan::{self, fs};
+
+/// Top-level entries the last materialization laid out, so the next one can
+/// replace them without touching what the harness wrote itself (logins,
+/// history, caches).
+pub(super) const MATERIALIZED_FILE: &str = ".bridle-materialized";
+
+impl ProfileManager {
+    /// Lays a profile out in `config_dir` the way a switch lays it out in the
+    /// harness's own config dir, inherited resources included, so the harness
+    /// can be run against the copy. Nothing outside `config_dir` changes and
+    /// the active profile stays as it is.
+    ///
+    /// Materializing again replaces what the previous run laid out and keeps
+    /// files the harness created in between.
+    pub fn materialize_profile(
+        &self,
+        harness: &Harness,
+        name: &ProfileName,
+        config_dir: &Path,
+    ) -> Result<()> {
+        let profile_path = self.profile_path(harness, name);
+        if !fs::exists(&profile_path) {
+            return Err(Error::ProfileNotFound(name.as_str().to_string()));
+        }
+
+        let relocated = Relocated {
+            harness,
+            config_dir,
+        };
+        let manifest = config_dir.join(MATERIALIZED_FILE);
+        let (result, plan) = plan::record(|| -> Result<()> {
+            if let Ok(previous) = fs::read_to_string(&manifest) {
+                for entry in previous.lines().filter(|l| is_single_name(l)) {
+                    let path = config_dir.join(entry);
+                    if fs::exists(&path) {
+                        remove_entry(&path)?;
+                    }
+                }
+            }
+            let kept = entry_names(config_dir)?;
+
+            files::copy_all_contents(&profile_path, config_dir)?;
+            if let Some(mcp_path) = relocated.mcp_config_path()
+                && let Some(filename) = mcp_path.file_name()
+                && fs::exists(profile_path.join(filename))
+            {
+                fs::copy(profile_path.join(filename), &mcp_path)?;
+            }
+            self.apply_inherited(&relocated, Some(harness), name, config_dir)?;
+
+            let mut laid_out: BTreeSet<String> = entry_names(config_dir)?
+                .difference(&kept)
+                .cloned()
+                .collect();
+            laid_out.extend(
+                entry_names(&profile_path)?
+                    .into_iter()
+                    .filter(|n| !is_excluded(n)),
+            );
+            laid_out.remove(MATERIALIZED_FILE);
+            let content: String = laid_out.iter().map(|n| format!("{n}\n")).collect();
+            fs::write(&manifest, content)?;
+            Ok(())
+        });
+        result?;
+        plan.apply()?;
+        Ok(())
+    }
+}
+
+fn entry_names(dir: &Path) -> Result<BTreeSet<String>> {
+    let mut names = BTreeSet::new();
+    if fs::exists(dir) {
+        for entry in fs::read_dir(dir)? {
+            names.insert(entry?.file_name().to_string_lossy().into_owned());
+        }
+    }
+    Ok(names)
+}
+
+/// Guards against a hand-edited manifest reaching outside the directory.
+fn is_single_name(entry: &str) -> bool {
+    let mut components = Path::new(entry).components();
+    matches!(
+        (components.next(), components.next()),
+        (Some(std::path::Component::Normal(_)), None)
+    )
+}
+
+/// A harness whose global config dir has been moved to `config_dir`.
+struct Relocated<'a> {
+    harness: &'a Harness,
+    config_dir: &'a Path,
+}
+
+impl HarnessConfig for Relocated<'_> {
+    fn id(&self) -> &str {
+        HarnessConfig::id(self.harness)
+    }
+
+    fn config_dir(&self) -> Result<PathBuf> {
+        Ok(self.config_dir.to_path_buf())
+    }
+
+    fn installation_status(&self) -> Result<harness_locate::InstallationStatus> {
+        HarnessConfig::installation_status(self.harness)
+    }
+
+    fn mcp_filename(&self) -> Option<String> {
+        self.harness.mcp_filename()
+    }
+
+    fn mcp_config_path(&self) -> Option<PathBuf> {
+        let path = self.harness.mcp_config_path()?;
+        let relative = HarnessConfig::config_dir(self.harness)
+            .ok()
+            .and_then(|dir| path.strip_prefix(dir).ok().map(Path::to_path_buf))
+            .or_else(|| path.file_name().map(PathBuf::from))?;
+        Some(self.config_dir.join(relative))
+    }
+
+    fn parse_mcp_servers(&self, content: &str, filename: &str) -> Result<Vec<(String, bool)>> {
+        self.harness.parse_mcp_servers(content, filename)
+    }
+}
@@ -3,13 +3,22 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+mod auto_switch;
 mod bridle;
 pub mod jsonc;
 mod manager;
 mod profile_name;
 mod types;
 
-pub use bridle::{BridleConfig, TuiConfig, ViewPreference};
-pub use manager::ProfileManager;
+pub use auto_switch::{AutoSwitch, AutoSwitchState, DIR_MARKER_FILE, DirMarker};
+pub use bridle::{
+    BackupRetention, BridleConfig, MarketplaceSubscription, TuiConfig, ViewPreference,
+};
+pub use manager::{
+    ARCHIVE_MANIFEST, ArchiveError, ArchiveManifest, BackupDetails, BackupInfo, BackupKind,
+    BackupRestore, CURRENT_CONFIG, Drift, DriftAction, EnvPlaceholder, ExportReport, Fingerprint,
+    McpDiff, McpServerChange, ProfileArchive, ProfileDiff, ProfileManager, SetDiff, ValueChange,
+};
+pub(crate) use manager::{extract_model, extract_rules_file};
 pub use profile_name::{InvalidProfileName, ProfileName};
-pub use types::{McpServerInfo, ProfileInfo, ResourceSummary};
+pub use types::{InheritedResource, McpServerInfo, ProfileInfo, ResourceKind, ResourceSummary};
@@ -55,6 +55,43 @@ pub struct ProfileInfo {
     pub model: Option<String>,
     /// Errors encountered during extraction.
     pub extraction_errors: Vec<String>,
+    /// Profiles this one extends, as listed.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<String>,
+    /// Resources that come from a profile this one extends.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub inherited: Vec<InheritedResource>,
+}
+
+/// Kind of resource a profile can inherit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResourceKind {
+    Mcp,
+    Skill,
+    Command,
+    Plugin,
+    Agent,
+    Rules,
+}
+
+/// A resource a profile gets from a profile it extends.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InheritedResource {
+    pub kind: ResourceKind,
+    pub name: String,
+    /// The base profile that provides it.
+    pub from: String,
+}
+
+impl ProfileInfo {
+    /// The base profile a resource comes from, if it is inherited.
+    pub fn inherited_from(&self, kind: ResourceKind, name: &str) -> Option<&str> {
+        self.inherited
+            .iter()
+            .find(|r| r.kind == kind && r.name == name)
+            .map(|r| r.from.as_str())
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,187 @@
+//! Shared rendering of semantic profile diffs for CLI and TUI.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::Line,
+};
+
+use crate::config::{ProfileDiff, SetDiff, ValueChange};
+
+/// Kind of a rendered diff line, used for markers and colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffMark {
+    Section,
+    Added,
+    Removed,
+    Changed,
+    Detail,
+}
+
+impl DiffMark {
+    fn prefix(self) -> &'static str {
+        match self {
+            DiffMark::Section => "",
+            DiffMark::Added => "  + ",
+            DiffMark::Removed => "  - ",
+            DiffMark::Changed => "  ~ ",
+            DiffMark::Detail => "      ",
+        }
+    }
+
+    fn style(self) -> Style {
+        match self {
+            DiffMark::Section => Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+            DiffMark::Added => Style::default().fg(Color::Green),
+            DiffMark::Removed => Style::default().fg(Color::Red),
+            DiffMark::Changed => Style::default().fg(Color::Yellow),
+            DiffMark::Detail => Style::default().fg(Color::Gray),
+        }
+    }
+}
+
+/// Flatten a diff into marked lines, one section per non-empty category.
+pub fn diff_entries(diff: &ProfileDiff) -> Vec<(DiffMark, String)> {
+    let mut out = Vec::new();
+
+    let mcp = &diff.mcp_servers;
+    if !mcp.is_empty() {
+        out.push((DiffMark::Section, "MCP Servers".to_string()));
+        out.extend(mcp.added.iter().map(|n| (DiffMark::Added, n.clone())));
+        out.extend(mcp.removed.iter().map(|n| (DiffMark::Removed, n.clone())));
+        for server in &mcp.changed {
+            out.push((DiffMark::Changed, server.name.clone()));
+            out.extend(
+                server
+                    .fields
+                    .iter()
+                    .map(|f| (DiffMark::Detail, format_change(f))),
+            );
+        }
+    }
+
+    for (label, set) in [
+        ("Skills", &diff.skills),
+        ("Commands", &diff.commands),
+        ("Agents", &diff.agents),
+        ("Plugins", &diff.plugins),
+    ] {
+        push_set(&mut out, label, set);
+    }
+
+    for (label, changes) in [("Settings", &diff.settings), ("Config", &diff.config)] {
+        if changes.is_empty() {
+            continue;
+        }
+        out.push((DiffMark::Section, label.to_string()));
+        out.extend(
+            changes
+                .iter()
+                .map(|c| (DiffMark::Changed, format_change(c))),
+        );
+    }
+
+    out
+}
+
+fn push_set(out: &mut Vec<(DiffMark, String)>, label: &str, set: &SetDiff) {
+    if set.is_empty() {
+        return;
+    }
+    out.push((DiffMark::Section, label.to_string()));
+    out.extend(set.added.iter().map(|n| (DiffMark::Added, n.clone())));
+    out.extend(set.removed.iter().map(|n| (DiffMark::Removed, n.clone())));
+}
+
+fn format_change(change: &ValueChange) -> String {
+    format!(
+        "{}: {} → {}",
+        change.key,
+        change.left.as_deref().unwrap_or("(unset)"),
+        change.right.as_deref().unwrap_or("(unset)")
+    )
+}
+
+fn header(diff: &ProfileDiff) -> String {
+    format!("{} → {} ({})", diff.left, diff.right, diff.harness_id)
+}
+
+pub fn diff_to_text(diff: &ProfileDiff) -> String {
+    let mut out = format!("{}\n", header(diff));
+    if diff.is_empty() {
+        out.push_str("No differences\n");
+        return out;
+    }
+    for (mark, text) in diff_entries(diff) {
+        if mark == DiffMark::Section {
+            out.push('\n');
+        }
+        out.push_str(mark.prefix());
+        out.push_str(&text);
+        out.push('\n');
+    }
+    out
+}
+
+pub fn diff_to_lines(diff: &ProfileDiff) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::styled(
+        header(diff),
+        Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD),
+    )];
+    if diff.is_empty() {
+        lines.push(Line::styled(
+            "  No differences",
+            Style::default().fg(Color::Gray),
+        ));
+        return lines;
+    }
+    for (mark, text) in diff_entries(diff) {
+        lines.push(Line::styled(
+            format!("{}{}", mark.prefix(), text),
+            mark.style(),
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{McpDiff, McpServerChange};
+
+    #[test]
+    fn diff_text_marks_added_removed_and_changed() {
+        let diff = ProfileDiff {
+            harness_id: "opencode".to_string(),
+            left: "work".to_string(),
+            right: "current".to_string(),
+            mcp_servers: McpDiff {
+                added: vec!["new".to_string()],
+                removed: Vec::new(),
+                changed: vec![McpServerChange {
+                    name: "fs".to_string(),
+                    fields: vec![ValueChange {
+                        key: "enabled".to_string(),
+                        left: Some("true".to_string()),
+                        right: Some("false".to_string()),
+                    }],
+                }],
+            },
+            skills: SetDiff {
+                added: Vec::new(),
+                removed: vec!["alpha".to_string()],
+            },
+            ..Default::default()
+        };
+
+        let text = diff_to_text(&diff);
+        assert!(text.starts_with("work → current (opencode)\n"));
+        assert!(text.contains("  + new\n"));
+        assert!(text.contains("  ~ fs\n      enabled: true → false\n"));
+        assert!(text.contains("Skills\n  - alpha\n"));
+        assert_eq!(diff_to_lines(&diff).len(), 7);
+    }
+}
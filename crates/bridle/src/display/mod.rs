@@ -4,12 +4,16 @@
 //! Both CLI and TUI consume the same `ProfileNode` tree structure, then render it
 //! according to their output format (flat text vs styled lines with tree branches).
 
+mod diff;
+
+pub use diff::{diff_to_lines, diff_to_text};
+
 use ratatui::{
     style::{Color, Modifier, Style},
     text::{Line, Span},
 };
 
-use crate::config::{McpServerInfo, ProfileInfo, ResourceSummary};
+use crate::config::{McpServerInfo, ProfileInfo, ResourceKind, ResourceSummary};
 
 /// Semantic section types for profile display.
 ///
@@ -99,19 +103,24 @@ pub fn format_mcp_detail(server: &McpServerInfo) -> String {
 pub fn profile_to_nodes(info: &ProfileInfo) -> Vec<ProfileNode> {
     let mut nodes = Vec::new();
 
+    let mut header = vec![
+        ProfileNode::new(SectionKind::Field, "Harness").with_text(&info.harness_id),
+        ProfileNode::new(SectionKind::Field, "Status").with_text(if info.is_active {
+            "Active"
+        } else {
+            "Inactive"
+        }),
+        ProfileNode::new(SectionKind::Field, "Path").with_text(info.path.display().to_string()),
+    ];
+    if !info.extends.is_empty() {
+        header.push(
+            ProfileNode::new(SectionKind::Field, "Extends").with_text(info.extends.join(", ")),
+        );
+    }
     nodes.push(
         ProfileNode::new(SectionKind::Header, "Profile")
             .with_text(&info.name)
-            .with_children(vec![
-                ProfileNode::new(SectionKind::Field, "Harness").with_text(&info.harness_id),
-                ProfileNode::new(SectionKind::Field, "Status").with_text(if info.is_active {
-                    "Active"
-                } else {
-                    "Inactive"
-                }),
-                ProfileNode::new(SectionKind::Field, "Path")
-                    .with_text(info.path.display().to_string()),
-            ]),
+            .with_children(header),
     );
 
     let theme_text = match &info.theme {
@@ -129,11 +138,23 @@ pub fn profile_to_nodes(info: &ProfileInfo) -> Vec<ProfileNode> {
 
     nodes.push(build_mcp_node(info));
 
-    nodes.push(build_resource_node("Skills", &info.skills, true));
-    nodes.push(build_resource_node("Commands", &info.commands, true));
+    nodes.push(build_resource_node(
+        "Skills",
+        &info.skills,
+        sources(info, ResourceKind::Skill),
+    ));
+    nodes.push(build_resource_node(
+        "Commands",
+        &info.commands,
+        sources(info, ResourceKind::Command),
+    ));
 
     match &info.plugins {
-        Some(plugins) => nodes.push(build_resource_node("Plugins", plugins, true)),
+        Some(plugins) => nodes.push(build_resource_node(
+            "Plugins",
+            plugins,
+            sources(info, ResourceKind::Plugin),
+        )),
         None => nodes.push(
             ProfileNode::new(SectionKind::ResourceGroup { exists: false }, "Plugins")
                 .with_text("(not supported)"),
@@ -141,7 +162,11 @@ pub fn profile_to_nodes(info: &ProfileInfo) -> Vec<ProfileNode> {
     }
 
     match &info.agents {
-        Some(agents) => nodes.push(build_resource_node("Agents", agents, true)),
+        Some(agents) => nodes.push(build_resource_node(
+            "Agents",
+            agents,
+            sources(info, ResourceKind::Agent),
+        )),
         None => nodes.push(
             ProfileNode::new(SectionKind::ResourceGroup { exists: false }, "Agents")
                 .with_text("(not supported)"),
@@ -155,7 +180,8 @@ pub fn profile_to_nodes(info: &ProfileInfo) -> Vec<ProfileNode> {
                 .file_name()
                 .and_then(|n| n.to_str())
                 .unwrap_or("(unknown)");
-            (true, filename.to_string())
+            let from = sources(info, ResourceKind::Rules)(filename);
+            (true, format!("{filename}{from}"))
         }
         None => (false, "(none)".to_string()),
     };
@@ -192,10 +218,11 @@ fn build_mcp_node(info: &ProfileInfo) -> ProfileNode {
         .map(|server| {
             let detail = format_mcp_detail(server);
             let disabled_suffix = if server.enabled { "" } else { " (disabled)" };
+            let from = sources(info, ResourceKind::Mcp)(&server.name);
             let text = if detail.is_empty() {
-                format!("{}{}", server.name, disabled_suffix)
+                format!("{}{}{}", server.name, disabled_suffix, from)
             } else {
-                format!("{} {}{}", server.name, detail, disabled_suffix)
+                format!("{} {}{}{}", server.name, detail, disabled_suffix, from)
             };
             ProfileNode::new(
                 SectionKind::McpServer {
@@ -212,10 +239,19 @@ fn build_mcp_node(info: &ProfileInfo) -> ProfileNode {
         .with_children(children)
 }
 
+/// Suffix naming the base profile an item is inherited from, if any.
+fn sources(info: &ProfileInfo, kind: ResourceKind) -> impl Fn(&str) -> String + '_ {
+    move |name| {
+        info.inherited_from(kind, name)
+            .map(|from| format!(" (from {from})"))
+            .unwrap_or_default()
+    }
+}
+
 fn build_resource_node(
     label: &'static str,
     summary: &ResourceSummary,
-    _supported: bool,
+    from: impl Fn(&str) -> String,
 ) -> ProfileNode {
     if !summary.directory_exists {
         return ProfileNode::new(SectionKind::ResourceGroup { exists: false }, label)
@@ -227,11 +263,15 @@ fn build_resource_node(
             .with_text("(none)");
     }
 
-    let items_text = summary.items.join(", ");
-    let children: Vec<ProfileNode> = summary
+    let items: Vec<String> = summary
         .items
         .iter()
-        .map(|item| ProfileNode::new(SectionKind::ResourceItem, "").with_text(item.clone()))
+        .map(|item| format!("{item}{}", from(item)))
+        .collect();
+    let items_text = items.join(", ");
+    let children: Vec<ProfileNode> = items
+        .into_iter()
+        .map(|item| ProfileNode::new(SectionKind::ResourceItem, "").with_text(item))
         .collect();
 
     ProfileNode::new(SectionKind::ResourceGroup { exists: true }, label)
@@ -603,6 +643,8 @@ mod tests {
             theme: Some("dark".to_string()),
             model: Some("gpt-4".to_string()),
             extraction_errors: vec![],
+            extends: vec![],
+            inherited: vec![],
         };
 
         let nodes = profile_to_nodes(&info);
@@ -628,6 +670,8 @@ mod tests {
             theme: None,
             model: None,
             extraction_errors: vec!["Error 1".to_string(), "Error 2".to_string()],
+            extends: vec![],
+            inherited: vec![],
         };
 
         let nodes = profile_to_nodes(&info);
@@ -637,6 +681,31 @@ mod tests {
         assert_eq!(errors_node.unwrap().children.len(), 2);
     }
 
+    #[test]
+    fn test_profile_to_nodes_names_inherited_sources() {
+        let info = ProfileInfo {
+            name: "child".to_string(),
+            harness_id: "claude-code".to_string(),
+            path: PathBuf::from("/tmp"),
+            skills: ResourceSummary {
+                items: vec!["own".to_string(), "shared".to_string()],
+                directory_exists: true,
+            },
+            extends: vec!["team-base".to_string()],
+            inherited: vec![crate::config::InheritedResource {
+                kind: ResourceKind::Skill,
+                name: "shared".to_string(),
+                from: "team-base".to_string(),
+            }],
+            ..Default::default()
+        };
+
+        let output = nodes_to_text(&profile_to_nodes(&info));
+
+        assert!(output.contains("Extends: team-base"));
+        assert!(output.contains("own, shared (from team-base)"));
+    }
+
     #[test]
     fn test_nodes_to_text_renders_header_and_fields() {
         let nodes = vec![
@@ -30,6 +30,10 @@ pub enum Error {
     #[error("profile already exists: {0}")]
     ProfileExists(String),
 
+    /// Backup with given id does not exist.
+    #[error("backup not found: {0}")]
+    BackupNotFound(String),
+
     /// No profile is currently active.
     #[error("no active profile")]
     NoActiveProfile,
@@ -49,7 +53,9 @@ pub enum Error {
     Command(String),
 
     /// Unknown configuration setting.
-    #[error("unknown setting: {0}\nValid options: editor, marker_files, default_harness")]
+    #[error(
+        "unknown setting: {0}\nValid options: editor, marker_files, default_harness, backup_keep, backup_max_age_days, backup_max_size_mb, registry_url"
+    )]
     UnknownSetting(String),
 
     /// Invalid configuration value.
@@ -75,4 +81,16 @@ pub enum Error {
     /// YAML parsing error.
     #[error(transparent)]
     Yaml(#[from] serde_yaml::Error),
+
+    /// Applying planned file changes failed.
+    #[error(transparent)]
+    Plan(#[from] crate::plan::PlanError),
+
+    /// Another bridle process holds the state lock.
+    #[error(transparent)]
+    Lock(#[from] crate::lock::LockError),
+
+    /// A profile archive could not be read or written.
+    #[error(transparent)]
+    Archive(#[from] crate::config::ArchiveError),
 }
@@ -7,6 +7,7 @@
 
 mod display;
 mod install_instructions;
+mod redirect;
 
 use std::path::PathBuf;
 
@@ -16,6 +17,7 @@ use crate::error::Result;
 
 pub use display::DisplayInfo;
 pub use install_instructions::{get_empty_state_message, get_install_instructions};
+pub use redirect::{ConfigRedirect, config_redirect};
 
 /// Configuration interface for AI coding assistant harnesses.
 ///
@@ -0,0 +1,176 @@
+//! Pointing a harness at a config dir other than its global one.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use harness_locate::HarnessKind;
+
+use crate::plan::fs;
+
+/// An environment variable the harness reads its global config dir from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConfigRedirect {
+    pub var: &'static str,
+    /// Directory below the variable's value that holds the config, for
+    /// variables shared by many programs such as `XDG_CONFIG_HOME`.
+    pub subdir: Option<&'static str>,
+}
+
+impl ConfigRedirect {
+    /// The config dir the harness uses once the variable is set to `root`.
+    pub fn config_dir(&self, root: &Path) -> PathBuf {
+        match self.subdir {
+            Some(subdir) => root.join(subdir),
+            None => root.to_path_buf(),
+        }
+    }
+
+    /// Links what the variable currently points at into `root`, apart from
+    /// the harness's own config dir.
+    ///
+    /// `XDG_CONFIG_HOME` is also read by every program the harness starts
+    /// (git, gh, language servers, ...), which would otherwise lose their
+    /// config under `root`. Does nothing for variables that only hold the
+    /// harness's config, and on Windows, where symlinks need extra privileges.
+    pub fn link_shared(&self, root: &Path) -> io::Result<()> {
+        let Some(subdir) = self.subdir else {
+            return Ok(());
+        };
+        let shared = match std::env::var_os(self.var) {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => match harness_locate::platform::home_dir() {
+                Ok(home) => home.join(".config"),
+                Err(_) => return Ok(()),
+            },
+        };
+        link_entries(&shared, root, subdir)
+    }
+}
+
+/// Symlinks each entry of `shared` except `skip` into `root`.
+///
+/// Links from an earlier run are replaced, so entries removed from `shared`
+/// go away; anything else already in `root` is kept and shadows its
+/// counterpart in `shared`.
+#[cfg(unix)]
+fn link_entries(shared: &Path, root: &Path, skip: &str) -> io::Result<()> {
+    if shared == root {
+        return Ok(());
+    }
+    fs::create_dir_all(root)?;
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        if entry.file_name() != skip && entry.file_type()?.is_symlink() {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    if !fs::is_dir(shared) {
+        return Ok(());
+    }
+    for entry in fs::read_dir(shared)? {
+        let entry = entry?;
+        let link = root.join(entry.file_name());
+        if entry.file_name() == skip || fs::exists(&link) {
+            continue;
+        }
+        fs::symlink(entry.path(), &link)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn link_entries(_shared: &Path, _root: &Path, _skip: &str) -> io::Result<()> {
+    Ok(())
+}
+
+/// How to redirect a harness's global config, if it can be redirected.
+///
+/// Claude Code honours `CLAUDE_CONFIG_DIR` and Copilot CLI `XDG_CONFIG_HOME`.
+/// The harnesses kept under [`harness_locate::platform::config_dir`] follow
+/// `XDG_CONFIG_HOME` outside Windows, where they live in `%APPDATA%` instead.
+/// Droid has no override.
+pub fn config_redirect(kind: HarnessKind) -> Option<ConfigRedirect> {
+    let xdg = |subdir| ConfigRedirect {
+        var: "XDG_CONFIG_HOME",
+        subdir: Some(subdir),
+    };
+    match kind {
+        HarnessKind::ClaudeCode => Some(ConfigRedirect {
+            var: "CLAUDE_CONFIG_DIR",
+            subdir: None,
+        }),
+        HarnessKind::CopilotCli => Some(xdg("copilot")),
+        HarnessKind::OpenCode if cfg!(unix) => Some(xdg("opencode")),
+        HarnessKind::Goose if cfg!(unix) => Some(xdg("goose")),
+        HarnessKind::AmpCode if cfg!(unix) => Some(xdg("amp")),
+        HarnessKind::Crush if cfg!(unix) => Some(xdg("crush")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use harness_locate::{Harness, Scope, platform};
+
+    #[cfg(unix)]
+    #[test]
+    fn xdg_redirects_match_where_harness_locate_looks() {
+        for kind in [
+            HarnessKind::OpenCode,
+            HarnessKind::Goose,
+            HarnessKind::AmpCode,
+            HarnessKind::Crush,
+        ] {
+            let redirect = config_redirect(kind).unwrap();
+            let located = Harness::new(kind).config(&Scope::Global).unwrap();
+            assert_eq!(
+                located,
+                redirect.config_dir(&platform::config_dir().unwrap()),
+                "{kind}"
+            );
+        }
+    }
+
+    #[test]
+    fn claude_code_config_dir_is_the_variable_itself() {
+        let redirect = config_redirect(HarnessKind::ClaudeCode).unwrap();
+        assert_eq!(redirect.var, "CLAUDE_CONFIG_DIR");
+        assert_eq!(
+            redirect.config_dir(Path::new("/tmp/exec")),
+            Path::new("/tmp/exec")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shared_config_stays_visible_under_the_exec_root() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let shared = temp.path().join("config");
+        let root = temp.path().join("exec");
+        for dir in ["git", "gh", "opencode"] {
+            std::fs::create_dir_all(shared.join(dir)).unwrap();
+        }
+        std::fs::write(shared.join("git/config"), "[user]").unwrap();
+        std::fs::create_dir_all(root.join("opencode")).unwrap();
+        std::fs::create_dir_all(root.join("gh")).unwrap();
+
+        link_entries(&shared, &root, "opencode").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(root.join("git/config")).unwrap(),
+            "[user]"
+        );
+        // The harness's own dir and what is already there are kept
+        assert!(!root.join("opencode").is_symlink());
+        assert!(!root.join("gh").is_symlink());
+
+        std::fs::remove_dir_all(shared.join("git")).unwrap();
+        link_entries(&shared, &root, "opencode").unwrap();
+        assert!(!root.join("git").is_symlink());
+    }
+
+    #[test]
+    fn droid_cannot_be_redirected() {
+        assert_eq!(config_redirect(HarnessKind::Droid), None);
+    }
+}
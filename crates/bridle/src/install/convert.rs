@@ -0,0 +1,574 @@
+//! Cross-harness profile conversion.
+
+use crate::plan::fs;
+use std::path::{Path, PathBuf};
+
+use harness_locate::validation::{
+    CODE_AGENT_UNSUPPORTED, CODE_SKILL_UNSUPPORTED, validate_agent_for_harness,
+    validate_for_harness, validate_skill_for_harness,
+};
+use harness_locate::{Harness, HarnessKind, McpServer, Scope, ValidationIssue};
+use serde::Serialize;
+use thiserror::Error;
+
+use super::installer::{
+    InstallError, install_agent_to_dir_with_source, install_command_to_dir_with_source,
+    install_skill_to_dir_with_source, skill_for_profile, transform_agent_for_opencode,
+};
+use super::manifest::{InstallManifest, manifest_path};
+use super::mcp_config::read_mcp_config;
+use super::mcp_installer::{
+    McpInstallOutcome, get_profile_config_path, install_mcp_to_dir_with_source,
+};
+use super::spec::{rules_file_name, write_model};
+use super::types::{
+    AgentInfo, CommandInfo, ComponentType, InstallOptions, InstallTarget, SkillInfo,
+};
+use crate::config::{extract_model, extract_rules_file};
+
+#[derive(Debug, Error)]
+pub enum ConvertError {
+    #[error("Profile not found: {harness}/{profile}")]
+    ProfileNotFound { harness: String, profile: String },
+
+    #[error("Profile already exists: {harness}/{profile}")]
+    TargetExists { harness: String, profile: String },
+
+    #[error("Failed to read {path}: {error}")]
+    Read {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("Failed to create profile directory: {0}")]
+    CreateDir(#[source] std::io::Error),
+}
+
+/// One component carried over (possibly degraded) or dropped by a conversion.
+#[derive(Debug, Serialize)]
+pub struct ConvertedComponent {
+    #[serde(rename = "type")]
+    pub component_type: String,
+    pub name: String,
+    pub issues: Vec<ValidationIssue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConversionReport {
+    pub from: InstallTarget,
+    pub to: InstallTarget,
+    pub converted: Vec<ConvertedComponent>,
+    pub dropped: Vec<ConvertedComponent>,
+}
+
+impl ConversionReport {
+    fn push(&mut self, component_type: &str, name: &str, issues: Vec<ValidationIssue>) {
+        let component = ConvertedComponent {
+            component_type: component_type.to_string(),
+            name: name.to_string(),
+            issues,
+        };
+        if is_dropped(&component.issues) {
+            self.dropped.push(component);
+        } else {
+            self.converted.push(component);
+        }
+    }
+
+    fn drop_with(&mut self, component_type: &str, name: &str, issue: ValidationIssue) {
+        self.dropped.push(ConvertedComponent {
+            component_type: component_type.to_string(),
+            name: name.to_string(),
+            issues: vec![issue],
+        });
+    }
+}
+
+/// Issues that mean the target harness cannot hold the component at all.
+fn is_dropped(issues: &[ValidationIssue]) -> bool {
+    issues.iter().any(|i| {
+        matches!(
+            i.code,
+            Some(CODE_SKILL_UNSUPPORTED | CODE_AGENT_UNSUPPORTED)
+        )
+    })
+}
+
+/// Translate a profile of one harness into a new profile of another.
+///
+/// Components are written through the regular installers so the target gets
+/// its native layout (and OpenCode transforms). Manifest sources carry over, so
+/// `bridle update` keeps working on the converted profile.
+pub fn convert_profile(
+    from: &InstallTarget,
+    from_kind: HarnessKind,
+    to: &InstallTarget,
+    to_kind: HarnessKind,
+    profiles_dir: &Path,
+) -> Result<ConversionReport, ConvertError> {
+    let from_dir = profiles_dir.join(&from.harness).join(from.profile.as_str());
+    if !fs::is_dir(&from_dir) {
+        return Err(ConvertError::ProfileNotFound {
+            harness: from.harness.clone(),
+            profile: from.profile.as_str().to_string(),
+        });
+    }
+    let to_dir = profiles_dir.join(&to.harness).join(to.profile.as_str());
+    if fs::exists(&to_dir) {
+        return Err(ConvertError::TargetExists {
+            harness: to.harness.clone(),
+            profile: to.profile.as_str().to_string(),
+        });
+    }
+
+    let from_harness = Harness::new(from_kind);
+    let to_harness = Harness::new(to_kind);
+    let manifest = InstallManifest::load(&manifest_path(&from_dir)).unwrap_or_default();
+    let source_of = |component_type, name: &str| {
+        manifest
+            .find_component(component_type, name)
+            .map(|e| e.source.clone())
+    };
+
+    let skills = read_skills(&from_harness, &from_dir)?;
+    let agents = read_markdown(
+        &from_dir,
+        &component_dirs(&from_harness, ComponentType::Agent),
+    )?;
+    let commands = read_markdown(
+        &from_dir,
+        &component_dirs(&from_harness, ComponentType::Command),
+    )?;
+    let mcp_servers = read_mcp_servers(&from_harness, &from_dir)?;
+
+    fs::create_dir_all(&to_dir).map_err(ConvertError::CreateDir)?;
+
+    let options = InstallOptions { force: true };
+    let mut report = ConversionReport {
+        from: from.clone(),
+        to: to.clone(),
+        converted: Vec::new(),
+        dropped: Vec::new(),
+    };
+
+    for skill in &skills {
+        let (name, content) = skill_for_profile(Some(to_kind), skill);
+        let issues = validate_skill_for_harness(&content, &name, to_kind);
+        if !is_dropped(&issues) {
+            let source = source_of(ComponentType::Skill, &skill.name);
+            let result = install_skill_to_dir_with_source(
+                skill,
+                to,
+                &options,
+                profiles_dir,
+                source.as_ref(),
+            );
+            if let Err(e) = result {
+                report.drop_with("skill", &skill.name, install_issue("skill", e));
+                continue;
+            }
+        }
+        report.push("skill", &skill.name, issues);
+    }
+
+    for (name, content) in &agents {
+        let content = if to_kind == HarnessKind::OpenCode {
+            transform_agent_for_opencode(content)
+        } else {
+            content.clone()
+        };
+        let issues = validate_agent_for_harness(&content, to_kind);
+        if !is_dropped(&issues) {
+            let agent = AgentInfo {
+                name: name.clone(),
+                description: None,
+                path: format!("agents/{}.md", name),
+                content,
+            };
+            let source = source_of(ComponentType::Agent, name);
+            let result = install_agent_to_dir_with_source(
+                &agent,
+                to,
+                &options,
+                profiles_dir,
+                source.as_ref(),
+            );
+            if let Err(e) = result {
+                report.drop_with("agent", name, install_issue("agent", e));
+                continue;
+            }
+        }
+        report.push("agent", name, issues);
+    }
+
+    let supports_commands = to_harness.commands(&Scope::Global).ok().flatten().is_some();
+    for (name, content) in &commands {
+        if !supports_commands {
+            report.drop_with(
+                "command",
+                name,
+                ValidationIssue::error(
+                    "command",
+                    format!("{} does not support commands", to_kind.as_str()),
+                    None,
+                ),
+            );
+            continue;
+        }
+        let command = CommandInfo {
+            name: name.clone(),
+            description: None,
+            path: format!("commands/{}.md", name),
+            content: content.clone(),
+        };
+        let source = source_of(ComponentType::Command, name);
+        match install_command_to_dir_with_source(
+            &command,
+            to,
+            &options,
+            profiles_dir,
+            source.as_ref(),
+        ) {
+            Ok(_) => report.push("command", name, Vec::new()),
+            Err(e) => report.drop_with("command", name, install_issue("command", e)),
+        }
+    }
+
+    for (name, parsed) in &mcp_servers {
+        let server = match parsed {
+            Ok(server) => server,
+            Err(message) => {
+                report.drop_with(
+                    "mcp",
+                    name,
+                    ValidationIssue::error("config", message.clone(), None),
+                );
+                continue;
+            }
+        };
+        if let Err(e) = server.validate_capabilities(to_kind) {
+            report.drop_with(
+                "mcp",
+                name,
+                ValidationIssue::error("transport", e.to_string(), None),
+            );
+            continue;
+        }
+        let issues = validate_for_harness(server, to_kind);
+        let source = source_of(ComponentType::Mcp, name);
+        match install_mcp_to_dir_with_source(
+            name,
+            server,
+            to,
+            &options,
+            profiles_dir,
+            source.as_ref(),
+        ) {
+            Ok(McpInstallOutcome::Installed(_) | McpInstallOutcome::Skipped(_)) => {
+                report.push("mcp", name, issues)
+            }
+            Err(e) => report.drop_with("mcp", name, install_issue("mcp", e)),
+        }
+    }
+
+    if let (Some(rules_path), _) = extract_rules_file(&from_harness, &from_dir) {
+        let content = fs::read_to_string(&rules_path).map_err(|error| ConvertError::Read {
+            path: rules_path.clone(),
+            error,
+        })?;
+        let file = rules_file_name(to_kind);
+        match fs::write(to_dir.join(file), content) {
+            Ok(()) => report.push("rules", file, Vec::new()),
+            Err(e) => report.drop_with(
+                "rules",
+                file,
+                ValidationIssue::error("rules", e.to_string(), None),
+            ),
+        }
+    }
+
+    if let Some(model) = extract_model(&from_harness, &from_dir) {
+        match write_model(to_kind, &to_dir, &model) {
+            Ok(true) => report.push("model", &model, Vec::new()),
+            Ok(false) => report.drop_with(
+                "model",
+                &model,
+                ValidationIssue::warning(
+                    "model",
+                    format!("{} has no model setting", to_kind.as_str()),
+                    None,
+                ),
+            ),
+            Err(e) => report.drop_with(
+                "model",
+                &model,
+                ValidationIssue::error("model", e.to_string(), None),
+            ),
+        }
+    }
+
+    Ok(report)
+}
+
+fn install_issue(field: &str, error: InstallError) -> ValidationIssue {
+    ValidationIssue::error(field, error.to_string(), None)
+}
+
+/// Profile subdirectories a harness may keep a component type in, native
+/// name first, then the canonical bridle names.
+pub(super) fn component_dirs(harness: &Harness, component_type: ComponentType) -> Vec<String> {
+    let native = match component_type {
+        ComponentType::Skill => harness.skills(&Scope::Global),
+        ComponentType::Agent => harness.agents(&Scope::Global),
+        ComponentType::Command => harness.commands(&Scope::Global),
+        ComponentType::Mcp | ComponentType::Hook => return Vec::new(),
+    };
+
+    let mut dirs: Vec<String> = native
+        .ok()
+        .flatten()
+        .and_then(|d| {
+            d.path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .map(String::from)
+        })
+        .into_iter()
+        .collect();
+    let canonical = component_type.dir_name();
+    for name in [canonical, canonical.trim_end_matches('s')] {
+        if !dirs.iter().any(|d| d == name) {
+            dirs.push(name.to_string());
+        }
+    }
+    dirs
+}
+
+pub(super) fn read_skills(
+    harness: &Harness,
+    profile_dir: &Path,
+) -> Result<Vec<SkillInfo>, ConvertError> {
+    let mut skills: Vec<SkillInfo> = Vec::new();
+
+    for subdir in component_dirs(harness, ComponentType::Skill) {
+        let dir = profile_dir.join(&subdir);
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path().join("SKILL.md");
+            let Some(name) = entry.file_name().to_str().map(String::from) else {
+                continue;
+            };
+            if !fs::is_file(&path) || skills.iter().any(|s| s.name == name) {
+                continue;
+            }
+            let content =
+                fs::read_to_string(&path).map_err(|error| ConvertError::Read { path, error })?;
+            skills.push(SkillInfo {
+                path: format!("{}/{}/SKILL.md", subdir, name),
+                name,
+                description: None,
+                content,
+            });
+        }
+    }
+
+    skills.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(skills)
+}
+
+pub(super) fn read_markdown(
+    profile_dir: &Path,
+    subdirs: &[String],
+) -> Result<Vec<(String, String)>, ConvertError> {
+    let mut files: Vec<(String, String)> = Vec::new();
+
+    for subdir in subdirs {
+        let Ok(entries) = fs::read_dir(profile_dir.join(subdir)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !fs::is_file(&path) || path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()).map(String::from) else {
+                continue;
+            };
+            if files.iter().any(|(n, _)| n == &name) {
+                continue;
+            }
+            let content =
+                fs::read_to_string(&path).map_err(|error| ConvertError::Read { path, error })?;
+            files.push((name, content));
+        }
+    }
+
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(files)
+}
+
+pub(super) type ParsedServers = Vec<(String, Result<McpServer, String>)>;
+
+pub(super) fn read_mcp_servers(
+    harness: &Harness,
+    profile_dir: &Path,
+) -> Result<ParsedServers, ConvertError> {
+    read_mcp_servers_at(
+        harness,
+        &get_profile_config_path(profile_dir, harness.kind()),
+    )
+}
+
+pub(super) fn read_mcp_servers_at(
+    harness: &Harness,
+    path: &Path,
+) -> Result<ParsedServers, ConvertError> {
+    let native = read_mcp_config(harness.kind(), path).map_err(|e| ConvertError::Read {
+        path: path.to_path_buf(),
+        error: std::io::Error::other(e.to_string()),
+    })?;
+
+    let mut servers: ParsedServers = native
+        .iter()
+        .map(|(name, value)| {
+            let server = harness
+                .parse_mcp_server_config(name, value)
+                .map_err(|e| e.to_string());
+            (name.clone(), server)
+        })
+        .collect();
+    servers.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(servers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProfileName;
+    use harness_locate::validation::CODE_AGENT_MODE_UNSUPPORTED;
+    use tempfile::TempDir;
+
+    fn target(harness: &str, profile: &str) -> InstallTarget {
+        InstallTarget {
+            harness: harness.to_string(),
+            profile: ProfileName::new(profile).unwrap(),
+        }
+    }
+
+    fn claude_profile(temp: &TempDir) -> PathBuf {
+        let profiles_dir = temp.path().join("profiles");
+        let dir = profiles_dir.join("claude-code/convert-src");
+        fs::create_dir_all(dir.join("skills/Code Review")).unwrap();
+        fs::write(
+            dir.join("skills/Code Review/SKILL.md"),
+            "---\nname: Code Review\ndescription: Reviews\n---\nReview",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.join("agents")).unwrap();
+        fs::write(
+            dir.join("agents/helper.md"),
+            "---\ndescription: Helps\nmode: turbo\ncolor: blue\n---\nHelp",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.join("commands")).unwrap();
+        fs::write(dir.join("commands/ship.md"), "Ship it").unwrap();
+        fs::write(dir.join("CLAUDE.md"), "Be concise.").unwrap();
+        fs::write(dir.join("settings.json"), r#"{"model": "opus"}"#).unwrap();
+        fs::write(
+            dir.join(".mcp.json"),
+            r#"{"mcpServers": {
+                "fs": {"command": "npx", "args": ["fs"], "cwd": "/tmp"},
+                "remote": {"type": "sse", "url": "https://example.com/sse"}
+            }}"#,
+        )
+        .unwrap();
+        profiles_dir
+    }
+
+    #[test]
+    fn convert_claude_to_opencode_translates_layout() {
+        let temp = TempDir::new().unwrap();
+        let profiles_dir = claude_profile(&temp);
+
+        let report = convert_profile(
+            &target("claude-code", "convert-src"),
+            HarnessKind::ClaudeCode,
+            &target("opencode", "convert-dst"),
+            HarnessKind::OpenCode,
+            &profiles_dir,
+        )
+        .unwrap();
+
+        let dst = profiles_dir.join("opencode/convert-dst");
+        assert!(dst.join("skills/code-review/SKILL.md").exists());
+        assert!(dst.join("agents/helper.md").exists());
+        assert!(dst.join("commands/ship.md").exists());
+        assert_eq!(
+            fs::read_to_string(dst.join("AGENTS.md")).unwrap(),
+            "Be concise."
+        );
+        let config = fs::read_to_string(dst.join("opencode.jsonc")).unwrap();
+        assert!(config.contains("\"model\": \"opus\""));
+        assert!(config.contains("\"fs\""));
+
+        let agent = fs::read_to_string(dst.join("agents/helper.md")).unwrap();
+        assert!(agent.contains("#0000FF"));
+
+        let helper = report
+            .converted
+            .iter()
+            .find(|c| c.name == "helper")
+            .unwrap();
+        assert!(
+            helper
+                .issues
+                .iter()
+                .any(|i| i.code == Some(CODE_AGENT_MODE_UNSUPPORTED))
+        );
+    }
+
+    #[test]
+    fn convert_reports_dropped_components() {
+        let temp = TempDir::new().unwrap();
+        let profiles_dir = claude_profile(&temp);
+
+        let report = convert_profile(
+            &target("claude-code", "convert-src"),
+            HarnessKind::ClaudeCode,
+            &target("goose", "convert-dst"),
+            HarnessKind::Goose,
+            &profiles_dir,
+        )
+        .unwrap();
+
+        let dropped: Vec<(&str, &str)> = report
+            .dropped
+            .iter()
+            .map(|c| (c.component_type.as_str(), c.name.as_str()))
+            .collect();
+        assert!(dropped.contains(&("agent", "helper")));
+        assert!(dropped.contains(&("command", "ship")));
+
+        let agent = report.dropped.iter().find(|c| c.name == "helper").unwrap();
+        assert_eq!(agent.issues[0].code, Some(CODE_AGENT_UNSUPPORTED));
+        assert!(!profiles_dir.join("goose/convert-dst/agents").exists());
+    }
+
+    #[test]
+    fn convert_refuses_existing_target() {
+        let temp = TempDir::new().unwrap();
+        let profiles_dir = claude_profile(&temp);
+        fs::create_dir_all(profiles_dir.join("opencode/convert-dst")).unwrap();
+
+        let result = convert_profile(
+            &target("claude-code", "convert-src"),
+            HarnessKind::ClaudeCode,
+            &target("opencode", "convert-dst"),
+            HarnessKind::OpenCode,
+            &profiles_dir,
+        );
+        assert!(matches!(result, Err(ConvertError::TargetExists { .. })));
+    }
+}
@@ -1,19 +1,25 @@
-//! Skill discovery from GitHub repositories.
+//! Skill discovery from component sources.
 //!
-//! Wraps the `skills-locate` crate to discover installable skills.
+//! Wraps the `skills-locate` crate to discover installable skills from GitHub
+//! repositories, git remotes, archives and local directories, and MCP servers
+//! from the MCP registry (`registry:<id>`).
 
 use std::collections::HashMap;
 
 use harness_locate::McpServer;
-use skills_locate::parse_mcp_json;
-use skills_locate::{GitHubRef, extract_file, fetch_bytes, list_files, parse_skill_descriptor};
+use skills_locate::{Source, SourceTree, parse_hooks_json, parse_mcp_json, parse_skill_descriptor};
 use thiserror::Error;
 
-use super::types::{AgentInfo, CommandInfo, DiscoveryResult, SkillInfo, SourceInfo};
+use super::marketplace::{PluginLocation, plugin_entries, plugin_manifest};
+use super::registry::{RegistrySource, discover_registry, registry_client};
+use super::types::{
+    AgentInfo, CommandInfo, ComponentType, DiscoveryResult, HookInfo, PluginInfo, SkillInfo,
+    SourceInfo,
+};
 
 #[derive(Debug, Error)]
 pub enum DiscoveryError {
-    #[error("Invalid GitHub URL: {0}")]
+    #[error("Invalid source: {0}")]
     InvalidUrl(String),
 
     #[error("Failed to fetch repository: {0}")]
@@ -24,23 +30,48 @@ pub enum DiscoveryError {
 }
 
 pub fn discover_skills(url: &str) -> Result<DiscoveryResult, DiscoveryError> {
-    let github_ref =
-        GitHubRef::parse(url).map_err(|e| DiscoveryError::InvalidUrl(e.to_string()))?;
-
-    let source = SourceInfo {
-        owner: github_ref.owner.clone(),
-        repo: github_ref.repo.clone(),
-        git_ref: Some(github_ref.git_ref.clone()),
-    };
+    if let Some(source) = RegistrySource::parse(url) {
+        return discover_registry(&registry_client(), &source);
+    }
+    let source = Source::parse(url).map_err(|e| DiscoveryError::InvalidUrl(e.to_string()))?;
+    let tree = source.fetch().map_err(DiscoveryError::FetchError)?;
+    let mut info = source_info(&source);
+    info.commit = tree.revision().map(String::from);
+    discover_from_tree(&tree, info)
+}
 
-    let archive_url = github_ref.archive_url();
-    let zip_bytes = fetch_bytes(&archive_url).map_err(DiscoveryError::FetchError)?;
+/// Builds the manifest metadata recorded for a parsed source.
+pub fn source_info(source: &Source) -> SourceInfo {
+    SourceInfo {
+        owner: source.owner(),
+        repo: source.name(),
+        git_ref: source.git_ref().map(String::from),
+        url: Some(source.to_string()),
+        commit: None,
+        plugin: None,
+    }
+}
 
-    let skill_paths = list_files(&zip_bytes, "SKILL.md").map_err(DiscoveryError::FetchError)?;
+/// Discovers components in an already-fetched source tree.
+///
+/// Components are grouped by plugin when the tree is a marketplace or
+/// contains plugin directories; plugins a marketplace hosts elsewhere are
+/// fetched and their components added.
+pub fn discover_from_tree(
+    tree: &SourceTree,
+    source: SourceInfo,
+) -> Result<DiscoveryResult, DiscoveryError> {
+    discover_tree(tree, source, true)
+}
 
+fn discover_tree(
+    tree: &SourceTree,
+    source: SourceInfo,
+    follow_remote: bool,
+) -> Result<DiscoveryResult, DiscoveryError> {
     let mut skills = Vec::new();
-    for path in skill_paths {
-        let content = match extract_file(&zip_bytes, &path) {
+    for path in tree.list_files("SKILL.md") {
+        let content = match tree.read_file(&path) {
             Ok(c) => c,
             Err(_) => continue,
         };
@@ -53,31 +84,30 @@ pub fn discover_skills(url: &str) -> Result<DiscoveryResult, DiscoveryError> {
         skills.push(SkillInfo {
             name: descriptor.name,
             description: descriptor.description,
-            path: normalize_archive_path(&path, &github_ref),
+            path: tree.relative_path(&path).to_string(),
             content,
         });
     }
 
-    let mcp_paths = list_files(&zip_bytes, ".mcp.json").map_err(DiscoveryError::FetchError)?;
-
     let mut mcp_servers: HashMap<String, McpServer> = HashMap::new();
-    for path in mcp_paths {
-        let content = match extract_file(&zip_bytes, &path) {
+    let mut mcp_paths: Vec<(String, String)> = Vec::new();
+    for path in tree.list_files(".mcp.json") {
+        let content = match tree.read_file(&path) {
             Ok(c) => c,
             Err(_) => continue,
         };
 
         if let Ok(servers) = parse_mcp_json(&content) {
+            let relative = tree.relative_path(&path).to_string();
+            mcp_paths.extend(servers.keys().map(|name| (name.clone(), relative.clone())));
             mcp_servers.extend(servers);
         }
     }
 
     // Discover agents from AGENT.md files (legacy format)
-    let agent_paths = list_files(&zip_bytes, "AGENT.md").map_err(DiscoveryError::FetchError)?;
-
     let mut agents = Vec::new();
-    for path in agent_paths {
-        let content = match extract_file(&zip_bytes, &path) {
+    for path in tree.list_files("AGENT.md") {
+        let content = match tree.read_file(&path) {
             Ok(c) => c,
             Err(_) => continue,
         };
@@ -86,19 +116,19 @@ pub fn discover_skills(url: &str) -> Result<DiscoveryResult, DiscoveryError> {
             agents.push(AgentInfo {
                 name: agent.0,
                 description: agent.1,
-                path: normalize_archive_path(&path, &github_ref),
+                path: tree.relative_path(&path).to_string(),
                 content,
             });
         }
     }
 
     // Discover agents from */agents/*.md directories (claude-code format)
-    let all_md_paths = list_files(&zip_bytes, ".md").map_err(DiscoveryError::FetchError)?;
+    let all_md_paths = tree.list_files(".md");
     for path in &all_md_paths {
         if !is_in_agents_dir(path) {
             continue;
         }
-        let content = match extract_file(&zip_bytes, path) {
+        let content = match tree.read_file(path) {
             Ok(c) => c,
             Err(_) => continue,
         };
@@ -107,18 +137,16 @@ pub fn discover_skills(url: &str) -> Result<DiscoveryResult, DiscoveryError> {
             agents.push(AgentInfo {
                 name: agent.0,
                 description: agent.1,
-                path: normalize_archive_path(path, &github_ref),
+                path: tree.relative_path(path).to_string(),
                 content,
             });
         }
     }
 
     // Discover commands from COMMAND.md files (legacy format)
-    let command_paths = list_files(&zip_bytes, "COMMAND.md").map_err(DiscoveryError::FetchError)?;
-
     let mut commands = Vec::new();
-    for path in command_paths {
-        let content = match extract_file(&zip_bytes, &path) {
+    for path in tree.list_files("COMMAND.md") {
+        let content = match tree.read_file(&path) {
             Ok(c) => c,
             Err(_) => continue,
         };
@@ -127,7 +155,7 @@ pub fn discover_skills(url: &str) -> Result<DiscoveryResult, DiscoveryError> {
             commands.push(CommandInfo {
                 name: cmd.0,
                 description: cmd.1,
-                path: normalize_archive_path(&path, &github_ref),
+                path: tree.relative_path(&path).to_string(),
                 content,
             });
         }
@@ -138,7 +166,7 @@ pub fn discover_skills(url: &str) -> Result<DiscoveryResult, DiscoveryError> {
         if !is_in_commands_dir(path) {
             continue;
         }
-        let content = match extract_file(&zip_bytes, path) {
+        let content = match tree.read_file(path) {
             Ok(c) => c,
             Err(_) => continue,
         };
@@ -147,23 +175,207 @@ pub fn discover_skills(url: &str) -> Result<DiscoveryResult, DiscoveryError> {
             commands.push(CommandInfo {
                 name: cmd.0,
                 description: cmd.1,
-                path: normalize_archive_path(path, &github_ref),
+                path: tree.relative_path(path).to_string(),
                 content,
             });
         }
     }
 
-    if skills.is_empty() && mcp_servers.is_empty() && agents.is_empty() && commands.is_empty() {
-        return Err(DiscoveryError::NoSkillsFound);
+    // Discover plugin hooks (.claude-plugin/hooks.json or hooks/hooks.json)
+    let mut hooks = Vec::new();
+    for path in tree.list_files("hooks.json") {
+        let relative = tree.relative_path(&path);
+        let Some(plugin_dir) = plugin_dir_of_hooks(relative) else {
+            continue;
+        };
+        let Ok(content) = tree.read_file(&path) else {
+            continue;
+        };
+        let Ok(parsed) = parse_hooks_json(&content) else {
+            continue;
+        };
+        if parsed.is_empty() {
+            continue;
+        }
+
+        let name = plugin_manifest(tree, plugin_dir)
+            .map(|(name, _)| name)
+            .unwrap_or_else(|| {
+                plugin_dir
+                    .trim_end_matches('/')
+                    .rsplit('/')
+                    .next()
+                    .filter(|n| !n.is_empty())
+                    .unwrap_or(&source.repo)
+                    .to_string()
+            });
+        if hooks.iter().any(|h: &HookInfo| h.name == name) {
+            continue;
+        }
+        hooks.push(HookInfo {
+            name,
+            path: relative.to_string(),
+            hooks: parsed,
+        });
     }
 
-    Ok(DiscoveryResult {
+    let mut result = DiscoveryResult {
         skills,
         mcp_servers,
         agents,
         commands,
+        hooks,
+        plugins: Vec::new(),
         source,
-    })
+    };
+    group_plugins(tree, &mut result, &mcp_paths, follow_remote);
+
+    if result.skills.is_empty()
+        && result.mcp_servers.is_empty()
+        && result.agents.is_empty()
+        && result.commands.is_empty()
+        && result.hooks.is_empty()
+    {
+        return Err(DiscoveryError::NoSkillsFound);
+    }
+
+    Ok(result)
+}
+
+/// Assigns components to the plugins listed in the tree, fetching remote
+/// plugins when `follow_remote` is set.
+fn group_plugins(
+    tree: &SourceTree,
+    result: &mut DiscoveryResult,
+    mcp_paths: &[(String, String)],
+    follow_remote: bool,
+) {
+    let mut remotes = Vec::new();
+    let mut dirs: Vec<(String, PluginInfo)> = Vec::new();
+    for entry in plugin_entries(tree) {
+        let dir = match entry.location {
+            PluginLocation::Dir(dir) if dir.is_empty() => dir,
+            PluginLocation::Dir(dir) => format!("{}/", dir),
+            PluginLocation::Remote(location) => {
+                remotes.push((entry.name, entry.description, location));
+                continue;
+            }
+        };
+        let manifest = plugin_manifest(tree, &dir);
+        let name = entry
+            .name
+            .or_else(|| manifest.as_ref().map(|(name, _)| name.clone()))
+            .or_else(|| {
+                dir.trim_end_matches('/')
+                    .rsplit('/')
+                    .next()
+                    .filter(|n| !n.is_empty())
+                    .map(String::from)
+            })
+            .unwrap_or_else(|| result.source.repo.clone());
+        let description = entry.description.or(manifest.and_then(|(_, d)| d));
+        let plugin = empty_plugin(name, description, &result.source);
+        dirs.push((dir, plugin));
+    }
+
+    // Each local component belongs to the most specific plugin directory
+    let owner = |path: &str| {
+        dirs.iter()
+            .enumerate()
+            .filter(|(_, (dir, _))| path.starts_with(dir.as_str()))
+            .max_by_key(|(_, (dir, _))| dir.len())
+            .map(|(i, _)| i)
+    };
+    let mut owned: Vec<(usize, ComponentType, String)> = Vec::new();
+    for skill in &result.skills {
+        owned.extend(owner(&skill.path).map(|i| (i, ComponentType::Skill, skill.name.clone())));
+    }
+    for agent in &result.agents {
+        owned.extend(owner(&agent.path).map(|i| (i, ComponentType::Agent, agent.name.clone())));
+    }
+    for command in &result.commands {
+        owned.extend(
+            owner(&command.path).map(|i| (i, ComponentType::Command, command.name.clone())),
+        );
+    }
+    for hook in &result.hooks {
+        owned.extend(owner(&hook.path).map(|i| (i, ComponentType::Hook, hook.name.clone())));
+    }
+    for (name, path) in mcp_paths {
+        owned.extend(owner(path).map(|i| (i, ComponentType::Mcp, name.clone())));
+    }
+    for (i, component_type, name) in owned {
+        let plugin = &mut dirs[i].1;
+        match component_type {
+            ComponentType::Skill => plugin.skills.push(name),
+            ComponentType::Agent => plugin.agents.push(name),
+            ComponentType::Command => plugin.commands.push(name),
+            ComponentType::Mcp => plugin.mcp_servers.push(name),
+            ComponentType::Hook => plugin.hooks.push(name),
+        }
+    }
+    result
+        .plugins
+        .extend(dirs.into_iter().map(|(_, p)| p).filter(|p| !p.is_empty()));
+
+    if !follow_remote {
+        return;
+    }
+    for (name, description, location) in remotes {
+        let Ok(remote) = discover_remote(&location) else {
+            continue;
+        };
+        let name = name.unwrap_or_else(|| remote.source.repo.clone());
+        let mut plugin = empty_plugin(name, description, &remote.source);
+        plugin.skills = remote.skills.iter().map(|s| s.name.clone()).collect();
+        plugin.agents = remote.agents.iter().map(|a| a.name.clone()).collect();
+        plugin.commands = remote.commands.iter().map(|c| c.name.clone()).collect();
+        plugin.hooks = remote.hooks.iter().map(|h| h.name.clone()).collect();
+        plugin.mcp_servers = remote.mcp_servers.keys().cloned().collect();
+        plugin.mcp_servers.sort();
+
+        result.skills.extend(remote.skills);
+        result.agents.extend(remote.agents);
+        result.commands.extend(remote.commands);
+        result.hooks.extend(remote.hooks);
+        result.mcp_servers.extend(remote.mcp_servers);
+        result.plugins.push(plugin);
+    }
+}
+
+fn empty_plugin(name: String, description: Option<String>, source: &SourceInfo) -> PluginInfo {
+    PluginInfo {
+        source: SourceInfo {
+            plugin: Some(name.clone()),
+            ..source.clone()
+        },
+        name,
+        description,
+        skills: Vec::new(),
+        agents: Vec::new(),
+        commands: Vec::new(),
+        mcp_servers: Vec::new(),
+        hooks: Vec::new(),
+    }
+}
+
+/// Fetches a plugin hosted outside the marketplace, without following any
+/// marketplace it may list in turn.
+fn discover_remote(location: &str) -> Result<DiscoveryResult, DiscoveryError> {
+    let source = Source::parse(location).map_err(|e| DiscoveryError::InvalidUrl(e.to_string()))?;
+    let tree = source.fetch().map_err(DiscoveryError::FetchError)?;
+    let mut info = source_info(&source);
+    info.commit = tree.revision().map(String::from);
+    discover_tree(&tree, info, false)
+}
+
+/// Plugin directory (with trailing `/`, or empty for the root) owning a
+/// hooks.json file at one of the plugin locations.
+fn plugin_dir_of_hooks(path: &str) -> Option<&str> {
+    [".claude-plugin/hooks.json", "hooks/hooks.json"]
+        .iter()
+        .find_map(|suffix| path.strip_suffix(suffix))
+        .filter(|dir| dir.is_empty() || dir.ends_with('/'))
 }
 
 fn parse_agent_frontmatter(content: &str, path: &str) -> Option<(String, Option<String>)> {
@@ -201,14 +413,6 @@ fn filename_stem(path: &str) -> Option<&str> {
     path.rsplit('/').next()?.strip_suffix(".md")
 }
 
-fn normalize_archive_path(archive_path: &str, github_ref: &GitHubRef) -> String {
-    let prefix = format!("{}-{}/", github_ref.repo, github_ref.git_ref);
-    archive_path
-        .strip_prefix(&prefix)
-        .unwrap_or(archive_path)
-        .to_string()
-}
-
 fn is_in_agents_dir(path: &str) -> bool {
     path.contains("/agents/") && path.ends_with(".md") && !path.ends_with("AGENT.md")
 }
@@ -220,6 +424,7 @@ fn is_in_commands_dir(path: &str) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
 
     #[test]
     fn discover_skills_invalid_url() {
@@ -235,22 +440,146 @@ mod tests {
     }
 
     #[test]
-    fn normalize_path_strips_prefix() {
-        let github_ref = GitHubRef::parse("https://github.com/owner/my-repo").unwrap();
-        let path = "my-repo-main/skills/test/SKILL.md";
+    fn discover_skills_from_local_dir() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("team-skills");
+        std::fs::create_dir_all(repo.join("skills/review")).unwrap();
+        std::fs::create_dir_all(repo.join("plugin/agents")).unwrap();
+        std::fs::write(
+            repo.join("skills/review/SKILL.md"),
+            "---\nname: review\ndescription: Review code\n---\nBody",
+        )
+        .unwrap();
+        std::fs::write(repo.join("plugin/agents/helper.md"), "Helper agent").unwrap();
+        std::fs::write(
+            repo.join(".mcp.json"),
+            r#"{"mcpServers": {"fs": {"command": "npx"}}}"#,
+        )
+        .unwrap();
+
+        let result = discover_skills(repo.to_str().unwrap()).unwrap();
+        assert_eq!(result.skills.len(), 1);
+        assert_eq!(result.skills[0].path, "skills/review/SKILL.md");
+        assert_eq!(result.agents.len(), 1);
+        assert_eq!(result.agents[0].name, "helper");
+        assert!(result.mcp_servers.contains_key("fs"));
+        assert_eq!(result.source.owner, "local");
+        assert_eq!(result.source.repo, "team-skills");
+        assert_eq!(result.source.url.as_deref(), repo.to_str());
+    }
+
+    #[test]
+    fn discover_plugin_hooks_from_local_dir() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("plugins");
+        std::fs::create_dir_all(repo.join("formatter/.claude-plugin")).unwrap();
+        std::fs::create_dir_all(repo.join("formatter/hooks")).unwrap();
+        std::fs::create_dir_all(repo.join("guard/.claude-plugin")).unwrap();
+        std::fs::write(
+            repo.join("formatter/.claude-plugin/plugin.json"),
+            r#"{"name": "auto-format"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            repo.join("formatter/hooks/hooks.json"),
+            r#"{"hooks": {"PostToolUse": [{"matcher": "Edit", "hooks": [{"type": "command", "command": "fmt"}]}]}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            repo.join("guard/.claude-plugin/hooks.json"),
+            r#"{"PreToolUse": [{"matcher": "Bash", "hooks": ["guard.sh"]}]}"#,
+        )
+        .unwrap();
+
+        let result = discover_skills(repo.to_str().unwrap()).unwrap();
+        let mut names: Vec<&str> = result.hooks.iter().map(|h| h.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["auto-format", "guard"]);
+        let format = result
+            .hooks
+            .iter()
+            .find(|h| h.name == "auto-format")
+            .unwrap();
+        assert_eq!(format.path, "formatter/hooks/hooks.json");
+    }
+
+    #[test]
+    fn discover_groups_components_by_marketplace_plugin() {
+        let temp = TempDir::new().unwrap();
+        let repo = temp.path().join("market");
+        let files = [
+            (
+                ".claude-plugin/marketplace.json",
+                r#"{"name": "team", "plugins": [
+                    {"name": "review", "description": "Code review", "source": "./plugins/review"},
+                    {"name": "docs", "source": "./plugins/docs"}
+                ]}"#,
+            ),
+            (
+                "plugins/review/skills/reviewer/SKILL.md",
+                "---\nname: reviewer\ndescription: Reviews\n---\nBody",
+            ),
+            (
+                "plugins/review/agents/critic.md",
+                "---\nname: critic\ndescription: Critic\n---\nBody",
+            ),
+            (
+                "plugins/docs/skills/writer/SKILL.md",
+                "---\nname: writer\ndescription: Writes\n---\nBody",
+            ),
+            (
+                "skills/loose/SKILL.md",
+                "---\nname: loose\ndescription: Loose\n---\nBody",
+            ),
+        ];
+        for (path, content) in files {
+            let path = repo.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        let mut result = discover_skills(repo.to_str().unwrap()).unwrap();
+        let names: Vec<&str> = result.plugins.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["review", "docs"]);
+        let review = &result.plugins[0];
+        assert_eq!(review.description.as_deref(), Some("Code review"));
+        assert_eq!(review.summary(), "1 skill, 1 agent");
+        assert_eq!(
+            result
+                .source_for(ComponentType::Agent, "critic")
+                .plugin
+                .as_deref(),
+            Some("review")
+        );
         assert_eq!(
-            normalize_archive_path(path, &github_ref),
-            "skills/test/SKILL.md"
+            result.source_for(ComponentType::Skill, "loose").plugin,
+            None
         );
+
+        result.retain_plugins(|p| p.name == "docs", true);
+        let mut skills: Vec<&str> = result.skills.iter().map(|s| s.name.as_str()).collect();
+        skills.sort();
+        assert_eq!(skills, vec!["loose", "writer"]);
+        assert!(result.agents.is_empty());
+    }
+
+    #[test]
+    fn discover_skills_empty_local_dir() {
+        let temp = TempDir::new().unwrap();
+        let result = discover_skills(temp.path().to_str().unwrap());
+        assert!(matches!(result, Err(DiscoveryError::NoSkillsFound)));
     }
 
     #[test]
-    fn normalize_path_handles_no_prefix() {
-        let github_ref = GitHubRef::parse("https://github.com/owner/repo").unwrap();
-        let path = "other/skills/SKILL.md";
+    fn source_info_for_git_remote() {
+        let source = Source::parse("git+https://gitlab.com/team/skills.git#v2").unwrap();
+        let info = source_info(&source);
+        assert_eq!(info.owner, "team");
+        assert_eq!(info.repo, "skills");
+        assert_eq!(info.git_ref.as_deref(), Some("v2"));
         assert_eq!(
-            normalize_archive_path(path, &github_ref),
-            "other/skills/SKILL.md"
+            info.to_string(),
+            "git+https://gitlab.com/team/skills.git#v2"
         );
     }
 
@@ -0,0 +1,430 @@
+//! Health checks for profiles and live harness configs (`bridle doctor`).
+//!
+//! Runs the install-time validators from `harness_locate::validation` over
+//! everything already on disk, plus environment checks (unset env vars,
+//! commands missing from `PATH`) and manifest consistency. Issues that have a
+//! mechanical repair can be fixed in place.
+
+use crate::plan::fs;
+use std::path::{Path, PathBuf};
+
+use harness_locate::validation::{
+    CODE_AGENT_COLOR_FORMAT, CODE_AGENT_TOOLS_FORMAT, CODE_MCP_PARSE_ERROR,
+    CODE_SKILL_NAME_DIRECTORY_MISMATCH, validate_agent_for_harness, validate_environment,
+    validate_for_harness, validate_skill_for_harness,
+};
+use harness_locate::{Harness, HarnessKind, Scope, Severity, ValidationIssue};
+use serde::Serialize;
+use thiserror::Error;
+
+use super::convert::{ConvertError, component_dirs, read_mcp_servers_at, read_skills};
+use super::hook_installer::{SETTINGS_FILE, contains_hooks, read_settings};
+use super::installer::{transform_agent_for_opencode, transform_skill_for_opencode};
+use super::manifest::{InstallManifest, ManifestError, manifest_path};
+use super::mcp_edit::disabled_server_names;
+use super::mcp_installer::get_profile_config_path;
+use super::types::ComponentType;
+use crate::harness::HarnessConfig;
+
+/// Manifest entry whose component is no longer on disk.
+pub const CODE_MANIFEST_ORPHANED: &str = "manifest.orphaned";
+
+#[derive(Debug, Error)]
+pub enum DoctorError {
+    #[error("Profile not found: {harness}/{profile}")]
+    ProfileNotFound { harness: String, profile: String },
+
+    #[error(transparent)]
+    Read(#[from] ConvertError),
+
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+
+    #[error("Failed to fix {path}: {error}")]
+    Fix {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Finding {
+    #[serde(rename = "type")]
+    pub component_type: String,
+    pub name: String,
+    pub issue: ValidationIssue,
+    /// Whether `--fix` can repair this issue.
+    pub fixable: bool,
+    /// Whether `--fix` repaired it in this run.
+    pub fixed: bool,
+}
+
+/// Findings for one profile or one live config.
+#[derive(Debug, Serialize)]
+pub struct DoctorReport {
+    pub harness: String,
+    /// Profile name, or `None` for the harness's live config.
+    pub profile: Option<String>,
+    pub path: PathBuf,
+    pub findings: Vec<Finding>,
+}
+
+impl DoctorReport {
+    /// Number of unrepaired findings with the given severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| !f.fixed && f.issue.severity == severity)
+            .count()
+    }
+}
+
+/// A mechanical repair for a finding.
+enum Fix {
+    /// Rewrite the skill's frontmatter `name` to its directory name.
+    SkillName { path: PathBuf, dir_name: String },
+    /// Convert agent `tools`/`color` to OpenCode's formats.
+    OpenCodeAgent { path: PathBuf },
+    /// Drop a manifest entry whose component is gone.
+    OrphanedEntry {
+        manifest: PathBuf,
+        component_type: ComponentType,
+        name: String,
+    },
+}
+
+impl Fix {
+    fn apply(&self) -> Result<(), DoctorError> {
+        match self {
+            Fix::SkillName { path, dir_name } => rewrite(path, |content| {
+                transform_skill_for_opencode(content, dir_name)
+            }),
+            Fix::OpenCodeAgent { path } => rewrite(path, transform_agent_for_opencode),
+            Fix::OrphanedEntry {
+                manifest,
+                component_type,
+                name,
+            } => {
+                let mut loaded = InstallManifest::load(manifest)?;
+                loaded.remove_component(*component_type, name);
+                loaded.save(manifest)?;
+                Ok(())
+            }
+        }
+    }
+}
+
+fn rewrite(path: &Path, transform: impl Fn(&str) -> String) -> Result<(), DoctorError> {
+    let fix_error = |error| DoctorError::Fix {
+        path: path.to_path_buf(),
+        error,
+    };
+    let content = fs::read_to_string(path).map_err(fix_error)?;
+    fs::write(path, transform(&content)).map_err(fix_error)
+}
+
+/// Checks a stored profile.
+pub fn check_profile(
+    kind: HarnessKind,
+    profile: &str,
+    profiles_dir: &Path,
+    fix: bool,
+) -> Result<DoctorReport, DoctorError> {
+    let harness = Harness::new(kind);
+    let harness_id = harness.id().to_string();
+    let dir = profiles_dir.join(&harness_id).join(profile);
+    if !fs::is_dir(&dir) {
+        return Err(DoctorError::ProfileNotFound {
+            harness: harness_id,
+            profile: profile.to_string(),
+        });
+    }
+
+    let mcp_path = get_profile_config_path(&dir, kind);
+    let findings = check_dir(&harness, &dir, Some(&mcp_path), fix)?;
+    Ok(DoctorReport {
+        harness: harness_id,
+        profile: Some(profile.to_string()),
+        path: dir,
+        findings,
+    })
+}
+
+/// Checks a harness's live global config, or returns `None` if it has none.
+pub fn check_live(kind: HarnessKind, fix: bool) -> Result<Option<DoctorReport>, DoctorError> {
+    let harness = Harness::new(kind);
+    let Ok(dir) = harness.config(&Scope::Global) else {
+        return Ok(None);
+    };
+    if !fs::is_dir(&dir) {
+        return Ok(None);
+    }
+
+    let mcp_path = harness.mcp(&Scope::Global).ok().flatten().map(|r| r.file);
+    let findings = check_dir(&harness, &dir, mcp_path.as_deref(), fix)?;
+    Ok(Some(DoctorReport {
+        harness: harness.id().to_string(),
+        profile: None,
+        path: dir,
+        findings,
+    }))
+}
+
+fn check_dir(
+    harness: &Harness,
+    root: &Path,
+    mcp_path: Option<&Path>,
+    fix: bool,
+) -> Result<Vec<Finding>, DoctorError> {
+    let kind = harness.kind();
+    let mut found: Vec<(Finding, Option<Fix>)> = Vec::new();
+    let mut push = |component_type: &str, name: &str, issue: ValidationIssue, fix: Option<Fix>| {
+        found.push((
+            Finding {
+                component_type: component_type.to_string(),
+                name: name.to_string(),
+                issue,
+                fixable: fix.is_some(),
+                fixed: false,
+            },
+            fix,
+        ))
+    };
+
+    let servers = match mcp_path {
+        Some(path) => read_mcp_servers_at(harness, path)?,
+        None => Vec::new(),
+    };
+    for (name, server) in &servers {
+        match server {
+            Ok(server) => {
+                for issue in validate_for_harness(server, kind)
+                    .into_iter()
+                    .chain(validate_environment(server))
+                {
+                    push("mcp", name, issue, None);
+                }
+            }
+            Err(e) => push(
+                "mcp",
+                name,
+                ValidationIssue::error("mcp", e.clone(), Some(CODE_MCP_PARSE_ERROR)),
+                None,
+            ),
+        }
+    }
+
+    for skill in read_skills(harness, root)? {
+        let path = root.join(&skill.path);
+        for issue in validate_skill_for_harness(&skill.content, &skill.name, kind) {
+            let repairable = issue.code == Some(CODE_SKILL_NAME_DIRECTORY_MISMATCH)
+                && validate_skill_for_harness(
+                    &transform_skill_for_opencode(&skill.content, &skill.name),
+                    &skill.name,
+                    kind,
+                )
+                .iter()
+                .all(|i| i.field != "name");
+            let fix = repairable.then(|| Fix::SkillName {
+                path: path.clone(),
+                dir_name: skill.name.clone(),
+            });
+            push("skill", &skill.name, issue, fix);
+        }
+    }
+
+    for (name, path) in markdown_files(root, &component_dirs(harness, ComponentType::Agent)) {
+        let content = fs::read_to_string(&path).map_err(|error| ConvertError::Read {
+            path: path.clone(),
+            error,
+        })?;
+        for issue in validate_agent_for_harness(&content, kind) {
+            let format_issue = matches!(
+                issue.code,
+                Some(CODE_AGENT_TOOLS_FORMAT) | Some(CODE_AGENT_COLOR_FORMAT)
+            );
+            let fix = (format_issue && kind == HarnessKind::OpenCode)
+                .then(|| Fix::OpenCodeAgent { path: path.clone() });
+            push("agent", &name, issue, fix);
+        }
+    }
+
+    let manifest_file = manifest_path(root);
+    if fs::is_file(&manifest_file) {
+        let manifest = InstallManifest::load(&manifest_file)?;
+        let disabled = disabled_server_names(root);
+        for entry in &manifest.entries {
+            let present = match entry.component_type {
+                ComponentType::Mcp => {
+                    servers.iter().any(|(name, _)| name == &entry.name)
+                        || disabled.contains(&entry.name)
+                }
+                ComponentType::Hook => {
+                    let settings = read_settings(&root.join(SETTINGS_FILE)).unwrap_or_default();
+                    entry
+                        .fragment
+                        .as_deref()
+                        .and_then(|f| serde_json::from_str(f).ok())
+                        .is_some_and(|fragment| contains_hooks(&settings, &fragment))
+                }
+                ComponentType::Skill => component_dirs(harness, ComponentType::Skill)
+                    .iter()
+                    .any(|d| fs::is_file(root.join(d).join(&entry.name).join("SKILL.md"))),
+                other => component_dirs(harness, other)
+                    .iter()
+                    .any(|d| fs::is_file(root.join(d).join(format!("{}.md", entry.name)))),
+            };
+            if present {
+                continue;
+            }
+            let label = entry.component_type.dir_name().trim_end_matches('s');
+            push(
+                label,
+                &entry.name,
+                ValidationIssue::warning(
+                    "manifest",
+                    format!(
+                        "{} '{}' is in the manifest but not installed",
+                        label, entry.name
+                    ),
+                    Some(CODE_MANIFEST_ORPHANED),
+                ),
+                Some(Fix::OrphanedEntry {
+                    manifest: manifest_file.clone(),
+                    component_type: entry.component_type,
+                    name: entry.name.clone(),
+                }),
+            );
+        }
+    }
+
+    let mut findings = Vec::with_capacity(found.len());
+    for (mut finding, repair) in found {
+        if fix && let Some(repair) = repair {
+            repair.apply()?;
+            finding.fixed = true;
+        }
+        findings.push(finding);
+    }
+    Ok(findings)
+}
+
+fn markdown_files(root: &Path, subdirs: &[String]) -> Vec<(String, PathBuf)> {
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    for subdir in subdirs {
+        let Ok(entries) = fs::read_dir(root.join(subdir)) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !fs::is_file(&path) || path.extension().and_then(|e| e.to_str()) != Some("md") {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()).map(String::from) else {
+                continue;
+            };
+            if !files.iter().any(|(n, _)| n == &name) {
+                files.push((name, path));
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use harness_locate::validation::{CODE_COMMAND_NOT_FOUND, CODE_ENV_MISSING};
+    use tempfile::TempDir;
+
+    fn opencode_profile(temp: &TempDir) -> PathBuf {
+        let dir = temp.path().join("opencode/doctor");
+        fs::create_dir_all(dir.join("skill/reviewer")).unwrap();
+        fs::write(
+            dir.join("skill/reviewer/SKILL.md"),
+            "---\nname: Reviewer\ndescription: Reviews\n---\nReview",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.join("agent")).unwrap();
+        fs::write(
+            dir.join("agent/helper.md"),
+            "---\ndescription: Helps\ntools: Read, Grep\ncolor: blue\n---\nHelp",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("opencode.jsonc"),
+            r#"{"mcp":{"fs":{"type":"local","command":["nonexistent-binary-xyz-12345"],
+                "environment":{"TOKEN":"{env:BRIDLE_DOCTOR_UNSET_VAR}"}}}}"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join(".bridle-manifest.json"),
+            r#"{"entries":[{"component_type":"skill","name":"gone",
+                "source":{"owner":"o","repo":"r","git_ref":null},"installed_at":"2026-01-01T00:00:00Z"}]}"#,
+        )
+        .unwrap();
+        dir
+    }
+
+    fn codes(report: &DoctorReport) -> Vec<&'static str> {
+        report
+            .findings
+            .iter()
+            .filter_map(|f| f.issue.code)
+            .collect()
+    }
+
+    #[test]
+    fn doctor_reports_profile_issues() {
+        let temp = TempDir::new().unwrap();
+        opencode_profile(&temp);
+
+        let report = check_profile(HarnessKind::OpenCode, "doctor", temp.path(), false).unwrap();
+        let codes = codes(&report);
+        assert!(codes.contains(&CODE_COMMAND_NOT_FOUND), "{codes:?}");
+        assert!(codes.contains(&CODE_ENV_MISSING), "{codes:?}");
+        assert!(codes.contains(&CODE_SKILL_NAME_DIRECTORY_MISMATCH));
+        assert!(codes.contains(&CODE_AGENT_TOOLS_FORMAT));
+        assert!(codes.contains(&CODE_AGENT_COLOR_FORMAT));
+        assert!(codes.contains(&CODE_MANIFEST_ORPHANED));
+        assert!(report.findings.iter().all(|f| !f.fixed));
+        assert!(report.count(Severity::Error) > 0);
+    }
+
+    #[test]
+    fn doctor_fix_repairs_mechanical_issues() {
+        let temp = TempDir::new().unwrap();
+        let dir = opencode_profile(&temp);
+
+        let report = check_profile(HarnessKind::OpenCode, "doctor", temp.path(), true).unwrap();
+        assert!(
+            report
+                .findings
+                .iter()
+                .filter(|f| f.fixable)
+                .all(|f| f.fixed)
+        );
+        assert!(
+            report
+                .findings
+                .iter()
+                .any(|f| f.issue.code == Some(CODE_COMMAND_NOT_FOUND) && !f.fixed)
+        );
+
+        let rerun = check_profile(HarnessKind::OpenCode, "doctor", temp.path(), false).unwrap();
+        assert!(
+            rerun.findings.iter().all(|f| !f.fixable),
+            "{:?}",
+            codes(&rerun)
+        );
+        let skill = fs::read_to_string(dir.join("skill/reviewer/SKILL.md")).unwrap();
+        assert!(skill.contains("name: reviewer"));
+        assert!(
+            InstallManifest::load(&manifest_path(&dir))
+                .unwrap()
+                .entries
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,40 @@
+//! Shared fixtures for install tests.
+
+use std::collections::HashMap;
+
+use super::types::{DiscoveryResult, SkillInfo, SourceInfo};
+
+/// A bare `owner/repo` source with no ref, URL or commit.
+pub(crate) fn source() -> SourceInfo {
+    SourceInfo {
+        owner: "owner".to_string(),
+        repo: "repo".to_string(),
+        git_ref: None,
+        url: None,
+        commit: None,
+        plugin: None,
+    }
+}
+
+/// A skill at `skills/<name>/SKILL.md` whose content is `body` under minimal frontmatter.
+pub(crate) fn skill(name: &str, body: &str) -> SkillInfo {
+    SkillInfo {
+        name: name.to_string(),
+        description: None,
+        path: format!("skills/{}/SKILL.md", name),
+        content: format!("---\nname: {}\n---\n{}", name, body),
+    }
+}
+
+/// A discovery of `skills` from [`source`], with no other components.
+pub(crate) fn discovery(skills: Vec<SkillInfo>) -> DiscoveryResult {
+    DiscoveryResult {
+        skills,
+        mcp_servers: HashMap::new(),
+        agents: Vec::new(),
+        commands: Vec::new(),
+        hooks: Vec::new(),
+        plugins: Vec::new(),
+        source: source(),
+    }
+}
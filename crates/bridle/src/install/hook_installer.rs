@@ -0,0 +1,489 @@
+//! Plugin hook installation executor.
+//!
+//! Claude Code plugins ship a `hooks.json`; Claude Code and Droid read the
+//! same event → matcher groups from the `hooks` section of their
+//! `settings.json`. Groups are merged beside the ones already configured and
+//! the exact fragment written is recorded in the manifest, so uninstall takes
+//! back only what bridle added.
+
+use crate::plan::fs;
+use std::path::{Path, PathBuf};
+
+use harness_locate::{Harness, HarnessKind};
+use serde_json::{Map, Value, json};
+use skills_locate::{HookAction, HooksConfig};
+
+use super::installer::{InstallError, InstallOutcome, InstallResult, validate_component_name};
+use super::manifest::{InstallManifest, ManifestEntry, manifest_path};
+use super::types::{
+    ComponentType, HookInfo, InstallOptions, InstallSkip, InstallSuccess, InstallTarget,
+    SkipReason, SourceInfo, parse_harness_kind,
+};
+use crate::config::BridleConfig;
+use crate::config::jsonc::strip_jsonc_comments;
+use crate::harness::HarnessConfig;
+
+/// Settings file holding the `hooks` section, relative to the config dir.
+pub const SETTINGS_FILE: &str = "settings.json";
+
+/// Whether bridle can install hooks for a harness.
+pub fn supports_hooks(kind: HarnessKind) -> bool {
+    matches!(kind, HarnessKind::ClaudeCode | HarnessKind::Droid)
+}
+
+/// Live settings file of a harness that supports hooks.
+pub fn live_settings_path(kind: HarnessKind) -> Option<PathBuf> {
+    if !supports_hooks(kind) {
+        return None;
+    }
+    let harness = Harness::locate(kind).ok()?;
+    harness.config_dir().ok().map(|dir| dir.join(SETTINGS_FILE))
+}
+
+/// Native `hooks` section for a harness, with events in a stable order.
+///
+/// Droid uses Claude Code's schema but names some tools and environment
+/// variables differently, so matchers and commands are translated.
+pub fn native_hooks(hooks: &HooksConfig, kind: HarnessKind) -> Value {
+    let mut events: Vec<(String, Value)> = hooks
+        .iter()
+        .map(|(event, groups)| {
+            let name = serde_json::to_value(event)
+                .ok()
+                .and_then(|v| v.as_str().map(String::from))
+                .unwrap_or_default();
+            let groups = groups
+                .iter()
+                .map(|group| {
+                    let actions: Vec<Value> = group
+                        .hooks
+                        .iter()
+                        .filter_map(|action| native_action(action, kind))
+                        .collect();
+                    let mut native = Map::new();
+                    if let Some(matcher) = &group.matcher {
+                        native.insert("matcher".into(), json!(translate_matcher(matcher, kind)));
+                    }
+                    native.insert("hooks".into(), Value::Array(actions));
+                    Value::Object(native)
+                })
+                .collect();
+            (name, Value::Array(groups))
+        })
+        .collect();
+    events.sort_by(|a, b| a.0.cmp(&b.0));
+    Value::Object(events.into_iter().collect())
+}
+
+fn native_action(action: &HookAction, kind: HarnessKind) -> Option<Value> {
+    match action {
+        HookAction::Simple(command) => {
+            Some(json!({"type": "command", "command": translate_command(command, kind)}))
+        }
+        HookAction::Extended {
+            command, timeout, ..
+        } => {
+            let mut native =
+                json!({"type": "command", "command": translate_command(command, kind)});
+            if let Some(timeout) = timeout {
+                native["timeout"] = json!(timeout);
+            }
+            Some(native)
+        }
+        _ => None,
+    }
+}
+
+fn translate_matcher(matcher: &str, kind: HarnessKind) -> String {
+    if kind != HarnessKind::Droid {
+        return matcher.to_string();
+    }
+    matcher
+        .split('|')
+        .map(|tool| match tool.trim() {
+            "Bash" => "Execute",
+            "Write" => "Create",
+            "WebFetch" => "FetchUrl",
+            other => other,
+        })
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+fn translate_command(command: &str, kind: HarnessKind) -> String {
+    if kind == HarnessKind::Droid {
+        command.replace("CLAUDE_PROJECT_DIR", "FACTORY_PROJECT_DIR")
+    } else {
+        command.to_string()
+    }
+}
+
+/// Manifest fragment recorded for a hook installed into a harness.
+pub fn hook_fragment(hook: &HookInfo, kind: HarnessKind) -> String {
+    serde_json::to_string_pretty(&native_hooks(&hook.hooks, kind)).unwrap_or_default()
+}
+
+/// One line per command a hook runs, for review before installing.
+pub fn hook_commands(hook: &HookInfo) -> Vec<String> {
+    settings_hook_commands(&native_hooks(&hook.hooks, HarnessKind::ClaudeCode))
+}
+
+/// One line per command in a native `hooks` section.
+pub fn settings_hook_commands(hooks: &Value) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (event, groups) in hooks.as_object().into_iter().flatten() {
+        for group in groups.as_array().into_iter().flatten() {
+            let scope = match group.get("matcher").and_then(Value::as_str) {
+                Some(matcher) => format!("{} [{}]", event, matcher),
+                None => event.clone(),
+            };
+            for action in group["hooks"].as_array().into_iter().flatten() {
+                if let Some(command) = action.get("command").and_then(Value::as_str) {
+                    lines.push(format!("{}: {}", scope, command));
+                }
+            }
+        }
+    }
+    lines
+}
+
+/// Append the fragment's groups to `settings.hooks`, skipping groups that are
+/// already present. Returns whether anything was added.
+pub fn merge_hooks(settings: &mut Value, fragment: &Value) -> bool {
+    if !settings.is_object() {
+        *settings = json!({});
+    }
+    if !settings["hooks"].is_object() {
+        settings["hooks"] = json!({});
+    }
+    let Some(hooks) = settings["hooks"].as_object_mut() else {
+        return false;
+    };
+
+    let mut changed = false;
+    for (event, groups) in fragment.as_object().into_iter().flatten() {
+        let existing = hooks.entry(event.clone()).or_insert_with(|| json!([]));
+        if !existing.is_array() {
+            *existing = json!([]);
+        }
+        let Some(existing) = existing.as_array_mut() else {
+            continue;
+        };
+        for group in groups.as_array().into_iter().flatten() {
+            if !existing.contains(group) {
+                existing.push(group.clone());
+                changed = true;
+            }
+        }
+    }
+    changed
+}
+
+/// Remove the fragment's groups from `settings.hooks`, dropping events (and
+/// the section) left empty. Returns whether anything was removed.
+pub fn remove_hooks(settings: &mut Value, fragment: &Value) -> bool {
+    let Some(hooks) = settings.get_mut("hooks").and_then(Value::as_object_mut) else {
+        return false;
+    };
+
+    let mut changed = false;
+    for (event, groups) in fragment.as_object().into_iter().flatten() {
+        let Some(existing) = hooks.get_mut(event).and_then(Value::as_array_mut) else {
+            continue;
+        };
+        for group in groups.as_array().into_iter().flatten() {
+            if let Some(index) = existing.iter().position(|g| g == group) {
+                existing.remove(index);
+                changed = true;
+            }
+        }
+        if existing.is_empty() {
+            hooks.remove(event);
+        }
+    }
+    if hooks.is_empty()
+        && let Some(settings) = settings.as_object_mut()
+    {
+        settings.remove("hooks");
+    }
+    changed
+}
+
+/// Whether every group of the fragment is present in `settings.hooks`.
+pub fn contains_hooks(settings: &Value, fragment: &Value) -> bool {
+    fragment
+        .as_object()
+        .into_iter()
+        .flatten()
+        .all(|(event, groups)| {
+            let existing = settings
+                .get("hooks")
+                .and_then(|h| h.get(event))
+                .and_then(Value::as_array);
+            groups
+                .as_array()
+                .into_iter()
+                .flatten()
+                .all(|group| existing.is_some_and(|e| e.contains(group)))
+        })
+}
+
+pub fn read_settings(path: &Path) -> std::io::Result<Value> {
+    match fs::read_to_string(path) {
+        Ok(content) if !content.trim().is_empty() => {
+            serde_json::from_str(&strip_jsonc_comments(&content))
+                .map_err(|e| std::io::Error::other(format!("{}: {}", path.display(), e)))
+        }
+        Ok(_) => Ok(json!({})),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(json!({})),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn write_settings(path: &Path, settings: &Value) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = serde_json::to_string_pretty(settings).map_err(std::io::Error::other)?;
+    fs::write(path, content)
+}
+
+/// Remove a recorded fragment from a settings file, if it exists.
+pub fn remove_hook_fragment(path: &Path, fragment: &str) -> std::io::Result<bool> {
+    if !fs::exists(path) {
+        return Ok(false);
+    }
+    let fragment: Value = serde_json::from_str(fragment).map_err(std::io::Error::other)?;
+    let mut settings = read_settings(path)?;
+    let removed = remove_hooks(&mut settings, &fragment);
+    if removed {
+        write_settings(path, &settings)?;
+    }
+    Ok(removed)
+}
+
+fn merge_into(path: &Path, fragment: &Value) -> std::io::Result<()> {
+    let mut settings = read_settings(path)?;
+    if merge_hooks(&mut settings, fragment) {
+        write_settings(path, &settings)?;
+    }
+    Ok(())
+}
+
+pub fn install_hook_with_source(
+    hook: &HookInfo,
+    target: &InstallTarget,
+    options: &InstallOptions,
+    source: Option<&SourceInfo>,
+) -> InstallResult {
+    let profiles_dir = BridleConfig::profiles_dir().map_err(|_| InstallError::ProfileNotFound {
+        harness: target.harness.clone(),
+        profile: target.profile.as_str().to_string(),
+    })?;
+    install_hook_to_dir_with_source(hook, target, options, &profiles_dir, source)
+}
+
+pub fn install_hook_to_dir_with_source(
+    hook: &HookInfo,
+    target: &InstallTarget,
+    options: &InstallOptions,
+    profiles_dir: &Path,
+    source: Option<&SourceInfo>,
+) -> InstallResult {
+    validate_component_name(&hook.name)?;
+
+    let kind = parse_harness_kind(&target.harness)
+        .ok_or_else(|| InstallError::HarnessNotFound(target.harness.clone()))?;
+    if !supports_hooks(kind) {
+        return Err(InstallError::Unsupported {
+            harness: target.harness.clone(),
+            component: "hooks".to_string(),
+        });
+    }
+
+    let profile_dir = profiles_dir
+        .join(&target.harness)
+        .join(target.profile.as_str());
+
+    if !fs::exists(&profile_dir) {
+        return Err(InstallError::ProfileNotFound {
+            harness: target.harness.clone(),
+            profile: target.profile.as_str().to_string(),
+        });
+    }
+
+    let manifest_file = manifest_path(&profile_dir);
+    let mut manifest = InstallManifest::load(&manifest_file).unwrap_or_default();
+    let previous = manifest
+        .find_component(ComponentType::Hook, &hook.name)
+        .and_then(|e| e.fragment.clone());
+
+    if previous.is_some() && !options.force {
+        return Ok(InstallOutcome::Skipped(InstallSkip {
+            skill: hook.name.clone(),
+            target: target.clone(),
+            reason: SkipReason::AlreadyExists,
+        }));
+    }
+
+    let is_active = is_active_profile(target);
+    let live_path = if is_active {
+        live_settings_path(kind)
+    } else {
+        None
+    };
+
+    let settings_path = profile_dir.join(SETTINGS_FILE);
+    if let Some(previous) = &previous {
+        remove_hook_fragment(&settings_path, previous).map_err(InstallError::WriteFile)?;
+        if let Some(live) = &live_path {
+            remove_hook_fragment(live, previous).map_err(InstallError::WriteFile)?;
+        }
+    }
+
+    let fragment = native_hooks(&hook.hooks, kind);
+    merge_into(&settings_path, &fragment).map_err(InstallError::WriteFile)?;
+
+    if let Some(source_info) = source {
+        manifest.add_entry(ManifestEntry {
+            component_type: ComponentType::Hook,
+            name: hook.name.clone(),
+            source: source_info.clone(),
+            installed_at: chrono::Utc::now().to_rfc3339(),
+            fragment: Some(hook_fragment(hook, kind)),
+        });
+        let _ = manifest.save(&manifest_file);
+    }
+
+    if let Some(live) = &live_path {
+        merge_into(live, &fragment).map_err(InstallError::WriteFile)?;
+    }
+
+    Ok(InstallOutcome::Installed(InstallSuccess {
+        skill: hook.name.clone(),
+        target: target.clone(),
+        profile_path: settings_path,
+        harness_path: live_path,
+    }))
+}
+
+fn is_active_profile(target: &InstallTarget) -> bool {
+    BridleConfig::load()
+        .ok()
+        .as_ref()
+        .and_then(|c| c.active_profile_for(&target.harness))
+        .is_some_and(|active| active == target.profile.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProfileName;
+    use crate::install::fixtures::source;
+    use skills_locate::parse_hooks_json;
+    use tempfile::TempDir;
+
+    fn hook(name: &str, json: &str) -> HookInfo {
+        HookInfo {
+            name: name.to_string(),
+            path: "hooks/hooks.json".to_string(),
+            hooks: parse_hooks_json(json).unwrap(),
+        }
+    }
+
+    const GUARD: &str = r#"{"hooks": {"PreToolUse": [
+        {"matcher": "Bash|Write", "hooks": [{"type": "command", "command": "$CLAUDE_PROJECT_DIR/guard.sh", "timeout": 5}]}
+    ]}}"#;
+
+    #[test]
+    fn install_merges_without_clobbering_and_uninstall_restores() {
+        let temp = TempDir::new().unwrap();
+        let profiles_dir = temp.path().join("profiles");
+        let profile_dir = profiles_dir.join("claude-code").join("hook-test");
+        fs::create_dir_all(&profile_dir).unwrap();
+        let existing = json!({
+            "model": "opus",
+            "hooks": {"PreToolUse": [{"matcher": "Edit", "hooks": [{"type": "command", "command": "lint"}]}]}
+        });
+        write_settings(&profile_dir.join(SETTINGS_FILE), &existing).unwrap();
+
+        let target = InstallTarget {
+            harness: "claude-code".to_string(),
+            profile: ProfileName::new("hook-test").unwrap(),
+        };
+        let guard = hook("guard", GUARD);
+        let options = InstallOptions::default();
+
+        let outcome = install_hook_to_dir_with_source(
+            &guard,
+            &target,
+            &options,
+            &profiles_dir,
+            Some(&source()),
+        )
+        .unwrap();
+        assert!(matches!(outcome, InstallOutcome::Installed(_)));
+
+        let settings = read_settings(&profile_dir.join(SETTINGS_FILE)).unwrap();
+        let groups = settings["hooks"]["PreToolUse"].as_array().unwrap();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[1]["matcher"], "Bash|Write");
+        assert_eq!(groups[1]["hooks"][0]["timeout"], 5);
+        assert_eq!(settings["model"], "opus");
+
+        let again = install_hook_to_dir_with_source(
+            &guard,
+            &target,
+            &options,
+            &profiles_dir,
+            Some(&source()),
+        )
+        .unwrap();
+        assert!(matches!(again, InstallOutcome::Skipped(_)));
+
+        let manifest = InstallManifest::load(&manifest_path(&profile_dir)).unwrap();
+        let fragment = manifest
+            .find_component(ComponentType::Hook, "guard")
+            .and_then(|e| e.fragment.clone())
+            .unwrap();
+        assert!(remove_hook_fragment(&profile_dir.join(SETTINGS_FILE), &fragment).unwrap());
+        assert_eq!(
+            read_settings(&profile_dir.join(SETTINGS_FILE)).unwrap(),
+            existing
+        );
+    }
+
+    #[test]
+    fn droid_hooks_translate_tools_and_env() {
+        let guard = hook("guard", GUARD);
+        let native = native_hooks(&guard.hooks, HarnessKind::Droid);
+        let group = &native["PreToolUse"][0];
+        assert_eq!(group["matcher"], "Execute|Create");
+        assert_eq!(
+            group["hooks"][0]["command"],
+            "$FACTORY_PROJECT_DIR/guard.sh"
+        );
+
+        assert_eq!(
+            hook_commands(&guard),
+            vec!["PreToolUse [Bash|Write]: $CLAUDE_PROJECT_DIR/guard.sh"]
+        );
+    }
+
+    #[test]
+    fn opencode_is_reported_unsupported() {
+        let temp = TempDir::new().unwrap();
+        let target = InstallTarget {
+            harness: "opencode".to_string(),
+            profile: ProfileName::new("hook-test").unwrap(),
+        };
+        let result = install_hook_to_dir_with_source(
+            &hook("guard", GUARD),
+            &target,
+            &InstallOptions::default(),
+            temp.path(),
+            None,
+        );
+        assert!(matches!(result, Err(InstallError::Unsupported { .. })));
+    }
+}
@@ -1,6 +1,6 @@
 //! Skill installation executor.
 
-use std::fs;
+use crate::plan::fs;
 use std::path::{Path, PathBuf};
 
 use thiserror::Error;
@@ -32,9 +32,12 @@ pub enum InstallError {
 
     #[error("Invalid component name: {0}")]
     InvalidComponentName(String),
+
+    #[error("{component} are not supported by {harness}")]
+    Unsupported { harness: String, component: String },
 }
 
-fn validate_component_name(name: &str) -> Result<(), InstallError> {
+pub(super) fn validate_component_name(name: &str) -> Result<(), InstallError> {
     if name.is_empty()
         || name.contains('/')
         || name.contains('\\')
@@ -137,7 +140,7 @@ fn color_name_to_hex(name: &str) -> Option<&'static str> {
     }
 }
 
-fn transform_agent_for_opencode(content: &str) -> String {
+pub fn transform_agent_for_opencode(content: &str) -> String {
     use std::borrow::Cow;
 
     let parts: Vec<&str> = content.splitn(3, "---").collect();
@@ -200,6 +203,35 @@ pub fn install_skill(
     install_skill_to_dir(skill, target, options, &profiles_dir)
 }
 
+/// Name and content a skill is stored under in a profile for the given harness.
+///
+/// For OpenCode, the skill name and content are sanitized before writing to the
+/// profile. This ensures consistency between profile and harness (both use
+/// sanitized names).
+pub fn skill_for_profile(kind: Option<HarnessKind>, skill: &SkillInfo) -> (String, String) {
+    if matches!(kind, Some(HarnessKind::OpenCode)) {
+        let sanitized = sanitize_name_for_opencode(&skill.name);
+        let transformed = transform_skill_for_opencode(&skill.content, &sanitized);
+        (sanitized, transformed)
+    } else {
+        (skill.name.clone(), skill.content.clone())
+    }
+}
+
+pub fn install_skill_with_source(
+    skill: &SkillInfo,
+    target: &InstallTarget,
+    options: &InstallOptions,
+    source: Option<&SourceInfo>,
+) -> InstallResult {
+    let profiles_dir = BridleConfig::profiles_dir().map_err(|_| InstallError::ProfileNotFound {
+        harness: target.harness.clone(),
+        profile: target.profile.as_str().to_string(),
+    })?;
+
+    install_skill_to_dir_with_source(skill, target, options, &profiles_dir, source)
+}
+
 fn install_skill_to_dir(
     skill: &SkillInfo,
     target: &InstallTarget,
@@ -209,7 +241,7 @@ fn install_skill_to_dir(
     install_skill_to_dir_with_source(skill, target, options, profiles_dir, None)
 }
 
-fn install_skill_to_dir_with_source(
+pub fn install_skill_to_dir_with_source(
     skill: &SkillInfo,
     target: &InstallTarget,
     options: &InstallOptions,
@@ -222,28 +254,19 @@ fn install_skill_to_dir_with_source(
         .join(&target.harness)
         .join(target.profile.as_str());
 
-    if !profile_dir.exists() {
+    if !fs::exists(&profile_dir) {
         return Err(InstallError::ProfileNotFound {
             harness: target.harness.clone(),
             profile: target.profile.as_str().to_string(),
         });
     }
 
-    // For OpenCode, sanitize skill name and content before writing to profile
-    // This ensures consistency between profile and harness (both use sanitized names)
-    let kind = parse_harness_kind(&target.harness);
-    let (skill_name, skill_content) = if matches!(kind, Some(HarnessKind::OpenCode)) {
-        let sanitized = sanitize_name_for_opencode(&skill.name);
-        let transformed = transform_skill_for_opencode(&skill.content, &sanitized);
-        (sanitized, transformed)
-    } else {
-        (skill.name.clone(), skill.content.clone())
-    };
+    let (skill_name, skill_content) = skill_for_profile(parse_harness_kind(&target.harness), skill);
 
     let skill_dir = profile_dir.join("skills").join(&skill_name);
     let skill_path = skill_dir.join("SKILL.md");
 
-    if skill_path.exists() && !options.force {
+    if fs::exists(&skill_path) && !options.force {
         return Ok(InstallOutcome::Skipped(InstallSkip {
             skill: skill_name.clone(),
             target: target.clone(),
@@ -408,6 +431,7 @@ fn update_manifest(
         name: name.to_string(),
         source: source.clone(),
         installed_at: chrono::Utc::now().to_rfc3339(),
+        fragment: None,
     });
 
     let _ = manifest.save(&manifest_file);
@@ -441,7 +465,7 @@ pub fn install_agent_to_dir(
     install_agent_to_dir_with_source(agent, target, options, profiles_dir, None)
 }
 
-fn install_agent_with_source(
+pub fn install_agent_with_source(
     agent: &AgentInfo,
     target: &InstallTarget,
     options: &InstallOptions,
@@ -454,7 +478,7 @@ fn install_agent_with_source(
     install_agent_to_dir_with_source(agent, target, options, &profiles_dir, source)
 }
 
-fn install_agent_to_dir_with_source(
+pub fn install_agent_to_dir_with_source(
     agent: &AgentInfo,
     target: &InstallTarget,
     options: &InstallOptions,
@@ -467,7 +491,7 @@ fn install_agent_to_dir_with_source(
         .join(&target.harness)
         .join(target.profile.as_str());
 
-    if !profile_dir.exists() {
+    if !fs::exists(&profile_dir) {
         return Err(InstallError::ProfileNotFound {
             harness: target.harness.clone(),
             profile: target.profile.as_str().to_string(),
@@ -477,7 +501,7 @@ fn install_agent_to_dir_with_source(
     let agents_dir = profile_dir.join(CANONICAL_AGENTS_DIR);
     let agent_path = agents_dir.join(format!("{}.md", &agent.name));
 
-    if agent_path.exists() && !options.force {
+    if fs::exists(&agent_path) && !options.force {
         return Ok(InstallOutcome::Skipped(InstallSkip {
             skill: agent.name.clone(),
             target: target.clone(),
@@ -523,7 +547,7 @@ pub fn install_command_to_dir(
     install_command_to_dir_with_source(command, target, options, profiles_dir, None)
 }
 
-fn install_command_with_source(
+pub fn install_command_with_source(
     command: &CommandInfo,
     target: &InstallTarget,
     options: &InstallOptions,
@@ -536,7 +560,7 @@ fn install_command_with_source(
     install_command_to_dir_with_source(command, target, options, &profiles_dir, source)
 }
 
-fn install_command_to_dir_with_source(
+pub fn install_command_to_dir_with_source(
     command: &CommandInfo,
     target: &InstallTarget,
     options: &InstallOptions,
@@ -549,7 +573,7 @@ fn install_command_to_dir_with_source(
         .join(&target.harness)
         .join(target.profile.as_str());
 
-    if !profile_dir.exists() {
+    if !fs::exists(&profile_dir) {
         return Err(InstallError::ProfileNotFound {
             harness: target.harness.clone(),
             profile: target.profile.as_str().to_string(),
@@ -559,7 +583,7 @@ fn install_command_to_dir_with_source(
     let commands_dir = profile_dir.join(CANONICAL_COMMANDS_DIR);
     let command_path = commands_dir.join(format!("{}.md", &command.name));
 
-    if command_path.exists() && !options.force {
+    if fs::exists(&command_path) && !options.force {
         return Ok(InstallOutcome::Skipped(InstallSkip {
             skill: command.name.clone(),
             target: target.clone(),
@@ -593,13 +617,22 @@ pub fn install_skills(
     skills: &[SkillInfo],
     target: &InstallTarget,
     options: &InstallOptions,
+) -> InstallReport {
+    install_skills_with_source(skills, target, options, None)
+}
+
+pub fn install_skills_with_source(
+    skills: &[SkillInfo],
+    target: &InstallTarget,
+    options: &InstallOptions,
+    source: Option<&SourceInfo>,
 ) -> InstallReport {
     let mut installed = Vec::new();
     let mut skipped = Vec::new();
     let mut errors = Vec::new();
 
     for skill in skills {
-        match install_skill(skill, target, options) {
+        match install_skill_with_source(skill, target, options, source) {
             Ok(InstallOutcome::Installed(success)) => installed.push(success),
             Ok(InstallOutcome::Skipped(skip)) => skipped.push(skip),
             Err(e) => errors.push(InstallFailure {
@@ -614,6 +647,8 @@ pub fn install_skills(
         installed,
         skipped,
         errors,
+        dry_run: false,
+        plan: None,
     }
 }
 
@@ -0,0 +1,497 @@
+//! `bridle.lock`: pinned sources and content digests for reproducible installs.
+
+use crate::plan::fs;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use harness_locate::HarnessKind;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::discovery::source_info;
+use super::hook_installer::SETTINGS_FILE;
+use super::manifest::{
+    InstallManifest, ManifestEntry, ManifestError, content_digest, manifest_path,
+};
+use super::mcp_installer::get_profile_config_path;
+use super::types::{
+    ComponentType, InstallFailure, InstallOptions, InstallReport, InstallTarget, SourceInfo,
+    parse_harness_kind,
+};
+use super::updater::{PendingInstall, SourceCache, find_upstream, installed_content};
+use crate::config::{BridleConfig, ProfileName};
+
+pub const LOCKFILE_NAME: &str = "bridle.lock";
+
+const LOCKFILE_VERSION: u32 = 1;
+
+const LOCKFILE_HEADER: &str = "# This file is generated by bridle. Do not edit it by hand.\n\n";
+
+#[derive(Debug, Error)]
+pub enum LockError {
+    #[error("Failed to read lockfile: {0}")]
+    Read(#[source] std::io::Error),
+
+    #[error("Failed to write lockfile: {0}")]
+    Write(#[source] std::io::Error),
+
+    #[error("Failed to parse lockfile: {0}")]
+    Parse(#[source] toml::de::Error),
+
+    #[error("Failed to serialize lockfile: {0}")]
+    Serialize(#[source] toml::ser::Error),
+
+    #[error("Unsupported lockfile version {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("Failed to fetch {location}: {error}")]
+    Fetch { location: String, error: String },
+
+    #[error("{component} not found in {location}")]
+    ComponentNotFound { component: String, location: String },
+
+    #[error("Digest mismatch for {component} ({file}): locked {expected}, fetched {actual}")]
+    DigestMismatch {
+        component: String,
+        file: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("Harness not found: {0}")]
+    HarnessNotFound(String),
+
+    #[error("Invalid profile name in lockfile: {0}")]
+    InvalidProfile(String),
+
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Lockfile {
+    pub version: u32,
+    #[serde(default, rename = "component")]
+    pub components: Vec<LockedComponent>,
+}
+
+impl Default for Lockfile {
+    fn default() -> Self {
+        Self {
+            version: LOCKFILE_VERSION,
+            components: Vec::new(),
+        }
+    }
+}
+
+/// A component installed into one profile, pinned to an exact source revision.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedComponent {
+    pub harness: String,
+    pub profile: String,
+    #[serde(rename = "type")]
+    pub component_type: ComponentType,
+    pub name: String,
+    /// Source as requested at install time (may name a branch)
+    pub source: String,
+    /// Source pinned to the resolved commit, used to reproduce the install
+    pub resolved: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Profile-relative file path -> content digest
+    pub files: BTreeMap<String, String>,
+}
+
+impl LockedComponent {
+    fn label(&self) -> String {
+        format!(
+            "{} {} in {}/{}",
+            format!("{:?}", self.component_type).to_lowercase(),
+            self.name,
+            self.harness,
+            self.profile
+        )
+    }
+}
+
+impl Lockfile {
+    pub fn load(path: &Path) -> Result<Self, LockError> {
+        if !fs::exists(path) {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path).map_err(LockError::Read)?;
+        let lockfile: Self = toml::from_str(&content).map_err(LockError::Parse)?;
+        if lockfile.version != LOCKFILE_VERSION {
+            return Err(LockError::UnsupportedVersion(lockfile.version));
+        }
+        Ok(lockfile)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), LockError> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(LockError::Write)?;
+        }
+
+        let body = toml::to_string_pretty(self).map_err(LockError::Serialize)?;
+        fs::write(path, format!("{}{}", LOCKFILE_HEADER, body)).map_err(LockError::Write)
+    }
+
+    /// Lockfile location: `./bridle.lock` if one exists in the working
+    /// directory, otherwise `bridle.lock` in the bridle config directory.
+    pub fn default_path() -> crate::error::Result<PathBuf> {
+        let local = PathBuf::from(LOCKFILE_NAME);
+        if fs::exists(&local) {
+            return Ok(local);
+        }
+        Ok(BridleConfig::config_dir()?.join(LOCKFILE_NAME))
+    }
+
+    /// Replace the entries for one profile with its current manifest contents.
+    pub fn record_profile(
+        &mut self,
+        target: &InstallTarget,
+        profile_dir: &Path,
+    ) -> Result<(), LockError> {
+        let kind = parse_harness_kind(&target.harness)
+            .ok_or_else(|| LockError::HarnessNotFound(target.harness.clone()))?;
+        let manifest = InstallManifest::load(&manifest_path(profile_dir))?;
+
+        self.components
+            .retain(|c| !(c.harness == target.harness && c.profile == target.profile.as_str()));
+
+        for entry in &manifest.entries {
+            let Some(content) = installed_content(profile_dir, entry) else {
+                continue;
+            };
+            let mut files = BTreeMap::new();
+            files.insert(
+                file_key(kind, entry.component_type, &entry.name),
+                content_digest(content.as_bytes()),
+            );
+            self.components.push(LockedComponent {
+                harness: target.harness.clone(),
+                profile: target.profile.as_str().to_string(),
+                component_type: entry.component_type,
+                name: entry.name.clone(),
+                source: entry.source.location(),
+                resolved: entry.source.pinned_location(),
+                commit: entry.source.commit.clone(),
+                files,
+            });
+        }
+
+        self.components.sort_by(|a, b| {
+            (&a.harness, &a.profile, a.component_type as u8, &a.name).cmp(&(
+                &b.harness,
+                &b.profile,
+                b.component_type as u8,
+                &b.name,
+            ))
+        });
+        Ok(())
+    }
+}
+
+/// Profile-relative path of the file a component is stored in.
+///
+/// MCP servers and hooks share a harness config file, so the component name
+/// is appended as a fragment.
+fn file_key(kind: HarnessKind, component_type: ComponentType, name: &str) -> String {
+    match component_type {
+        ComponentType::Skill => format!("{}/{}/SKILL.md", component_type.dir_name(), name),
+        ComponentType::Agent | ComponentType::Command => {
+            format!("{}/{}.md", component_type.dir_name(), name)
+        }
+        ComponentType::Mcp => {
+            let config = get_profile_config_path(Path::new(""), kind);
+            format!("{}#{}", config.display(), name)
+        }
+        ComponentType::Hook => format!("{}#hooks/{}", SETTINGS_FILE, name),
+    }
+}
+
+/// A locked component fetched and verified against its recorded digest.
+#[derive(Debug)]
+pub struct VerifiedComponent {
+    pub locked: LockedComponent,
+    target: InstallTarget,
+    pending: PendingInstall,
+    source: SourceInfo,
+}
+
+/// Fetch every selected component at its pinned revision and verify its
+/// digest. Nothing is written; any mismatch fails the whole sync.
+pub fn verify_locked(
+    lockfile: &Lockfile,
+    filter: impl Fn(&LockedComponent) -> bool,
+    cache: &mut SourceCache,
+) -> Result<Vec<VerifiedComponent>, LockError> {
+    let mut verified = Vec::new();
+
+    for locked in lockfile.components.iter().filter(|c| filter(c)) {
+        let kind = parse_harness_kind(&locked.harness)
+            .ok_or_else(|| LockError::HarnessNotFound(locked.harness.clone()))?;
+        let target = InstallTarget {
+            harness: locked.harness.clone(),
+            profile: ProfileName::new(&locked.profile)
+                .map_err(|_| LockError::InvalidProfile(locked.profile.clone()))?,
+        };
+
+        let discovery = cache
+            .get(&locked.resolved)
+            .map_err(|error| LockError::Fetch {
+                location: locked.resolved.clone(),
+                error,
+            })?;
+
+        let (pending, content) =
+            find_upstream(discovery, locked.component_type, &locked.name, kind).ok_or_else(
+                || LockError::ComponentNotFound {
+                    component: locked.label(),
+                    location: locked.resolved.clone(),
+                },
+            )?;
+
+        let file = file_key(kind, locked.component_type, &locked.name);
+        let actual = content_digest(content.as_bytes());
+        let expected = locked.files.get(&file).cloned().unwrap_or_default();
+        if actual != expected {
+            return Err(LockError::DigestMismatch {
+                component: locked.label(),
+                file,
+                expected,
+                actual,
+            });
+        }
+
+        // Record the originally requested source so `bridle update` keeps
+        // tracking the branch rather than the pinned commit.
+        let mut source = skills_locate::Source::parse(&locked.source)
+            .map(|s| source_info(&s))
+            .unwrap_or_else(|_| discovery.source.clone());
+        source.commit = locked.commit.clone();
+
+        verified.push(VerifiedComponent {
+            locked: locked.clone(),
+            target,
+            pending,
+            source,
+        });
+    }
+
+    Ok(verified)
+}
+
+/// Install verified components into their profiles, creating missing ones.
+pub fn install_verified(components: &[VerifiedComponent], profiles_dir: &Path) -> InstallReport {
+    let options = InstallOptions { force: true };
+    let mut report = InstallReport::default();
+
+    for component in components {
+        let locked = &component.locked;
+        let target = component.target.clone();
+
+        let profile_dir = profiles_dir.join(&locked.harness).join(&locked.profile);
+        if let Err(e) = fs::create_dir_all(&profile_dir) {
+            report.errors.push(InstallFailure {
+                skill: locked.name.clone(),
+                target,
+                error: e.to_string(),
+            });
+            continue;
+        }
+
+        match component
+            .pending
+            .install(&target, &options, profiles_dir, &component.source)
+        {
+            Ok(Some(success)) => report.installed.push(success),
+            Ok(None) => {}
+            Err(e) => report.errors.push(InstallFailure {
+                skill: locked.name.clone(),
+                target,
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::install::fixtures;
+    use crate::install::installer::install_skill_to_dir_with_source;
+    use crate::install::types::{DiscoveryResult, SkillInfo};
+    use tempfile::TempDir;
+
+    const SHA: &str = "0123456789abcdef0123456789abcdef01234567";
+
+    fn source() -> SourceInfo {
+        SourceInfo {
+            git_ref: Some("main".to_string()),
+            url: Some("https://github.com/owner/repo/tree/main".to_string()),
+            commit: Some(SHA.to_string()),
+            ..fixtures::source()
+        }
+    }
+
+    fn skill(body: &str) -> SkillInfo {
+        fixtures::skill("demo", body)
+    }
+
+    fn discovery(body: &str) -> DiscoveryResult {
+        DiscoveryResult {
+            source: source(),
+            ..fixtures::discovery(vec![skill(body)])
+        }
+    }
+
+    fn setup() -> (TempDir, InstallTarget, PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let profiles_dir = temp.path().join("profiles");
+        fs::create_dir_all(profiles_dir.join("claude-code/work")).unwrap();
+        let target = InstallTarget {
+            harness: "claude-code".to_string(),
+            profile: ProfileName::new("work").unwrap(),
+        };
+        install_skill_to_dir_with_source(
+            &skill("v1"),
+            &target,
+            &InstallOptions::default(),
+            &profiles_dir,
+            Some(&source()),
+        )
+        .unwrap();
+        (temp, target, profiles_dir)
+    }
+
+    fn locked_after_install() -> (TempDir, Lockfile, PathBuf) {
+        let (temp, target, profiles_dir) = setup();
+        let mut lockfile = Lockfile::default();
+        lockfile
+            .record_profile(&target, &profiles_dir.join("claude-code/work"))
+            .unwrap();
+        (temp, lockfile, profiles_dir)
+    }
+
+    #[test]
+    fn record_profile_pins_commit_and_digests_files() {
+        let (_temp, lockfile, _) = locked_after_install();
+
+        assert_eq!(lockfile.components.len(), 1);
+        let locked = &lockfile.components[0];
+        assert_eq!(locked.source, "https://github.com/owner/repo/tree/main");
+        assert_eq!(
+            locked.resolved,
+            format!("https://github.com/owner/repo/tree/{}", SHA)
+        );
+        assert_eq!(locked.commit.as_deref(), Some(SHA));
+        assert_eq!(
+            locked.files.get("skills/demo/SKILL.md"),
+            Some(&content_digest(skill("v1").content.as_bytes()))
+        );
+    }
+
+    #[test]
+    fn lockfile_roundtrips_through_toml() {
+        let (temp, lockfile, _) = locked_after_install();
+        let path = temp.path().join(LOCKFILE_NAME);
+        lockfile.save(&path).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.starts_with("# This file is generated by bridle"));
+        assert!(content.contains("[[component]]"));
+
+        let loaded = Lockfile::load(&path).unwrap();
+        assert_eq!(loaded.components, lockfile.components);
+    }
+
+    #[test]
+    fn save_is_planned_and_applied_through_the_journal() {
+        let (temp, lockfile, _) = locked_after_install();
+        let path = temp.path().join(LOCKFILE_NAME);
+        let journal = temp.path().join("journal.json");
+
+        let (result, plan) = crate::plan::record(|| lockfile.save(&path));
+        result.unwrap();
+        assert!(!path.exists());
+        assert_eq!(plan.count(crate::plan::Action::Create), 1);
+
+        plan.apply_with_journal(&journal).unwrap();
+        assert_eq!(
+            Lockfile::load(&path).unwrap().components,
+            lockfile.components
+        );
+        assert!(!journal.exists());
+    }
+
+    #[test]
+    fn load_rejects_unknown_version() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(LOCKFILE_NAME);
+        fs::write(&path, "version = 99\n").unwrap();
+        assert!(matches!(
+            Lockfile::load(&path),
+            Err(LockError::UnsupportedVersion(99))
+        ));
+    }
+
+    #[test]
+    fn sync_reinstalls_matching_digests_into_new_profile() {
+        let (_temp, mut lockfile, profiles_dir) = locked_after_install();
+        lockfile.components[0].profile = "fresh".to_string();
+
+        let mut cache = SourceCache::new();
+        cache.insert(&lockfile.components[0].resolved, Ok(discovery("v1")));
+
+        let verified = verify_locked(&lockfile, |_| true, &mut cache).unwrap();
+        assert_eq!(verified.len(), 1);
+        assert_eq!(verified[0].source.git_ref.as_deref(), Some("main"));
+
+        let report = install_verified(&verified, &profiles_dir);
+        assert!(report.errors.is_empty(), "{:?}", report.errors);
+        assert!(
+            profiles_dir
+                .join("claude-code/fresh/skills/demo/SKILL.md")
+                .exists()
+        );
+    }
+
+    #[test]
+    fn sync_fails_on_digest_mismatch() {
+        let (_temp, lockfile, _) = locked_after_install();
+
+        let mut cache = SourceCache::new();
+        cache.insert(&lockfile.components[0].resolved, Ok(discovery("tampered")));
+
+        let result = verify_locked(&lockfile, |_| true, &mut cache);
+        assert!(matches!(result, Err(LockError::DigestMismatch { .. })));
+    }
+
+    #[test]
+    fn sync_fails_when_component_missing_upstream() {
+        let (_temp, mut lockfile, _) = locked_after_install();
+        lockfile.components[0].name = "gone".to_string();
+
+        let mut cache = SourceCache::new();
+        cache.insert(&lockfile.components[0].resolved, Ok(discovery("v1")));
+
+        let result = verify_locked(&lockfile, |_| true, &mut cache);
+        assert!(matches!(result, Err(LockError::ComponentNotFound { .. })));
+    }
+
+    #[test]
+    fn file_key_for_mcp_names_config_file() {
+        assert_eq!(
+            file_key(HarnessKind::ClaudeCode, ComponentType::Mcp, "fs"),
+            ".mcp.json#fs"
+        );
+        assert_eq!(
+            file_key(HarnessKind::OpenCode, ComponentType::Agent, "reviewer"),
+            "agents/reviewer.md"
+        );
+    }
+}
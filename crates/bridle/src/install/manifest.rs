@@ -1,7 +1,8 @@
-use std::fs;
+use crate::plan::fs;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 
 use super::types::{ComponentType, SourceInfo};
@@ -27,6 +28,9 @@ pub struct ManifestEntry {
     pub name: String,
     pub source: SourceInfo,
     pub installed_at: String,
+    /// Exact native config fragment written for MCP entries (JSON or Goose YAML)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fragment: Option<String>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -36,7 +40,7 @@ pub struct InstallManifest {
 
 impl InstallManifest {
     pub fn load(path: &Path) -> Result<Self, ManifestError> {
-        if !path.exists() {
+        if !fs::exists(path) {
             return Ok(Self::default());
         }
 
@@ -72,17 +76,40 @@ impl InstallManifest {
             .iter()
             .find(|e| e.component_type as u8 == component_type as u8 && e.name == name)
     }
+
+    pub fn components_of(&self, component_type: ComponentType) -> Vec<&ManifestEntry> {
+        self.entries
+            .iter()
+            .filter(|e| e.component_type as u8 == component_type as u8)
+            .collect()
+    }
 }
 
 pub fn manifest_path(profile_dir: &Path) -> PathBuf {
     profile_dir.join(".bridle-manifest.json")
 }
 
+/// SHA-256 digest of component content, formatted as `sha256:<hex>`.
+pub fn content_digest(content: &[u8]) -> String {
+    let hash = Sha256::digest(content);
+    let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256:{}", hex)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_content_digest_is_stable_sha256() {
+        assert_eq!(
+            content_digest(b"hello"),
+            "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_ne!(content_digest(b"a"), content_digest(b"b"));
+    }
+
     #[test]
     fn test_manifest_load_empty() {
         let temp = TempDir::new().unwrap();
@@ -105,7 +132,11 @@ mod tests {
                 owner: "test".to_string(),
                 repo: "repo".to_string(),
                 git_ref: Some("main".to_string()),
+                url: None,
+                commit: None,
+                plugin: None,
             },
+            fragment: None,
             installed_at: "2025-01-02T12:00:00Z".to_string(),
         });
 
@@ -127,7 +158,11 @@ mod tests {
                 owner: "old".to_string(),
                 repo: "repo".to_string(),
                 git_ref: None,
+                url: None,
+                commit: None,
+                plugin: None,
             },
+            fragment: None,
             installed_at: "2025-01-01T00:00:00Z".to_string(),
         });
 
@@ -138,7 +173,11 @@ mod tests {
                 owner: "new".to_string(),
                 repo: "repo".to_string(),
                 git_ref: None,
+                url: None,
+                commit: None,
+                plugin: None,
             },
+            fragment: None,
             installed_at: "2025-01-02T00:00:00Z".to_string(),
         });
 
@@ -157,7 +196,11 @@ mod tests {
                 owner: "test".to_string(),
                 repo: "repo".to_string(),
                 git_ref: None,
+                url: None,
+                commit: None,
+                plugin: None,
             },
+            fragment: None,
             installed_at: "2025-01-02T00:00:00Z".to_string(),
         });
 
@@ -168,7 +211,11 @@ mod tests {
                 owner: "test".to_string(),
                 repo: "repo".to_string(),
                 git_ref: None,
+                url: None,
+                commit: None,
+                plugin: None,
             },
+            fragment: None,
             installed_at: "2025-01-02T00:00:00Z".to_string(),
         });
 
@@ -188,7 +235,11 @@ mod tests {
                 owner: "test".to_string(),
                 repo: "repo".to_string(),
                 git_ref: None,
+                url: None,
+                commit: None,
+                plugin: None,
             },
+            fragment: None,
             installed_at: "2025-01-02T00:00:00Z".to_string(),
         });
 
@@ -199,4 +250,36 @@ mod tests {
         let not_found = manifest.find_component(ComponentType::Agent, "skill1");
         assert!(not_found.is_none());
     }
+
+    #[test]
+    fn test_manifest_mcp_entry_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let path = manifest_path(temp.path());
+
+        let mut manifest = InstallManifest::default();
+        manifest.add_entry(ManifestEntry {
+            component_type: ComponentType::Mcp,
+            name: "filesystem".to_string(),
+            source: SourceInfo {
+                owner: "test".to_string(),
+                repo: "repo".to_string(),
+                git_ref: None,
+                url: None,
+                commit: None,
+                plugin: None,
+            },
+            fragment: Some(r#"{"command": "npx"}"#.to_string()),
+            installed_at: "2025-01-02T00:00:00Z".to_string(),
+        });
+        manifest.save(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains(r#""component_type": "mcp""#));
+
+        let loaded = InstallManifest::load(&path).unwrap();
+        let mcps = loaded.components_of(ComponentType::Mcp);
+        assert_eq!(mcps.len(), 1);
+        assert_eq!(mcps[0].fragment.as_deref(), Some(r#"{"command": "npx"}"#));
+        assert!(loaded.components_of(ComponentType::Skill).is_empty());
+    }
 }
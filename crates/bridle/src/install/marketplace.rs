@@ -0,0 +1,314 @@
+//! Plugin marketplaces.
+//!
+//! Reads `.claude-plugin/marketplace.json` (or plugin directories with a
+//! `.claude-plugin/plugin.json`) to group discovered components by plugin,
+//! and keeps the list of marketplaces subscribed in `config.toml`.
+
+use skills_locate::{Marketplace, PluginSource, Source, SourceTree};
+use thiserror::Error;
+
+use crate::config::{BridleConfig, MarketplaceSubscription};
+
+const MARKETPLACE_FILE: &str = ".claude-plugin/marketplace.json";
+const PLUGIN_FILE: &str = ".claude-plugin/plugin.json";
+
+#[derive(Debug, Error)]
+pub enum MarketplaceError {
+    #[error("Invalid source: {0}")]
+    InvalidSource(String),
+
+    #[error("Failed to fetch marketplace: {0}")]
+    Fetch(#[source] skills_locate::Error),
+
+    #[error("No {MARKETPLACE_FILE} found in {0}")]
+    NotAMarketplace(String),
+
+    #[error("Invalid {MARKETPLACE_FILE}: {0}")]
+    Parse(#[source] serde_json::Error),
+
+    #[error("Marketplace '{0}' is already subscribed")]
+    AlreadySubscribed(String),
+
+    #[error("No subscribed marketplace named '{0}'")]
+    NotFound(String),
+
+    #[error("Invalid marketplace name: {0}")]
+    InvalidName(String),
+
+    #[error("Failed to update config: {0}")]
+    Config(String),
+}
+
+/// Where a plugin's files live.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PluginLocation {
+    /// Directory inside the fetched tree, root-relative without a trailing
+    /// slash (empty for the root).
+    Dir(String),
+    /// Another source, fetched separately.
+    Remote(String),
+}
+
+/// A plugin listed by a marketplace or found by its plugin.json.
+#[derive(Debug, Clone)]
+pub struct PluginEntry {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub location: PluginLocation,
+}
+
+/// Plugins in a tree: the marketplace listing when there is one, otherwise
+/// every directory with a `.claude-plugin/plugin.json`.
+pub fn plugin_entries(tree: &SourceTree) -> Vec<PluginEntry> {
+    if let Ok(Some(marketplace)) = read_marketplace(tree) {
+        return marketplace
+            .plugins
+            .into_iter()
+            .filter_map(|entry| {
+                Some(PluginEntry {
+                    location: plugin_location(&entry.source)?,
+                    name: entry.name,
+                    description: entry.description,
+                })
+            })
+            .collect();
+    }
+
+    let mut entries: Vec<PluginEntry> = tree
+        .list_files(PLUGIN_FILE)
+        .iter()
+        .filter_map(|path| {
+            let relative = tree.relative_path(path);
+            let dir = relative.strip_suffix(PLUGIN_FILE)?;
+            if !(dir.is_empty() || dir.ends_with('/')) {
+                return None;
+            }
+            Some(PluginEntry {
+                name: None,
+                description: None,
+                location: PluginLocation::Dir(dir.trim_end_matches('/').to_string()),
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| a.location.cmp(&b.location));
+    entries
+}
+
+/// The tree's root marketplace.json, if it has one.
+pub fn read_marketplace(tree: &SourceTree) -> Result<Option<Marketplace>, MarketplaceError> {
+    let Ok(content) = tree.read_file(MARKETPLACE_FILE) else {
+        return Ok(None);
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(MarketplaceError::Parse)
+}
+
+fn plugin_location(source: &PluginSource) -> Option<PluginLocation> {
+    let location = match source {
+        PluginSource::Relative(path) => {
+            PluginLocation::Dir(path.trim_start_matches("./").trim_matches('/').to_string())
+        }
+        PluginSource::GitHub { github } if !github.contains("://") => {
+            PluginLocation::Remote(format!("https://github.com/{}", github))
+        }
+        PluginSource::GitHub { github } => PluginLocation::Remote(github.clone()),
+        PluginSource::Url { url } => PluginLocation::Remote(url.clone()),
+        _ => return None,
+    };
+    Some(location)
+}
+
+/// Name and description from a plugin directory's plugin.json.
+///
+/// `dir` is root-relative with a trailing slash, or empty for the root.
+pub fn plugin_manifest(tree: &SourceTree, dir: &str) -> Option<(String, Option<String>)> {
+    let content = tree.read_file(&format!("{}{}", dir, PLUGIN_FILE)).ok()?;
+    let value: serde_json::Value = serde_json::from_str(&content).ok()?;
+    let name = value.get("name")?.as_str()?.to_string();
+    let description = value
+        .get("description")
+        .and_then(|d| d.as_str())
+        .map(String::from);
+    Some((name, description))
+}
+
+pub fn subscriptions() -> Vec<MarketplaceSubscription> {
+    BridleConfig::load()
+        .map(|c| c.marketplaces)
+        .unwrap_or_default()
+}
+
+/// Resolve `<marketplace>` or `<plugin>@<marketplace>` against the
+/// subscriptions, returning the marketplace source and requested plugin.
+pub fn resolve(spec: &str) -> Option<(MarketplaceSubscription, Option<String>)> {
+    resolve_in(&subscriptions(), spec)
+}
+
+fn resolve_in(
+    subscriptions: &[MarketplaceSubscription],
+    spec: &str,
+) -> Option<(MarketplaceSubscription, Option<String>)> {
+    let (plugin, name) = match spec.rsplit_once('@') {
+        Some((plugin, name)) if !plugin.is_empty() => (Some(plugin.to_string()), name),
+        _ => (None, spec),
+    };
+    subscriptions
+        .iter()
+        .find(|s| s.name == name)
+        .map(|s| (s.clone(), plugin))
+}
+
+/// Fetch `source`, check it has a marketplace.json and subscribe to it under
+/// `name` (default: the marketplace's own name, then the repository name).
+pub fn subscribe(
+    source: &str,
+    name: Option<&str>,
+) -> Result<(MarketplaceSubscription, usize), MarketplaceError> {
+    let parsed =
+        Source::parse(source).map_err(|e| MarketplaceError::InvalidSource(e.to_string()))?;
+    let tree = parsed.fetch().map_err(MarketplaceError::Fetch)?;
+    let marketplace =
+        read_marketplace(&tree)?.ok_or_else(|| MarketplaceError::NotAMarketplace(source.into()))?;
+
+    let name = name
+        .map(String::from)
+        .or(marketplace.name)
+        .unwrap_or_else(|| parsed.name());
+    let subscription = MarketplaceSubscription {
+        name,
+        source: parsed.to_string(),
+    };
+
+    let mut config = BridleConfig::load().map_err(|e| MarketplaceError::Config(e.to_string()))?;
+    add_subscription(&mut config.marketplaces, subscription.clone())?;
+    config
+        .save()
+        .map_err(|e| MarketplaceError::Config(e.to_string()))?;
+    Ok((subscription, marketplace.plugins.len()))
+}
+
+pub fn unsubscribe(name: &str) -> Result<MarketplaceSubscription, MarketplaceError> {
+    let mut config = BridleConfig::load().map_err(|e| MarketplaceError::Config(e.to_string()))?;
+    let index = config
+        .marketplaces
+        .iter()
+        .position(|s| s.name == name)
+        .ok_or_else(|| MarketplaceError::NotFound(name.to_string()))?;
+    let removed = config.marketplaces.remove(index);
+    config
+        .save()
+        .map_err(|e| MarketplaceError::Config(e.to_string()))?;
+    Ok(removed)
+}
+
+fn add_subscription(
+    subscriptions: &mut Vec<MarketplaceSubscription>,
+    subscription: MarketplaceSubscription,
+) -> Result<(), MarketplaceError> {
+    let name = &subscription.name;
+    if name.is_empty() || name.contains(['@', '/', ' ']) {
+        return Err(MarketplaceError::InvalidName(name.clone()));
+    }
+    if subscriptions.iter().any(|s| &s.name == name) {
+        return Err(MarketplaceError::AlreadySubscribed(name.clone()));
+    }
+    subscriptions.push(subscription);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn tree(files: &[(&str, &str)]) -> (TempDir, SourceTree) {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("market");
+        for (path, content) in files {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let tree = Source::Local(root).fetch().unwrap();
+        (temp, tree)
+    }
+
+    #[test]
+    fn plugin_entries_follow_marketplace_listing() {
+        let (_temp, tree) = tree(&[(
+            MARKETPLACE_FILE,
+            r#"{"name": "team", "plugins": [
+                {"name": "review", "description": "Reviews", "source": "./plugins/review/"},
+                {"name": "remote", "source": {"github": "owner/remote"}},
+                {"name": "zip", "source": {"url": "https://example.com/p.zip"}}
+            ]}"#,
+        )]);
+        let entries = plugin_entries(&tree);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(
+            entries[0].location,
+            PluginLocation::Dir("plugins/review".to_string())
+        );
+        assert_eq!(entries[0].description.as_deref(), Some("Reviews"));
+        assert_eq!(
+            entries[1].location,
+            PluginLocation::Remote("https://github.com/owner/remote".to_string())
+        );
+        assert_eq!(
+            entries[2].location,
+            PluginLocation::Remote("https://example.com/p.zip".to_string())
+        );
+    }
+
+    #[test]
+    fn plugin_entries_fall_back_to_plugin_json() {
+        let (_temp, tree) = tree(&[
+            ("a/.claude-plugin/plugin.json", r#"{"name": "alpha"}"#),
+            ("b/.claude-plugin/plugin.json", r#"{"name": "beta"}"#),
+        ]);
+        let entries = plugin_entries(&tree);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].location, PluginLocation::Dir("a".to_string()));
+        assert_eq!(
+            plugin_manifest(&tree, "b/")
+                .map(|(name, _)| name)
+                .as_deref(),
+            Some("beta")
+        );
+    }
+
+    #[test]
+    fn resolve_and_add_subscriptions() {
+        let mut subscriptions = Vec::new();
+        let team = MarketplaceSubscription {
+            name: "team".to_string(),
+            source: "https://github.com/team/plugins".to_string(),
+        };
+        add_subscription(&mut subscriptions, team.clone()).unwrap();
+        assert!(matches!(
+            add_subscription(&mut subscriptions, team.clone()),
+            Err(MarketplaceError::AlreadySubscribed(_))
+        ));
+        assert!(matches!(
+            add_subscription(
+                &mut subscriptions,
+                MarketplaceSubscription {
+                    name: "a@b".to_string(),
+                    source: String::new(),
+                }
+            ),
+            Err(MarketplaceError::InvalidName(_))
+        ));
+
+        assert_eq!(
+            resolve_in(&subscriptions, "team"),
+            Some((team.clone(), None))
+        );
+        assert_eq!(
+            resolve_in(&subscriptions, "review@team"),
+            Some((team, Some("review".to_string())))
+        );
+        assert_eq!(resolve_in(&subscriptions, "owner/repo"), None);
+    }
+}
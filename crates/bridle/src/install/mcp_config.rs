@@ -1,12 +1,12 @@
 //! MCP configuration read/write helpers for all harnesses.
 
 use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
 
 use harness_locate::HarnessKind;
 
 use crate::config::jsonc::strip_jsonc_comments;
+use crate::plan::{self, fs};
 
 #[derive(Debug, thiserror::Error)]
 pub enum McpConfigError {
@@ -40,25 +40,33 @@ pub fn read_mcp_config(
     kind: HarnessKind,
     config_path: &Path,
 ) -> Result<HashMap<String, serde_json::Value>, McpConfigError> {
-    if !config_path.exists() {
+    if !fs::exists(config_path) {
         return Ok(HashMap::new());
     }
 
     let content = fs::read_to_string(config_path)?;
+    parse_mcp_servers(kind, &content)
+}
+
+/// MCP servers declared in the contents of a harness config file.
+pub fn parse_mcp_servers(
+    kind: HarnessKind,
+    content: &str,
+) -> Result<HashMap<String, serde_json::Value>, McpConfigError> {
     if content.trim().is_empty() {
         return Ok(HashMap::new());
     }
 
     let parsed: serde_json::Value = match kind {
         HarnessKind::Goose => {
-            let yaml: serde_yaml::Value = serde_yaml::from_str(&content)?;
+            let yaml: serde_yaml::Value = serde_yaml::from_str(content)?;
             serde_json::to_value(yaml)?
         }
         HarnessKind::OpenCode => {
-            let stripped = strip_jsonc_comments(&content);
+            let stripped = strip_jsonc_comments(content);
             serde_json::from_str(&stripped)?
         }
-        _ => serde_json::from_str(&content)?,
+        _ => serde_json::from_str(content)?,
     };
 
     let key = get_mcp_key(kind);
@@ -90,13 +98,14 @@ pub fn write_mcp_config(
     config_path: &Path,
     servers: &HashMap<String, serde_json::Value>,
 ) -> Result<(), McpConfigError> {
+    plan::note_mcp_config(config_path, kind);
     if kind == HarnessKind::Goose {
         return write_goose_yaml_preserving_comments(config_path, servers);
     }
 
     let key = get_mcp_key(kind);
 
-    let mut existing: serde_json::Value = if config_path.exists() {
+    let mut existing: serde_json::Value = if fs::exists(config_path) {
         let content = fs::read_to_string(config_path)?;
         if content.trim().is_empty() {
             serde_json::json!({})
@@ -144,7 +153,7 @@ fn write_goose_yaml_preserving_comments(
         fs::create_dir_all(parent)?;
     }
 
-    let content = if config_path.exists() {
+    let content = if fs::exists(config_path) {
         fs::read_to_string(config_path)?
     } else {
         String::new()
@@ -283,6 +292,293 @@ pub fn mcp_exists(
     Ok(servers.contains_key(name))
 }
 
+/// Render the exact fragment `write_mcp_config` writes for a server.
+pub fn native_fragment(kind: HarnessKind, name: &str, value: &serde_json::Value) -> String {
+    if kind == HarnessKind::Goose {
+        format_goose_mcp_entry(name, value)
+    } else {
+        serde_json::to_string_pretty(value).unwrap_or_default()
+    }
+}
+
+/// Remove a single MCP server from a harness config, leaving other servers
+/// and unrelated settings untouched. Returns `false` if it was not present.
+pub fn remove_mcp_config(
+    kind: HarnessKind,
+    config_path: &Path,
+    name: &str,
+) -> Result<bool, McpConfigError> {
+    if !fs::exists(config_path) {
+        return Ok(false);
+    }
+    plan::note_mcp_config(config_path, kind);
+
+    let content = fs::read_to_string(config_path)?;
+    if content.trim().is_empty() {
+        return Ok(false);
+    }
+
+    let edited = match kind {
+        HarnessKind::Goose => Some(remove_goose_yaml_entry(&content, name)),
+        HarnessKind::OpenCode => {
+            // Parse first so a malformed file is reported rather than edited
+            serde_json::from_str::<serde_json::Value>(&strip_jsonc_comments(&content))?;
+            Some(remove_jsonc_entry(&content, get_mcp_key(kind), name))
+        }
+        _ => None,
+    };
+    if let Some(edited) = edited {
+        return match edited {
+            Some(output) => {
+                fs::write(config_path, output)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        };
+    }
+
+    let mut existing: serde_json::Value = serde_json::from_str(&content)?;
+
+    let key = get_mcp_key(kind);
+    let removed = existing
+        .get_mut(key)
+        .and_then(|section| section.as_object_mut())
+        .and_then(|obj| obj.remove(name))
+        .is_some();
+
+    if removed {
+        let output = serde_json::to_string_pretty(&existing)?;
+        fs::write(config_path, output)?;
+    }
+    Ok(removed)
+}
+
+/// Remove the `name:` block from the top-level `extensions:` mapping,
+/// preserving comments and formatting elsewhere in the file.
+fn remove_goose_yaml_entry(content: &str, name: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let indent_of = |line: &str| line.len() - line.trim_start().len();
+
+    let mut in_extensions = false;
+    // Indent of the extension names; deeper keys (`envs:` entries, say) may
+    // share a name with an extension and must be left alone
+    let mut child_indent = None;
+    let mut start = None;
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if indent_of(line) == 0 {
+            in_extensions = trimmed.starts_with("extensions:");
+            child_indent = None;
+            continue;
+        }
+        if !in_extensions {
+            continue;
+        }
+        if *child_indent.get_or_insert(indent_of(line)) != indent_of(line) {
+            continue;
+        }
+        if trimmed.starts_with(&format!("{}:", name))
+            || trimmed.starts_with(&format!("\"{}\":", name))
+        {
+            start = Some(i);
+            break;
+        }
+    }
+
+    let start = start?;
+    let entry_indent = indent_of(lines[start]);
+    let mut end = start + 1;
+    while end < lines.len() {
+        let line = lines[end];
+        if line.trim().is_empty() {
+            // Blank lines belong to the entry only if more of it follows
+            let continues = lines[end..]
+                .iter()
+                .find(|l| !l.trim().is_empty())
+                .is_some_and(|l| indent_of(l) > entry_indent);
+            if !continues {
+                break;
+            }
+        } else if indent_of(line) <= entry_indent {
+            break;
+        }
+        end += 1;
+    }
+
+    let mut result: Vec<&str> = lines[..start].to_vec();
+    result.extend_from_slice(&lines[end..]);
+    let mut output = result.join("\n");
+    if content.ends_with('\n') && !output.is_empty() {
+        output.push('\n');
+    }
+    Some(output)
+}
+
+/// Remove the `name` member of the top-level `section` object from JSONC
+/// text, leaving comments and formatting elsewhere untouched.
+fn remove_jsonc_entry(content: &str, section: &str, name: &str) -> Option<String> {
+    let bytes = content.as_bytes();
+    let root = skip_trivia(bytes, 0);
+    if bytes.get(root) != Some(&b'{') {
+        return None;
+    }
+    let section = find_member(bytes, root, section)?;
+    if bytes.get(section.value) != Some(&b'{') {
+        return None;
+    }
+    let entry = find_member(bytes, section.value, name)?;
+    let value_end = skip_value(bytes, entry.value)?;
+
+    let after = skip_trivia(bytes, value_end);
+    let (start, end) = if bytes.get(after) == Some(&b',') {
+        (line_start(bytes, entry.key), line_end(bytes, after + 1))
+    } else if let Some(previous_end) = entry.previous_end {
+        // Last member: take the comma after the previous one with it
+        (previous_end, value_end)
+    } else {
+        (line_start(bytes, entry.key), line_end(bytes, value_end))
+    };
+    Some(format!("{}{}", &content[..start], &content[end..]))
+}
+
+/// Byte offsets of an object member: where its key and value start, and
+/// where the value of the member before it ends.
+struct Member {
+    key: usize,
+    value: usize,
+    previous_end: Option<usize>,
+}
+
+/// Find member `key` of the JSONC object starting at `object`.
+fn find_member(bytes: &[u8], object: usize, key: &str) -> Option<Member> {
+    let mut pos = object + 1;
+    let mut previous_end = None;
+    loop {
+        pos = skip_trivia(bytes, pos);
+        match bytes.get(pos)? {
+            b',' => pos += 1,
+            b'"' => {
+                let key_end = skip_string(bytes, pos)?;
+                let colon = skip_trivia(bytes, key_end);
+                if bytes.get(colon) != Some(&b':') {
+                    return None;
+                }
+                let value = skip_trivia(bytes, colon + 1);
+                if serde_json::from_slice::<String>(&bytes[pos..key_end]).ok()? == key {
+                    return Some(Member {
+                        key: pos,
+                        value,
+                        previous_end,
+                    });
+                }
+                pos = skip_value(bytes, value)?;
+                previous_end = Some(pos);
+            }
+            _ => return None,
+        }
+    }
+}
+
+/// Skip whitespace and comments.
+fn skip_trivia(bytes: &[u8], mut pos: usize) -> usize {
+    loop {
+        match (bytes.get(pos), bytes.get(pos + 1)) {
+            (Some(b), _) if b.is_ascii_whitespace() => pos += 1,
+            (Some(b'/'), Some(b'/')) => {
+                while bytes.get(pos).is_some_and(|&b| b != b'\n') {
+                    pos += 1;
+                }
+            }
+            (Some(b'/'), Some(b'*')) => {
+                pos += 2;
+                while pos < bytes.len() && !bytes[pos..].starts_with(b"*/") {
+                    pos += 1;
+                }
+                pos = (pos + 2).min(bytes.len());
+            }
+            _ => return pos,
+        }
+    }
+}
+
+/// Offset just past the string starting at `pos`.
+fn skip_string(bytes: &[u8], mut pos: usize) -> Option<usize> {
+    pos += 1;
+    loop {
+        match bytes.get(pos)? {
+            b'\\' => pos += 2,
+            b'"' => return Some(pos + 1),
+            _ => pos += 1,
+        }
+    }
+}
+
+/// Offset just past the value starting at `pos`.
+fn skip_value(bytes: &[u8], mut pos: usize) -> Option<usize> {
+    match bytes.get(pos)? {
+        b'"' => skip_string(bytes, pos),
+        b'{' | b'[' => {
+            let mut depth = 0;
+            loop {
+                pos = skip_trivia(bytes, pos);
+                match bytes.get(pos)? {
+                    b'"' => {
+                        pos = skip_string(bytes, pos)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(pos + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                pos += 1;
+            }
+        }
+        _ => {
+            while bytes
+                .get(pos)
+                .is_some_and(|&b| !b.is_ascii_whitespace() && !b",}]/".contains(&b))
+            {
+                pos += 1;
+            }
+            Some(pos)
+        }
+    }
+}
+
+/// Start of the line `pos` is on, if only indentation precedes it.
+fn line_start(bytes: &[u8], pos: usize) -> usize {
+    let start = bytes[..pos]
+        .iter()
+        .rposition(|&b| b != b' ' && b != b'\t')
+        .map_or(0, |i| i + 1);
+    if start == 0 || bytes[start - 1] == b'\n' {
+        start
+    } else {
+        pos
+    }
+}
+
+/// Offset past the end of the line `pos` is on, if only spaces follow it.
+fn line_end(bytes: &[u8], pos: usize) -> usize {
+    let mut end = pos;
+    while matches!(bytes.get(end), Some(b' ' | b'\t' | b'\r')) {
+        end += 1;
+    }
+    if bytes.get(end) == Some(&b'\n') {
+        end + 1
+    } else {
+        pos
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -566,4 +862,191 @@ GOOSE_PROVIDER: anthropic
         );
         assert!(content.contains("new-mcp"), "New MCP added");
     }
+
+    #[test]
+    fn remove_json_mcp_preserves_other_servers() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join(".mcp.json");
+        fs::write(
+            &path,
+            r#"{"mcpServers": {"keep": {"command": "a"}, "drop": {"command": "b"}}, "other": 1}"#,
+        )
+        .unwrap();
+
+        assert!(remove_mcp_config(HarnessKind::ClaudeCode, &path, "drop").unwrap());
+
+        let servers = read_mcp_config(HarnessKind::ClaudeCode, &path).unwrap();
+        assert!(servers.contains_key("keep"));
+        assert!(!servers.contains_key("drop"));
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("\"other\": 1"));
+    }
+
+    #[test]
+    fn remove_missing_mcp_returns_false() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("crush.json");
+        fs::write(&path, r#"{"mcp": {"keep": {"command": "a"}}}"#).unwrap();
+
+        assert!(!remove_mcp_config(HarnessKind::Crush, &path, "drop").unwrap());
+        assert!(
+            !remove_mcp_config(HarnessKind::Crush, &tmp.path().join("none.json"), "x").unwrap()
+        );
+    }
+
+    #[test]
+    fn remove_opencode_and_amp_mcp() {
+        let tmp = TempDir::new().unwrap();
+        let oc = tmp.path().join("opencode.jsonc");
+        fs::write(
+            &oc,
+            r#"{
+  // comment
+  "mcp": {"drop": {"type": "local"}, "keep": {"type": "remote"}}
+}"#,
+        )
+        .unwrap();
+        assert!(remove_mcp_config(HarnessKind::OpenCode, &oc, "drop").unwrap());
+        let servers = read_mcp_config(HarnessKind::OpenCode, &oc).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert!(servers.contains_key("keep"));
+
+        let amp = tmp.path().join("settings.json");
+        fs::write(&amp, r#"{"amp.mcpServers": {"drop": {"command": "x"}}}"#).unwrap();
+        assert!(remove_mcp_config(HarnessKind::AmpCode, &amp, "drop").unwrap());
+        assert!(
+            read_mcp_config(HarnessKind::AmpCode, &amp)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn remove_goose_yaml_entry_preserves_comments_and_neighbours() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("config.yaml");
+        let original = r#"# Goose config
+GOOSE_PROVIDER: anthropic
+extensions:
+  # builtin
+  developer:
+    type: builtin
+    enabled: true
+  drop:
+    type: stdio
+    cmd: npx
+    args: ["-y", "server"]
+
+  keep:
+    type: stdio
+    cmd: uvx
+# trailing comment
+GOOSE_MODEL: claude
+"#;
+        fs::write(&path, original).unwrap();
+
+        assert!(remove_mcp_config(HarnessKind::Goose, &path, "drop").unwrap());
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("drop:"));
+        assert!(!content.contains("npx"));
+        assert!(content.contains("# Goose config"));
+        assert!(content.contains("# builtin"));
+        assert!(content.contains("developer:"));
+        assert!(content.contains("keep:"));
+        assert!(content.contains("# trailing comment"));
+        assert!(content.contains("GOOSE_MODEL: claude"));
+
+        let servers = read_mcp_config(HarnessKind::Goose, &path).unwrap();
+        assert!(servers.contains_key("keep"));
+        assert!(!servers.contains_key("drop"));
+    }
+
+    #[test]
+    fn goose_remove_ignores_keys_outside_extensions() {
+        let content = "drop: 1\nextensions:\n  keep:\n    type: stdio\n";
+        assert!(remove_goose_yaml_entry(content, "drop").is_none());
+    }
+
+    #[test]
+    fn goose_remove_ignores_nested_keys_named_like_the_server() {
+        let content = "extensions:\n  keep:\n    envs:\n      drop: x\n    type: stdio\n  drop:\n    type: stdio\n";
+        let output = remove_goose_yaml_entry(content, "drop").unwrap();
+        assert_eq!(
+            output,
+            "extensions:\n  keep:\n    envs:\n      drop: x\n    type: stdio\n"
+        );
+
+        let nested_only = "extensions:\n  keep:\n    envs:\n      drop: x\n";
+        assert!(remove_goose_yaml_entry(nested_only, "drop").is_none());
+    }
+
+    #[test]
+    fn remove_opencode_mcp_keeps_comments_and_formatting() {
+        let tmp = TempDir::new().unwrap();
+        let path = tmp.path().join("opencode.jsonc");
+        let original = r#"{
+  // Theme comment
+  "theme": "dark",
+  "mcp": {
+    // first server
+    "first": { "type": "local", "command": ["a"] },
+    "drop": {
+      "type": "remote", /* inline */
+      "url": "https://example.com/{drop}"
+    },
+    "last": { "type": "local" } // trailing
+  },
+}
+"#;
+        fs::write(&path, original).unwrap();
+        assert!(remove_mcp_config(HarnessKind::OpenCode, &path, "drop").unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            r#"{
+  // Theme comment
+  "theme": "dark",
+  "mcp": {
+    // first server
+    "first": { "type": "local", "command": ["a"] },
+    "last": { "type": "local" } // trailing
+  },
+}
+"#
+        );
+
+        assert!(remove_mcp_config(HarnessKind::OpenCode, &path, "last").unwrap());
+        assert!(remove_mcp_config(HarnessKind::OpenCode, &path, "first").unwrap());
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("// Theme comment"));
+        assert!(content.contains("// first server"));
+        assert!(
+            read_mcp_config(HarnessKind::OpenCode, &path)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(!remove_mcp_config(HarnessKind::OpenCode, &path, "first").unwrap());
+    }
+
+    #[test]
+    fn remove_jsonc_entry_only_matches_direct_members_of_the_section() {
+        let content = r#"{"other": {"drop": 1}, "mcp": {"keep": {"drop": true}}}"#;
+        assert!(remove_jsonc_entry(content, "mcp", "drop").is_none());
+        assert_eq!(
+            remove_jsonc_entry(content, "mcp", "keep").unwrap(),
+            r#"{"other": {"drop": 1}, "mcp": {}}"#
+        );
+    }
+
+    #[test]
+    fn native_fragment_matches_written_format() {
+        let value = serde_json::json!({"type": "stdio", "cmd": "npx"});
+        let yaml = native_fragment(HarnessKind::Goose, "fs", &value);
+        assert!(yaml.starts_with("  fs:"));
+        assert!(yaml.contains("cmd: npx"));
+
+        let json = native_fragment(HarnessKind::ClaudeCode, "fs", &value);
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, value);
+    }
 }
@@ -0,0 +1,863 @@
+//! Direct edits to the MCP servers of a profile (`bridle mcp add|rm|...`).
+//!
+//! Servers are written in each harness's native shape through
+//! `Harness::mcp_to_native`, and, like `bridle install`, edits to the active
+//! profile are mirrored into the live harness config. Harnesses without a
+//! per-server `enabled` flag get disabling emulated: the native entry is
+//! moved into [`DISABLED_FILE`] in the profile and moved back on enable.
+
+use crate::plan::fs;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use harness_locate::validation::validate_for_harness;
+use harness_locate::{Harness, HarnessKind, McpServer, Severity};
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+
+use super::installer::validate_component_name;
+use super::manifest::{InstallManifest, ManifestError, manifest_path};
+use super::mcp_config::{McpConfigError, read_mcp_config, remove_mcp_config, write_mcp_config};
+use super::mcp_installer::get_profile_config_path;
+use super::types::ComponentType;
+use crate::config::BridleConfig;
+use crate::harness::HarnessConfig;
+
+/// Native entries of servers disabled on harnesses without a toggle.
+pub const DISABLED_FILE: &str = ".bridle-mcp-disabled.json";
+
+#[derive(Debug, Error)]
+pub enum McpEditError {
+    #[error("Profile not found: {harness}/{profile}")]
+    ProfileNotFound { harness: String, profile: String },
+
+    #[error("MCP server '{name}' not found in {harness}/{profile}")]
+    NotFound {
+        harness: String,
+        profile: String,
+        name: String,
+    },
+
+    #[error("MCP server '{name}' already exists in {harness}/{profile} (use --force to replace)")]
+    AlreadyExists {
+        harness: String,
+        profile: String,
+        name: String,
+    },
+
+    #[error("Invalid MCP server name: {0}")]
+    InvalidName(String),
+
+    #[error("{harness} cannot use this server: {reason}")]
+    Unsupported { harness: String, reason: String },
+
+    #[error("Failed to parse MCP server '{name}': {reason}")]
+    Parse { name: String, reason: String },
+
+    #[error(transparent)]
+    Config(#[from] McpConfigError),
+
+    #[error("Failed to update {path}: {error}")]
+    Write {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+}
+
+/// One server as shown by `bridle mcp list`.
+#[derive(Debug, Clone, Serialize)]
+pub struct McpEntry {
+    pub name: String,
+    pub transport: &'static str,
+    pub enabled: bool,
+    /// Command line for stdio servers, URL for remote ones.
+    pub target: String,
+    /// Set when the native entry could not be parsed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// How `enable`/`disable` was carried out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ToggleMode {
+    /// The harness's own `enabled`/`disabled` field was set.
+    Native,
+    /// The entry was moved into or out of [`DISABLED_FILE`].
+    Emulated,
+    /// The server was already in the requested state.
+    Unchanged,
+}
+
+/// The MCP config of one profile, plus the live config when it is active.
+struct ProfileMcp {
+    kind: HarnessKind,
+    harness: Harness,
+    harness_id: String,
+    profile: String,
+    dir: PathBuf,
+    config_path: PathBuf,
+    /// Live MCP config file and config dir, when the profile is active.
+    live: Option<(PathBuf, PathBuf)>,
+}
+
+impl ProfileMcp {
+    fn open(kind: HarnessKind, profile: &str, profiles_dir: &Path) -> Result<Self, McpEditError> {
+        let harness = Harness::new(kind);
+        let harness_id = harness.id().to_string();
+        let dir = profiles_dir.join(&harness_id).join(profile);
+        if !fs::is_dir(&dir) {
+            return Err(McpEditError::ProfileNotFound {
+                harness: harness_id,
+                profile: profile.to_string(),
+            });
+        }
+
+        let is_active = BridleConfig::load()
+            .ok()
+            .and_then(|c| c.active_profile_for(&harness_id).map(|a| a == profile))
+            .unwrap_or(false);
+        let live = is_active
+            .then(|| Harness::locate(kind).ok())
+            .flatten()
+            .and_then(|h| Some((h.mcp_config_path()?, h.config_dir().ok()?)));
+
+        Ok(Self {
+            kind,
+            config_path: get_profile_config_path(&dir, kind),
+            harness,
+            harness_id,
+            profile: profile.to_string(),
+            dir,
+            live,
+        })
+    }
+
+    fn not_found(&self, name: &str) -> McpEditError {
+        McpEditError::NotFound {
+            harness: self.harness_id.clone(),
+            profile: self.profile.clone(),
+            name: name.to_string(),
+        }
+    }
+
+    fn config_paths(&self) -> Vec<&Path> {
+        let mut paths = vec![self.config_path.as_path()];
+        if let Some((live, _)) = &self.live {
+            paths.push(live);
+        }
+        paths
+    }
+
+    fn stash_paths(&self) -> Vec<PathBuf> {
+        let mut paths = vec![self.dir.join(DISABLED_FILE)];
+        if let Some((_, live_dir)) = &self.live {
+            paths.push(live_dir.join(DISABLED_FILE));
+        }
+        paths
+    }
+
+    fn servers(&self) -> Result<HashMap<String, Value>, McpEditError> {
+        Ok(read_mcp_config(self.kind, &self.config_path)?)
+    }
+
+    fn put(&self, name: &str, value: &Value) -> Result<(), McpEditError> {
+        let entry = HashMap::from([(name.to_string(), value.clone())]);
+        for path in self.config_paths() {
+            write_mcp_config(self.kind, path, &entry)?;
+        }
+        Ok(())
+    }
+
+    fn remove(&self, name: &str) -> Result<bool, McpEditError> {
+        let mut removed = false;
+        for path in self.config_paths() {
+            removed |= remove_mcp_config(self.kind, path, name)?;
+        }
+        Ok(removed)
+    }
+
+    fn stash(&self) -> BTreeMap<String, Value> {
+        fs::read_to_string(self.dir.join(DISABLED_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save_stash(&self, stash: &BTreeMap<String, Value>) -> Result<(), McpEditError> {
+        for path in self.stash_paths() {
+            let result = if stash.is_empty() {
+                match fs::remove_file(&path) {
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                    other => other,
+                }
+            } else {
+                serde_json::to_string_pretty(stash)
+                    .map_err(std::io::Error::other)
+                    .and_then(|content| fs::write(&path, content))
+            };
+            result.map_err(|error| McpEditError::Write { path, error })?;
+        }
+        Ok(())
+    }
+
+    fn parse(&self, name: &str, value: &Value) -> Result<McpServer, McpEditError> {
+        self.harness
+            .parse_mcp_server_config(name, value)
+            .map_err(|e| McpEditError::Parse {
+                name: name.to_string(),
+                reason: e.to_string(),
+            })
+    }
+
+    fn native(&self, name: &str, server: &McpServer) -> Result<Value, McpEditError> {
+        self.harness
+            .mcp_to_native(name, server)
+            .map_err(|e| McpEditError::Unsupported {
+                harness: self.harness_id.clone(),
+                reason: e.to_string(),
+            })
+    }
+
+    fn update_manifest(&self, edit: impl FnOnce(&mut InstallManifest)) -> Result<(), McpEditError> {
+        let path = manifest_path(&self.dir);
+        if !fs::exists(&path) {
+            return Ok(());
+        }
+        let mut manifest = InstallManifest::load(&path)?;
+        edit(&mut manifest);
+        manifest.save(&path)?;
+        Ok(())
+    }
+}
+
+fn check_name(name: &str) -> Result<(), McpEditError> {
+    validate_component_name(name).map_err(|_| McpEditError::InvalidName(name.to_string()))
+}
+
+fn set_enabled_flag(server: &mut McpServer, enabled: bool) {
+    match server {
+        McpServer::Stdio(s) => s.enabled = enabled,
+        McpServer::Sse(s) => s.enabled = enabled,
+        McpServer::Http(s) => s.enabled = enabled,
+    }
+}
+
+fn is_enabled(server: &McpServer) -> bool {
+    match server {
+        McpServer::Stdio(s) => s.enabled,
+        McpServer::Sse(s) => s.enabled,
+        McpServer::Http(s) => s.enabled,
+    }
+}
+
+fn entry(name: &str, parsed: Result<McpServer, McpEditError>, stashed: bool) -> McpEntry {
+    let (transport, enabled, target) = match &parsed {
+        Ok(McpServer::Stdio(s)) => {
+            let mut target = s.command.clone();
+            for arg in &s.args {
+                target.push(' ');
+                target.push_str(arg);
+            }
+            ("stdio", s.enabled, target)
+        }
+        Ok(McpServer::Sse(s)) => ("sse", s.enabled, s.url.clone()),
+        Ok(McpServer::Http(s)) => ("http", s.enabled, s.url.clone()),
+        Err(_) => ("unknown", true, String::new()),
+    };
+    McpEntry {
+        name: name.to_string(),
+        transport,
+        enabled: enabled && !stashed,
+        target,
+        error: parsed.err().map(|e| e.to_string()),
+    }
+}
+
+/// Lists a profile's servers, including ones disabled by emulation.
+pub fn list_servers(
+    kind: HarnessKind,
+    profile: &str,
+    profiles_dir: &Path,
+) -> Result<Vec<McpEntry>, McpEditError> {
+    let mcp = ProfileMcp::open(kind, profile, profiles_dir)?;
+    let mut entries: Vec<McpEntry> = mcp
+        .servers()?
+        .iter()
+        .map(|(name, value)| entry(name, mcp.parse(name, value), false))
+        .chain(
+            mcp.stash()
+                .iter()
+                .map(|(name, value)| entry(name, mcp.parse(name, value), true)),
+        )
+        .collect();
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(entries)
+}
+
+/// Adds a server, replacing an existing one of the same name only with `force`.
+pub fn add_server(
+    kind: HarnessKind,
+    profile: &str,
+    profiles_dir: &Path,
+    name: &str,
+    server: &McpServer,
+    force: bool,
+) -> Result<(), McpEditError> {
+    check_name(name)?;
+    let mcp = ProfileMcp::open(kind, profile, profiles_dir)?;
+
+    // Fields the native format has no place for would be dropped silently
+    if let Some(issue) = validate_for_harness(server, kind)
+        .into_iter()
+        .find(|issue| issue.severity == Severity::Error)
+    {
+        return Err(McpEditError::Unsupported {
+            harness: mcp.harness_id.clone(),
+            reason: match issue.code {
+                Some(code) => format!("{} ({})", issue.message, code),
+                None => issue.message,
+            },
+        });
+    }
+
+    let mut stash = mcp.stash();
+    if !force && (mcp.servers()?.contains_key(name) || stash.contains_key(name)) {
+        return Err(McpEditError::AlreadyExists {
+            harness: mcp.harness_id.clone(),
+            profile: mcp.profile.clone(),
+            name: name.to_string(),
+        });
+    }
+
+    let native = mcp.native(name, server)?;
+    mcp.put(name, &native)?;
+    if stash.remove(name).is_some() {
+        mcp.save_stash(&stash)?;
+    }
+    Ok(())
+}
+
+/// Removes a server, whether enabled or disabled.
+pub fn remove_server(
+    kind: HarnessKind,
+    profile: &str,
+    profiles_dir: &Path,
+    name: &str,
+) -> Result<(), McpEditError> {
+    let mcp = ProfileMcp::open(kind, profile, profiles_dir)?;
+
+    let mut removed = mcp.remove(name)?;
+    let mut stash = mcp.stash();
+    if stash.remove(name).is_some() {
+        mcp.save_stash(&stash)?;
+        removed = true;
+    }
+    if !removed {
+        return Err(mcp.not_found(name));
+    }
+
+    mcp.update_manifest(|m| m.remove_component(ComponentType::Mcp, name))
+}
+
+/// Enables or disables a server, natively when the harness supports it.
+pub fn set_server_enabled(
+    kind: HarnessKind,
+    profile: &str,
+    profiles_dir: &Path,
+    name: &str,
+    enabled: bool,
+) -> Result<ToggleMode, McpEditError> {
+    let mcp = ProfileMcp::open(kind, profile, profiles_dir)?;
+    let mut stash = mcp.stash();
+
+    if let Some(value) = mcp.servers()?.get(name) {
+        let mut server = mcp.parse(name, value)?;
+        if is_enabled(&server) == enabled {
+            return Ok(ToggleMode::Unchanged);
+        }
+        if enabled || mcp.harness.mcp_capabilities().toggle {
+            set_enabled_flag(&mut server, enabled);
+            mcp.put(name, &mcp.native(name, &server)?)?;
+            return Ok(ToggleMode::Native);
+        }
+        stash.insert(name.to_string(), value.clone());
+        mcp.save_stash(&stash)?;
+        mcp.remove(name)?;
+        return Ok(ToggleMode::Emulated);
+    }
+
+    let Some(value) = stash.remove(name) else {
+        return Err(mcp.not_found(name));
+    };
+    if !enabled {
+        return Ok(ToggleMode::Unchanged);
+    }
+    mcp.put(name, &value)?;
+    mcp.save_stash(&stash)?;
+    Ok(ToggleMode::Emulated)
+}
+
+/// Renames a server, re-rendering it for harnesses that embed the name.
+pub fn rename_server(
+    kind: HarnessKind,
+    profile: &str,
+    profiles_dir: &Path,
+    from: &str,
+    to: &str,
+) -> Result<(), McpEditError> {
+    check_name(to)?;
+    let mcp = ProfileMcp::open(kind, profile, profiles_dir)?;
+    let servers = mcp.servers()?;
+    let mut stash = mcp.stash();
+
+    if servers.contains_key(to) || stash.contains_key(to) {
+        return Err(McpEditError::AlreadyExists {
+            harness: mcp.harness_id.clone(),
+            profile: mcp.profile.clone(),
+            name: to.to_string(),
+        });
+    }
+
+    if let Some(value) = servers.get(from) {
+        let server = mcp.parse(from, value)?;
+        mcp.put(to, &mcp.native(to, &server)?)?;
+        mcp.remove(from)?;
+    } else if let Some(value) = stash.remove(from) {
+        let mut server = mcp.parse(from, &value)?;
+        // Stashed entries keep their native shape, which for a toggle-less
+        // harness cannot carry `enabled: false`.
+        set_enabled_flag(&mut server, true);
+        stash.insert(to.to_string(), mcp.native(to, &server)?);
+        mcp.save_stash(&stash)?;
+    } else {
+        return Err(mcp.not_found(from));
+    }
+
+    mcp.update_manifest(|m| {
+        if let Some(mut entry) = m.find_component(ComponentType::Mcp, from).cloned() {
+            m.remove_component(ComponentType::Mcp, from);
+            entry.name = to.to_string();
+            m.add_entry(entry);
+        }
+    })
+}
+
+/// Names of servers disabled by emulation in a profile directory.
+pub fn disabled_server_names(profile_dir: &Path) -> Vec<String> {
+    fs::read_to_string(profile_dir.join(DISABLED_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str::<BTreeMap<String, Value>>(&content).ok())
+        .map(|stash| stash.into_keys().collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::install::manifest::ManifestEntry;
+    use crate::install::types::SourceInfo;
+    use harness_locate::validation::CODE_CWD_UNSUPPORTED;
+    use harness_locate::{HttpMcpServer, StdioMcpServer};
+    use tempfile::TempDir;
+
+    fn stdio(command: &str) -> McpServer {
+        McpServer::Stdio(StdioMcpServer {
+            command: command.to_string(),
+            args: vec!["--stdio".to_string()],
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        })
+    }
+
+    fn profile(temp: &TempDir, harness: &str) -> PathBuf {
+        let profiles = temp.path().join("profiles");
+        fs::create_dir_all(profiles.join(harness).join("mcp-edit")).unwrap();
+        profiles
+    }
+
+    fn installed(profile_dir: &Path, name: &str) {
+        let mut manifest = InstallManifest::default();
+        manifest.add_entry(ManifestEntry {
+            component_type: ComponentType::Mcp,
+            name: name.to_string(),
+            source: SourceInfo {
+                owner: "owner".to_string(),
+                repo: "repo".to_string(),
+                git_ref: None,
+                url: None,
+                commit: None,
+                plugin: None,
+            },
+            installed_at: "2025-01-02T00:00:00Z".to_string(),
+            fragment: None,
+        });
+        manifest.save(&manifest_path(profile_dir)).unwrap();
+    }
+
+    fn manifest_names(profile_dir: &Path) -> Vec<String> {
+        InstallManifest::load(&manifest_path(profile_dir))
+            .unwrap()
+            .components_of(ComponentType::Mcp)
+            .iter()
+            .map(|e| e.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn add_toggle_rename_and_remove_with_native_toggle() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profile(&temp, "opencode");
+        let kind = HarnessKind::OpenCode;
+
+        add_server(
+            kind,
+            "mcp-edit",
+            &profiles,
+            "fs",
+            &stdio("fs-server"),
+            false,
+        )
+        .unwrap();
+        assert!(matches!(
+            add_server(kind, "mcp-edit", &profiles, "fs", &stdio("other"), false),
+            Err(McpEditError::AlreadyExists { .. })
+        ));
+
+        let config = profiles.join("opencode/mcp-edit/opencode.jsonc");
+        let native: Value = serde_json::from_str(&fs::read_to_string(&config).unwrap()).unwrap();
+        assert_eq!(native["mcp"]["fs"]["type"], "local");
+        assert_eq!(
+            native["mcp"]["fs"]["command"],
+            serde_json::json!(["fs-server", "--stdio"])
+        );
+
+        let mode = set_server_enabled(kind, "mcp-edit", &profiles, "fs", false).unwrap();
+        assert_eq!(mode, ToggleMode::Native);
+        assert_eq!(
+            set_server_enabled(kind, "mcp-edit", &profiles, "fs", false).unwrap(),
+            ToggleMode::Unchanged
+        );
+
+        rename_server(kind, "mcp-edit", &profiles, "fs", "files").unwrap();
+        let entries = list_servers(kind, "mcp-edit", &profiles).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "files");
+        assert!(!entries[0].enabled);
+        assert_eq!(entries[0].target, "fs-server --stdio");
+
+        remove_server(kind, "mcp-edit", &profiles, "files").unwrap();
+        assert!(
+            list_servers(kind, "mcp-edit", &profiles)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(
+            remove_server(kind, "mcp-edit", &profiles, "files"),
+            Err(McpEditError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn disable_is_emulated_without_toggle_capability() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profile(&temp, "claude-code");
+        let kind = HarnessKind::ClaudeCode;
+        let dir = profiles.join("claude-code/mcp-edit");
+
+        add_server(
+            kind,
+            "mcp-edit",
+            &profiles,
+            "fs",
+            &stdio("fs-server"),
+            false,
+        )
+        .unwrap();
+        add_server(kind, "mcp-edit", &profiles, "keep", &stdio("keep"), false).unwrap();
+
+        let mode = set_server_enabled(kind, "mcp-edit", &profiles, "fs", false).unwrap();
+        assert_eq!(mode, ToggleMode::Emulated);
+        let servers = read_mcp_config(kind, &dir.join(".mcp.json")).unwrap();
+        assert!(!servers.contains_key("fs"));
+        assert!(servers.contains_key("keep"));
+        assert_eq!(disabled_server_names(&dir), vec!["fs"]);
+
+        let entries = list_servers(kind, "mcp-edit", &profiles).unwrap();
+        let fs_entry = entries.iter().find(|e| e.name == "fs").unwrap();
+        assert!(!fs_entry.enabled);
+
+        rename_server(kind, "mcp-edit", &profiles, "fs", "files").unwrap();
+        assert_eq!(disabled_server_names(&dir), vec!["files"]);
+
+        let mode = set_server_enabled(kind, "mcp-edit", &profiles, "files", true).unwrap();
+        assert_eq!(mode, ToggleMode::Emulated);
+        let servers = read_mcp_config(kind, &dir.join(".mcp.json")).unwrap();
+        assert_eq!(servers["files"]["command"], "fs-server");
+        assert!(!dir.join(DISABLED_FILE).exists());
+    }
+
+    #[test]
+    fn edits_need_an_existing_profile_and_a_valid_name() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profile(&temp, "claude-code");
+        let kind = HarnessKind::ClaudeCode;
+
+        assert!(matches!(
+            list_servers(kind, "missing", &profiles),
+            Err(McpEditError::ProfileNotFound { .. })
+        ));
+        assert!(matches!(
+            add_server(kind, "missing", &profiles, "fs", &stdio("fs"), false),
+            Err(McpEditError::ProfileNotFound { .. })
+        ));
+        assert!(matches!(
+            add_server(kind, "mcp-edit", &profiles, "../fs", &stdio("fs"), false),
+            Err(McpEditError::InvalidName(_))
+        ));
+        add_server(kind, "mcp-edit", &profiles, "fs", &stdio("fs"), false).unwrap();
+        assert!(matches!(
+            rename_server(kind, "mcp-edit", &profiles, "fs", "a/b"),
+            Err(McpEditError::InvalidName(_))
+        ));
+        let entries = list_servers(kind, "mcp-edit", &profiles).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name, "fs");
+    }
+
+    #[test]
+    fn list_shows_transport_target_and_unparseable_entries() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profile(&temp, "claude-code");
+        let kind = HarnessKind::ClaudeCode;
+        fs::write(
+            profiles.join("claude-code/mcp-edit/.mcp.json"),
+            r#"{"mcpServers": {
+                "web": {"type": "http", "url": "https://mcp.example"},
+                "broken": {"type": "carrier-pigeon"},
+                "fs": {"command": "fs-server", "args": ["--root", "/tmp"]}
+            }}"#,
+        )
+        .unwrap();
+
+        let entries = list_servers(kind, "mcp-edit", &profiles).unwrap();
+        let names: Vec<&str> = entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["broken", "fs", "web"]);
+
+        assert_eq!(entries[0].transport, "unknown");
+        assert!(entries[0].error.is_some());
+        assert_eq!(entries[1].transport, "stdio");
+        assert_eq!(entries[1].target, "fs-server --root /tmp");
+        assert!(entries[1].enabled && entries[1].error.is_none());
+        assert_eq!(entries[2].transport, "http");
+        assert_eq!(entries[2].target, "https://mcp.example");
+    }
+
+    #[test]
+    fn add_replaces_only_with_force() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profile(&temp, "claude-code");
+        let kind = HarnessKind::ClaudeCode;
+        let config = profiles.join("claude-code/mcp-edit/.mcp.json");
+        fs::write(&config, r#"{"mcpServers": {}, "other": true}"#).unwrap();
+
+        add_server(kind, "mcp-edit", &profiles, "fs", &stdio("old"), false).unwrap();
+        assert!(matches!(
+            add_server(kind, "mcp-edit", &profiles, "fs", &stdio("new"), false),
+            Err(McpEditError::AlreadyExists { .. })
+        ));
+        assert_eq!(
+            read_mcp_config(kind, &config).unwrap()["fs"]["command"],
+            "old"
+        );
+
+        let web = McpServer::Http(HttpMcpServer {
+            url: "https://mcp.example".to_string(),
+            headers: HashMap::new(),
+            oauth: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+        add_server(kind, "mcp-edit", &profiles, "fs", &web, true).unwrap();
+        let servers = read_mcp_config(kind, &config).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers["fs"]["url"], "https://mcp.example");
+
+        let content: Value = serde_json::from_str(&fs::read_to_string(&config).unwrap()).unwrap();
+        assert_eq!(content["other"], true, "the rest of the file is kept");
+    }
+
+    #[test]
+    fn add_over_a_disabled_server_needs_force_and_enables_it() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profile(&temp, "claude-code");
+        let kind = HarnessKind::ClaudeCode;
+        let dir = profiles.join("claude-code/mcp-edit");
+
+        add_server(kind, "mcp-edit", &profiles, "fs", &stdio("old"), false).unwrap();
+        set_server_enabled(kind, "mcp-edit", &profiles, "fs", false).unwrap();
+        assert!(matches!(
+            add_server(kind, "mcp-edit", &profiles, "fs", &stdio("new"), false),
+            Err(McpEditError::AlreadyExists { .. })
+        ));
+
+        add_server(kind, "mcp-edit", &profiles, "fs", &stdio("new"), true).unwrap();
+        assert!(disabled_server_names(&dir).is_empty());
+        let entries = list_servers(kind, "mcp-edit", &profiles).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries[0].enabled);
+        assert_eq!(entries[0].target, "new --stdio");
+    }
+
+    #[test]
+    fn remove_drops_disabled_servers_and_the_manifest_entry() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profile(&temp, "claude-code");
+        let kind = HarnessKind::ClaudeCode;
+        let dir = profiles.join("claude-code/mcp-edit");
+
+        add_server(kind, "mcp-edit", &profiles, "fs", &stdio("fs"), false).unwrap();
+        add_server(kind, "mcp-edit", &profiles, "keep", &stdio("keep"), false).unwrap();
+        installed(&dir, "fs");
+        set_server_enabled(kind, "mcp-edit", &profiles, "fs", false).unwrap();
+
+        remove_server(kind, "mcp-edit", &profiles, "fs").unwrap();
+        assert!(!dir.join(DISABLED_FILE).exists());
+        assert!(manifest_names(&dir).is_empty());
+        let names: Vec<String> = list_servers(kind, "mcp-edit", &profiles)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(names, ["keep"]);
+    }
+
+    #[test]
+    fn toggling_reports_unchanged_and_missing_servers() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profile(&temp, "opencode");
+        let kind = HarnessKind::OpenCode;
+        let config = profiles.join("opencode/mcp-edit/opencode.jsonc");
+
+        add_server(kind, "mcp-edit", &profiles, "fs", &stdio("fs"), false).unwrap();
+        assert_eq!(
+            set_server_enabled(kind, "mcp-edit", &profiles, "fs", true).unwrap(),
+            ToggleMode::Unchanged
+        );
+        assert!(matches!(
+            set_server_enabled(kind, "mcp-edit", &profiles, "nope", false),
+            Err(McpEditError::NotFound { .. })
+        ));
+
+        set_server_enabled(kind, "mcp-edit", &profiles, "fs", false).unwrap();
+        let native: Value = serde_json::from_str(&fs::read_to_string(&config).unwrap()).unwrap();
+        assert_eq!(native["mcp"]["fs"]["enabled"], false);
+
+        assert_eq!(
+            set_server_enabled(kind, "mcp-edit", &profiles, "fs", true).unwrap(),
+            ToggleMode::Native
+        );
+        let native: Value = serde_json::from_str(&fs::read_to_string(&config).unwrap()).unwrap();
+        assert_ne!(native["mcp"]["fs"]["enabled"], false);
+        assert!(
+            !profiles
+                .join("opencode/mcp-edit")
+                .join(DISABLED_FILE)
+                .exists()
+        );
+    }
+
+    #[test]
+    fn disabling_an_emulated_server_twice_is_unchanged() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profile(&temp, "claude-code");
+        let kind = HarnessKind::ClaudeCode;
+
+        add_server(kind, "mcp-edit", &profiles, "fs", &stdio("fs"), false).unwrap();
+        set_server_enabled(kind, "mcp-edit", &profiles, "fs", false).unwrap();
+        assert_eq!(
+            set_server_enabled(kind, "mcp-edit", &profiles, "fs", false).unwrap(),
+            ToggleMode::Unchanged
+        );
+        assert_eq!(
+            disabled_server_names(&profiles.join("claude-code/mcp-edit")),
+            ["fs"]
+        );
+    }
+
+    #[test]
+    fn rename_refuses_taken_names_and_moves_the_manifest_entry() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profile(&temp, "claude-code");
+        let kind = HarnessKind::ClaudeCode;
+        let dir = profiles.join("claude-code/mcp-edit");
+
+        add_server(kind, "mcp-edit", &profiles, "fs", &stdio("fs"), false).unwrap();
+        add_server(kind, "mcp-edit", &profiles, "off", &stdio("off"), false).unwrap();
+        set_server_enabled(kind, "mcp-edit", &profiles, "off", false).unwrap();
+        installed(&dir, "fs");
+
+        assert!(matches!(
+            rename_server(kind, "mcp-edit", &profiles, "fs", "off"),
+            Err(McpEditError::AlreadyExists { .. })
+        ));
+        assert!(matches!(
+            rename_server(kind, "mcp-edit", &profiles, "nope", "other"),
+            Err(McpEditError::NotFound { .. })
+        ));
+
+        rename_server(kind, "mcp-edit", &profiles, "fs", "files").unwrap();
+        assert_eq!(manifest_names(&dir), ["files"]);
+        let servers = read_mcp_config(kind, &dir.join(".mcp.json")).unwrap();
+        assert!(!servers.contains_key("fs"));
+        assert_eq!(servers["files"]["command"], "fs");
+    }
+
+    #[test]
+    fn add_rejects_fields_the_harness_cannot_store() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profile(&temp, "goose");
+        let kind = HarnessKind::Goose;
+        let McpServer::Stdio(mut server) = stdio("fs") else {
+            unreachable!()
+        };
+        server.cwd = Some(PathBuf::from("/srv/project"));
+
+        let err = add_server(
+            kind,
+            "mcp-edit",
+            &profiles,
+            "fs",
+            &McpServer::Stdio(server),
+            false,
+        )
+        .unwrap_err();
+        assert!(matches!(err, McpEditError::Unsupported { .. }));
+        assert!(err.to_string().contains(CODE_CWD_UNSUPPORTED), "{err}");
+
+        let config = get_profile_config_path(&profiles.join("goose/mcp-edit"), kind);
+        assert!(!config.exists());
+    }
+
+    #[test]
+    fn rename_rewrites_the_name_goose_embeds() {
+        let temp = TempDir::new().unwrap();
+        let profiles = profile(&temp, "goose");
+        let kind = HarnessKind::Goose;
+
+        add_server(kind, "mcp-edit", &profiles, "fs", &stdio("fs"), false).unwrap();
+        rename_server(kind, "mcp-edit", &profiles, "fs", "files").unwrap();
+
+        let config = get_profile_config_path(&profiles.join("goose/mcp-edit"), kind);
+        let servers = read_mcp_config(kind, &config).unwrap();
+        assert!(!servers.contains_key("fs"));
+        assert_eq!(servers["files"]["name"], "files");
+        assert_eq!(servers["files"]["cmd"], "fs");
+    }
+}
@@ -9,9 +9,13 @@ use crate::harness::HarnessConfig;
 use serde_json::Value;
 
 use super::installer::InstallError;
-use super::mcp_config::{mcp_exists, write_mcp_config};
-use super::types::{InstallOptions, InstallTarget, SkipReason, parse_harness_kind};
+use super::manifest::{InstallManifest, ManifestEntry, manifest_path};
+use super::mcp_config::{mcp_exists, native_fragment, write_mcp_config};
+use super::types::{
+    ComponentType, InstallOptions, InstallTarget, SkipReason, SourceInfo, parse_harness_kind,
+};
 use crate::config::BridleConfig;
+use crate::plan::fs;
 
 #[derive(Debug, Clone)]
 pub struct McpInstallSuccess {
@@ -35,7 +39,7 @@ pub enum McpInstallOutcome {
 
 pub type McpInstallResult = Result<McpInstallOutcome, InstallError>;
 
-fn get_profile_config_path(profile_dir: &Path, harness_kind: HarnessKind) -> PathBuf {
+pub(crate) fn get_profile_config_path(profile_dir: &Path, harness_kind: HarnessKind) -> PathBuf {
     match harness_kind {
         HarnessKind::ClaudeCode => profile_dir.join(".mcp.json"),
         HarnessKind::OpenCode => profile_dir.join("opencode.jsonc"),
@@ -74,12 +78,38 @@ pub fn install_mcp(
     install_mcp_to_dir(name, server, target, options, &profiles_dir)
 }
 
+pub fn install_mcp_with_source(
+    name: &str,
+    server: &McpServer,
+    target: &InstallTarget,
+    options: &InstallOptions,
+    source: Option<&SourceInfo>,
+) -> McpInstallResult {
+    let profiles_dir = BridleConfig::profiles_dir().map_err(|_| InstallError::ProfileNotFound {
+        harness: target.harness.clone(),
+        profile: target.profile.as_str().to_string(),
+    })?;
+
+    install_mcp_to_dir_with_source(name, server, target, options, &profiles_dir, source)
+}
+
 pub fn install_mcp_to_dir(
     name: &str,
     server: &McpServer,
     target: &InstallTarget,
     options: &InstallOptions,
     profiles_dir: &Path,
+) -> McpInstallResult {
+    install_mcp_to_dir_with_source(name, server, target, options, profiles_dir, None)
+}
+
+pub fn install_mcp_to_dir_with_source(
+    name: &str,
+    server: &McpServer,
+    target: &InstallTarget,
+    options: &InstallOptions,
+    profiles_dir: &Path,
+    source: Option<&SourceInfo>,
 ) -> McpInstallResult {
     let kind = parse_harness_kind(&target.harness)
         .ok_or_else(|| InstallError::HarnessNotFound(target.harness.clone()))?;
@@ -88,7 +118,7 @@ pub fn install_mcp_to_dir(
         .join(&target.harness)
         .join(target.profile.as_str());
 
-    if !profile_dir.exists() {
+    if !fs::exists(&profile_dir) {
         return Err(InstallError::ProfileNotFound {
             harness: target.harness.clone(),
             profile: target.profile.as_str().to_string(),
@@ -125,12 +155,18 @@ pub fn install_mcp_to_dir(
         .to_native_value(kind, name)
         .map_err(|e| InstallError::WriteFile(std::io::Error::other(e)))?;
 
+    let fragment = native_fragment(kind, name, &native_value);
+
     let mut servers_to_write: HashMap<String, Value> = HashMap::new();
     servers_to_write.insert(name.to_string(), native_value);
 
     write_mcp_config(kind, &profile_config_path, &servers_to_write)
         .map_err(|e| InstallError::WriteFile(std::io::Error::other(e)))?;
 
+    if let Some(source_info) = source {
+        record_mcp_in_manifest(&profile_dir, name, source_info, fragment);
+    }
+
     let harness_path = write_mcp_to_harness_if_active(name, server, target, kind)?;
 
     Ok(McpInstallOutcome::Installed(McpInstallSuccess {
@@ -141,6 +177,21 @@ pub fn install_mcp_to_dir(
     }))
 }
 
+fn record_mcp_in_manifest(profile_dir: &Path, name: &str, source: &SourceInfo, fragment: String) {
+    let manifest_file = manifest_path(profile_dir);
+    let mut manifest = InstallManifest::load(&manifest_file).unwrap_or_default();
+
+    manifest.add_entry(ManifestEntry {
+        component_type: ComponentType::Mcp,
+        name: name.to_string(),
+        source: source.clone(),
+        installed_at: chrono::Utc::now().to_rfc3339(),
+        fragment: Some(fragment),
+    });
+
+    let _ = manifest.save(&manifest_file);
+}
+
 fn write_mcp_to_harness_if_active(
     name: &str,
     server: &McpServer,
@@ -554,4 +605,82 @@ mod tests {
             panic!("Expected Installed outcome");
         }
     }
+
+    fn test_source() -> SourceInfo {
+        SourceInfo {
+            git_ref: Some("main".to_string()),
+            ..crate::install::fixtures::source()
+        }
+    }
+
+    #[test]
+    fn install_mcp_with_source_records_manifest_entry() {
+        let (temp, target, profiles_dir) = setup_test_env("claude-code");
+        let server = create_stdio_server();
+
+        let result = install_mcp_to_dir_with_source(
+            "filesystem",
+            &server,
+            &target,
+            &InstallOptions::default(),
+            &profiles_dir,
+            Some(&test_source()),
+        );
+        assert!(matches!(result, Ok(McpInstallOutcome::Installed(_))));
+
+        let profile_dir = temp.path().join("profiles/claude-code/test");
+        let manifest = InstallManifest::load(&manifest_path(&profile_dir)).unwrap();
+        let entry = manifest
+            .find_component(ComponentType::Mcp, "filesystem")
+            .expect("MCP should be recorded");
+        assert_eq!(entry.source.owner, "owner");
+
+        let fragment: Value = serde_json::from_str(entry.fragment.as_deref().unwrap()).unwrap();
+        let written: Value =
+            serde_json::from_str(&fs::read_to_string(profile_dir.join(".mcp.json")).unwrap())
+                .unwrap();
+        assert_eq!(written["mcpServers"]["filesystem"], fragment);
+    }
+
+    #[test]
+    fn install_mcp_to_goose_records_yaml_fragment() {
+        let (temp, target, profiles_dir) = setup_test_env("goose");
+        let server = create_stdio_server();
+
+        install_mcp_to_dir_with_source(
+            "filesystem",
+            &server,
+            &target,
+            &InstallOptions::default(),
+            &profiles_dir,
+            Some(&test_source()),
+        )
+        .unwrap();
+
+        let profile_dir = temp.path().join("profiles/goose/test");
+        let manifest = InstallManifest::load(&manifest_path(&profile_dir)).unwrap();
+        let fragment = manifest
+            .find_component(ComponentType::Mcp, "filesystem")
+            .and_then(|e| e.fragment.clone())
+            .unwrap();
+        let written = fs::read_to_string(profile_dir.join("config.yaml")).unwrap();
+        assert!(written.contains(&fragment));
+    }
+
+    #[test]
+    fn install_mcp_without_source_skips_manifest() {
+        let (temp, target, profiles_dir) = setup_test_env("claude-code");
+
+        install_mcp_to_dir(
+            "filesystem",
+            &create_stdio_server(),
+            &target,
+            &InstallOptions::default(),
+            &profiles_dir,
+        )
+        .unwrap();
+
+        let profile_dir = temp.path().join("profiles/claude-code/test");
+        assert!(!manifest_path(&profile_dir).exists());
+    }
 }
@@ -0,0 +1,905 @@
+//! Live probing of MCP servers (`bridle mcp test`).
+//!
+//! Launches stdio servers or connects to HTTP/SSE endpoints, performs the
+//! JSON-RPC `initialize` handshake and lists what the server advertises.
+//! Every failure is captured in the [`ProbeReport`] rather than returned, so
+//! one broken server doesn't stop the others from being tested.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use harness_locate::types::EnvValue;
+use harness_locate::{
+    Harness, HarnessKind, HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer,
+};
+use serde::Serialize;
+use serde_json::{Value, json};
+use thiserror::Error;
+
+use super::convert::{ConvertError, read_mcp_servers};
+
+/// Protocol revision bridle offers in `initialize`.
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Used when a server has no `timeout_ms` of its own.
+pub const DEFAULT_TIMEOUT_MS: u64 = 10_000;
+
+/// Lines of server stderr kept in a failed report.
+const STDERR_TAIL_LINES: usize = 20;
+
+#[derive(Debug, Error)]
+pub enum ProbeError {
+    #[error("Profile not found: {harness}/{profile}")]
+    ProfileNotFound { harness: String, profile: String },
+
+    #[error("MCP server '{server}' not found in {harness}/{profile}")]
+    ServerNotFound {
+        harness: String,
+        profile: String,
+        server: String,
+    },
+
+    #[error(transparent)]
+    Read(#[from] ConvertError),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ServerInfo {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Outcome of probing a single server.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProbeReport {
+    pub server: String,
+    pub transport: &'static str,
+    pub enabled: bool,
+    pub ok: bool,
+    /// Round trip of the `initialize` request.
+    pub latency_ms: Option<u64>,
+    /// Wall time of the whole probe, including process start-up.
+    pub total_ms: u64,
+    pub protocol_version: Option<String>,
+    pub server_info: Option<ServerInfo>,
+    pub tools: Vec<String>,
+    pub resources: Vec<String>,
+    pub prompts: Vec<String>,
+    pub error: Option<String>,
+    /// Tail of the server's stderr, kept when a stdio probe fails.
+    pub stderr: Option<String>,
+}
+
+impl ProbeReport {
+    fn new(server: &str, transport: &'static str, enabled: bool) -> Self {
+        Self {
+            server: server.to_string(),
+            transport,
+            enabled,
+            ok: false,
+            latency_ms: None,
+            total_ms: 0,
+            protocol_version: None,
+            server_info: None,
+            tools: Vec::new(),
+            resources: Vec::new(),
+            prompts: Vec::new(),
+            error: None,
+            stderr: None,
+        }
+    }
+}
+
+/// Probes the MCP servers of a profile, or only `server` when given.
+pub fn probe_profile(
+    kind: HarnessKind,
+    profile: &str,
+    profiles_dir: &Path,
+    server: Option<&str>,
+) -> Result<Vec<ProbeReport>, ProbeError> {
+    let harness = Harness::new(kind);
+    let harness_id = crate::harness::HarnessConfig::id(&harness).to_string();
+    let profile_dir = profiles_dir.join(&harness_id).join(profile);
+    if !profile_dir.is_dir() {
+        return Err(ProbeError::ProfileNotFound {
+            harness: harness_id,
+            profile: profile.to_string(),
+        });
+    }
+
+    let mut servers = read_mcp_servers(&harness, &profile_dir)?;
+    if let Some(name) = server {
+        servers.retain(|(n, _)| n == name);
+        if servers.is_empty() {
+            return Err(ProbeError::ServerNotFound {
+                harness: harness_id,
+                profile: profile.to_string(),
+                server: name.to_string(),
+            });
+        }
+    }
+
+    Ok(servers
+        .iter()
+        .map(|(name, parsed)| match parsed {
+            Ok(server) => probe_server(name, server),
+            Err(e) => {
+                let mut report = ProbeReport::new(name, "unknown", true);
+                report.error = Some(format!("Failed to parse server config: {}", e));
+                report
+            }
+        })
+        .collect())
+}
+
+/// Connects to one server and runs `initialize` plus the list requests.
+pub fn probe_server(name: &str, server: &McpServer) -> ProbeReport {
+    let started = Instant::now();
+    let (transport, enabled, timeout_ms) = match server {
+        McpServer::Stdio(s) => ("stdio", s.enabled, s.timeout_ms),
+        McpServer::Sse(s) => ("sse", s.enabled, s.timeout_ms),
+        McpServer::Http(s) => ("http", s.enabled, s.timeout_ms),
+    };
+    let mut report = ProbeReport::new(name, transport, enabled);
+    let deadline = started + Duration::from_millis(timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS));
+
+    let result = match server {
+        McpServer::Stdio(s) => match StdioSession::spawn(s) {
+            Ok(mut session) => {
+                let result = handshake(&mut session, deadline, &mut report);
+                if result.is_err() {
+                    report.stderr = session.stderr_tail();
+                }
+                result
+            }
+            Err(e) => Err(e),
+        },
+        McpServer::Http(s) => handshake(&mut HttpSession::new(s), deadline, &mut report),
+        McpServer::Sse(s) => SseSession::connect(s, deadline)
+            .and_then(|mut session| handshake(&mut session, deadline, &mut report)),
+    };
+
+    report.ok = result.is_ok();
+    report.error = result.err();
+    report.total_ms = started.elapsed().as_millis() as u64;
+    report
+}
+
+/// A JSON-RPC channel to a server.
+trait Session {
+    /// Sends a request and waits for the response with the same id.
+    fn request(
+        &mut self,
+        id: u64,
+        method: &str,
+        params: Value,
+        deadline: Instant,
+    ) -> Result<Value, String>;
+
+    fn notify(&mut self, method: &str, deadline: Instant) -> Result<(), String>;
+}
+
+fn handshake(
+    session: &mut dyn Session,
+    deadline: Instant,
+    report: &mut ProbeReport,
+) -> Result<(), String> {
+    let params = json!({
+        "protocolVersion": PROTOCOL_VERSION,
+        "capabilities": {},
+        "clientInfo": { "name": "bridle", "version": env!("CARGO_PKG_VERSION") },
+    });
+    let sent = Instant::now();
+    let init = session.request(1, "initialize", params, deadline)?;
+    report.latency_ms = Some(sent.elapsed().as_millis() as u64);
+    report.protocol_version = init["protocolVersion"].as_str().map(String::from);
+    report.server_info = init["serverInfo"]["name"].as_str().map(|name| ServerInfo {
+        name: name.to_string(),
+        version: init["serverInfo"]["version"].as_str().map(String::from),
+    });
+
+    session.notify("notifications/initialized", deadline)?;
+
+    let capabilities = &init["capabilities"];
+    let mut next_id = 2;
+    match list_all(session, &mut next_id, "tools/list", "tools", deadline) {
+        Ok(tools) => report.tools = tools,
+        // Servers without tools may not implement the method at all.
+        Err(e) if capabilities.get("tools").is_some() => return Err(e),
+        Err(_) => {}
+    }
+    if capabilities.get("resources").is_some() {
+        report.resources = list_all(
+            session,
+            &mut next_id,
+            "resources/list",
+            "resources",
+            deadline,
+        )?;
+    }
+    if capabilities.get("prompts").is_some() {
+        report.prompts = list_all(session, &mut next_id, "prompts/list", "prompts", deadline)?;
+    }
+    Ok(())
+}
+
+/// Runs a paginated list request and collects item names.
+fn list_all(
+    session: &mut dyn Session,
+    next_id: &mut u64,
+    method: &str,
+    key: &str,
+    deadline: Instant,
+) -> Result<Vec<String>, String> {
+    let mut names = Vec::new();
+    let mut cursor: Option<String> = None;
+    loop {
+        let params = match &cursor {
+            Some(cursor) => json!({ "cursor": cursor }),
+            None => json!({}),
+        };
+        let id = *next_id;
+        *next_id += 1;
+        let result = session.request(id, method, params, deadline)?;
+
+        for item in result[key].as_array().into_iter().flatten() {
+            if let Some(name) = item["name"].as_str().or_else(|| item["uri"].as_str()) {
+                names.push(name.to_string());
+            }
+        }
+
+        match result["nextCursor"].as_str() {
+            Some(next) if !next.is_empty() && cursor.as_deref() != Some(next) => {
+                cursor = Some(next.to_string());
+            }
+            _ => return Ok(names),
+        }
+    }
+}
+
+fn request_body(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn notification_body(method: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method })
+}
+
+/// Extracts the result if `message` is the response to request `id`.
+fn match_response(message: &Value, id: u64) -> Option<Result<Value, String>> {
+    if message.get("method").is_some() || message["id"].as_u64() != Some(id) {
+        return None;
+    }
+    if let Some(error) = message.get("error") {
+        return Some(Err(format!(
+            "JSON-RPC error {}: {}",
+            error["code"],
+            error["message"].as_str().unwrap_or("unknown error")
+        )));
+    }
+    Some(Ok(message.get("result").cloned().unwrap_or(Value::Null)))
+}
+
+fn remaining(deadline: Instant) -> Result<Duration, String> {
+    deadline
+        .checked_duration_since(Instant::now())
+        .filter(|d| !d.is_zero())
+        .ok_or_else(|| "Timed out waiting for the server".to_string())
+}
+
+fn resolve_values(values: &HashMap<String, EnvValue>) -> Vec<(String, String)> {
+    values
+        .iter()
+        .filter_map(|(key, value)| value.resolve().map(|v| (key.clone(), v)))
+        .collect()
+}
+
+/// A server launched as a child process, speaking newline-delimited JSON.
+struct StdioSession {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+    stderr: Arc<Mutex<String>>,
+}
+
+impl StdioSession {
+    fn spawn(server: &StdioMcpServer) -> Result<Self, String> {
+        let mut command = Command::new(&server.command);
+        command
+            .args(&server.args)
+            .envs(resolve_values(&server.env))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        if let Some(cwd) = &server.cwd {
+            command.current_dir(cwd);
+        }
+
+        let mut child = command
+            .spawn()
+            .map_err(|e| format!("Failed to start '{}': {}", server.command, e))?;
+        let stdin = child.stdin.take().expect("stdin is piped");
+        let stdout = child.stdout.take().expect("stdout is piped");
+        let mut stderr_pipe = child.stderr.take().expect("stderr is piped");
+
+        // Reader threads are detached: a server's own children can hold the
+        // pipes open after it is killed, and joining would hang the probe.
+        let (tx, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                let Ok(line) = line else { break };
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let stderr = Arc::new(Mutex::new(String::new()));
+        let sink = Arc::clone(&stderr);
+        thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            while let Ok(n) = stderr_pipe.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                if let Ok(mut out) = sink.lock() {
+                    out.push_str(&String::from_utf8_lossy(&buf[..n]));
+                }
+            }
+        });
+
+        Ok(Self {
+            child,
+            stdin,
+            lines,
+            stderr,
+        })
+    }
+
+    fn send(&mut self, message: &Value) -> Result<(), String> {
+        if let Err(e) = writeln!(self.stdin, "{}", message).and_then(|_| self.stdin.flush()) {
+            thread::sleep(Duration::from_millis(20));
+            if let Ok(Some(_)) = self.child.try_wait() {
+                return Err(self.exited());
+            }
+            return Err(format!("Failed to write to server: {}", e));
+        }
+        Ok(())
+    }
+
+    fn exited(&mut self) -> String {
+        match self.child.try_wait() {
+            Ok(Some(status)) => format!("Server exited before responding ({})", status),
+            _ => "Server closed stdout before responding".to_string(),
+        }
+    }
+
+    fn stderr_tail(&mut self) -> Option<String> {
+        // Give the reader a moment to drain what the server wrote on its way out.
+        thread::sleep(Duration::from_millis(50));
+        let stderr = self.stderr.lock().ok()?;
+        let lines: Vec<&str> = stderr.lines().collect();
+        let tail = lines[lines.len().saturating_sub(STDERR_TAIL_LINES)..].join("\n");
+        (!tail.trim().is_empty()).then_some(tail)
+    }
+}
+
+impl Session for StdioSession {
+    fn request(
+        &mut self,
+        id: u64,
+        method: &str,
+        params: Value,
+        deadline: Instant,
+    ) -> Result<Value, String> {
+        self.send(&request_body(id, method, params))?;
+        loop {
+            let line = match self.lines.recv_timeout(remaining(deadline)?) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(format!("Timed out waiting for '{}' response", method));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    // Let the exit status settle before reporting it.
+                    thread::sleep(Duration::from_millis(20));
+                    return Err(self.exited());
+                }
+            };
+            // Servers may log to stdout or send notifications; skip those.
+            let Ok(message) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            if let Some(result) = match_response(&message, id) {
+                return result;
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str, _deadline: Instant) -> Result<(), String> {
+        self.send(&notification_body(method))
+    }
+}
+
+impl Drop for StdioSession {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+fn agent() -> ureq::Agent {
+    ureq::Agent::config_builder()
+        .http_status_as_error(false)
+        .build()
+        .new_agent()
+}
+
+fn http_error(url: &str, e: ureq::Error) -> String {
+    format!("Request to {} failed: {}", url, e)
+}
+
+/// A Streamable HTTP server: every message is a POST, answered with JSON or
+/// a short SSE stream.
+struct HttpSession {
+    agent: ureq::Agent,
+    url: String,
+    headers: Vec<(String, String)>,
+    session_id: Option<String>,
+}
+
+impl HttpSession {
+    fn new(server: &HttpMcpServer) -> Self {
+        Self {
+            agent: agent(),
+            url: server.url.clone(),
+            headers: resolve_values(&server.headers),
+            session_id: None,
+        }
+    }
+
+    fn post(
+        &mut self,
+        message: &Value,
+        deadline: Instant,
+    ) -> Result<ureq::http::Response<ureq::Body>, String> {
+        let mut request = self
+            .agent
+            .post(&self.url)
+            .header("Content-Type", "application/json")
+            .header("Accept", "application/json, text/event-stream")
+            .header("MCP-Protocol-Version", PROTOCOL_VERSION);
+        for (key, value) in &self.headers {
+            request = request.header(key, value);
+        }
+        if let Some(session_id) = &self.session_id {
+            request = request.header("Mcp-Session-Id", session_id);
+        }
+
+        let mut response = request
+            .config()
+            .timeout_global(Some(remaining(deadline)?))
+            .build()
+            .send(message.to_string())
+            .map_err(|e| http_error(&self.url, e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.body_mut().read_to_string().unwrap_or_default();
+            return Err(format!(
+                "HTTP {} from {}: {}",
+                status,
+                self.url,
+                body.trim()
+            ));
+        }
+        if let Some(id) = response
+            .headers()
+            .get("mcp-session-id")
+            .and_then(|v| v.to_str().ok())
+        {
+            self.session_id = Some(id.to_string());
+        }
+        Ok(response)
+    }
+}
+
+impl Session for HttpSession {
+    fn request(
+        &mut self,
+        id: u64,
+        method: &str,
+        params: Value,
+        deadline: Instant,
+    ) -> Result<Value, String> {
+        let response = self.post(&request_body(id, method, params), deadline)?;
+        let is_stream = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.starts_with("text/event-stream"));
+        let mut body = response.into_body();
+
+        if is_stream {
+            let mut events = SseReader::new(BufReader::new(body.into_reader()));
+            while let Some((_, data)) = events.next_event() {
+                if let Ok(message) = serde_json::from_str::<Value>(&data)
+                    && let Some(result) = match_response(&message, id)
+                {
+                    return result;
+                }
+            }
+            return Err(format!("Stream ended without a '{}' response", method));
+        }
+
+        let text = body
+            .read_to_string()
+            .map_err(|e| http_error(&self.url, e))?;
+        let message: Value = serde_json::from_str(&text)
+            .map_err(|e| format!("Invalid JSON-RPC response to '{}': {}", method, e))?;
+        let messages = match message {
+            Value::Array(batch) => batch,
+            single => vec![single],
+        };
+        messages
+            .iter()
+            .find_map(|m| match_response(m, id))
+            .unwrap_or_else(|| Err(format!("No response to '{}'", method)))
+    }
+
+    fn notify(&mut self, method: &str, deadline: Instant) -> Result<(), String> {
+        self.post(&notification_body(method), deadline).map(|_| ())
+    }
+}
+
+/// A legacy HTTP+SSE server: responses arrive on a long-lived GET stream and
+/// requests are POSTed to the endpoint that stream announces.
+struct SseSession {
+    agent: ureq::Agent,
+    endpoint: String,
+    headers: Vec<(String, String)>,
+    events: Receiver<(String, String)>,
+}
+
+impl SseSession {
+    fn connect(server: &SseMcpServer, deadline: Instant) -> Result<Self, String> {
+        let agent = agent();
+        let headers = resolve_values(&server.headers);
+
+        let mut request = agent.get(&server.url).header("Accept", "text/event-stream");
+        for (key, value) in &headers {
+            request = request.header(key, value);
+        }
+        let response = request
+            .config()
+            .timeout_global(Some(remaining(deadline)?))
+            .build()
+            .call()
+            .map_err(|e| http_error(&server.url, e))?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {} from {}", response.status(), server.url));
+        }
+
+        let (tx, events) = mpsc::channel();
+        let body = response.into_body();
+        thread::spawn(move || {
+            let mut reader = SseReader::new(BufReader::new(body.into_reader()));
+            while let Some(event) = reader.next_event() {
+                if tx.send(event).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let endpoint = loop {
+            match events.recv_timeout(remaining(deadline)?) {
+                Ok((event, data)) if event == "endpoint" => break data,
+                Ok(_) => continue,
+                Err(_) => return Err("Server did not announce a message endpoint".to_string()),
+            }
+        };
+
+        Ok(Self {
+            agent,
+            endpoint: join_url(&server.url, endpoint.trim()),
+            headers,
+            events,
+        })
+    }
+
+    fn post(&self, message: &Value, deadline: Instant) -> Result<(), String> {
+        let mut request = self
+            .agent
+            .post(&self.endpoint)
+            .header("Content-Type", "application/json");
+        for (key, value) in &self.headers {
+            request = request.header(key, value);
+        }
+        let mut response = request
+            .config()
+            .timeout_global(Some(remaining(deadline)?))
+            .build()
+            .send(message.to_string())
+            .map_err(|e| http_error(&self.endpoint, e))?;
+        if !response.status().is_success() {
+            let body = response.body_mut().read_to_string().unwrap_or_default();
+            return Err(format!(
+                "HTTP {} from {}: {}",
+                response.status(),
+                self.endpoint,
+                body.trim()
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Session for SseSession {
+    fn request(
+        &mut self,
+        id: u64,
+        method: &str,
+        params: Value,
+        deadline: Instant,
+    ) -> Result<Value, String> {
+        self.post(&request_body(id, method, params), deadline)?;
+        loop {
+            let (_, data) = match self.events.recv_timeout(remaining(deadline)?) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(format!("Timed out waiting for '{}' response", method));
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err("Server closed the event stream".to_string());
+                }
+            };
+            if let Ok(message) = serde_json::from_str::<Value>(&data)
+                && let Some(result) = match_response(&message, id)
+            {
+                return result;
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str, deadline: Instant) -> Result<(), String> {
+        self.post(&notification_body(method), deadline)
+    }
+}
+
+/// Minimal `text/event-stream` parser yielding `(event, data)` pairs.
+struct SseReader<R> {
+    reader: R,
+}
+
+impl<R: BufRead> SseReader<R> {
+    fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    fn next_event(&mut self) -> Option<(String, String)> {
+        let mut event = String::from("message");
+        let mut data: Vec<String> = Vec::new();
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line).ok()? == 0 {
+                return (!data.is_empty()).then(|| (event, data.join("\n")));
+            }
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                if data.is_empty() {
+                    continue;
+                }
+                return Some((event, data.join("\n")));
+            }
+            let (field, value) = line.split_once(':').unwrap_or((line, ""));
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            match field {
+                "event" => event = value.to_string(),
+                "data" => data.push(value.to_string()),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Resolves the endpoint an SSE server announces against its stream URL.
+fn join_url(base: &str, endpoint: &str) -> String {
+    if endpoint.starts_with("http://") || endpoint.starts_with("https://") {
+        return endpoint.to_string();
+    }
+    let origin_end = base
+        .find("://")
+        .and_then(|scheme| base[scheme + 3..].find('/').map(|i| scheme + 3 + i))
+        .unwrap_or(base.len());
+    if endpoint.starts_with('/') {
+        return format!("{}{}", &base[..origin_end], endpoint);
+    }
+    let dir_end = base[origin_end..]
+        .rfind('/')
+        .map(|i| origin_end + i + 1)
+        .unwrap_or(base.len());
+    let dir = &base[..dir_end];
+    if dir.ends_with('/') {
+        format!("{}{}", dir, endpoint)
+    } else {
+        format!("{}/{}", dir, endpoint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use tempfile::TempDir;
+
+    /// Answers `initialize` and `tools/list` the way a minimal server would.
+    #[cfg(unix)]
+    const FAKE_SERVER: &str = r#"#!/bin/sh
+read init
+echo 'starting up'
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-06-18","capabilities":{"tools":{},"prompts":{}},"serverInfo":{"name":"fake","version":"0.1.0"}}}'
+read initialized
+read tools
+echo '{"jsonrpc":"2.0","method":"notifications/message","params":{}}'
+echo '{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"echo"},{"name":"add"}]}}'
+read prompts
+echo '{"jsonrpc":"2.0","id":3,"result":{"prompts":[{"name":"greet"}]}}'
+"#;
+
+    #[cfg(unix)]
+    fn script(temp: &TempDir, name: &str, body: &str) -> StdioMcpServer {
+        use std::os::unix::fs::PermissionsExt;
+        let path = temp.path().join(name);
+        std::fs::write(&path, body).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        StdioMcpServer {
+            command: path.to_string_lossy().into_owned(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: Some(5_000),
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn probe_stdio_server_lists_tools_and_prompts() {
+        let temp = TempDir::new().unwrap();
+        let server = script(&temp, "fake-mcp", FAKE_SERVER);
+
+        let report = probe_server("fake", &McpServer::Stdio(server));
+        assert!(report.ok, "{:?}", report.error);
+        assert_eq!(report.protocol_version.as_deref(), Some("2025-06-18"));
+        assert_eq!(report.server_info.unwrap().name, "fake");
+        assert_eq!(report.tools, vec!["echo", "add"]);
+        assert_eq!(report.prompts, vec!["greet"]);
+        assert!(report.latency_ms.is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn probe_stdio_failure_captures_stderr_and_timeout() {
+        let temp = TempDir::new().unwrap();
+        let crashing = script(
+            &temp,
+            "crash",
+            "#!/bin/sh\necho 'missing API_KEY' >&2\nexit 3\n",
+        );
+        let report = probe_server("crash", &McpServer::Stdio(crashing));
+        assert!(!report.ok);
+        assert!(report.error.unwrap().contains("exited"));
+        assert_eq!(report.stderr.as_deref(), Some("missing API_KEY"));
+
+        let mut silent = script(&temp, "silent", "#!/bin/sh\nread line\nsleep 5\n");
+        silent.timeout_ms = Some(200);
+        let started = Instant::now();
+        let report = probe_server("silent", &McpServer::Stdio(silent));
+        assert!(!report.ok);
+        assert!(report.error.unwrap().contains("Timed out"));
+        assert!(started.elapsed() < Duration::from_secs(3));
+    }
+
+    #[test]
+    fn probe_http_server_tracks_session() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/mcp", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut sessions = Vec::new();
+            for _ in 0..3 {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut length = 0;
+                let mut session = None;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let lower = line.to_ascii_lowercase();
+                    if let Some(v) = lower.strip_prefix("content-length:") {
+                        length = v.trim().parse().unwrap();
+                    }
+                    if let Some(v) = lower.strip_prefix("mcp-session-id:") {
+                        session = Some(v.trim().to_string());
+                    }
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                let request: Value = serde_json::from_slice(&body).unwrap();
+                sessions.push(session);
+
+                let (status, body) = match request["method"].as_str().unwrap() {
+                    "initialize" => (
+                        "200 OK",
+                        r#"{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2025-03-26","capabilities":{"tools":{}},"serverInfo":{"name":"web"}}}"#,
+                    ),
+                    "tools/list" => (
+                        "200 OK",
+                        r#"{"jsonrpc":"2.0","id":2,"result":{"tools":[{"name":"fetch"}]}}"#,
+                    ),
+                    _ => ("202 Accepted", ""),
+                };
+                let mut stream = reader.into_inner();
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nMcp-Session-Id: s1\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            sessions
+        });
+
+        let server = McpServer::Http(HttpMcpServer {
+            url,
+            headers: HashMap::new(),
+            oauth: None,
+            enabled: true,
+            timeout_ms: Some(5_000),
+        });
+        let report = probe_server("web", &server);
+        assert!(report.ok, "{:?}", report.error);
+        assert_eq!(report.protocol_version.as_deref(), Some("2025-03-26"));
+        assert_eq!(report.tools, vec!["fetch"]);
+
+        let sessions = handle.join().unwrap();
+        assert_eq!(sessions[0], None);
+        assert_eq!(sessions[2].as_deref(), Some("s1"));
+    }
+
+    #[test]
+    fn sse_reader_and_endpoint_resolution() {
+        let stream =
+            "event: endpoint\ndata: /messages?session=1\n\n: ping\n\ndata: {\"a\":\ndata: 1}\n\n";
+        let mut reader = SseReader::new(stream.as_bytes());
+        assert_eq!(
+            reader.next_event(),
+            Some(("endpoint".to_string(), "/messages?session=1".to_string()))
+        );
+        assert_eq!(
+            reader.next_event(),
+            Some(("message".to_string(), "{\"a\":\n1}".to_string()))
+        );
+        assert_eq!(reader.next_event(), None);
+
+        assert_eq!(
+            join_url("http://host:8080/mcp/sse", "/messages?s=1"),
+            "http://host:8080/messages?s=1"
+        );
+        assert_eq!(
+            join_url("http://host/mcp/sse", "messages"),
+            "http://host/mcp/messages"
+        );
+        assert_eq!(join_url("http://host/sse", "https://x/m"), "https://x/m");
+    }
+}
@@ -3,13 +3,26 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+pub mod convert;
 pub mod discovery;
+pub mod doctor;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub mod hook_installer;
 pub mod installer;
+pub mod lockfile;
 pub mod manifest;
+pub mod marketplace;
 pub mod mcp_config;
+pub mod mcp_edit;
 pub mod mcp_installer;
+pub mod mcp_probe;
+pub mod project;
+pub mod registry;
+pub mod spec;
 pub mod types;
 pub mod uninstaller;
+pub mod updater;
 
 pub use discovery::{DiscoveryError, discover_skills};
 pub use types::{parse_harness_kind, *};
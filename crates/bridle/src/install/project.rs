@@ -0,0 +1,767 @@
+//! Project-scoped installs into a repository's harness directories.
+//!
+//! Components land in the harness's project locations (`.claude/`, `.opencode/`,
+//! `.mcp.json`, ...) and are tracked in `<root>/.bridle-manifest.json`, keyed by
+//! harness, so bridle can list, remove and replace only what it put there.
+
+use crate::plan::fs;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+use harness_locate::{DirectoryResource, Harness, HarnessKind, McpServer, Scope};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use skills_locate::Source;
+use thiserror::Error;
+
+use super::convert::{ConvertError, component_dirs, read_markdown, read_mcp_servers, read_skills};
+use super::discovery::source_info;
+use super::installer::{
+    InstallError, skill_for_profile, transform_agent_for_opencode, validate_component_name,
+};
+use super::manifest::{InstallManifest, ManifestEntry, ManifestError, manifest_path};
+use super::mcp_config::{
+    mcp_exists, native_fragment, read_mcp_config, remove_mcp_config, write_mcp_config,
+};
+use super::types::{AgentInfo, CommandInfo, ComponentType, InstallOptions, SkillInfo, SourceInfo};
+use crate::harness::HarnessConfig;
+
+#[derive(Debug, Error)]
+pub enum ProjectError {
+    #[error("{harness} has no project-scope location for {component}")]
+    Unsupported { harness: String, component: String },
+
+    #[error("Profile not found: {harness}/{profile}")]
+    ProfileNotFound { harness: String, profile: String },
+
+    #[error("{component} is not installed in this project by bridle")]
+    NotInstalled { component: String },
+
+    #[error("Failed to update MCP config: {0}")]
+    McpConfig(String),
+
+    #[error(transparent)]
+    Install(#[from] InstallError),
+
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+
+    #[error(transparent)]
+    Read(#[from] ConvertError),
+}
+
+/// Per-repository manifest of components bridle installed at project scope.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProjectManifest {
+    #[serde(default)]
+    pub harnesses: BTreeMap<String, ProjectState>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProjectState {
+    /// Profile last switched into the project for this harness
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    #[serde(flatten)]
+    pub manifest: InstallManifest,
+}
+
+impl ProjectManifest {
+    pub fn load(root: &Path) -> Result<Self, ManifestError> {
+        let path = manifest_path(root);
+        if !fs::exists(&path) {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&path).map_err(ManifestError::Read)?;
+        serde_json::from_str(&content).map_err(ManifestError::Parse)
+    }
+
+    pub fn save(&self, root: &Path) -> Result<(), ManifestError> {
+        let content = serde_json::to_string_pretty(self).map_err(ManifestError::Serialize)?;
+        fs::write(manifest_path(root), content).map_err(ManifestError::Write)
+    }
+
+    pub fn state(&self, harness_id: &str) -> Option<&ProjectState> {
+        self.harnesses.get(harness_id)
+    }
+
+    fn state_mut(&mut self, harness_id: &str) -> &mut ProjectState {
+        self.harnesses.entry(harness_id.to_string()).or_default()
+    }
+}
+
+/// Nearest ancestor of `start` containing a `.git` entry.
+pub fn find_project_root(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .find(|dir| dir.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Resolve a `--project [path]` argument. Without a path, the enclosing git
+/// repository of the current directory is used, falling back to the
+/// directory itself.
+pub fn resolve_project_root(path: Option<&Path>) -> std::io::Result<PathBuf> {
+    match path {
+        Some(path) => std::fs::canonicalize(path),
+        None => {
+            let cwd = std::env::current_dir()?;
+            Ok(find_project_root(&cwd).unwrap_or(cwd))
+        }
+    }
+}
+
+/// A component to write at project scope.
+#[derive(Debug, Clone, Copy)]
+pub enum ProjectComponent<'a> {
+    Skill(&'a SkillInfo),
+    Agent(&'a AgentInfo),
+    Command(&'a CommandInfo),
+    Mcp(&'a str, &'a McpServer),
+}
+
+impl ProjectComponent<'_> {
+    pub fn component_type(&self) -> ComponentType {
+        match self {
+            Self::Skill(_) => ComponentType::Skill,
+            Self::Agent(_) => ComponentType::Agent,
+            Self::Command(_) => ComponentType::Command,
+            Self::Mcp(..) => ComponentType::Mcp,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Skill(skill) => &skill.name,
+            Self::Agent(agent) => &agent.name,
+            Self::Command(command) => &command.name,
+            Self::Mcp(name, _) => name,
+        }
+    }
+}
+
+/// Install one component into the project and record it in the project
+/// manifest. Returns the written path, or `None` when it already existed and
+/// `options.force` is unset.
+pub fn install_to_project(
+    component: ProjectComponent<'_>,
+    kind: HarnessKind,
+    root: &Path,
+    options: &InstallOptions,
+    source: &SourceInfo,
+) -> Result<Option<PathBuf>, ProjectError> {
+    let Some((name, path, fragment)) = write_component(component, kind, root, options)? else {
+        return Ok(None);
+    };
+
+    let mut manifest = ProjectManifest::load(root)?;
+    record(
+        manifest.state_mut(Harness::new(kind).id()),
+        component.component_type(),
+        name,
+        source,
+        fragment,
+    );
+    manifest.save(root)?;
+
+    Ok(Some(path))
+}
+
+/// Remove a bridle-installed component from the project.
+pub fn uninstall_from_project(
+    kind: HarnessKind,
+    root: &Path,
+    component_type: ComponentType,
+    name: &str,
+) -> Result<(), ProjectError> {
+    let harness = Harness::new(kind);
+    let harness_id = harness.id();
+    let mut manifest = ProjectManifest::load(root)?;
+    let tracked = manifest
+        .state(harness_id)
+        .is_some_and(|s| s.manifest.find_component(component_type, name).is_some());
+    if !tracked {
+        return Err(ProjectError::NotInstalled {
+            component: label(component_type, name),
+        });
+    }
+
+    remove_component(kind, root, component_type, name)?;
+    manifest
+        .state_mut(harness_id)
+        .manifest
+        .remove_component(component_type, name);
+    manifest.save(root)?;
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProjectSwitch {
+    pub harness: String,
+    pub profile: String,
+    pub root: PathBuf,
+    pub previous: Option<String>,
+    pub removed: Vec<String>,
+    pub installed: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+/// Switch a project to a profile without touching the global harness config.
+///
+/// Components copied in by the previously switched profile are removed first;
+/// anything else in the project (hand-written or installed from a source) is
+/// left alone. Profile components overwrite same-named project files.
+pub fn switch_project_profile(
+    kind: HarnessKind,
+    root: &Path,
+    profile: &str,
+    profile_dir: &Path,
+) -> Result<ProjectSwitch, ProjectError> {
+    let harness = Harness::new(kind);
+    let harness_id = harness.id();
+    if !fs::is_dir(profile_dir) {
+        return Err(ProjectError::ProfileNotFound {
+            harness: harness_id.to_string(),
+            profile: profile.to_string(),
+        });
+    }
+
+    let mut manifest = ProjectManifest::load(root)?;
+    let state = manifest.state_mut(harness_id);
+    let previous = state.profile.take();
+
+    let mut switch = ProjectSwitch {
+        harness: harness_id.to_string(),
+        profile: profile.to_string(),
+        root: root.to_path_buf(),
+        previous: previous.clone(),
+        removed: Vec::new(),
+        installed: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    if let Some(previous) = &previous {
+        let previous_source = profile_source(&profile_dir.with_file_name(previous));
+        let stale: Vec<(ComponentType, String)> = state
+            .manifest
+            .entries
+            .iter()
+            .filter(|e| e.source.location() == previous_source.location())
+            .map(|e| (e.component_type, e.name.clone()))
+            .collect();
+        for (component_type, name) in stale {
+            remove_component(kind, root, component_type, &name)?;
+            state.manifest.remove_component(component_type, &name);
+            switch.removed.push(label(component_type, &name));
+        }
+    }
+
+    let source = profile_source(profile_dir);
+    let skills = read_skills(&harness, profile_dir)?;
+    let agents = read_markdown(profile_dir, &component_dirs(&harness, ComponentType::Agent))?;
+    let commands = read_markdown(
+        profile_dir,
+        &component_dirs(&harness, ComponentType::Command),
+    )?;
+    let servers = read_mcp_servers(&harness, profile_dir)?;
+
+    let agents: Vec<AgentInfo> = agents
+        .into_iter()
+        .map(|(name, content)| AgentInfo {
+            path: format!("agents/{}.md", name),
+            name,
+            description: None,
+            content,
+        })
+        .collect();
+    let commands: Vec<CommandInfo> = commands
+        .into_iter()
+        .map(|(name, content)| CommandInfo {
+            path: format!("commands/{}.md", name),
+            name,
+            description: None,
+            content,
+        })
+        .collect();
+    let servers: Vec<(String, McpServer)> = servers
+        .into_iter()
+        .filter_map(|(name, server)| match server {
+            Ok(server) if server.validate_capabilities(kind).is_ok() => Some((name, server)),
+            _ => {
+                switch.skipped.push(format!("mcp {} (unsupported)", name));
+                None
+            }
+        })
+        .collect();
+
+    let components = skills
+        .iter()
+        .map(ProjectComponent::Skill)
+        .chain(agents.iter().map(ProjectComponent::Agent))
+        .chain(commands.iter().map(ProjectComponent::Command))
+        .chain(servers.iter().map(|(n, s)| ProjectComponent::Mcp(n, s)));
+
+    let options = InstallOptions { force: true };
+    for component in components {
+        let label = label(component.component_type(), component.name());
+        match write_component(component, kind, root, &options) {
+            Ok(Some((name, _, fragment))) => {
+                record(state, component.component_type(), name, &source, fragment);
+                switch.installed.push(label);
+            }
+            Ok(None) => {}
+            Err(ProjectError::Unsupported { .. }) => {
+                switch
+                    .skipped
+                    .push(format!("{} (no project location)", label));
+            }
+            Err(e) => return Err(e),
+        }
+    }
+
+    state.profile = Some(profile.to_string());
+    manifest.save(root)?;
+    Ok(switch)
+}
+
+/// What a harness currently has at project scope.
+#[derive(Debug, Default, Serialize)]
+pub struct ProjectResources {
+    pub harness: String,
+    pub root: PathBuf,
+    pub profile: Option<String>,
+    pub skills: Vec<String>,
+    pub agents: Vec<String>,
+    pub commands: Vec<String>,
+    pub mcp_servers: Vec<String>,
+    /// Number of these components bridle installed and tracks
+    pub managed: usize,
+}
+
+impl ProjectResources {
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty()
+            && self.agents.is_empty()
+            && self.commands.is_empty()
+            && self.mcp_servers.is_empty()
+    }
+}
+
+/// List the skills, agents, commands and MCP servers present in a project
+/// for one harness, whether or not bridle installed them.
+pub fn scan_project(kind: HarnessKind, root: &Path) -> ProjectResources {
+    let harness = Harness::new(kind);
+    let scope = Scope::Project(root.to_path_buf());
+    let state = ProjectManifest::load(root).ok();
+    let state = state.as_ref().and_then(|m| m.state(harness.id()));
+
+    let skills = harness
+        .skills(&scope)
+        .ok()
+        .flatten()
+        .map(|r| list_entries(&r.path, |p| p.join("SKILL.md").is_file()))
+        .unwrap_or_default();
+    let agents = harness
+        .agents(&scope)
+        .ok()
+        .flatten()
+        .map(|r| list_markdown(&r.path))
+        .unwrap_or_default();
+    let commands = harness
+        .commands(&scope)
+        .ok()
+        .flatten()
+        .map(|r| list_markdown(&r.path))
+        .unwrap_or_default();
+    let mut mcp_servers: Vec<String> = harness
+        .mcp(&scope)
+        .ok()
+        .flatten()
+        .and_then(|r| read_mcp_config(kind, &r.file).ok())
+        .map(|servers| servers.into_keys().collect())
+        .unwrap_or_default();
+    mcp_servers.sort();
+
+    ProjectResources {
+        harness: harness.id().to_string(),
+        root: root.to_path_buf(),
+        profile: state.and_then(|s| s.profile.clone()),
+        skills,
+        agents,
+        commands,
+        mcp_servers,
+        managed: state.map(|s| s.manifest.entries.len()).unwrap_or(0),
+    }
+}
+
+/// Singular "type name" label, e.g. `skill review`.
+fn label(component_type: ComponentType, name: &str) -> String {
+    format!(
+        "{} {}",
+        component_type.dir_name().trim_end_matches('s'),
+        name
+    )
+}
+
+/// Manifest source recorded for components copied in from a profile.
+fn profile_source(profile_dir: &Path) -> SourceInfo {
+    source_info(&Source::Local(profile_dir.to_path_buf()))
+}
+
+fn record(
+    state: &mut ProjectState,
+    component_type: ComponentType,
+    name: String,
+    source: &SourceInfo,
+    fragment: Option<String>,
+) {
+    state.manifest.add_entry(ManifestEntry {
+        component_type,
+        name,
+        source: source.clone(),
+        installed_at: chrono::Utc::now().to_rfc3339(),
+        fragment,
+    });
+}
+
+fn project_dir(
+    resource: harness_locate::Result<Option<DirectoryResource>>,
+    kind: HarnessKind,
+    component_type: ComponentType,
+) -> Result<PathBuf, ProjectError> {
+    match resource {
+        Ok(Some(resource)) => Ok(resource.path),
+        _ => Err(unsupported(kind, component_type)),
+    }
+}
+
+fn project_mcp_file(kind: HarnessKind, root: &Path) -> Result<PathBuf, ProjectError> {
+    match Harness::new(kind).mcp(&Scope::Project(root.to_path_buf())) {
+        Ok(Some(resource)) => Ok(resource.file),
+        _ => Err(unsupported(kind, ComponentType::Mcp)),
+    }
+}
+
+fn unsupported(kind: HarnessKind, component_type: ComponentType) -> ProjectError {
+    ProjectError::Unsupported {
+        harness: Harness::new(kind).id().to_string(),
+        component: component_type.dir_name().to_string(),
+    }
+}
+
+/// Written name, path and (for MCPs) native fragment of a component.
+type Written = (String, PathBuf, Option<String>);
+
+fn write_component(
+    component: ProjectComponent<'_>,
+    kind: HarnessKind,
+    root: &Path,
+    options: &InstallOptions,
+) -> Result<Option<Written>, ProjectError> {
+    validate_component_name(component.name())?;
+    let harness = Harness::new(kind);
+    let scope = Scope::Project(root.to_path_buf());
+
+    let (name, path, content) = match component {
+        ProjectComponent::Skill(skill) => {
+            let dir = project_dir(harness.skills(&scope), kind, ComponentType::Skill)?;
+            let (name, content) = skill_for_profile(Some(kind), skill);
+            let path = dir.join(&name).join("SKILL.md");
+            (name, path, content)
+        }
+        ProjectComponent::Agent(agent) => {
+            let dir = project_dir(harness.agents(&scope), kind, ComponentType::Agent)?;
+            let content = if kind == HarnessKind::OpenCode {
+                transform_agent_for_opencode(&agent.content)
+            } else {
+                agent.content.clone()
+            };
+            (
+                agent.name.clone(),
+                dir.join(format!("{}.md", agent.name)),
+                content,
+            )
+        }
+        ProjectComponent::Command(command) => {
+            let dir = project_dir(harness.commands(&scope), kind, ComponentType::Command)?;
+            let path = dir.join(format!("{}.md", command.name));
+            (command.name.clone(), path, command.content.clone())
+        }
+        ProjectComponent::Mcp(name, server) => {
+            let path = project_mcp_file(kind, root)?;
+            if !options.force && mcp_exists(kind, &path, name).unwrap_or(false) {
+                return Ok(None);
+            }
+            let value = server
+                .to_native_value(kind, name)
+                .map_err(|e| ProjectError::McpConfig(e.to_string()))?;
+            let fragment = native_fragment(kind, name, &value);
+            let servers: HashMap<String, Value> = HashMap::from([(name.to_string(), value)]);
+            write_mcp_config(kind, &path, &servers)
+                .map_err(|e| ProjectError::McpConfig(e.to_string()))?;
+            return Ok(Some((name.to_string(), path, Some(fragment))));
+        }
+    };
+
+    if fs::exists(&path) && !options.force {
+        return Ok(None);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(InstallError::CreateDir)?;
+    }
+    fs::write(&path, content).map_err(InstallError::WriteFile)?;
+    Ok(Some((name, path, None)))
+}
+
+fn remove_component(
+    kind: HarnessKind,
+    root: &Path,
+    component_type: ComponentType,
+    name: &str,
+) -> Result<(), ProjectError> {
+    validate_component_name(name)?;
+    let harness = Harness::new(kind);
+    let scope = Scope::Project(root.to_path_buf());
+
+    let path = match component_type {
+        ComponentType::Skill => {
+            project_dir(harness.skills(&scope), kind, component_type)?.join(name)
+        }
+        ComponentType::Agent => {
+            project_dir(harness.agents(&scope), kind, component_type)?.join(format!("{}.md", name))
+        }
+        ComponentType::Command => project_dir(harness.commands(&scope), kind, component_type)?
+            .join(format!("{}.md", name)),
+        ComponentType::Mcp => {
+            let file = project_mcp_file(kind, root)?;
+            remove_mcp_config(kind, &file, name)
+                .map_err(|e| ProjectError::McpConfig(e.to_string()))?;
+            return Ok(());
+        }
+        ComponentType::Hook => return Err(unsupported(kind, component_type)),
+    };
+
+    let removed = if fs::is_dir(&path) {
+        fs::remove_dir_all(&path)
+    } else {
+        fs::remove_file(&path)
+    };
+    match removed {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(InstallError::WriteFile(e).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+fn list_entries(dir: &Path, keep: impl Fn(&Path) -> bool) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|e| keep(&e.path()))
+        .filter_map(|e| e.file_name().to_str().map(String::from))
+        .collect();
+    names.sort();
+    names
+}
+
+fn list_markdown(dir: &Path) -> Vec<String> {
+    list_entries(dir, |p| {
+        fs::is_file(p) && p.extension().and_then(|e| e.to_str()) == Some("md")
+    })
+    .into_iter()
+    .map(|n| n.trim_end_matches(".md").to_string())
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::install::fixtures::source;
+    use harness_locate::mcp::StdioMcpServer;
+    use tempfile::TempDir;
+
+    fn skill(name: &str) -> SkillInfo {
+        SkillInfo {
+            name: name.to_string(),
+            description: None,
+            path: format!("skills/{}/SKILL.md", name),
+            content: format!("---\nname: {}\ndescription: Test\n---\nBody", name),
+        }
+    }
+
+    fn stdio(command: &str) -> McpServer {
+        McpServer::Stdio(StdioMcpServer {
+            command: command.to_string(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        })
+    }
+
+    #[test]
+    fn install_writes_to_project_dirs_and_tracks_in_manifest() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let options = InstallOptions { force: false };
+        let server = stdio("npx");
+
+        let path = install_to_project(
+            ProjectComponent::Skill(&skill("review")),
+            HarnessKind::ClaudeCode,
+            root,
+            &options,
+            &source(),
+        )
+        .unwrap();
+        assert_eq!(path, Some(root.join(".claude/skills/review/SKILL.md")));
+
+        install_to_project(
+            ProjectComponent::Mcp("fs", &server),
+            HarnessKind::ClaudeCode,
+            root,
+            &options,
+            &source(),
+        )
+        .unwrap();
+        assert!(mcp_exists(HarnessKind::ClaudeCode, &root.join(".mcp.json"), "fs").unwrap());
+
+        let again = install_to_project(
+            ProjectComponent::Skill(&skill("review")),
+            HarnessKind::ClaudeCode,
+            root,
+            &options,
+            &source(),
+        )
+        .unwrap();
+        assert_eq!(again, None);
+
+        let manifest = ProjectManifest::load(root).unwrap();
+        let state = manifest.state("claude-code").unwrap();
+        assert_eq!(state.manifest.entries.len(), 2);
+        assert!(
+            state
+                .manifest
+                .find_component(ComponentType::Mcp, "fs")
+                .is_some()
+        );
+
+        let resources = scan_project(HarnessKind::ClaudeCode, root);
+        assert_eq!(resources.skills, vec!["review"]);
+        assert_eq!(resources.mcp_servers, vec!["fs"]);
+        assert_eq!(resources.managed, 2);
+    }
+
+    #[test]
+    fn uninstall_only_removes_tracked_components() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join(".opencode/skill/hand-written")).unwrap();
+        fs::write(root.join(".opencode/skill/hand-written/SKILL.md"), "x").unwrap();
+
+        install_to_project(
+            ProjectComponent::Skill(&skill("Code Review")),
+            HarnessKind::OpenCode,
+            root,
+            &InstallOptions { force: false },
+            &source(),
+        )
+        .unwrap();
+        assert!(root.join(".opencode/skill/code-review/SKILL.md").exists());
+
+        let err = uninstall_from_project(
+            HarnessKind::OpenCode,
+            root,
+            ComponentType::Skill,
+            "hand-written",
+        );
+        assert!(matches!(err, Err(ProjectError::NotInstalled { .. })));
+
+        uninstall_from_project(
+            HarnessKind::OpenCode,
+            root,
+            ComponentType::Skill,
+            "code-review",
+        )
+        .unwrap();
+        assert!(!root.join(".opencode/skill/code-review").exists());
+        assert!(root.join(".opencode/skill/hand-written/SKILL.md").exists());
+    }
+
+    #[test]
+    fn switch_replaces_previous_profile_components_only() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("repo");
+        let profiles = temp.path().join("profiles/claude-code");
+        fs::create_dir_all(profiles.join("work/skills/alpha")).unwrap();
+        fs::write(profiles.join("work/skills/alpha/SKILL.md"), "alpha").unwrap();
+        fs::create_dir_all(profiles.join("work/agents")).unwrap();
+        fs::write(profiles.join("work/agents/helper.md"), "help").unwrap();
+        fs::create_dir_all(profiles.join("home/skills/beta")).unwrap();
+        fs::write(profiles.join("home/skills/beta/SKILL.md"), "beta").unwrap();
+        fs::create_dir_all(&root).unwrap();
+
+        install_to_project(
+            ProjectComponent::Skill(&skill("mine")),
+            HarnessKind::ClaudeCode,
+            &root,
+            &InstallOptions { force: false },
+            &source(),
+        )
+        .unwrap();
+
+        let first = switch_project_profile(
+            HarnessKind::ClaudeCode,
+            &root,
+            "work",
+            &profiles.join("work"),
+        )
+        .unwrap();
+        assert_eq!(first.installed, vec!["skill alpha", "agent helper"]);
+        assert!(root.join(".claude/agents/helper.md").exists());
+
+        let second = switch_project_profile(
+            HarnessKind::ClaudeCode,
+            &root,
+            "home",
+            &profiles.join("home"),
+        )
+        .unwrap();
+        assert_eq!(second.previous.as_deref(), Some("work"));
+        assert_eq!(second.removed.len(), 2);
+        assert!(!root.join(".claude/skills/alpha").exists());
+        assert!(!root.join(".claude/agents/helper.md").exists());
+        assert!(root.join(".claude/skills/beta/SKILL.md").exists());
+        assert!(root.join(".claude/skills/mine/SKILL.md").exists());
+
+        let resources = scan_project(HarnessKind::ClaudeCode, &root);
+        assert_eq!(resources.profile.as_deref(), Some("home"));
+        assert_eq!(resources.skills, vec!["beta", "mine"]);
+    }
+
+    #[test]
+    fn unsupported_project_locations_are_reported() {
+        let temp = TempDir::new().unwrap();
+        let result = install_to_project(
+            ProjectComponent::Mcp("fs", &stdio("npx")),
+            HarnessKind::AmpCode,
+            temp.path(),
+            &InstallOptions { force: false },
+            &source(),
+        );
+        assert!(matches!(result, Err(ProjectError::Unsupported { .. })));
+    }
+
+    #[test]
+    fn find_project_root_walks_up_to_git() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join(".git")).unwrap();
+        let nested = temp.path().join("src/deep");
+        fs::create_dir_all(&nested).unwrap();
+        assert_eq!(find_project_root(&nested), Some(temp.path().to_path_buf()));
+    }
+}
@@ -0,0 +1,223 @@
+//! MCP Registry sources (`registry:<server-id>[@version]`).
+//!
+//! Adapts `skills_locate::RegistryClient` entries to the same
+//! [`DiscoveryResult`] the repository installers consume, so registry
+//! servers go through the usual selection, install, manifest and lockfile
+//! paths.
+
+use std::collections::HashMap;
+
+use skills_locate::{RegistryClient, ServerEntry};
+
+use super::discovery::DiscoveryError;
+use super::types::{DiscoveryResult, SourceInfo};
+use crate::config::BridleConfig;
+
+pub const REGISTRY_PREFIX: &str = "registry:";
+
+/// A parsed `registry:` source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistrySource {
+    pub id: String,
+    pub version: Option<String>,
+}
+
+impl RegistrySource {
+    /// Parses `registry:<id>` or `registry:<id>@<version>`.
+    pub fn parse(source: &str) -> Option<Self> {
+        let spec = source.strip_prefix(REGISTRY_PREFIX)?.trim();
+        let (id, version) = match spec.rsplit_once('@') {
+            Some((id, version)) if !id.is_empty() && !version.contains('/') => {
+                (id, Some(version.to_string()))
+            }
+            _ => (spec, None),
+        };
+        (!id.is_empty()).then(|| Self {
+            id: id.to_string(),
+            version: version.filter(|v| !v.is_empty()),
+        })
+    }
+
+    /// The same source pinned to `version`.
+    pub fn pinned(&self, version: &str) -> Self {
+        Self {
+            id: self.id.clone(),
+            version: Some(version.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for RegistrySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "{}{}@{}", REGISTRY_PREFIX, self.id, version),
+            None => write!(f, "{}{}", REGISTRY_PREFIX, self.id),
+        }
+    }
+}
+
+/// Client for the registry configured in `config.toml` (`registry_url`).
+pub fn registry_client() -> RegistryClient {
+    BridleConfig::load()
+        .ok()
+        .and_then(|c| c.registry_url)
+        .map(RegistryClient::with_base_url)
+        .unwrap_or_default()
+}
+
+pub fn fetch_entry(
+    client: &RegistryClient,
+    source: &RegistrySource,
+) -> Result<ServerEntry, DiscoveryError> {
+    match &source.version {
+        Some(version) => client.fetch_server_version(&source.id, version),
+        None => client.fetch_server(&source.id),
+    }
+    .map_err(DiscoveryError::FetchError)
+}
+
+/// Builds the install-time view of a registry entry.
+///
+/// The requested source is recorded as the manifest URL and the resolved
+/// version as its "commit", so `bridle update` follows the latest release of
+/// an unpinned server and the lockfile pins the exact one.
+pub fn discovery_from_entry(entry: &ServerEntry, source: &RegistrySource) -> DiscoveryResult {
+    DiscoveryResult {
+        skills: Vec::new(),
+        mcp_servers: entry.to_mcp_servers(),
+        agents: Vec::new(),
+        commands: Vec::new(),
+        hooks: Vec::new(),
+        plugins: Vec::new(),
+        source: SourceInfo {
+            owner: "registry".to_string(),
+            repo: source.id.clone(),
+            git_ref: source.version.clone(),
+            url: Some(source.to_string()),
+            commit: entry.version.clone(),
+            plugin: None,
+        },
+    }
+}
+
+pub fn discover_registry(
+    client: &RegistryClient,
+    source: &RegistrySource,
+) -> Result<DiscoveryResult, DiscoveryError> {
+    let entry = fetch_entry(client, source)?;
+    Ok(discovery_from_entry(&entry, source))
+}
+
+/// Descriptions of the environment variables an entry's packages need.
+pub fn env_descriptions(entry: &ServerEntry) -> HashMap<String, String> {
+    entry
+        .packages
+        .iter()
+        .flat_map(|pkg| pkg.environment_variables.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use harness_locate::{EnvValue, McpServer};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves canned JSON bodies by request path, one connection per request.
+    fn stand_in_registry(routes: Vec<(&'static str, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        thread::spawn(move || {
+            for stream in listener.incoming().take(routes.len()) {
+                let mut stream = stream.unwrap();
+                let mut request_line = String::new();
+                BufReader::new(&stream)
+                    .read_line(&mut request_line)
+                    .unwrap();
+                let path = request_line.split_whitespace().nth(1).unwrap_or("");
+                let (status, body) = routes
+                    .iter()
+                    .find(|(route, _)| *route == path)
+                    .map(|(_, body)| ("200 OK", body.as_str()))
+                    .unwrap_or(("404 Not Found", "{}"));
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        base
+    }
+
+    const FILES_ENTRY: &str = r#"{
+        "id": "io.test.files",
+        "name": "Files",
+        "description": "File access",
+        "version": "1.2.0",
+        "packages": [{
+            "registry": "npm",
+            "name": "@test/files",
+            "version": "1.2.0",
+            "environment_variables": {"FILES_TOKEN": "API token"}
+        }],
+        "remotes": [{"transport_type": "sse", "url": "https://files.test/sse"}]
+    }"#;
+
+    #[test]
+    fn parse_registry_sources() {
+        assert_eq!(
+            RegistrySource::parse("registry:io.github.acme/files@1.0.0"),
+            Some(RegistrySource {
+                id: "io.github.acme/files".to_string(),
+                version: Some("1.0.0".to_string()),
+            })
+        );
+        let latest = RegistrySource::parse("registry:files").unwrap();
+        assert_eq!(latest.version, None);
+        assert_eq!(latest.pinned("2.0.0").to_string(), "registry:files@2.0.0");
+        assert_eq!(RegistrySource::parse("registry:"), None);
+        assert_eq!(RegistrySource::parse("owner/repo"), None);
+    }
+
+    #[test]
+    fn search_and_discover_against_stand_in_registry() {
+        let base = stand_in_registry(vec![
+            (
+                "/servers?search=file%20access",
+                format!(r#"{{"servers": [{}]}}"#, FILES_ENTRY),
+            ),
+            (
+                "/servers/io.test.files/versions/1.2.0",
+                FILES_ENTRY.to_string(),
+            ),
+        ]);
+        let client = RegistryClient::with_base_url(base);
+
+        let results = client.search("file access").unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, "io.test.files");
+
+        let source = RegistrySource::parse("registry:io.test.files@1.2.0").unwrap();
+        let discovery = discover_registry(&client, &source).unwrap();
+        assert_eq!(discovery.mcp_servers.len(), 2);
+        match &discovery.mcp_servers["io.test.files"] {
+            McpServer::Stdio(s) => {
+                assert_eq!(s.args, vec!["-y", "@test/files@1.2.0"]);
+                assert_eq!(s.env["FILES_TOKEN"], EnvValue::env("FILES_TOKEN"));
+            }
+            other => panic!("expected stdio, got {:?}", other),
+        }
+        assert!(discovery.mcp_servers.contains_key("io.test.files-remote-0"));
+        assert_eq!(discovery.source.to_string(), "registry:io.test.files@1.2.0");
+        assert_eq!(
+            discovery.source.pinned_location(),
+            "registry:io.test.files@1.2.0"
+        );
+    }
+}
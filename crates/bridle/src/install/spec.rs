@@ -0,0 +1,622 @@
+//! Declarative profile specs (`bridle.toml`) applied with `bridle apply`.
+
+use crate::plan::fs;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use harness_locate::validation::validate_for_harness;
+use harness_locate::{Harness, HarnessKind, McpServer, Scope, Severity};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::convert::ConvertedComponent;
+use super::hook_installer::supports_hooks;
+use super::mcp_installer::{McpInstallOutcome, install_mcp_to_dir_with_source};
+use super::types::{
+    DiscoveryResult, InstallFailure, InstallOptions, InstallTarget, parse_harness_kind,
+};
+use super::updater::{PendingInstall, SourceCache};
+use crate::config::ProfileName;
+use crate::config::jsonc::strip_jsonc_comments;
+
+pub const SPEC_FILE_NAME: &str = "bridle.toml";
+
+#[derive(Debug, Error)]
+pub enum SpecError {
+    #[error("Failed to read {path}: {error}")]
+    Read {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    #[error("Failed to parse spec: {0}")]
+    Parse(#[source] toml::de::Error),
+
+    #[error("Spec lists no harnesses")]
+    NoHarnesses,
+
+    #[error("Unknown harness in spec: {0}")]
+    UnknownHarness(String),
+
+    #[error("Invalid profile name in spec: {0}")]
+    InvalidProfileName(String),
+
+    #[error("Failed to fetch {location}: {error}")]
+    Fetch { location: String, error: String },
+
+    #[error("{component} not found in {location}")]
+    ComponentNotFound { component: String, location: String },
+
+    #[error("Failed to create profile {path}: {error}")]
+    CreateProfile {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+/// A profile described declaratively, materialised into one profile per harness.
+///
+/// ```toml
+/// name = "team"
+/// harnesses = ["claude-code", "opencode"]
+/// model = "anthropic/claude-sonnet-4"
+/// rules = "RULES.md"
+///
+/// [mcp.filesystem]
+/// transport = "stdio"
+/// command = "npx"
+/// args = ["-y", "@modelcontextprotocol/server-filesystem"]
+///
+/// [[source]]
+/// url = "owner/repo"
+/// skills = ["review"]
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileSpec {
+    /// Profile name created in every listed harness
+    pub name: String,
+    pub harnesses: Vec<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Rules file, relative to the spec file
+    #[serde(default)]
+    pub rules: Option<PathBuf>,
+    #[serde(default)]
+    pub mcp: BTreeMap<String, McpServer>,
+    #[serde(default, rename = "source")]
+    pub sources: Vec<SourceSpec>,
+}
+
+/// Components to install from one source. An omitted list installs every
+/// component of that type; an empty list installs none.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SourceSpec {
+    pub url: String,
+    #[serde(default)]
+    pub skills: Option<Vec<String>>,
+    #[serde(default)]
+    pub agents: Option<Vec<String>>,
+    #[serde(default)]
+    pub commands: Option<Vec<String>>,
+    #[serde(default)]
+    pub mcp: Option<Vec<String>>,
+}
+
+impl ProfileSpec {
+    pub fn parse(content: &str) -> Result<Self, SpecError> {
+        toml::from_str(content).map_err(SpecError::Parse)
+    }
+
+    /// Load a spec file, resolving the rules path against its directory.
+    pub fn load(path: &Path) -> Result<Self, SpecError> {
+        let content = fs::read_to_string(path).map_err(|error| SpecError::Read {
+            path: path.to_path_buf(),
+            error,
+        })?;
+        let mut spec = Self::parse(&content)?;
+        if let Some(rules) = &spec.rules {
+            let base = path.parent().unwrap_or(Path::new("."));
+            spec.rules = Some(base.join(rules));
+        }
+        Ok(spec)
+    }
+}
+
+/// What `apply` did to one harness profile.
+#[derive(Debug, Serialize)]
+pub struct ProfileApply {
+    pub target: InstallTarget,
+    pub installed: Vec<String>,
+    pub skipped: Vec<String>,
+    pub errors: Vec<InstallFailure>,
+    /// Validation issues of MCP servers for this harness, as `profile convert`
+    /// reports them. Servers with an error-level issue are skipped.
+    pub issues: Vec<ConvertedComponent>,
+}
+
+/// Materialise a spec into `profiles_dir/<harness>/<name>` for each harness.
+///
+/// Harnesses, the profile name, the rules file and every source are resolved
+/// before anything is written, so a bad spec leaves profiles untouched.
+pub fn apply_spec(
+    spec: &ProfileSpec,
+    profiles_dir: &Path,
+    cache: &mut SourceCache,
+) -> Result<Vec<ProfileApply>, SpecError> {
+    if spec.harnesses.is_empty() {
+        return Err(SpecError::NoHarnesses);
+    }
+    let kinds = spec
+        .harnesses
+        .iter()
+        .map(|h| {
+            parse_harness_kind(h)
+                .map(|kind| (h.clone(), kind))
+                .ok_or_else(|| SpecError::UnknownHarness(h.clone()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let profile = ProfileName::new(&spec.name)
+        .map_err(|_| SpecError::InvalidProfileName(spec.name.clone()))?;
+
+    let rules = match &spec.rules {
+        Some(path) => Some(fs::read_to_string(path).map_err(|error| SpecError::Read {
+            path: path.clone(),
+            error,
+        })?),
+        None => None,
+    };
+
+    let mut selected = Vec::new();
+    for source in &spec.sources {
+        let discovery = cache.get(&source.url).map_err(|error| SpecError::Fetch {
+            location: source.url.clone(),
+            error,
+        })?;
+        selected.push((
+            discovery.source.clone(),
+            select_components(source, discovery)?,
+        ));
+    }
+
+    let options = InstallOptions { force: true };
+    let mut results = Vec::new();
+
+    for (harness, kind) in kinds {
+        let target = InstallTarget {
+            harness: harness.clone(),
+            profile: profile.clone(),
+        };
+        let profile_dir = profiles_dir.join(&harness).join(profile.as_str());
+        fs::create_dir_all(&profile_dir).map_err(|error| SpecError::CreateProfile {
+            path: profile_dir.clone(),
+            error,
+        })?;
+
+        let mut result = ProfileApply {
+            target: target.clone(),
+            installed: Vec::new(),
+            skipped: Vec::new(),
+            errors: Vec::new(),
+            issues: Vec::new(),
+        };
+        let fail = |name: String, error: String| InstallFailure {
+            skill: name,
+            target: target.clone(),
+            error,
+        };
+
+        if let Some(model) = &spec.model {
+            match write_model(kind, &profile_dir, model) {
+                Ok(true) => result.installed.push(format!("model: {}", model)),
+                Ok(false) => result
+                    .skipped
+                    .push(format!("model ({} has no model setting)", harness)),
+                Err(e) => result.errors.push(fail("model".to_string(), e.to_string())),
+            }
+        }
+
+        if let Some(content) = &rules {
+            let file = rules_file_name(kind);
+            match fs::write(profile_dir.join(file), content) {
+                Ok(()) => result.installed.push(format!("rules: {}", file)),
+                Err(e) => result.errors.push(fail("rules".to_string(), e.to_string())),
+            }
+        }
+
+        for (name, server) in &spec.mcp {
+            if let Err(e) = server.validate_capabilities(kind) {
+                result.skipped.push(format!("mcp: {} ({})", name, e));
+                continue;
+            }
+            let issues = validate_for_harness(server, kind);
+            let unsupported = issues.iter().any(|i| i.severity == Severity::Error);
+            if !issues.is_empty() {
+                result.issues.push(ConvertedComponent {
+                    component_type: "mcp".to_string(),
+                    name: name.clone(),
+                    issues,
+                });
+            }
+            if unsupported {
+                result
+                    .skipped
+                    .push(format!("mcp: {} (not supported by {})", name, harness));
+                continue;
+            }
+            match install_mcp_to_dir_with_source(
+                name,
+                server,
+                &target,
+                &options,
+                profiles_dir,
+                None,
+            ) {
+                Ok(McpInstallOutcome::Installed(_)) => {
+                    result.installed.push(format!("mcp: {}", name))
+                }
+                Ok(McpInstallOutcome::Skipped(_)) => {}
+                Err(e) => result.errors.push(fail(name.clone(), e.to_string())),
+            }
+        }
+
+        for (source, components) in &selected {
+            for component in components {
+                let label = component_label(component);
+                if !harness_supports(kind, component) {
+                    result
+                        .skipped
+                        .push(format!("{} (not supported by {})", label, harness));
+                    continue;
+                }
+                match component.install(&target, &options, profiles_dir, source) {
+                    Ok(Some(_)) => result.installed.push(label),
+                    Ok(None) => {}
+                    Err(e) => result.errors.push(fail(label, e.to_string())),
+                }
+            }
+        }
+
+        results.push(result);
+    }
+
+    Ok(results)
+}
+
+fn select_components(
+    source: &SourceSpec,
+    discovery: &DiscoveryResult,
+) -> Result<Vec<PendingInstall>, SpecError> {
+    let not_found = |kind: &str, name: &str| SpecError::ComponentNotFound {
+        component: format!("{} {}", kind, name),
+        location: source.url.clone(),
+    };
+
+    let mut selected = Vec::new();
+
+    match &source.skills {
+        None => selected.extend(discovery.skills.iter().cloned().map(PendingInstall::Skill)),
+        Some(names) => {
+            for name in names {
+                let skill = discovery
+                    .skills
+                    .iter()
+                    .find(|s| &s.name == name)
+                    .ok_or_else(|| not_found("skill", name))?;
+                selected.push(PendingInstall::Skill(skill.clone()));
+            }
+        }
+    }
+
+    match &source.agents {
+        None => selected.extend(discovery.agents.iter().cloned().map(PendingInstall::Agent)),
+        Some(names) => {
+            for name in names {
+                let agent = discovery
+                    .agents
+                    .iter()
+                    .find(|a| &a.name == name)
+                    .ok_or_else(|| not_found("agent", name))?;
+                selected.push(PendingInstall::Agent(agent.clone()));
+            }
+        }
+    }
+
+    match &source.commands {
+        None => selected.extend(
+            discovery
+                .commands
+                .iter()
+                .cloned()
+                .map(PendingInstall::Command),
+        ),
+        Some(names) => {
+            for name in names {
+                let command = discovery
+                    .commands
+                    .iter()
+                    .find(|c| &c.name == name)
+                    .ok_or_else(|| not_found("command", name))?;
+                selected.push(PendingInstall::Command(command.clone()));
+            }
+        }
+    }
+
+    let mut mcp_names: Vec<&String> = match &source.mcp {
+        None => discovery.mcp_servers.keys().collect(),
+        Some(names) => names.iter().collect(),
+    };
+    mcp_names.sort();
+    for name in mcp_names {
+        let server = discovery
+            .mcp_servers
+            .get(name)
+            .ok_or_else(|| not_found("mcp", name))?;
+        selected.push(PendingInstall::Mcp(name.clone(), server.clone()));
+    }
+
+    Ok(selected)
+}
+
+fn component_label(component: &PendingInstall) -> String {
+    let kind = match component {
+        PendingInstall::Skill(_) => "skill",
+        PendingInstall::Agent(_) => "agent",
+        PendingInstall::Command(_) => "command",
+        PendingInstall::Mcp(..) => "mcp",
+        PendingInstall::Hook(_) => "hook",
+    };
+    format!("{}: {}", kind, component.name())
+}
+
+fn harness_supports(kind: HarnessKind, component: &PendingInstall) -> bool {
+    let harness = Harness::new(kind);
+    match component {
+        PendingInstall::Skill(_) => harness.skills(&Scope::Global).ok().flatten().is_some(),
+        PendingInstall::Agent(_) => harness.agents(&Scope::Global).ok().flatten().is_some(),
+        PendingInstall::Command(_) => harness.commands(&Scope::Global).ok().flatten().is_some(),
+        PendingInstall::Mcp(_, server) => server.validate_capabilities(kind).is_ok(),
+        PendingInstall::Hook(_) => supports_hooks(kind),
+    }
+}
+
+/// Native rules file name written into the profile directory.
+pub(super) fn rules_file_name(kind: HarnessKind) -> &'static str {
+    match kind {
+        HarnessKind::ClaudeCode => "CLAUDE.md",
+        HarnessKind::Goose => ".goosehints",
+        HarnessKind::CopilotCli => "copilot-instructions.md",
+        HarnessKind::Crush => "CRUSH.md",
+        HarnessKind::OpenCode | HarnessKind::AmpCode | HarnessKind::Droid => "AGENTS.md",
+        _ => "AGENTS.md",
+    }
+}
+
+/// Set the default model in the profile's harness config.
+///
+/// Returns `Ok(false)` for harnesses without a single model setting.
+pub(super) fn write_model(
+    kind: HarnessKind,
+    profile_dir: &Path,
+    model: &str,
+) -> std::io::Result<bool> {
+    let (file, key) = match kind {
+        HarnessKind::ClaudeCode => ("settings.json", "model"),
+        HarnessKind::OpenCode => ("opencode.jsonc", "model"),
+        HarnessKind::AmpCode => ("settings.json", "amp.model.default"),
+        HarnessKind::Goose => return write_goose_model(&profile_dir.join("config.yaml"), model),
+        _ => return Ok(false),
+    };
+
+    let path = profile_dir.join(file);
+    let mut config: serde_json::Value = match fs::read_to_string(&path) {
+        Ok(content) if !content.trim().is_empty() => {
+            serde_json::from_str(&strip_jsonc_comments(&content)).map_err(std::io::Error::other)?
+        }
+        _ => serde_json::json!({}),
+    };
+    let object = config
+        .as_object_mut()
+        .ok_or_else(|| std::io::Error::other(format!("{} root is not an object", file)))?;
+    object.insert(
+        key.to_string(),
+        serde_json::Value::String(model.to_string()),
+    );
+
+    fs::write(
+        &path,
+        serde_json::to_string_pretty(&config).map_err(std::io::Error::other)?,
+    )?;
+    Ok(true)
+}
+
+/// Replace or append `GOOSE_MODEL` line-wise so comments survive.
+fn write_goose_model(path: &Path, model: &str) -> std::io::Result<bool> {
+    let content = fs::read_to_string(path).unwrap_or_default();
+    let line = format!("GOOSE_MODEL: {}", model);
+
+    let mut replaced = false;
+    let mut lines: Vec<String> = content
+        .lines()
+        .map(|l| {
+            if l.starts_with("GOOSE_MODEL:") {
+                replaced = true;
+                line.clone()
+            } else {
+                l.to_string()
+            }
+        })
+        .collect();
+    if !replaced {
+        lines.push(line);
+    }
+
+    fs::write(path, lines.join("\n") + "\n")?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::install::fixtures::{self, skill};
+    use crate::install::types::AgentInfo;
+    use tempfile::TempDir;
+
+    const SPEC: &str = r#"
+name = "spec-test"
+harnesses = ["opencode", "goose"]
+model = "anthropic/claude-sonnet-4"
+rules = "RULES.md"
+
+[mcp.filesystem]
+transport = "stdio"
+command = "npx"
+args = ["-y", "@modelcontextprotocol/server-filesystem"]
+
+[[source]]
+url = "https://github.com/owner/repo"
+skills = ["review"]
+agents = []
+"#;
+
+    fn discovery() -> DiscoveryResult {
+        DiscoveryResult {
+            agents: vec![AgentInfo {
+                name: "reviewer".to_string(),
+                description: None,
+                path: "agents/reviewer.md".to_string(),
+                content: "reviewer".to_string(),
+            }],
+            ..fixtures::discovery(vec![
+                skill("review", "Review code"),
+                skill("other", "Other"),
+            ])
+        }
+    }
+
+    fn load_spec(temp: &TempDir) -> ProfileSpec {
+        fs::write(temp.path().join("RULES.md"), "Be concise.").unwrap();
+        let path = temp.path().join(SPEC_FILE_NAME);
+        fs::write(&path, SPEC).unwrap();
+        ProfileSpec::load(&path).unwrap()
+    }
+
+    #[test]
+    fn parse_reads_mcp_and_sources() {
+        let spec = ProfileSpec::parse(SPEC).unwrap();
+        assert_eq!(spec.harnesses, vec!["opencode", "goose"]);
+        assert!(matches!(
+            spec.mcp.get("filesystem"),
+            Some(McpServer::Stdio(_))
+        ));
+        assert_eq!(spec.sources.len(), 1);
+        assert_eq!(spec.sources[0].skills, Some(vec!["review".to_string()]));
+        assert_eq!(spec.sources[0].agents, Some(Vec::new()));
+        assert!(spec.sources[0].commands.is_none());
+    }
+
+    #[test]
+    fn parse_rejects_unknown_keys() {
+        assert!(matches!(
+            ProfileSpec::parse("name = \"x\"\nharnesses = []\ntheme = \"dark\"\n"),
+            Err(SpecError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn apply_materialises_profile_for_each_harness() {
+        let temp = TempDir::new().unwrap();
+        let spec = load_spec(&temp);
+        let profiles_dir = temp.path().join("profiles");
+        let mut cache = SourceCache::new();
+        cache.insert("https://github.com/owner/repo", Ok(discovery()));
+
+        let results = apply_spec(&spec, &profiles_dir, &mut cache).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|r| r.errors.is_empty()));
+
+        let opencode = profiles_dir.join("opencode/spec-test");
+        assert!(opencode.join("skills/review/SKILL.md").exists());
+        assert!(!opencode.join("skills/other").exists());
+        assert!(!opencode.join("agents/reviewer.md").exists());
+        assert_eq!(
+            fs::read_to_string(opencode.join("AGENTS.md")).unwrap(),
+            "Be concise."
+        );
+        let config = fs::read_to_string(opencode.join("opencode.jsonc")).unwrap();
+        assert!(config.contains("\"model\": \"anthropic/claude-sonnet-4\""));
+        assert!(config.contains("filesystem"));
+
+        let goose = profiles_dir.join("goose/spec-test");
+        assert!(goose.join(".goosehints").exists());
+        let config = fs::read_to_string(goose.join("config.yaml")).unwrap();
+        assert!(config.contains("GOOSE_MODEL: anthropic/claude-sonnet-4"));
+        assert!(config.contains("filesystem"));
+    }
+
+    #[test]
+    fn apply_reports_mcp_fields_the_harness_cannot_store() {
+        let temp = TempDir::new().unwrap();
+        let mut spec = load_spec(&temp);
+        let Some(McpServer::Stdio(server)) = spec.mcp.get_mut("filesystem") else {
+            unreachable!()
+        };
+        server.cwd = Some(PathBuf::from("/srv/project"));
+        let profiles_dir = temp.path().join("profiles");
+        let mut cache = SourceCache::new();
+        cache.insert("https://github.com/owner/repo", Ok(discovery()));
+
+        let results = apply_spec(&spec, &profiles_dir, &mut cache).unwrap();
+        for result in &results {
+            assert!(result.installed.iter().all(|i| i != "mcp: filesystem"));
+            assert_eq!(result.issues.len(), 1);
+            assert_eq!(result.issues[0].name, "filesystem");
+            assert!(
+                result.issues[0]
+                    .issues
+                    .iter()
+                    .any(|i| i.code == Some(harness_locate::validation::CODE_CWD_UNSUPPORTED))
+            );
+        }
+        let config = fs::read_to_string(profiles_dir.join("goose/spec-test/config.yaml")).unwrap();
+        assert!(!config.contains("filesystem"));
+    }
+
+    #[test]
+    fn apply_fails_before_writing_when_component_missing() {
+        let temp = TempDir::new().unwrap();
+        let mut spec = load_spec(&temp);
+        spec.sources[0].skills = Some(vec!["missing".to_string()]);
+        let profiles_dir = temp.path().join("profiles");
+        let mut cache = SourceCache::new();
+        cache.insert("https://github.com/owner/repo", Ok(discovery()));
+
+        let result = apply_spec(&spec, &profiles_dir, &mut cache);
+        assert!(matches!(result, Err(SpecError::ComponentNotFound { .. })));
+        assert!(!profiles_dir.exists());
+    }
+
+    #[test]
+    fn apply_rejects_unknown_harness() {
+        let temp = TempDir::new().unwrap();
+        let mut spec = load_spec(&temp);
+        spec.harnesses.push("vim".to_string());
+
+        let result = apply_spec(&spec, temp.path(), &mut SourceCache::new());
+        assert!(matches!(result, Err(SpecError::UnknownHarness(h)) if h == "vim"));
+    }
+
+    #[test]
+    fn goose_model_replaces_existing_line() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("config.yaml");
+        fs::write(&path, "# comment\nGOOSE_MODEL: old\nGOOSE_PROVIDER: x\n").unwrap();
+
+        write_goose_model(&path, "new").unwrap();
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "# comment\nGOOSE_MODEL: new\nGOOSE_PROVIDER: x\n"
+        );
+    }
+}
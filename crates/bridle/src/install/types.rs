@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 use harness_locate::{HarnessKind, McpServer};
 use serde::Serialize;
+use skills_locate::HooksConfig;
 
 use crate::config::ProfileName;
 
@@ -53,6 +54,74 @@ pub struct CommandInfo {
     pub content: String,
 }
 
+/// Information about a discovered plugin hooks.json
+#[derive(Debug, Clone)]
+pub struct HookInfo {
+    /// Plugin name (from plugin.json, or the plugin directory)
+    pub name: String,
+    /// Path within source archive (e.g., "plugins/fmt/hooks/hooks.json")
+    pub path: String,
+    /// Parsed hook groups by event
+    pub hooks: HooksConfig,
+}
+
+/// A plugin grouping some of the discovered components
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginInfo {
+    /// Plugin name (from marketplace.json or plugin.json)
+    pub name: String,
+    pub description: Option<String>,
+    /// Where the plugin's components were fetched from; differs from the
+    /// marketplace's source for plugins hosted in another repository
+    pub source: SourceInfo,
+    pub skills: Vec<String>,
+    pub agents: Vec<String>,
+    pub commands: Vec<String>,
+    pub mcp_servers: Vec<String>,
+    pub hooks: Vec<String>,
+}
+
+impl PluginInfo {
+    pub fn is_empty(&self) -> bool {
+        self.skills.is_empty()
+            && self.agents.is_empty()
+            && self.commands.is_empty()
+            && self.mcp_servers.is_empty()
+            && self.hooks.is_empty()
+    }
+
+    /// Component counts, e.g. "2 skills, 1 agent".
+    pub fn summary(&self) -> String {
+        let counts = [
+            (self.skills.len(), "skill"),
+            (self.agents.len(), "agent"),
+            (self.commands.len(), "command"),
+            (self.mcp_servers.len(), "MCP server"),
+            (self.hooks.len(), "hook set"),
+        ];
+        counts
+            .iter()
+            .filter(|(n, _)| *n > 0)
+            .map(|(n, label)| format!("{} {}{}", n, label, if *n == 1 { "" } else { "s" }))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn names(&self, component_type: ComponentType) -> &[String] {
+        match component_type {
+            ComponentType::Skill => &self.skills,
+            ComponentType::Agent => &self.agents,
+            ComponentType::Command => &self.commands,
+            ComponentType::Mcp => &self.mcp_servers,
+            ComponentType::Hook => &self.hooks,
+        }
+    }
+
+    pub fn contains(&self, component_type: ComponentType, name: &str) -> bool {
+        self.names(component_type).iter().any(|n| n == name)
+    }
+}
+
 /// Target harness + profile for installation
 #[derive(Debug, Clone, Serialize)]
 pub struct InstallTarget {
@@ -79,16 +148,115 @@ pub struct DiscoveryResult {
     pub agents: Vec<AgentInfo>,
     /// Discovered commands
     pub commands: Vec<CommandInfo>,
+    /// Discovered plugin hooks
+    pub hooks: Vec<HookInfo>,
+    /// Plugins the components are grouped into (empty for plain repositories)
+    pub plugins: Vec<PluginInfo>,
     /// Source repository metadata
     pub source: SourceInfo,
 }
 
+impl DiscoveryResult {
+    /// Plugin a component belongs to, if any.
+    pub fn plugin_of(&self, component_type: ComponentType, name: &str) -> Option<&PluginInfo> {
+        self.plugins
+            .iter()
+            .find(|p| p.contains(component_type, name))
+    }
+
+    /// Drops the plugins `keep` rejects along with their components;
+    /// components outside any plugin stay only with `keep_loose`.
+    pub fn retain_plugins(&mut self, keep: impl Fn(&PluginInfo) -> bool, keep_loose: bool) {
+        let (kept, dropped): (Vec<_>, Vec<_>) = std::mem::take(&mut self.plugins)
+            .into_iter()
+            .partition(|p| keep(p));
+        let wanted = |component_type: ComponentType, name: &str| {
+            if kept.iter().any(|p| p.contains(component_type, name)) {
+                true
+            } else if dropped.iter().any(|p| p.contains(component_type, name)) {
+                false
+            } else {
+                keep_loose
+            }
+        };
+        self.skills
+            .retain(|s| wanted(ComponentType::Skill, &s.name));
+        self.agents
+            .retain(|a| wanted(ComponentType::Agent, &a.name));
+        self.commands
+            .retain(|c| wanted(ComponentType::Command, &c.name));
+        self.hooks.retain(|h| wanted(ComponentType::Hook, &h.name));
+        self.mcp_servers
+            .retain(|name, _| wanted(ComponentType::Mcp, name));
+        self.plugins = kept;
+    }
+
+    /// Source recorded in the manifest for a component: its plugin's source
+    /// (tagged with the plugin name) when it belongs to one.
+    pub fn source_for(&self, component_type: ComponentType, name: &str) -> SourceInfo {
+        match self.plugin_of(component_type, name) {
+            Some(plugin) => plugin.source.clone(),
+            None => self.source.clone(),
+        }
+    }
+}
+
 /// Metadata about the source repository
 #[derive(Debug, Clone, Serialize, serde::Deserialize)]
 pub struct SourceInfo {
     pub owner: String,
     pub repo: String,
     pub git_ref: Option<String>,
+    /// Canonical source string (GitHub URL, git remote, archive URL or path)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Commit SHA the source resolved to when it was fetched
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    /// Plugin the component was installed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<String>,
+}
+
+impl SourceInfo {
+    /// Source string that `discover_skills` can re-fetch this source from.
+    pub fn location(&self) -> String {
+        match &self.url {
+            Some(url) => url.clone(),
+            None => format!(
+                "https://github.com/{}/{}/tree/{}",
+                self.owner,
+                self.repo,
+                self.git_ref.as_deref().unwrap_or("main")
+            ),
+        }
+    }
+}
+
+impl SourceInfo {
+    /// Source string pinned to the resolved commit, when one is known.
+    pub fn pinned_location(&self) -> String {
+        let location = self.location();
+        if let Some(source) = super::registry::RegistrySource::parse(&location) {
+            return match &self.commit {
+                Some(version) => source.pinned(version).to_string(),
+                None => location,
+            };
+        }
+        match (&self.commit, skills_locate::Source::parse(&location)) {
+            (Some(commit), Ok(source)) => source.pinned(commit).to_string(),
+            _ => location,
+        }
+    }
+}
+
+impl std::fmt::Display for SourceInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.url {
+            Some(url) if !url.contains("github.com/") => write!(f, "{url}"),
+            _ => write!(f, "{}/{}", self.owner, self.repo),
+        }
+    }
 }
 
 /// Result of installation operation
@@ -97,6 +265,12 @@ pub struct InstallReport {
     pub installed: Vec<InstallSuccess>,
     pub skipped: Vec<InstallSkip>,
     pub errors: Vec<InstallFailure>,
+    /// Nothing was written; `installed` lists what would have been
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+    /// File changes a dry run would make
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<crate::plan::Plan>,
 }
 
 #[derive(Debug, Serialize)]
@@ -122,6 +296,8 @@ pub struct InstallSkip {
 pub enum SkipReason {
     /// File already exists and --force not specified
     AlreadyExists,
+    /// Target harness (or its transport support) can't hold the component
+    Unsupported,
 }
 
 #[derive(Debug, Serialize)]
@@ -132,12 +308,16 @@ pub struct InstallFailure {
 }
 
 /// Component type for uninstall operations
-#[derive(Debug, Clone, Copy, Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, serde::Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ComponentType {
     Skill,
     Agent,
     Command,
+    /// MCP server entry inside the harness config file (not a directory)
+    Mcp,
+    /// Hook groups merged into the harness `settings.json`
+    Hook,
 }
 
 impl ComponentType {
@@ -146,6 +326,8 @@ impl ComponentType {
             ComponentType::Skill => "skills",
             ComponentType::Agent => "agents",
             ComponentType::Command => "commands",
+            ComponentType::Mcp => "mcp",
+            ComponentType::Hook => "hooks",
         }
     }
 }
@@ -1,13 +1,16 @@
 //! Component uninstallation executor.
 
-use std::fs;
-use std::path::PathBuf;
+use crate::plan::fs;
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
 use harness_locate::{Harness, HarnessKind, Scope};
 
+use super::hook_installer::{SETTINGS_FILE, live_settings_path, remove_hook_fragment};
 use super::manifest::{InstallManifest, manifest_path};
+use super::mcp_config::{McpConfigError, remove_mcp_config};
+use super::mcp_installer::get_profile_config_path;
 use super::types::{
     ComponentType, InstallTarget, UninstallFailure, UninstallReport, UninstallSuccess,
     parse_harness_kind,
@@ -28,6 +31,12 @@ pub enum UninstallError {
 
     #[error("Harness not found: {0}")]
     HarnessNotFound(String),
+
+    #[error("Failed to update MCP config: {0}")]
+    McpConfig(#[source] McpConfigError),
+
+    #[error("Failed to update hooks: {0}")]
+    Hooks(#[source] std::io::Error),
 }
 
 pub fn uninstall_component(
@@ -48,24 +57,31 @@ fn uninstall_component_from_dir(
     component_name: &str,
     component_type: ComponentType,
     target: &InstallTarget,
-    profiles_dir: &std::path::Path,
+    profiles_dir: &Path,
 ) -> Result<UninstallSuccess, UninstallError> {
     let profile_dir = profiles_dir
         .join(&target.harness)
         .join(target.profile.as_str());
 
-    if !profile_dir.exists() {
+    if !fs::exists(&profile_dir) {
         return Err(UninstallError::ProfileNotFound {
             harness: target.harness.clone(),
             profile: target.profile.as_str().to_string(),
         });
     }
 
+    if matches!(component_type, ComponentType::Mcp) {
+        return uninstall_mcp_from_profile(component_name, target, &profile_dir);
+    }
+    if matches!(component_type, ComponentType::Hook) {
+        return uninstall_hook_from_profile(component_name, target, &profile_dir);
+    }
+
     let component_dir = profile_dir
         .join(component_type.dir_name())
         .join(component_name);
 
-    if !component_dir.exists() {
+    if !fs::exists(&component_dir) {
         return Err(UninstallError::ComponentNotFound(
             component_name.to_string(),
         ));
@@ -90,6 +106,84 @@ fn uninstall_component_from_dir(
     })
 }
 
+fn uninstall_mcp_from_profile(
+    name: &str,
+    target: &InstallTarget,
+    profile_dir: &Path,
+) -> Result<UninstallSuccess, UninstallError> {
+    let kind = parse_harness_kind(&target.harness)
+        .ok_or_else(|| UninstallError::HarnessNotFound(target.harness.clone()))?;
+    let config_path = get_profile_config_path(profile_dir, kind);
+
+    let manifest_file = manifest_path(profile_dir);
+    let mut manifest = InstallManifest::load(&manifest_file).unwrap_or_default();
+    let tracked = manifest.find_component(ComponentType::Mcp, name).is_some();
+
+    let removed = remove_mcp_config(kind, &config_path, name).map_err(UninstallError::McpConfig)?;
+    if !removed && !tracked {
+        return Err(UninstallError::ComponentNotFound(name.to_string()));
+    }
+
+    if tracked {
+        manifest.remove_component(ComponentType::Mcp, name);
+        let _ = manifest.save(&manifest_file);
+    }
+
+    let harness_path = remove_from_harness_if_active(target, name, ComponentType::Mcp)?;
+
+    Ok(UninstallSuccess {
+        component: name.to_string(),
+        component_type: format!("{:?}", ComponentType::Mcp).to_lowercase(),
+        target: target.clone(),
+        profile_path: config_path,
+        harness_path,
+    })
+}
+
+/// Hooks are removed group by group using the fragment recorded at install,
+/// leaving hooks configured by hand (or by other plugins) in place.
+fn uninstall_hook_from_profile(
+    name: &str,
+    target: &InstallTarget,
+    profile_dir: &Path,
+) -> Result<UninstallSuccess, UninstallError> {
+    let kind = parse_harness_kind(&target.harness)
+        .ok_or_else(|| UninstallError::HarnessNotFound(target.harness.clone()))?;
+    let settings_path = profile_dir.join(SETTINGS_FILE);
+
+    let manifest_file = manifest_path(profile_dir);
+    let mut manifest = InstallManifest::load(&manifest_file).unwrap_or_default();
+    let Some(fragment) = manifest
+        .find_component(ComponentType::Hook, name)
+        .and_then(|e| e.fragment.clone())
+    else {
+        return Err(UninstallError::ComponentNotFound(name.to_string()));
+    };
+
+    remove_hook_fragment(&settings_path, &fragment).map_err(UninstallError::Hooks)?;
+    manifest.remove_component(ComponentType::Hook, name);
+    let _ = manifest.save(&manifest_file);
+
+    let is_active = BridleConfig::load()
+        .ok()
+        .and_then(|c| c.active_profile_for(&target.harness).map(String::from))
+        .is_some_and(|active| active == target.profile.as_str());
+    let harness_path = match live_settings_path(kind) {
+        Some(live) if is_active => remove_hook_fragment(&live, &fragment)
+            .map_err(UninstallError::Hooks)?
+            .then_some(live),
+        _ => None,
+    };
+
+    Ok(UninstallSuccess {
+        component: name.to_string(),
+        component_type: format!("{:?}", ComponentType::Hook).to_lowercase(),
+        target: target.clone(),
+        profile_path: settings_path,
+        harness_path,
+    })
+}
+
 fn remove_from_harness_if_active(
     target: &InstallTarget,
     component_name: &str,
@@ -115,6 +209,15 @@ fn remove_from_harness_if_active(
         ComponentType::Skill => harness.skills(&Scope::Global),
         ComponentType::Agent => harness.agents(&Scope::Global),
         ComponentType::Command => harness.commands(&Scope::Global),
+        ComponentType::Mcp => {
+            let Some(config_path) = harness.mcp_config_path() else {
+                return Ok(None);
+            };
+            let removed = remove_mcp_config(kind, &config_path, component_name)
+                .map_err(UninstallError::McpConfig)?;
+            return Ok(removed.then_some(config_path));
+        }
+        ComponentType::Hook => return Ok(None),
     };
 
     let harness_component_dir = component_dir_result
@@ -128,7 +231,7 @@ fn remove_from_harness_if_active(
                 .unwrap_or_default()
         });
 
-    if harness_component_dir.exists() {
+    if fs::exists(&harness_component_dir) {
         fs::remove_dir_all(&harness_component_dir).map_err(UninstallError::RemoveDir)?;
         Ok(Some(harness_component_dir))
     } else {
@@ -162,6 +265,8 @@ pub fn uninstall_components(
 mod tests {
     use super::*;
     use crate::config::ProfileName;
+    use crate::install::manifest::ManifestEntry;
+    use crate::install::types::SourceInfo;
     use tempfile::TempDir;
 
     fn setup_test_env() -> (TempDir, InstallTarget, PathBuf) {
@@ -233,4 +338,121 @@ mod tests {
             Err(UninstallError::ProfileNotFound { .. })
         ));
     }
+
+    #[test]
+    fn uninstall_mcp_removes_only_that_server() {
+        let (temp, target, profiles_dir) = setup_test_env();
+        let profile_dir = temp.path().join("profiles/opencode/test");
+        let config_path = profile_dir.join("opencode.jsonc");
+        fs::write(
+            &config_path,
+            r#"{"mcp": {"drop": {"type": "local"}, "keep": {"type": "remote"}}, "theme": "x"}"#,
+        )
+        .unwrap();
+
+        let mut manifest = InstallManifest::default();
+        manifest.add_entry(ManifestEntry {
+            component_type: ComponentType::Mcp,
+            name: "drop".to_string(),
+            source: SourceInfo {
+                owner: "owner".to_string(),
+                repo: "repo".to_string(),
+                git_ref: None,
+                url: None,
+                commit: None,
+                plugin: None,
+            },
+            installed_at: "2025-01-02T00:00:00Z".to_string(),
+            fragment: Some(r#"{"type": "local"}"#.to_string()),
+        });
+        manifest.save(&manifest_path(&profile_dir)).unwrap();
+
+        let result =
+            uninstall_component_from_dir("drop", ComponentType::Mcp, &target, &profiles_dir)
+                .unwrap();
+        assert_eq!(result.component_type, "mcp");
+        assert_eq!(result.profile_path, config_path);
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(!content.contains("drop"));
+        assert!(content.contains("keep"));
+        assert!(content.contains("theme"));
+
+        let manifest = InstallManifest::load(&manifest_path(&profile_dir)).unwrap();
+        assert!(manifest.entries.is_empty());
+    }
+
+    #[test]
+    fn uninstall_mcp_returns_error_when_unknown() {
+        let (_temp, target, profiles_dir) = setup_test_env();
+
+        let result =
+            uninstall_component_from_dir("missing", ComponentType::Mcp, &target, &profiles_dir);
+        assert!(matches!(result, Err(UninstallError::ComponentNotFound(_))));
+    }
+
+    #[test]
+    fn uninstall_hook_keeps_other_groups() {
+        use crate::install::hook_installer::{install_hook_to_dir_with_source, read_settings};
+        use crate::install::installer::InstallOutcome;
+        use crate::install::types::{HookInfo, InstallOptions};
+
+        let temp = TempDir::new().unwrap();
+        let profiles_dir = temp.path().join("profiles");
+        let profile_dir = profiles_dir.join("claude-code").join("hook-test");
+        fs::create_dir_all(&profile_dir).unwrap();
+        let settings_path = profile_dir.join(SETTINGS_FILE);
+        fs::write(
+            &settings_path,
+            r#"{"hooks": {"Stop": [{"hooks": [{"type": "command", "command": "notify"}]}]}}"#,
+        )
+        .unwrap();
+        let target = InstallTarget {
+            harness: "claude-code".to_string(),
+            profile: ProfileName::new("hook-test").unwrap(),
+        };
+        let hook = HookInfo {
+            name: "chime".to_string(),
+            path: "hooks/hooks.json".to_string(),
+            hooks: skills_locate::parse_hooks_json(r#"{"Stop": [{"hooks": ["chime.sh"]}]}"#)
+                .unwrap(),
+        };
+        let source = SourceInfo {
+            owner: "owner".to_string(),
+            repo: "repo".to_string(),
+            git_ref: None,
+            url: None,
+            commit: None,
+            plugin: None,
+        };
+        let installed = install_hook_to_dir_with_source(
+            &hook,
+            &target,
+            &InstallOptions::default(),
+            &profiles_dir,
+            Some(&source),
+        )
+        .unwrap();
+        assert!(matches!(installed, InstallOutcome::Installed(_)));
+        assert_eq!(
+            read_settings(&settings_path).unwrap()["hooks"]["Stop"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let result =
+            uninstall_component_from_dir("chime", ComponentType::Hook, &target, &profiles_dir)
+                .unwrap();
+        assert_eq!(result.component_type, "hook");
+
+        let stop = read_settings(&settings_path).unwrap()["hooks"]["Stop"].clone();
+        assert_eq!(stop.as_array().unwrap().len(), 1);
+        assert_eq!(stop[0]["hooks"][0]["command"], "notify");
+        assert!(matches!(
+            uninstall_component_from_dir("chime", ComponentType::Hook, &target, &profiles_dir),
+            Err(UninstallError::ComponentNotFound(_))
+        ));
+    }
 }
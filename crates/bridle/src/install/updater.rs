@@ -0,0 +1,523 @@
+//! Update installed components from their recorded sources.
+
+use crate::plan::fs;
+use std::collections::HashMap;
+use std::path::Path;
+
+use harness_locate::{HarnessKind, McpServer};
+use serde::Serialize;
+use thiserror::Error;
+
+use super::discovery::{DiscoveryError, discover_skills};
+use super::hook_installer::{hook_fragment, install_hook_to_dir_with_source};
+use super::installer::{
+    InstallError, InstallOutcome, install_agent_to_dir_with_source,
+    install_command_to_dir_with_source, install_skill_to_dir_with_source, skill_for_profile,
+};
+use super::manifest::{
+    InstallManifest, ManifestEntry, ManifestError, content_digest, manifest_path,
+};
+use super::mcp_config::native_fragment;
+use super::mcp_installer::{McpInstallOutcome, install_mcp_to_dir_with_source};
+use super::types::{
+    AgentInfo, CommandInfo, ComponentType, DiscoveryResult, HookInfo, InstallFailure,
+    InstallOptions, InstallReport, InstallSuccess, InstallTarget, SkillInfo, SourceInfo,
+    parse_harness_kind,
+};
+use crate::config::BridleConfig;
+
+#[derive(Debug, Error)]
+pub enum UpdateError {
+    #[error("Profile directory not found for {harness}/{profile}")]
+    ProfileNotFound { harness: String, profile: String },
+
+    #[error("Harness not found: {0}")]
+    HarnessNotFound(String),
+
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+}
+
+/// How an installed component compares to its source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Unchanged,
+    /// Upstream content differs from the installed content
+    Modified,
+    /// Component is tracked in the manifest but its files are gone
+    Missing,
+    /// Component no longer exists in the source; left installed
+    RemovedUpstream,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ComponentChange {
+    pub component_type: ComponentType,
+    pub name: String,
+    pub source: String,
+    pub change: ChangeKind,
+    pub installed_digest: Option<String>,
+    pub upstream_digest: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceFailure {
+    pub source: String,
+    pub error: String,
+}
+
+/// Upstream component that will be re-installed when the plan is applied.
+#[derive(Debug, Clone)]
+pub(super) enum PendingInstall {
+    Skill(SkillInfo),
+    Agent(AgentInfo),
+    Command(CommandInfo),
+    Mcp(String, McpServer),
+    Hook(HookInfo),
+}
+
+impl PendingInstall {
+    pub(super) fn name(&self) -> &str {
+        match self {
+            PendingInstall::Skill(s) => &s.name,
+            PendingInstall::Agent(a) => &a.name,
+            PendingInstall::Command(c) => &c.name,
+            PendingInstall::Mcp(name, _) => name,
+            PendingInstall::Hook(h) => &h.name,
+        }
+    }
+
+    /// Install through the regular installer, returning `None` if skipped.
+    pub(super) fn install(
+        &self,
+        target: &InstallTarget,
+        options: &InstallOptions,
+        profiles_dir: &Path,
+        source: &SourceInfo,
+    ) -> Result<Option<InstallSuccess>, InstallError> {
+        match self {
+            PendingInstall::Skill(skill) => {
+                install_skill_to_dir_with_source(skill, target, options, profiles_dir, Some(source))
+                    .map(installed_success)
+            }
+            PendingInstall::Agent(agent) => {
+                install_agent_to_dir_with_source(agent, target, options, profiles_dir, Some(source))
+                    .map(installed_success)
+            }
+            PendingInstall::Command(command) => install_command_to_dir_with_source(
+                command,
+                target,
+                options,
+                profiles_dir,
+                Some(source),
+            )
+            .map(installed_success),
+            PendingInstall::Mcp(name, server) => install_mcp_to_dir_with_source(
+                name,
+                server,
+                target,
+                options,
+                profiles_dir,
+                Some(source),
+            )
+            .map(|outcome| match outcome {
+                McpInstallOutcome::Installed(success) => Some(InstallSuccess {
+                    skill: success.name,
+                    target: success.target,
+                    profile_path: success.profile_path,
+                    harness_path: success.harness_path,
+                }),
+                McpInstallOutcome::Skipped(_) => None,
+            }),
+            PendingInstall::Hook(hook) => {
+                install_hook_to_dir_with_source(hook, target, options, profiles_dir, Some(source))
+                    .map(installed_success)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpdatePlan {
+    pub target: InstallTarget,
+    pub components: Vec<ComponentChange>,
+    pub failures: Vec<SourceFailure>,
+    #[serde(skip)]
+    pending: Vec<(PendingInstall, SourceInfo)>,
+}
+
+impl UpdatePlan {
+    /// Components that will be re-installed.
+    pub fn changed(&self) -> impl Iterator<Item = &ComponentChange> {
+        self.components
+            .iter()
+            .filter(|c| matches!(c.change, ChangeKind::Modified | ChangeKind::Missing))
+    }
+
+    pub fn has_changes(&self) -> bool {
+        !self.pending.is_empty()
+    }
+}
+
+/// Discovery results keyed by source location, so a source shared by many
+/// profiles is only fetched once per run.
+#[derive(Default)]
+pub struct SourceCache {
+    results: HashMap<String, Result<DiscoveryResult, String>>,
+}
+
+impl SourceCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(super) fn get(&mut self, location: &str) -> Result<&DiscoveryResult, String> {
+        self.results
+            .entry(location.to_string())
+            .or_insert_with(|| discover_skills(location).map_err(|e| e.to_string()))
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn insert(
+        &mut self,
+        location: &str,
+        result: Result<DiscoveryResult, DiscoveryError>,
+    ) {
+        self.results
+            .insert(location.to_string(), result.map_err(|e| e.to_string()));
+    }
+}
+
+pub fn plan_update(
+    target: &InstallTarget,
+    cache: &mut SourceCache,
+) -> Result<UpdatePlan, UpdateError> {
+    let profiles_dir = BridleConfig::profiles_dir().map_err(|_| UpdateError::ProfileNotFound {
+        harness: target.harness.clone(),
+        profile: target.profile.as_str().to_string(),
+    })?;
+
+    plan_update_in_dir(target, &profiles_dir, cache)
+}
+
+pub fn plan_update_in_dir(
+    target: &InstallTarget,
+    profiles_dir: &Path,
+    cache: &mut SourceCache,
+) -> Result<UpdatePlan, UpdateError> {
+    let kind = parse_harness_kind(&target.harness)
+        .ok_or_else(|| UpdateError::HarnessNotFound(target.harness.clone()))?;
+
+    let profile_dir = profiles_dir
+        .join(&target.harness)
+        .join(target.profile.as_str());
+
+    if !fs::exists(&profile_dir) {
+        return Err(UpdateError::ProfileNotFound {
+            harness: target.harness.clone(),
+            profile: target.profile.as_str().to_string(),
+        });
+    }
+
+    let manifest = InstallManifest::load(&manifest_path(&profile_dir))?;
+
+    let mut plan = UpdatePlan {
+        target: target.clone(),
+        components: Vec::new(),
+        failures: Vec::new(),
+        pending: Vec::new(),
+    };
+
+    let mut by_source: Vec<(String, Vec<&ManifestEntry>)> = Vec::new();
+    for entry in &manifest.entries {
+        let location = entry.source.location();
+        match by_source.iter_mut().find(|(loc, _)| *loc == location) {
+            Some((_, entries)) => entries.push(entry),
+            None => by_source.push((location, vec![entry])),
+        }
+    }
+
+    for (location, entries) in by_source {
+        let discovery = match cache.get(&location) {
+            Ok(d) => d,
+            Err(error) => {
+                plan.failures.push(SourceFailure {
+                    source: location,
+                    error,
+                });
+                continue;
+            }
+        };
+
+        for entry in entries {
+            let upstream = find_upstream(discovery, entry.component_type, &entry.name, kind);
+            let installed = installed_content(&profile_dir, entry);
+
+            let change = match (&upstream, &installed) {
+                (None, _) => ChangeKind::RemovedUpstream,
+                (Some(_), None) => ChangeKind::Missing,
+                (Some((_, up)), Some(local)) if up == local => ChangeKind::Unchanged,
+                (Some(_), Some(_)) => ChangeKind::Modified,
+            };
+
+            plan.components.push(ComponentChange {
+                component_type: entry.component_type,
+                name: entry.name.clone(),
+                source: entry.source.to_string(),
+                change,
+                installed_digest: installed.as_deref().map(|c| content_digest(c.as_bytes())),
+                upstream_digest: upstream.as_ref().map(|(_, c)| content_digest(c.as_bytes())),
+            });
+
+            if matches!(change, ChangeKind::Modified | ChangeKind::Missing)
+                && let Some((pending, _)) = upstream
+            {
+                plan.pending.push((pending, entry.source.clone()));
+            }
+        }
+    }
+
+    Ok(plan)
+}
+
+/// Locate the upstream counterpart of an installed component, along with the
+/// content the installer would store for it in this harness's profile.
+pub(super) fn find_upstream(
+    discovery: &DiscoveryResult,
+    component_type: ComponentType,
+    name: &str,
+    kind: HarnessKind,
+) -> Option<(PendingInstall, String)> {
+    match component_type {
+        ComponentType::Skill => discovery.skills.iter().find_map(|skill| {
+            let (profile_name, content) = skill_for_profile(Some(kind), skill);
+            (profile_name == name).then(|| (PendingInstall::Skill(skill.clone()), content))
+        }),
+        ComponentType::Agent => discovery
+            .agents
+            .iter()
+            .find(|a| a.name == name)
+            .map(|a| (PendingInstall::Agent(a.clone()), a.content.clone())),
+        ComponentType::Command => discovery
+            .commands
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| (PendingInstall::Command(c.clone()), c.content.clone())),
+        ComponentType::Mcp => {
+            let server = discovery.mcp_servers.get(name)?;
+            let value = server.to_native_value(kind, name).ok()?;
+            Some((
+                PendingInstall::Mcp(name.to_string(), server.clone()),
+                native_fragment(kind, name, &value),
+            ))
+        }
+        ComponentType::Hook => discovery
+            .hooks
+            .iter()
+            .find(|h| h.name == name)
+            .map(|h| (PendingInstall::Hook(h.clone()), hook_fragment(h, kind))),
+    }
+}
+
+pub(super) fn installed_content(profile_dir: &Path, entry: &ManifestEntry) -> Option<String> {
+    let path = match entry.component_type {
+        ComponentType::Skill => profile_dir
+            .join(ComponentType::Skill.dir_name())
+            .join(&entry.name)
+            .join("SKILL.md"),
+        ComponentType::Agent | ComponentType::Command => profile_dir
+            .join(entry.component_type.dir_name())
+            .join(format!("{}.md", entry.name)),
+        ComponentType::Mcp | ComponentType::Hook => return entry.fragment.clone(),
+    };
+    fs::read_to_string(path).ok()
+}
+
+pub fn apply_update(plan: &UpdatePlan) -> InstallReport {
+    match BridleConfig::profiles_dir() {
+        Ok(profiles_dir) => apply_update_in_dir(plan, &profiles_dir),
+        Err(e) => InstallReport {
+            errors: plan
+                .pending
+                .iter()
+                .map(|(pending, _)| InstallFailure {
+                    skill: pending.name().to_string(),
+                    target: plan.target.clone(),
+                    error: e.to_string(),
+                })
+                .collect(),
+            ..Default::default()
+        },
+    }
+}
+
+pub fn apply_update_in_dir(plan: &UpdatePlan, profiles_dir: &Path) -> InstallReport {
+    let options = InstallOptions { force: true };
+    let target = &plan.target;
+    let mut report = InstallReport::default();
+
+    for (pending, source) in &plan.pending {
+        let result = pending.install(target, &options, profiles_dir, source);
+
+        match result {
+            Ok(Some(success)) => report.installed.push(success),
+            Ok(None) => {}
+            Err(e) => report.errors.push(InstallFailure {
+                skill: pending.name().to_string(),
+                target: target.clone(),
+                error: e.to_string(),
+            }),
+        }
+    }
+
+    report
+}
+
+fn installed_success(outcome: InstallOutcome) -> Option<InstallSuccess> {
+    match outcome {
+        InstallOutcome::Installed(success) => Some(success),
+        InstallOutcome::Skipped(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ProfileName;
+    use crate::install::fixtures::{self, skill};
+    use crate::install::installer::install_skill_to_dir_with_source;
+    use tempfile::TempDir;
+
+    const LOCATION: &str = "https://github.com/owner/repo/tree/main";
+
+    fn source() -> SourceInfo {
+        SourceInfo {
+            git_ref: Some("main".to_string()),
+            ..fixtures::source()
+        }
+    }
+
+    fn setup(harness: &str) -> (TempDir, InstallTarget, std::path::PathBuf) {
+        let temp = TempDir::new().unwrap();
+        let profiles_dir = temp.path().join("profiles");
+        fs::create_dir_all(profiles_dir.join(harness).join("test")).unwrap();
+        let target = InstallTarget {
+            harness: harness.to_string(),
+            profile: ProfileName::new("test").unwrap(),
+        };
+        (temp, target, profiles_dir)
+    }
+
+    fn discovery(skills: Vec<SkillInfo>) -> DiscoveryResult {
+        DiscoveryResult {
+            source: source(),
+            ..fixtures::discovery(skills)
+        }
+    }
+
+    fn install(target: &InstallTarget, profiles_dir: &Path, skill: &SkillInfo) {
+        install_skill_to_dir_with_source(
+            skill,
+            target,
+            &InstallOptions::default(),
+            profiles_dir,
+            Some(&source()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn plan_reports_unchanged_when_content_matches() {
+        let (_temp, target, profiles_dir) = setup("claude-code");
+        install(&target, &profiles_dir, &skill("alpha", "v1"));
+
+        let mut cache = SourceCache::new();
+        cache.insert(LOCATION, Ok(discovery(vec![skill("alpha", "v1")])));
+
+        let plan = plan_update_in_dir(&target, &profiles_dir, &mut cache).unwrap();
+        assert_eq!(plan.components.len(), 1);
+        assert_eq!(plan.components[0].change, ChangeKind::Unchanged);
+        assert!(!plan.has_changes());
+    }
+
+    #[test]
+    fn plan_and_apply_modified_skill_with_opencode_transform() {
+        let (temp, target, profiles_dir) = setup("opencode");
+        install(&target, &profiles_dir, &skill("My Skill", "v1"));
+
+        let mut cache = SourceCache::new();
+        cache.insert(LOCATION, Ok(discovery(vec![skill("My Skill", "v2")])));
+
+        let plan = plan_update_in_dir(&target, &profiles_dir, &mut cache).unwrap();
+        let changed: Vec<_> = plan.changed().collect();
+        assert_eq!(changed.len(), 1);
+        assert_eq!(changed[0].name, "my-skill");
+        assert_eq!(changed[0].change, ChangeKind::Modified);
+        assert_ne!(changed[0].installed_digest, changed[0].upstream_digest);
+
+        let report = apply_update_in_dir(&plan, &profiles_dir);
+        assert!(report.errors.is_empty());
+        assert_eq!(report.installed.len(), 1);
+
+        let content = fs::read_to_string(
+            temp.path()
+                .join("profiles/opencode/test/skills/my-skill/SKILL.md"),
+        )
+        .unwrap();
+        assert!(content.contains("v2"));
+        assert!(content.contains("name: my-skill"));
+
+        let replan = plan_update_in_dir(&target, &profiles_dir, &mut cache).unwrap();
+        assert!(!replan.has_changes());
+    }
+
+    #[test]
+    fn plan_detects_missing_and_removed_upstream() {
+        let (temp, target, profiles_dir) = setup("claude-code");
+        install(&target, &profiles_dir, &skill("alpha", "v1"));
+        install(&target, &profiles_dir, &skill("beta", "v1"));
+        fs::remove_dir_all(temp.path().join("profiles/claude-code/test/skills/alpha")).unwrap();
+
+        let mut cache = SourceCache::new();
+        cache.insert(LOCATION, Ok(discovery(vec![skill("alpha", "v1")])));
+
+        let plan = plan_update_in_dir(&target, &profiles_dir, &mut cache).unwrap();
+        let change_of = |name: &str| {
+            plan.components
+                .iter()
+                .find(|c| c.name == name)
+                .map(|c| c.change)
+        };
+        assert_eq!(change_of("alpha"), Some(ChangeKind::Missing));
+        assert_eq!(change_of("beta"), Some(ChangeKind::RemovedUpstream));
+        assert_eq!(plan.changed().count(), 1);
+    }
+
+    #[test]
+    fn plan_records_source_failures() {
+        let (_temp, target, profiles_dir) = setup("claude-code");
+        install(&target, &profiles_dir, &skill("alpha", "v1"));
+
+        let mut cache = SourceCache::new();
+        cache.insert(LOCATION, Err(DiscoveryError::NoSkillsFound));
+
+        let plan = plan_update_in_dir(&target, &profiles_dir, &mut cache).unwrap();
+        assert!(plan.components.is_empty());
+        assert_eq!(plan.failures.len(), 1);
+        assert_eq!(plan.failures[0].source, LOCATION);
+    }
+
+    #[test]
+    fn plan_errors_for_missing_profile() {
+        let temp = TempDir::new().unwrap();
+        let target = InstallTarget {
+            harness: "claude-code".to_string(),
+            profile: ProfileName::new("nope").unwrap(),
+        };
+        let result = plan_update_in_dir(&target, temp.path(), &mut SourceCache::new());
+        assert!(matches!(result, Err(UpdateError::ProfileNotFound { .. })));
+    }
+}
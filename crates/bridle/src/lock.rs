@@ -0,0 +1,264 @@
+//! Advisory lock around changes to bridle state.
+//!
+//! Commands that change profiles, harness configs, `config.toml` or install
+//! manifests hold an exclusive lock on `state.lock` in the bridle config dir
+//! for as long as they run; read-only commands hold a shared lock so they
+//! never see a change half made. The lock is advisory (`flock` on Unix,
+//! `LockFileEx` on Windows) and is released when the process exits, so a
+//! crashed bridle never leaves it held.
+
+use std::fs::{File, OpenOptions, TryLockError};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+const LOCK_FILE: &str = "state.lock";
+
+#[derive(Debug, Error)]
+pub enum LockError {
+    #[error("Another bridle process{} is using bridle state", holder_suffix(*.holder))]
+    Busy { holder: Option<u32> },
+
+    #[error("Failed to lock {path}: {source}")]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+fn holder_suffix(holder: Option<u32>) -> String {
+    holder
+        .map(|pid| format!(" (pid {pid})"))
+        .unwrap_or_default()
+}
+
+/// How a command uses bridle state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LockMode {
+    /// Reads only; any number of readers can hold the lock together.
+    Shared,
+    /// Makes changes; excludes every other holder.
+    Exclusive,
+}
+
+/// A held lock, released on drop.
+#[derive(Debug)]
+pub struct StateLock {
+    file: File,
+    mode: LockMode,
+}
+
+/// Where the lock file is kept.
+pub fn lock_path() -> crate::error::Result<PathBuf> {
+    crate::config::BridleConfig::config_dir().map(|dir| dir.join(LOCK_FILE))
+}
+
+impl StateLock {
+    /// Take the lock in the bridle config dir. When another process holds it,
+    /// waits (saying so on stderr) or, when `wait` is false, fails with
+    /// [`LockError::Busy`].
+    pub fn acquire(mode: LockMode, wait: bool) -> crate::error::Result<Self> {
+        let path = lock_path()?;
+        let lock = Self::acquire_at(&path, mode, wait, |holder| {
+            eprintln!(
+                "Waiting for another bridle process{} to finish... (use --no-wait to fail instead)",
+                holder_suffix(holder)
+            );
+        })?;
+        Ok(lock)
+    }
+
+    /// Take the lock at `path`, calling `on_wait` with the holder's pid (when
+    /// known) before blocking.
+    pub fn acquire_at(
+        path: &Path,
+        mode: LockMode,
+        wait: bool,
+        on_wait: impl FnOnce(Option<u32>),
+    ) -> Result<Self, LockError> {
+        let io_error = |source| LockError::Io {
+            path: path.to_path_buf(),
+            source,
+        };
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(io_error)?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .map_err(io_error)?;
+
+        let attempt = match mode {
+            LockMode::Shared => file.try_lock_shared(),
+            LockMode::Exclusive => file.try_lock(),
+        };
+        match attempt {
+            Ok(()) => {}
+            Err(TryLockError::WouldBlock) => {
+                let holder = read_holder(&mut file);
+                if !wait {
+                    return Err(LockError::Busy { holder });
+                }
+                on_wait(holder);
+                match mode {
+                    LockMode::Shared => file.lock_shared(),
+                    LockMode::Exclusive => file.lock(),
+                }
+                .map_err(io_error)?;
+            }
+            Err(TryLockError::Error(e)) => return Err(io_error(e)),
+        }
+
+        if mode == LockMode::Exclusive {
+            // Tell anyone who has to wait who they are waiting for
+            write_holder(&mut file, Some(std::process::id())).map_err(io_error)?;
+        }
+        Ok(Self { file, mode })
+    }
+}
+
+impl Drop for StateLock {
+    fn drop(&mut self) {
+        if self.mode == LockMode::Exclusive {
+            let _ = write_holder(&mut self.file, None);
+        }
+        let _ = self.file.unlock();
+    }
+}
+
+fn read_holder(file: &mut File) -> Option<u32> {
+    let mut content = String::new();
+    file.seek(SeekFrom::Start(0)).ok()?;
+    file.read_to_string(&mut content).ok()?;
+    content.trim().parse().ok()
+}
+
+fn write_holder(file: &mut File, pid: Option<u32>) -> io::Result<()> {
+    file.set_len(0)?;
+    file.seek(SeekFrom::Start(0))?;
+    if let Some(pid) = pid {
+        write!(file, "{pid}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::{Command, Stdio};
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    const RACE_DIR_VAR: &str = "BRIDLE_LOCK_RACE_DIR";
+    const RACE_ROUNDS: u32 = 20;
+
+    fn no_wait(_: Option<u32>) {
+        panic!("expected to get the lock without waiting");
+    }
+
+    #[test]
+    fn no_wait_fails_fast_naming_the_holder() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(LOCK_FILE);
+        let _held = StateLock::acquire_at(&path, LockMode::Exclusive, false, no_wait).unwrap();
+
+        for mode in [LockMode::Shared, LockMode::Exclusive] {
+            let err = StateLock::acquire_at(&path, mode, false, no_wait).unwrap_err();
+            assert!(matches!(
+                err,
+                LockError::Busy { holder: Some(pid) } if pid == std::process::id()
+            ));
+        }
+    }
+
+    #[test]
+    fn shared_locks_coexist_and_exclude_writers() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(LOCK_FILE);
+        let first = StateLock::acquire_at(&path, LockMode::Shared, false, no_wait).unwrap();
+        let _second = StateLock::acquire_at(&path, LockMode::Shared, false, no_wait).unwrap();
+
+        let err = StateLock::acquire_at(&path, LockMode::Exclusive, false, no_wait).unwrap_err();
+        assert!(matches!(err, LockError::Busy { holder: None }));
+        drop(first);
+    }
+
+    #[test]
+    fn released_on_drop() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(LOCK_FILE);
+        drop(StateLock::acquire_at(&path, LockMode::Exclusive, false, no_wait).unwrap());
+
+        StateLock::acquire_at(&path, LockMode::Exclusive, false, no_wait).unwrap();
+    }
+
+    #[test]
+    fn waiter_blocks_until_holder_releases() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(LOCK_FILE);
+        let held = StateLock::acquire_at(&path, LockMode::Exclusive, false, no_wait).unwrap();
+
+        let waiter_path = path.clone();
+        let waiter = std::thread::spawn(move || {
+            let mut waited = None;
+            let _lock = StateLock::acquire_at(&waiter_path, LockMode::Shared, true, |holder| {
+                waited = Some(holder);
+            })
+            .unwrap();
+            waited
+        });
+        std::thread::sleep(Duration::from_millis(100));
+        drop(held);
+
+        assert_eq!(waiter.join().unwrap(), Some(Some(std::process::id())));
+    }
+
+    /// One side of [`two_processes_never_interleave_changes`]. Does nothing
+    /// unless started by that test.
+    #[test]
+    fn race_worker() {
+        let Some(dir) = std::env::var_os(RACE_DIR_VAR).map(PathBuf::from) else {
+            return;
+        };
+        let counter = dir.join("counter");
+        for _ in 0..RACE_ROUNDS {
+            let _lock =
+                StateLock::acquire_at(&dir.join(LOCK_FILE), LockMode::Exclusive, true, |_| {})
+                    .unwrap();
+            // A read-modify-write that loses updates unless the lock holds
+            let value: u32 = std::fs::read_to_string(&counter)
+                .map(|s| s.trim().parse().unwrap())
+                .unwrap_or(0);
+            std::thread::sleep(Duration::from_millis(2));
+            std::fs::write(&counter, (value + 1).to_string()).unwrap();
+        }
+    }
+
+    #[test]
+    fn two_processes_never_interleave_changes() {
+        let temp = TempDir::new().unwrap();
+        let exe = std::env::current_exe().unwrap();
+        let workers: Vec<_> = (0..2)
+            .map(|_| {
+                Command::new(&exe)
+                    .args(["--exact", "lock::tests::race_worker", "--quiet"])
+                    .env(RACE_DIR_VAR, temp.path())
+                    .stdout(Stdio::null())
+                    .spawn()
+                    .unwrap()
+            })
+            .collect();
+        for mut worker in workers {
+            assert!(worker.wait().unwrap().success());
+        }
+
+        let count = std::fs::read_to_string(temp.path().join("counter")).unwrap();
+        assert_eq!(count, (2 * RACE_ROUNDS).to_string());
+    }
+}
@@ -4,11 +4,20 @@ mod display;
 mod error;
 mod harness;
 mod install;
+mod lock;
+mod plan;
 mod tui;
 
 use clap::Parser;
 use cli::output::OutputFormat;
-use cli::{Commands, ConfigCommands, ProfileCommands};
+use cli::{
+    BackupCommands, Commands, ConfigCommands, MarketplaceCommands, McpCommands, ProfileCommands,
+    WorkspaceCommands,
+};
+use color_eyre::eyre::eyre;
+use install::project::resolve_project_root;
+use lock::{LockMode, StateLock};
+use plan::Recovery;
 
 #[derive(Parser)]
 #[command(name = "bridle")]
@@ -17,6 +26,14 @@ struct Cli {
     #[arg(long, short = 'o', default_value = "auto", global = true)]
     output: OutputFormat,
 
+    /// Print the file changes a command would make instead of making them
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Fail instead of waiting when another bridle process is running
+    #[arg(long, global = true)]
+    no_wait: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -27,10 +44,56 @@ fn main() -> color_eyre::Result<()> {
     let cli = Cli::parse();
     let format = cli.output.resolve();
 
+    let wait = !cli.no_wait;
+
+    if let Ok(journal) = plan::journal_path()
+        && journal.exists()
+    {
+        // Recovering under the lock keeps us from undoing a change that
+        // another bridle process is still making
+        let _lock = StateLock::acquire(LockMode::Exclusive, wait)?;
+        let recovered = plan::recover(&journal).map_err(|e| {
+            eyre!(
+                "Failed to recover an interrupted change recorded in {}: {}",
+                journal.display(),
+                e
+            )
+        })?;
+        match recovered {
+            Some(Recovery::RolledBack) => eprintln!("Rolled back an interrupted change"),
+            Some(Recovery::Completed) => eprintln!("Finished an interrupted change"),
+            None => {}
+        }
+    }
+    let dry_run = cli.dry_run;
+    if dry_run && !cli.command.as_ref().is_some_and(Commands::supports_dry_run) {
+        return Err(eyre!("--dry-run is not supported for this command"));
+    }
+    let _lock = cli
+        .command
+        .as_ref()
+        .and_then(|command| command.lock_mode(dry_run))
+        .map(|mode| StateLock::acquire(mode, wait))
+        .transpose()?;
+
     match cli.command {
         None | Some(Commands::Tui) => cli::tui::run_tui()?,
-        Some(Commands::Status) => cli::status::display_status(format),
-        Some(Commands::Init) => cli::init::run_init()?,
+        Some(Commands::Exec {
+            harness,
+            profile,
+            args,
+        }) => cli::exec::exec(&harness, &profile, &args, wait)?,
+        Some(Commands::Hook { shell }) => cli::hook::print_hook(shell),
+        Some(Commands::Resolve { dir, apply }) => {
+            cli::hook::resolve(dir.as_deref(), apply, wait, dry_run, format)?
+        }
+        Some(Commands::Status { project }) => {
+            let root = project
+                .map(|p| resolve_project_root(p.as_deref()))
+                .transpose()?;
+            cli::status::display_status(format, root.as_deref())
+        }
+        Some(Commands::Init) => cli::init::run_init(dry_run, format)?,
         Some(Commands::Profile(profile_cmd)) => match profile_cmd {
             ProfileCommands::List { harness } => cli::profile::list_profiles(&harness, format)?,
             ProfileCommands::Show { harness, name } => {
@@ -40,32 +103,204 @@ fn main() -> color_eyre::Result<()> {
                 harness,
                 name,
                 from_current,
+                extends,
             } => {
                 if from_current {
-                    cli::profile::create_profile_from_current(&harness, &name)?
+                    cli::profile::create_profile_from_current(&harness, &name, dry_run, format)?
                 } else {
-                    cli::profile::create_profile(&harness, &name)?
+                    cli::profile::create_profile(&harness, &name, &extends, dry_run, format)?
                 }
             }
             ProfileCommands::Delete { harness, name } => {
-                cli::profile::delete_profile(&harness, &name)?
+                cli::profile::delete_profile(&harness, &name, dry_run, format)?
             }
-            ProfileCommands::Switch { harness, name } => {
-                cli::profile::switch_profile(&harness, &name)?
+            ProfileCommands::Switch {
+                harness,
+                name,
+                project: None,
+                keep_drift,
+                discard_drift,
+                fork_drift,
+            } => {
+                let drift = cli::profile::drift_action_from_flags(
+                    keep_drift,
+                    discard_drift,
+                    fork_drift.as_deref(),
+                )?;
+                cli::profile::switch_profile(&harness, &name, drift, dry_run, format)?
+            }
+            ProfileCommands::Switch {
+                harness,
+                name,
+                project: Some(project),
+                ..
+            } => {
+                let root = resolve_project_root(project.as_deref())?;
+                cli::profile::switch_project_profile(&harness, &name, &root, dry_run, format)?
             }
             ProfileCommands::Edit { harness, name } => cli::profile::edit_profile(&harness, &name)?,
             ProfileCommands::Diff {
                 harness,
                 name,
                 other,
-            } => cli::profile::diff_profiles(&harness, &name, other.as_deref())?,
+                raw: false,
+            } => cli::profile::diff_profiles(&harness, &name, other.as_deref(), format)?,
+            ProfileCommands::Diff {
+                harness,
+                name,
+                other,
+                raw: true,
+            } => cli::profile::diff_profiles_raw(&harness, &name, other.as_deref())?,
+            ProfileCommands::Convert {
+                from_harness,
+                name,
+                to_harness,
+                new_name,
+            } => cli::profile::convert_profile(
+                &from_harness,
+                &name,
+                &to_harness,
+                new_name.as_deref(),
+                dry_run,
+                format,
+            )?,
+            ProfileCommands::Export {
+                harness,
+                name,
+                file,
+                force,
+            } => cli::profile::export_profile(&harness, &name, file.as_deref(), force, format)?,
+            ProfileCommands::Import { file, name, yes } => {
+                cli::profile::import_profile(&file, name.as_deref(), yes, dry_run, format)?
+            }
+        },
+        Some(Commands::Use { workspace }) => {
+            cli::workspace::use_workspace(&workspace, dry_run, format)?
+        }
+        Some(Commands::Workspace(workspace_cmd)) => match workspace_cmd {
+            WorkspaceCommands::Set { name, profiles } => {
+                cli::workspace::set(&name, &profiles, dry_run, format)?
+            }
+            WorkspaceCommands::List => cli::workspace::list(format)?,
+            WorkspaceCommands::Remove { name } => cli::workspace::remove(&name, dry_run, format)?,
         },
         Some(Commands::Config(config_cmd)) => match config_cmd {
-            ConfigCommands::Set { key, value } => cli::config_cmd::set_config(&key, &value)?,
+            ConfigCommands::Set { key, value } => {
+                cli::config_cmd::set_config(&key, &value, dry_run, format)?
+            }
             ConfigCommands::Get { key } => cli::config_cmd::get_config(&key)?,
         },
-        Some(Commands::Install { source, force }) => cli::install::run(&source, force)?,
-        Some(Commands::Uninstall { harness, profile }) => cli::uninstall::run(&harness, &profile)?,
+        Some(Commands::Backup(backup_cmd)) => match backup_cmd {
+            BackupCommands::List { harness } => cli::backup::list(&harness, format)?,
+            BackupCommands::Show { harness, id } => cli::backup::show(&harness, &id, format)?,
+            BackupCommands::Restore { harness, id } => {
+                cli::backup::restore(&harness, &id, dry_run, format)?
+            }
+            BackupCommands::Prune {
+                harness,
+                keep,
+                max_age_days,
+                max_size_mb,
+            } => {
+                let overrides = config::BackupRetention {
+                    keep,
+                    max_age_days,
+                    max_size_mb,
+                };
+                cli::backup::prune(&harness, overrides, dry_run, format)?
+            }
+        },
+        Some(Commands::Search { query }) => cli::search::run(&query, format)?,
+        Some(Commands::Marketplace(marketplace_cmd)) => match marketplace_cmd {
+            MarketplaceCommands::Add { source, name } => {
+                cli::marketplace::add(&source, name.as_deref(), dry_run, format)?
+            }
+            MarketplaceCommands::List => cli::marketplace::list(format)?,
+            MarketplaceCommands::Remove { name } => {
+                cli::marketplace::remove(&name, dry_run, format)?
+            }
+        },
+        Some(Commands::Mcp(mcp_cmd)) => match mcp_cmd {
+            McpCommands::List { harness, profile } => cli::mcp::list(&harness, &profile, format)?,
+            McpCommands::Add {
+                harness,
+                profile,
+                name,
+                server,
+                force,
+            } => cli::mcp::add(&harness, &profile, &name, &server, force, dry_run, format)?,
+            McpCommands::Rm {
+                harness,
+                profile,
+                name,
+            } => cli::mcp::remove(&harness, &profile, &name, dry_run, format)?,
+            McpCommands::Enable {
+                harness,
+                profile,
+                name,
+            } => cli::mcp::set_enabled(&harness, &profile, &name, true, dry_run, format)?,
+            McpCommands::Disable {
+                harness,
+                profile,
+                name,
+            } => cli::mcp::set_enabled(&harness, &profile, &name, false, dry_run, format)?,
+            McpCommands::Mv {
+                harness,
+                profile,
+                from,
+                to,
+            } => cli::mcp::rename(&harness, &profile, &from, &to, dry_run, format)?,
+            McpCommands::Test {
+                harness,
+                profile,
+                server,
+            } => cli::mcp::test(&harness, &profile, server.as_deref(), format)?,
+        },
+        Some(Commands::Install {
+            source,
+            force,
+            locked,
+            project,
+            select,
+        }) => match source {
+            Some(source) if !locked => {
+                let root = project
+                    .map(|p| resolve_project_root(p.as_deref()))
+                    .transpose()?;
+                let code =
+                    cli::install::run(&source, force, root.as_deref(), &select, dry_run, format)?;
+                if code != 0 {
+                    std::process::exit(code);
+                }
+            }
+            source => cli::sync::run(None, source.as_deref(), dry_run, format)?,
+        },
+        Some(Commands::Sync { lockfile }) => {
+            cli::sync::run(lockfile.as_deref(), None, dry_run, format)?
+        }
+        Some(Commands::Update {
+            harness,
+            profile,
+            all,
+        }) => cli::update::run(harness.as_deref(), profile.as_deref(), all, dry_run, format)?,
+        Some(Commands::Apply { spec }) => cli::apply::run(&spec, dry_run, format)?,
+        Some(Commands::Doctor {
+            harness,
+            profile,
+            fix,
+        }) => cli::doctor::run(harness.as_deref(), profile.as_deref(), fix, dry_run, format)?,
+        Some(Commands::Uninstall {
+            harness,
+            profile,
+            project,
+        }) => match (profile, project) {
+            (_, Some(project)) => {
+                let root = resolve_project_root(project.as_deref())?;
+                cli::uninstall::run_project(&harness, &root, dry_run, format)?
+            }
+            (Some(profile), None) => cli::uninstall::run(&harness, &profile, dry_run, format)?,
+            (None, None) => unreachable!("clap requires a profile or --project"),
+        },
     }
 
     Ok(())
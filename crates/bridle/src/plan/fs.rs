@@ -0,0 +1,491 @@
+//! Filesystem facade for code that changes harness configs or profiles.
+//!
+//! Outside [`record`](super::record) every function forwards to `std::fs`.
+//! While a plan is being recorded, changes land in an in-memory overlay and
+//! reads see that overlay on top of the disk, so the same code computes a
+//! [`Plan`](super::Plan) without touching anything.
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::ffi::OsString;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use harness_locate::HarnessKind;
+
+thread_local! {
+    static OVERLAY: RefCell<Option<Overlay>> = const { RefCell::new(None) };
+}
+
+/// Symlinks followed before giving up, as a guard against loops.
+const MAX_SYMLINK_DEPTH: usize = 8;
+
+/// Where a planned file's bytes come from.
+#[derive(Debug, Clone)]
+pub(super) enum Content {
+    Bytes(Vec<u8>),
+    /// The file as it is on disk before the plan is applied.
+    Disk(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum Node {
+    File(Content),
+    Symlink(PathBuf),
+    /// A `fresh` directory was (re)created after its disk counterpart was
+    /// removed, so nothing on disk below it shows through.
+    Dir {
+        fresh: bool,
+    },
+    Absent,
+}
+
+/// Entry state as seen through the overlay.
+#[derive(Debug, Clone)]
+pub(super) enum State {
+    Missing,
+    File(Content),
+    Symlink(PathBuf),
+    Dir,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct Overlay {
+    pub(super) nodes: BTreeMap<PathBuf, Node>,
+    pub(super) backups: Vec<PathBuf>,
+    pub(super) mcp_configs: Vec<(PathBuf, HarnessKind)>,
+}
+
+impl Overlay {
+    /// State of `path` itself, not following a final symlink.
+    pub(super) fn lookup(&self, path: &Path) -> State {
+        match self.nodes.get(path) {
+            Some(Node::File(content)) => State::File(content.clone()),
+            Some(Node::Symlink(target)) => State::Symlink(target.clone()),
+            Some(Node::Dir { .. }) => State::Dir,
+            Some(Node::Absent) => State::Missing,
+            None if self.disk_visible(path) => disk_state(path),
+            None => State::Missing,
+        }
+    }
+
+    /// Whether disk entries at `path` show through: no ancestor was removed,
+    /// replaced or recreated fresh.
+    fn disk_visible(&self, path: &Path) -> bool {
+        path.ancestors().skip(1).all(|ancestor| {
+            matches!(
+                self.nodes.get(ancestor),
+                None | Some(Node::Dir { fresh: false })
+            )
+        })
+    }
+
+    /// Follow symlinks at `path`, returning the final path and its state.
+    fn resolve(&self, path: &Path) -> (PathBuf, State) {
+        let mut path = path.to_path_buf();
+        for _ in 0..MAX_SYMLINK_DEPTH {
+            match self.lookup(&path) {
+                State::Symlink(target) => {
+                    path = match path.parent() {
+                        Some(parent) => parent.join(target),
+                        None => target,
+                    };
+                }
+                state => return (path, state),
+            }
+        }
+        (path, State::Missing)
+    }
+
+    fn require_parent_dir(&self, path: &Path) -> io::Result<()> {
+        match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => match self.resolve(parent).1 {
+                State::Dir => Ok(()),
+                _ => Err(not_found(parent)),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    fn put_file(&mut self, path: &Path, content: Content) -> io::Result<()> {
+        let (path, state) = self.resolve(path);
+        if matches!(state, State::Dir) {
+            return Err(io::Error::other(format!(
+                "{} is a directory",
+                path.display()
+            )));
+        }
+        self.require_parent_dir(&path)?;
+        self.nodes.insert(path, Node::File(content));
+        Ok(())
+    }
+
+    fn read(&self, path: &Path) -> io::Result<Vec<u8>> {
+        match self.resolve(path).1 {
+            State::File(Content::Bytes(bytes)) => Ok(bytes),
+            State::File(Content::Disk(source)) => std::fs::read(source),
+            State::Dir => Err(io::Error::other(format!(
+                "{} is a directory",
+                path.display()
+            ))),
+            _ => Err(not_found(path)),
+        }
+    }
+
+    fn copy(&mut self, from: &Path, to: &Path) -> io::Result<u64> {
+        let content = match self.resolve(from).1 {
+            State::File(content) => content,
+            _ => return Err(not_found(from)),
+        };
+        let len = match &content {
+            Content::Bytes(bytes) => bytes.len() as u64,
+            Content::Disk(source) => std::fs::metadata(source)?.len(),
+        };
+        self.put_file(to, content)?;
+        Ok(len)
+    }
+
+    fn create_dir_all(&mut self, path: &Path) -> io::Result<()> {
+        let mut ancestors: Vec<&Path> = path
+            .ancestors()
+            .filter(|a| !a.as_os_str().is_empty())
+            .collect();
+        ancestors.reverse();
+        for dir in ancestors {
+            match self.resolve(dir).1 {
+                State::Dir => {}
+                State::Missing => {
+                    let fresh = std::fs::symlink_metadata(dir).is_ok();
+                    self.nodes.insert(dir.to_path_buf(), Node::Dir { fresh });
+                }
+                _ => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::AlreadyExists,
+                        format!("{} exists and is not a directory", dir.display()),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn remove(&mut self, path: &Path, dir: bool) -> io::Result<()> {
+        match (self.lookup(path), dir) {
+            (State::Missing, _) => return Err(not_found(path)),
+            (State::Dir, false) => {
+                return Err(io::Error::other(format!(
+                    "{} is a directory",
+                    path.display()
+                )));
+            }
+            (State::File(_), true) => {
+                return Err(io::Error::other(format!(
+                    "{} is not a directory",
+                    path.display()
+                )));
+            }
+            _ => {}
+        }
+        let below: Vec<PathBuf> = self
+            .nodes
+            .range(path.to_path_buf()..)
+            .map(|(p, _)| p)
+            .take_while(|p| p.starts_with(path))
+            .filter(|p| p.as_path() != path)
+            .cloned()
+            .collect();
+        for p in below {
+            self.nodes.remove(&p);
+        }
+        self.nodes.insert(path.to_path_buf(), Node::Absent);
+        Ok(())
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<DirEntry>> {
+        let (resolved, state) = self.resolve(path);
+        if !matches!(state, State::Dir) {
+            return Err(not_found(path));
+        }
+
+        let mut names: Vec<OsString> = Vec::new();
+        let fresh = matches!(self.nodes.get(&resolved), Some(Node::Dir { fresh: true }));
+        if !fresh
+            && self.disk_visible(&resolved)
+            && let Ok(entries) = std::fs::read_dir(&resolved)
+        {
+            names.extend(entries.flatten().map(|e| e.file_name()));
+        }
+        names.extend(
+            self.nodes
+                .range(resolved.clone()..)
+                .map(|(p, _)| p)
+                .take_while(|p| p.starts_with(&resolved))
+                .filter(|p| p.parent() == Some(resolved.as_path()))
+                .filter_map(|p| p.file_name().map(OsString::from)),
+        );
+        names.sort();
+        names.dedup();
+
+        Ok(names
+            .into_iter()
+            .filter_map(|name| {
+                let kind = match self.lookup(&resolved.join(&name)) {
+                    State::Missing => return None,
+                    State::Dir => Kind::Dir,
+                    State::File(_) => Kind::File,
+                    State::Symlink(_) => Kind::Symlink,
+                };
+                Some(DirEntry {
+                    path: path.join(&name),
+                    file_type: FileType(kind),
+                })
+            })
+            .collect())
+    }
+}
+
+/// State of `path` on disk, ignoring the overlay.
+pub(super) fn disk_state(path: &Path) -> State {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_symlink() => std::fs::read_link(path)
+            .map(State::Symlink)
+            .unwrap_or(State::Missing),
+        Ok(meta) if meta.is_dir() => State::Dir,
+        Ok(_) => State::File(Content::Disk(path.to_path_buf())),
+        Err(_) => State::Missing,
+    }
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("{} not found", path.display()),
+    )
+}
+
+/// Run `f` against the overlay if a plan is being recorded.
+fn planned<R>(f: impl FnOnce(&mut Overlay) -> R) -> Option<R> {
+    OVERLAY.with(|overlay| overlay.borrow_mut().as_mut().map(f))
+}
+
+pub(super) fn is_recording() -> bool {
+    OVERLAY.with(|overlay| overlay.borrow().is_some())
+}
+
+pub(super) fn start() {
+    OVERLAY.with(|overlay| *overlay.borrow_mut() = Some(Overlay::default()));
+}
+
+pub(super) fn finish() -> Overlay {
+    OVERLAY.with(|overlay| overlay.borrow_mut().take().unwrap_or_default())
+}
+
+pub(super) fn note_backup(path: &Path) {
+    planned(|overlay| overlay.backups.push(path.to_path_buf()));
+}
+
+pub(super) fn note_mcp_config(path: &Path, kind: HarnessKind) {
+    planned(|overlay| {
+        if !overlay.mcp_configs.iter().any(|(p, _)| p == path) {
+            overlay.mcp_configs.push((path.to_path_buf(), kind));
+        }
+    });
+}
+
+/// Write `contents` to `path` atomically; see [`write_atomic`].
+pub fn write(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let (path, contents) = (path.as_ref(), contents.as_ref());
+    planned(|o| o.put_file(path, Content::Bytes(contents.to_vec())))
+        .unwrap_or_else(|| write_atomic(path, contents))
+}
+
+/// Write to a temporary file next to `path` and rename it into place, so
+/// readers see either the old or the new contents and never a partial file.
+///
+/// A symlink at `path` is followed and its target replaced, and an existing
+/// file's permissions are kept.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let target = match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_symlink() => std::fs::canonicalize(path)?,
+        _ => path.to_path_buf(),
+    };
+    let mut name = OsString::from(".");
+    name.push(target.file_name().unwrap_or_default());
+    name.push(format!(".bridle-tmp-{}", std::process::id()));
+    let temp = target.with_file_name(name);
+
+    let result = (|| {
+        let mut file = std::fs::File::create(&temp)?;
+        file.write_all(contents)?;
+        if let Ok(meta) = std::fs::metadata(&target) {
+            file.set_permissions(meta.permissions())?;
+        }
+        file.sync_all()?;
+        std::fs::rename(&temp, &target)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp);
+    }
+    result
+}
+
+pub fn read(path: impl AsRef<Path>) -> io::Result<Vec<u8>> {
+    let path = path.as_ref();
+    planned(|o| o.read(path)).unwrap_or_else(|| std::fs::read(path))
+}
+
+pub fn read_to_string(path: impl AsRef<Path>) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn copy(from: impl AsRef<Path>, to: impl AsRef<Path>) -> io::Result<u64> {
+    let (from, to) = (from.as_ref(), to.as_ref());
+    planned(|o| o.copy(from, to)).unwrap_or_else(|| std::fs::copy(from, to))
+}
+
+pub fn create_dir_all(path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+    planned(|o| o.create_dir_all(path)).unwrap_or_else(|| std::fs::create_dir_all(path))
+}
+
+pub fn remove_file(path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+    planned(|o| o.remove(path, false)).unwrap_or_else(|| std::fs::remove_file(path))
+}
+
+pub fn remove_dir_all(path: impl AsRef<Path>) -> io::Result<()> {
+    let path = path.as_ref();
+    planned(|o| o.remove(path, true)).unwrap_or_else(|| std::fs::remove_dir_all(path))
+}
+
+/// Size in bytes of the file at `path`.
+pub fn len(path: impl AsRef<Path>) -> io::Result<u64> {
+    let path = path.as_ref();
+    planned(|o| match o.resolve(path).1 {
+        State::File(Content::Bytes(bytes)) => Ok(bytes.len() as u64),
+        State::File(Content::Disk(source)) => std::fs::metadata(source).map(|m| m.len()),
+        _ => Err(not_found(path)),
+    })
+    .unwrap_or_else(|| std::fs::metadata(path).map(|m| m.len()))
+}
+
+pub fn read_link(path: impl AsRef<Path>) -> io::Result<PathBuf> {
+    let path = path.as_ref();
+    planned(|o| match o.lookup(path) {
+        State::Symlink(target) => Ok(target),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a symlink", path.display()),
+        )),
+    })
+    .unwrap_or_else(|| std::fs::read_link(path))
+}
+
+#[cfg(unix)]
+pub fn symlink(target: impl AsRef<Path>, link: impl AsRef<Path>) -> io::Result<()> {
+    let (target, link) = (target.as_ref(), link.as_ref());
+    planned(|o| {
+        if !matches!(o.lookup(link), State::Missing) {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!("{} already exists", link.display()),
+            ));
+        }
+        o.require_parent_dir(link)?;
+        o.nodes
+            .insert(link.to_path_buf(), Node::Symlink(target.to_path_buf()));
+        Ok(())
+    })
+    .unwrap_or_else(|| std::os::unix::fs::symlink(target, link))
+}
+
+pub fn read_dir(path: impl AsRef<Path>) -> io::Result<ReadDir> {
+    let path = path.as_ref();
+    let entries = match planned(|o| o.read_dir(path)) {
+        Some(entries) => entries?.into_iter().map(Ok).collect(),
+        None => std::fs::read_dir(path)?
+            .map(|entry| {
+                let entry = entry?;
+                let file_type = entry.file_type()?;
+                let kind = if file_type.is_symlink() {
+                    Kind::Symlink
+                } else if file_type.is_dir() {
+                    Kind::Dir
+                } else {
+                    Kind::File
+                };
+                Ok(DirEntry {
+                    path: entry.path(),
+                    file_type: FileType(kind),
+                })
+            })
+            .collect::<Vec<_>>(),
+    };
+    Ok(entries.into_iter())
+}
+
+/// Whether `path` exists, following symlinks like [`Path::exists`].
+pub fn exists(path: impl AsRef<Path>) -> bool {
+    let path = path.as_ref();
+    planned(|o| !matches!(o.resolve(path).1, State::Missing)).unwrap_or_else(|| path.exists())
+}
+
+pub fn is_dir(path: impl AsRef<Path>) -> bool {
+    let path = path.as_ref();
+    planned(|o| matches!(o.resolve(path).1, State::Dir)).unwrap_or_else(|| path.is_dir())
+}
+
+pub fn is_file(path: impl AsRef<Path>) -> bool {
+    let path = path.as_ref();
+    planned(|o| matches!(o.resolve(path).1, State::File(_))).unwrap_or_else(|| path.is_file())
+}
+
+pub type ReadDir = std::vec::IntoIter<io::Result<DirEntry>>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    File,
+    Dir,
+    Symlink,
+}
+
+/// Kind of a directory entry; symlinks are not followed.
+#[derive(Debug, Clone, Copy)]
+pub struct FileType(Kind);
+
+impl FileType {
+    pub fn is_file(&self) -> bool {
+        self.0 == Kind::File
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.0 == Kind::Dir
+    }
+
+    pub fn is_symlink(&self) -> bool {
+        self.0 == Kind::Symlink
+    }
+}
+
+#[derive(Debug)]
+pub struct DirEntry {
+    path: PathBuf,
+    file_type: FileType,
+}
+
+impl DirEntry {
+    pub fn path(&self) -> PathBuf {
+        self.path.clone()
+    }
+
+    pub fn file_name(&self) -> OsString {
+        self.path
+            .file_name()
+            .map(OsString::from)
+            .unwrap_or_default()
+    }
+
+    pub fn file_type(&self) -> io::Result<FileType> {
+        Ok(self.file_type)
+    }
+}
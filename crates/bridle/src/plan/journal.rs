@@ -0,0 +1,147 @@
+//! Journal of a plan being applied.
+//!
+//! Before the first change is made, the entries that will be moved aside and
+//! the entries that will be created are written to `journal.json` in the
+//! bridle config dir. If bridle stops part way, the next run finds the
+//! journal and rolls the changes back, or finishes the cleanup if every
+//! change had already been made.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use super::fs::write_atomic;
+
+const JOURNAL_FILE: &str = "journal.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(super) enum Phase {
+    /// Changes are being made; an interrupted apply is rolled back.
+    Applying,
+    /// Every change was made; only moved-aside entries remain to delete.
+    Committed,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct Journal {
+    pub(super) pid: u32,
+    pub(super) started: DateTime<Local>,
+    pub(super) phase: Phase,
+    /// Entries being replaced or deleted, and where they were moved.
+    pub(super) asides: Vec<(PathBuf, PathBuf)>,
+    /// Entries the plan creates, in creation order.
+    pub(super) created: Vec<PathBuf>,
+}
+
+/// What recovering an interrupted apply did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recovery {
+    RolledBack,
+    Completed,
+}
+
+/// Where the journal is kept.
+pub fn journal_path() -> crate::error::Result<PathBuf> {
+    crate::config::BridleConfig::config_dir().map(|dir| dir.join(JOURNAL_FILE))
+}
+
+impl Journal {
+    pub(super) fn new(asides: Vec<(PathBuf, PathBuf)>, created: Vec<PathBuf>) -> Self {
+        Self {
+            pid: std::process::id(),
+            started: Local::now(),
+            phase: Phase::Applying,
+            asides,
+            created,
+        }
+    }
+
+    pub(super) fn load(path: &Path) -> io::Result<Option<Self>> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub(super) fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        write_atomic(path, content.as_bytes())
+    }
+
+    /// Undo whatever part of the plan was made: remove created entries and
+    /// move the originals back.
+    pub(super) fn rollback(&self) -> io::Result<()> {
+        for path in self.created.iter().rev() {
+            // A replaced entry that was never moved aside is still the original
+            let moved = self
+                .asides
+                .iter()
+                .find(|(original, _)| original == path)
+                .is_none_or(|(_, aside)| exists(aside));
+            if moved && exists(path) {
+                remove_any(path)?;
+            }
+        }
+        for (original, aside) in self.asides.iter().rev() {
+            if exists(aside) {
+                if exists(original) {
+                    remove_any(original)?;
+                }
+                std::fs::rename(aside, original)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete the replaced originals once every change is in place.
+    pub(super) fn discard_asides(&self) -> io::Result<()> {
+        for (_, aside) in &self.asides {
+            if exists(aside) {
+                remove_any(aside)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Finish or roll back an apply that was interrupted, if the journal at
+/// `path` says there was one.
+pub fn recover(path: &Path) -> io::Result<Option<Recovery>> {
+    let Some(journal) = Journal::load(path)? else {
+        return Ok(None);
+    };
+    let recovery = match journal.phase {
+        Phase::Applying => {
+            journal.rollback()?;
+            Recovery::RolledBack
+        }
+        Phase::Committed => {
+            journal.discard_asides()?;
+            Recovery::Completed
+        }
+    };
+    std::fs::remove_file(path)?;
+    Ok(Some(recovery))
+}
+
+pub(super) fn exists(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok()
+}
+
+pub(super) fn remove_any(path: &Path) -> io::Result<()> {
+    let meta = std::fs::symlink_metadata(path)?;
+    if meta.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
@@ -0,0 +1,692 @@
+//! Planned filesystem changes.
+//!
+//! Commands that modify profiles or harness configs do their file work
+//! through [`fs`]. Running that work inside [`record`] captures it as a
+//! [`Plan`] instead: the files that would be created, overwritten or
+//! deleted, the MCP servers each harness config would gain or lose, and the
+//! backups that would be taken. A plan can be rendered for `--dry-run` or
+//! applied as a separate step.
+
+pub mod fs;
+mod journal;
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use harness_locate::{Harness, HarnessKind};
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::harness::HarnessConfig;
+use crate::install::mcp_config::parse_mcp_servers;
+use fs::{Content, Node, Overlay, State, disk_state};
+use journal::{Journal, Phase};
+pub use journal::{Recovery, journal_path, recover};
+
+#[derive(Debug, Error)]
+pub enum PlanError {
+    #[error("Failed to apply change to {path}: {source}")]
+    Apply {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Failed to apply plan ({error}); rollback also failed: {rollback}")]
+    Rollback {
+        error: Box<PlanError>,
+        rollback: io::Error,
+    },
+
+    #[error("Failed to update journal: {0}")]
+    Journal(String),
+
+    #[error(
+        "An interrupted change from {started} is still recorded in {path}; run bridle again to recover it"
+    )]
+    Pending { path: PathBuf, started: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Create,
+    Overwrite,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryKind {
+    File,
+    Dir,
+    Symlink,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Change {
+    pub path: PathBuf,
+    pub action: Action,
+    pub kind: EntryKind,
+    #[serde(skip)]
+    content: Option<Content>,
+    #[serde(skip)]
+    target: Option<PathBuf>,
+}
+
+/// MCP servers a harness config file would gain, lose or have rewritten.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct McpChange {
+    pub config: PathBuf,
+    pub harness: String,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub changed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Plan {
+    pub changes: Vec<Change>,
+    pub mcp: Vec<McpChange>,
+    pub backups: Vec<PathBuf>,
+}
+
+/// Run `f` with file changes captured instead of performed.
+///
+/// Reads inside `f` see the planned changes. A nested call joins the outer
+/// recording and returns an empty plan.
+pub fn record<R>(f: impl FnOnce() -> R) -> (R, Plan) {
+    if fs::is_recording() {
+        return (f(), Plan::default());
+    }
+
+    struct Guard;
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            fs::finish();
+        }
+    }
+
+    fs::start();
+    let guard = Guard;
+    let result = f();
+    let overlay = fs::finish();
+    drop(guard);
+    (result, Plan::from_overlay(overlay))
+}
+
+/// Report `path` as a backup in the plan being recorded, if any.
+pub fn note_backup(path: &Path) {
+    fs::note_backup(path);
+}
+
+/// Report MCP server changes to `path` in the plan being recorded, if any.
+pub fn note_mcp_config(path: &Path, kind: HarnessKind) {
+    fs::note_mcp_config(path, kind);
+}
+
+impl Plan {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn count(&self, action: Action) -> usize {
+        self.changes.iter().filter(|c| c.action == action).count()
+    }
+
+    fn from_overlay(overlay: Overlay) -> Self {
+        let mut changes = Vec::new();
+        let mut deleted_dirs: Vec<PathBuf> = Vec::new();
+
+        for (path, node) in &overlay.nodes {
+            if deleted_dirs.iter().any(|dir| path.starts_with(dir)) {
+                continue;
+            }
+            let before = disk_state(path);
+            match node {
+                Node::Absent => {
+                    if let Some(kind) = kind_of(&before) {
+                        if kind == EntryKind::Dir {
+                            deleted_dirs.push(path.clone());
+                        }
+                        changes.push(Change::delete(path.clone(), kind));
+                    }
+                }
+                Node::Dir { fresh } => {
+                    match before {
+                        State::Dir => {}
+                        State::Missing => {
+                            changes.push(Change::new(path, Action::Create, EntryKind::Dir))
+                        }
+                        ref other => {
+                            let kind = kind_of(other).unwrap_or(EntryKind::File);
+                            changes.push(Change::delete(path.clone(), kind));
+                            changes.push(Change::new(path, Action::Create, EntryKind::Dir));
+                        }
+                    }
+                    if *fresh {
+                        changes.extend(stale_children(&overlay, path));
+                    }
+                }
+                Node::File(content) => {
+                    let action = match &before {
+                        State::Missing => Action::Create,
+                        State::File(_) if same_content(path, content) => continue,
+                        _ => Action::Overwrite,
+                    };
+                    if matches!(before, State::Dir) {
+                        deleted_dirs.push(path.clone());
+                    }
+                    let mut change = Change::new(path, action, EntryKind::File);
+                    change.content = Some(content.clone());
+                    changes.push(change);
+                }
+                Node::Symlink(target) => {
+                    let action = match &before {
+                        State::Missing => Action::Create,
+                        State::Symlink(current) if current == target => continue,
+                        _ => Action::Overwrite,
+                    };
+                    let mut change = Change::new(path, action, EntryKind::Symlink);
+                    change.target = Some(target.clone());
+                    changes.push(change);
+                }
+            }
+        }
+
+        let mcp = overlay
+            .mcp_configs
+            .iter()
+            .filter_map(|(config, kind)| mcp_change(&overlay, config, *kind))
+            .collect();
+
+        Plan {
+            changes,
+            mcp,
+            backups: overlay.backups,
+        }
+    }
+
+    /// Perform the planned changes, journaled in the bridle config dir.
+    ///
+    /// Entries being replaced or deleted are moved aside first, so a failure
+    /// part way through puts them back and removes what was created. If the
+    /// process dies instead, [`recover`] does the same on the next run.
+    pub fn apply(&self) -> Result<(), PlanError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let journal = journal_path().map_err(|e| PlanError::Journal(e.to_string()))?;
+        self.apply_with_journal(&journal)
+    }
+
+    pub fn apply_with_journal(&self, journal_path: &Path) -> Result<(), PlanError> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        if let Some(existing) = Journal::load(journal_path).map_err(journal_error)? {
+            return Err(PlanError::Pending {
+                path: journal_path.to_path_buf(),
+                started: existing.started.format("%Y-%m-%d %H:%M:%S").to_string(),
+            });
+        }
+
+        let mut journal = self.journal()?;
+        journal.save(journal_path).map_err(journal_error)?;
+
+        let mut result = self.apply_steps(&journal);
+        #[cfg(test)]
+        if failpoint::crashed() {
+            return result;
+        }
+        if result.is_ok() {
+            journal.phase = Phase::Committed;
+            result = journal.save(journal_path).map_err(journal_error);
+        }
+        if let Err(error) = result {
+            return match journal.rollback() {
+                Ok(()) => {
+                    let _ = std::fs::remove_file(journal_path);
+                    Err(error)
+                }
+                // The journal stays so the next run retries the rollback
+                Err(rollback) => Err(PlanError::Rollback {
+                    error: Box::new(error),
+                    rollback,
+                }),
+            };
+        }
+
+        // Every change is in place; leftovers are deleted now or by the next run
+        if checkpoint().and_then(|()| journal.discard_asides()).is_ok() {
+            let _ = std::fs::remove_file(journal_path);
+        }
+        Ok(())
+    }
+
+    /// Journal for applying this plan: which entries move aside and which
+    /// get created. Fails if an entry to create has appeared since planning.
+    fn journal(&self) -> Result<Journal, PlanError> {
+        let mut asides: Vec<(PathBuf, PathBuf)> = Vec::new();
+        let mut created = Vec::new();
+        for change in &self.changes {
+            let under_aside = asides.iter().any(|(p, _)| change.path.starts_with(p));
+            let on_disk = journal::exists(&change.path);
+            if change.action == Action::Create {
+                if on_disk && !under_aside {
+                    return Err(apply_error(
+                        &change.path,
+                        io::Error::new(io::ErrorKind::AlreadyExists, "created since planning"),
+                    ));
+                }
+            } else if on_disk && !under_aside {
+                asides.push((change.path.clone(), aside_path(&change.path)));
+            }
+            if change.action != Action::Delete {
+                created.push(change.path.clone());
+            }
+        }
+        Ok(Journal::new(asides, created))
+    }
+
+    fn apply_steps(&self, journal: &Journal) -> Result<(), PlanError> {
+        // Sources on disk may sit under a path that is about to be moved aside
+        let mut sources: HashMap<PathBuf, Vec<u8>> = HashMap::new();
+        for change in &self.changes {
+            if let Some(Content::Disk(source)) = &change.content
+                && !sources.contains_key(source)
+                && self.replaces(source)
+            {
+                let bytes = std::fs::read(source).map_err(|e| apply_error(source, e))?;
+                sources.insert(source.clone(), bytes);
+            }
+        }
+
+        for (original, aside) in &journal.asides {
+            checkpoint()
+                .and_then(|()| std::fs::rename(original, aside))
+                .map_err(|e| apply_error(original, e))?;
+        }
+
+        for change in &self.changes {
+            if change.action == Action::Delete {
+                continue;
+            }
+            let path = &change.path;
+            checkpoint().map_err(|e| apply_error(path, e))?;
+            match change.kind {
+                EntryKind::Dir => {
+                    std::fs::create_dir(path).map_err(|e| apply_error(path, e))?;
+                }
+                EntryKind::File => {
+                    let bytes = match &change.content {
+                        Some(Content::Bytes(bytes)) => bytes.clone(),
+                        Some(Content::Disk(source)) => match sources.get(source) {
+                            Some(bytes) => bytes.clone(),
+                            None => std::fs::read(source).map_err(|e| apply_error(source, e))?,
+                        },
+                        None => Vec::new(),
+                    };
+                    fs::write_atomic(path, &bytes).map_err(|e| apply_error(path, e))?;
+                }
+                EntryKind::Symlink => {
+                    let target = change.target.clone().unwrap_or_default();
+                    create_symlink(&target, path).map_err(|e| apply_error(path, e))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `path` is deleted or overwritten by this plan.
+    fn replaces(&self, path: &Path) -> bool {
+        self.changes
+            .iter()
+            .any(|c| c.action != Action::Create && path.starts_with(&c.path))
+    }
+}
+
+impl Change {
+    fn new(path: &Path, action: Action, kind: EntryKind) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            action,
+            kind,
+            content: None,
+            target: None,
+        }
+    }
+
+    fn delete(path: PathBuf, kind: EntryKind) -> Self {
+        Self::new(&path, Action::Delete, kind)
+    }
+}
+
+fn kind_of(state: &State) -> Option<EntryKind> {
+    match state {
+        State::Missing => None,
+        State::File(_) => Some(EntryKind::File),
+        State::Dir => Some(EntryKind::Dir),
+        State::Symlink(_) => Some(EntryKind::Symlink),
+    }
+}
+
+fn same_content(path: &Path, content: &Content) -> bool {
+    match content {
+        Content::Disk(source) if source == path => true,
+        Content::Disk(source) => match (std::fs::read(source), std::fs::read(path)) {
+            (Ok(a), Ok(b)) => a == b,
+            _ => false,
+        },
+        Content::Bytes(bytes) => std::fs::read(path).is_ok_and(|current| &current == bytes),
+    }
+}
+
+/// Disk entries directly below a recreated directory that the plan does not
+/// bring back.
+fn stale_children(overlay: &Overlay, dir: &Path) -> Vec<Change> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut stale: Vec<Change> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| !overlay.nodes.contains_key(path))
+        .filter_map(|path| {
+            let kind = kind_of(&disk_state(&path))?;
+            Some(Change::delete(path, kind))
+        })
+        .collect();
+    stale.sort_by(|a, b| a.path.cmp(&b.path));
+    stale
+}
+
+fn mcp_change(overlay: &Overlay, config: &Path, kind: HarnessKind) -> Option<McpChange> {
+    let read = |content: Option<Content>| -> BTreeMap<String, serde_json::Value> {
+        let text = match content {
+            Some(Content::Bytes(bytes)) => String::from_utf8(bytes).unwrap_or_default(),
+            Some(Content::Disk(path)) => std::fs::read_to_string(path).unwrap_or_default(),
+            None => String::new(),
+        };
+        parse_mcp_servers(kind, &text)
+            .map(|servers| servers.into_iter().collect())
+            .unwrap_or_default()
+    };
+    let file = |state: State| match state {
+        State::File(content) => Some(content),
+        _ => None,
+    };
+
+    let before = read(file(disk_state(config)));
+    let after = read(file(overlay.lookup(config)));
+    let names: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+
+    let mut change = McpChange {
+        config: config.to_path_buf(),
+        harness: Harness::new(kind).id().to_string(),
+        ..Default::default()
+    };
+    for name in names {
+        match (before.get(name), after.get(name)) {
+            (None, Some(_)) => change.added.push(name.clone()),
+            (Some(_), None) => change.removed.push(name.clone()),
+            (Some(a), Some(b)) if a != b => change.changed.push(name.clone()),
+            _ => {}
+        }
+    }
+
+    let unchanged =
+        change.added.is_empty() && change.removed.is_empty() && change.changed.is_empty();
+    (!unchanged).then_some(change)
+}
+
+fn aside_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".bridle-undo-{}", std::process::id()));
+    path.with_file_name(name)
+}
+
+#[cfg(unix)]
+fn create_symlink(target: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(not(unix))]
+fn create_symlink(_target: &Path, link: &Path) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!("cannot create symlink {}", link.display()),
+    ))
+}
+
+fn journal_error(error: io::Error) -> PlanError {
+    PlanError::Journal(error.to_string())
+}
+
+/// A point where applying can be interrupted; see [`failpoint`].
+fn checkpoint() -> io::Result<()> {
+    #[cfg(test)]
+    failpoint::hit()?;
+    Ok(())
+}
+
+/// Makes the n-th checkpoint of the next apply on this thread fail, either
+/// as an I/O error or as if the process was killed there.
+#[cfg(test)]
+pub(crate) mod failpoint {
+    use std::cell::Cell;
+    use std::io;
+
+    thread_local! {
+        static ARMED: Cell<Option<(usize, bool)>> = const { Cell::new(None) };
+        static CRASHED: Cell<bool> = const { Cell::new(false) };
+        static HIT: Cell<bool> = const { Cell::new(false) };
+    }
+
+    pub(crate) fn fail_at(step: usize, crash: bool) {
+        ARMED.set(Some((step, crash)));
+        CRASHED.set(false);
+        HIT.set(false);
+    }
+
+    /// Whether the armed failure fired; disarms it.
+    pub(crate) fn fired() -> bool {
+        ARMED.set(None);
+        CRASHED.set(false);
+        HIT.replace(false)
+    }
+
+    pub(super) fn crashed() -> bool {
+        CRASHED.get()
+    }
+
+    pub(super) fn hit() -> io::Result<()> {
+        match ARMED.get() {
+            Some((0, crash)) => {
+                ARMED.set(None);
+                HIT.set(true);
+                CRASHED.set(crash);
+                Err(io::Error::other("injected failure"))
+            }
+            Some((n, crash)) => {
+                ARMED.set(Some((n - 1, crash)));
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+fn apply_error(path: &Path, source: io::Error) -> PlanError {
+    PlanError::Apply {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn actions(plan: &Plan, root: &Path) -> Vec<(String, Action)> {
+        plan.changes
+            .iter()
+            .map(|c| {
+                let relative = c.path.strip_prefix(root).unwrap();
+                (relative.to_string_lossy().replace('\\', "/"), c.action)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn record_reads_planned_changes_without_writing() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join("keep.txt"), "old").unwrap();
+        std::fs::write(root.join("gone.txt"), "bye").unwrap();
+
+        let ((), plan) = record(|| {
+            fs::write(root.join("keep.txt"), "new").unwrap();
+            fs::remove_file(root.join("gone.txt")).unwrap();
+            fs::create_dir_all(root.join("dir/nested")).unwrap();
+            fs::copy(root.join("keep.txt"), root.join("dir/nested/copy.txt")).unwrap();
+
+            assert_eq!(fs::read_to_string(root.join("keep.txt")).unwrap(), "new");
+            assert!(!fs::exists(root.join("gone.txt")));
+            assert_eq!(
+                fs::read_to_string(root.join("dir/nested/copy.txt")).unwrap(),
+                "new"
+            );
+            assert!(fs::write(root.join("missing/file.txt"), "x").is_err());
+        });
+
+        assert_eq!(
+            std::fs::read_to_string(root.join("keep.txt")).unwrap(),
+            "old"
+        );
+        assert!(root.join("gone.txt").exists());
+        assert!(!root.join("dir").exists());
+        assert_eq!(
+            actions(&plan, root),
+            vec![
+                ("dir".to_string(), Action::Create),
+                ("dir/nested".to_string(), Action::Create),
+                ("dir/nested/copy.txt".to_string(), Action::Create),
+                ("gone.txt".to_string(), Action::Delete),
+                ("keep.txt".to_string(), Action::Overwrite),
+            ]
+        );
+    }
+
+    #[test]
+    fn recreated_directory_reports_net_changes() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let dir = root.join("live");
+        std::fs::create_dir_all(dir.join("agents")).unwrap();
+        std::fs::write(dir.join("same.txt"), "same").unwrap();
+        std::fs::write(dir.join("stale.txt"), "stale").unwrap();
+        std::fs::write(dir.join("agents/old.md"), "old").unwrap();
+
+        let ((), plan) = record(|| {
+            fs::remove_dir_all(&dir).unwrap();
+            fs::create_dir_all(&dir).unwrap();
+            assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+            fs::write(dir.join("same.txt"), "same").unwrap();
+            fs::write(dir.join("added.txt"), "added").unwrap();
+        });
+
+        assert_eq!(
+            actions(&plan, root),
+            vec![
+                ("live/agents".to_string(), Action::Delete),
+                ("live/stale.txt".to_string(), Action::Delete),
+                ("live/added.txt".to_string(), Action::Create),
+            ]
+        );
+
+        plan.apply_with_journal(&root.join("journal.json")).unwrap();
+        let mut names: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["added.txt", "same.txt"]);
+    }
+
+    #[test]
+    fn nested_record_joins_outer_plan() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("file.txt");
+
+        let (inner, outer) = record(|| record(|| fs::write(&path, "x").unwrap()).1);
+        assert!(inner.is_empty());
+        assert_eq!(outer.count(Action::Create), 1);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn apply_rolls_back_on_failure() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join("replaced.txt"), "original").unwrap();
+        std::fs::write(root.join("deleted.txt"), "keep me").unwrap();
+
+        let ((), plan) = record(|| {
+            fs::write(root.join("replaced.txt"), "changed").unwrap();
+            fs::remove_file(root.join("deleted.txt")).unwrap();
+            fs::create_dir_all(root.join("new")).unwrap();
+            fs::write(root.join("new/file.txt"), "new").unwrap();
+        });
+
+        // Something else takes the path the plan wants for a directory
+        std::fs::write(root.join("new"), "in the way").unwrap();
+        assert!(matches!(
+            plan.apply_with_journal(&root.join("journal.json")),
+            Err(PlanError::Apply { .. })
+        ));
+
+        assert_eq!(
+            std::fs::read_to_string(root.join("replaced.txt")).unwrap(),
+            "original"
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("deleted.txt")).unwrap(),
+            "keep me"
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("new")).unwrap(),
+            "in the way"
+        );
+        assert_eq!(std::fs::read_dir(root).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn plan_lists_mcp_servers_per_config() {
+        use crate::install::mcp_config::{remove_mcp_config, write_mcp_config};
+
+        let temp = TempDir::new().unwrap();
+        let config = temp.path().join(".claude.json");
+        std::fs::write(
+            &config,
+            r#"{"mcpServers": {"old": {"command": "old"}, "kept": {"command": "kept"}}}"#,
+        )
+        .unwrap();
+
+        let ((), plan) = record(|| {
+            let servers =
+                HashMap::from([("new".to_string(), serde_json::json!({"command": "new"}))]);
+            write_mcp_config(HarnessKind::ClaudeCode, &config, &servers).unwrap();
+            remove_mcp_config(HarnessKind::ClaudeCode, &config, "old").unwrap();
+        });
+
+        assert_eq!(plan.mcp.len(), 1);
+        assert_eq!(plan.mcp[0].config, config);
+        assert_eq!(plan.mcp[0].added, vec!["new"]);
+        assert_eq!(plan.mcp[0].removed, vec!["old"]);
+        assert!(plan.mcp[0].changed.is_empty());
+        assert_eq!(plan.count(Action::Overwrite), 1);
+    }
+}
@@ -30,10 +30,14 @@ use ratatui::{
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, TableState},
 };
 
-use crate::config::{BridleConfig, ProfileInfo, ProfileManager, ProfileName};
+use crate::config::{
+    BridleConfig, Drift, DriftAction, ProfileDiff, ProfileInfo, ProfileManager, ProfileName,
+};
 use crate::error::Error;
+use crate::install::project::{ProjectResources, find_project_root, scan_project};
+use crate::lock::{LockMode, StateLock};
 use views::ViewMode;
-use widgets::{DetailPane, HarnessTabs, ProfileTable, StatusBar};
+use widgets::{DetailPane, HarnessTabs, ProfileTable, ProjectPane, StatusBar};
 
 type Tui = Terminal<CrosstermBackend<Stdout>>;
 
@@ -85,6 +89,8 @@ enum InputMode {
     Normal,
     CreatingProfile,
     ConfirmingDelete,
+    ResolvingDrift,
+    NamingFork,
 }
 
 #[derive(Debug)]
@@ -113,6 +119,14 @@ struct App {
     harness_area: Option<Rect>,
     profile_area: Option<Rect>,
     detail_area: Option<Rect>,
+    /// Git repository the TUI was launched from, if any
+    project_root: Option<std::path::PathBuf>,
+    /// Project-scope resources of the selected harness
+    project: Option<ProjectResources>,
+    /// Diff of the selected profile against the live config, when toggled on
+    diff: Option<ProfileDiff>,
+    /// Live edits to the selected harness's active profile since activation
+    drift: Option<Drift>,
 }
 
 impl App {
@@ -136,9 +150,12 @@ impl App {
             harnesses.insert(0, kind);
         }
 
-        for kind in &harnesses {
-            let harness = Harness::new(*kind);
-            let _ = manager.create_from_current_if_missing(&harness);
+        // Skipped while another bridle process is making changes
+        if let Ok(_lock) = StateLock::acquire(LockMode::Exclusive, false) {
+            for kind in &harnesses {
+                let harness = Harness::new(*kind);
+                let _ = manager.create_from_current_if_missing(&harness);
+            }
         }
         let mut harness_state = ListState::default();
         // Always select the first harness (which is now the default or first installed)
@@ -169,6 +186,12 @@ impl App {
             harness_area: None,
             profile_area: None,
             detail_area: None,
+            project_root: std::env::current_dir()
+                .ok()
+                .and_then(|cwd| find_project_root(&cwd)),
+            project: None,
+            diff: None,
+            drift: None,
         };
 
         app.refresh_profiles();
@@ -195,7 +218,22 @@ impl App {
         }
     }
 
-    fn sync_active_profiles(&mut self) {
+    /// Take the state lock for a change, or show why it can't be taken.
+    /// The TUI never waits for the lock, since that would freeze the screen.
+    fn lock_for_change(&mut self) -> Option<StateLock> {
+        match StateLock::acquire(LockMode::Exclusive, false) {
+            Ok(lock) => Some(lock),
+            Err(e) => {
+                self.status_message = Some(e.to_string());
+                None
+            }
+        }
+    }
+
+    fn sync_active_profiles(&mut self) -> bool {
+        let Some(_lock) = self.lock_for_change() else {
+            return false;
+        };
         for &kind in &self.harnesses {
             let harness = Harness::new(kind);
             let harness_id = harness.id();
@@ -207,6 +245,7 @@ impl App {
                     .save_to_profile(&harness, Some(&harness), &profile_name);
             }
         }
+        true
     }
 
     fn refresh_profiles(&mut self) {
@@ -215,9 +254,24 @@ impl App {
         self.profile_table_state.select(None);
         self.expanded_profile = None;
         self.detail_scroll = 0;
+        self.diff = None;
+        self.project = self
+            .project_root
+            .as_deref()
+            .zip(self.selected_harness())
+            .map(|(root, kind)| scan_project(kind, root))
+            .filter(|r| !r.is_empty() || r.profile.is_some());
+
+        self.drift = None;
 
         if let Some(kind) = self.selected_harness() {
             let harness = Harness::new(kind);
+            self.drift = self
+                .manager
+                .detect_drift(&harness)
+                .ok()
+                .flatten()
+                .filter(|d| !d.is_empty());
 
             if let Ok(names) = self.manager.list_profiles(&harness) {
                 for name in names {
@@ -270,6 +324,7 @@ impl App {
         self.profile_state.select(Some(i));
         self.profile_table_state.select(Some(i));
         self.detail_scroll = 0;
+        self.diff = None;
         self.update_detail_content_height();
     }
 
@@ -290,11 +345,14 @@ impl App {
         self.profile_state.select(Some(i));
         self.profile_table_state.select(Some(i));
         self.detail_scroll = 0;
+        self.diff = None;
         self.update_detail_content_height();
     }
 
     fn update_detail_content_height(&mut self) {
-        self.detail_content_height = if let Some(idx) = self.profile_state.selected() {
+        self.detail_content_height = if let Some(diff) = &self.diff {
+            crate::display::diff_to_lines(diff).len() as u16
+        } else if let Some(idx) = self.profile_state.selected() {
             let profile = &self.profiles[idx];
             let lines = widgets::render_profile_details(profile);
             lines.len() as u16
@@ -386,6 +444,9 @@ impl App {
             self.status_message = Some("No profile selected".to_string());
             return;
         };
+        let Some(_lock) = self.lock_for_change() else {
+            return;
+        };
         let profile = &self.profiles[idx];
         let harness = Harness::new(kind);
         let Ok(profile_name) = ProfileName::new(&profile.name) else {
@@ -490,6 +551,31 @@ impl App {
             .is_some_and(|idx| self.expanded_profile == Some(idx))
     }
 
+    /// Toggle a diff of the selected profile against the live harness config.
+    fn toggle_diff(&mut self) {
+        if self.diff.take().is_none() {
+            let Some(kind) = self.selected_harness() else {
+                return;
+            };
+            let Some(profile) = self
+                .profile_state
+                .selected()
+                .and_then(|i| self.profiles.get(i))
+            else {
+                return;
+            };
+            let Ok(name) = ProfileName::new(&profile.name) else {
+                return;
+            };
+            match self.manager.diff_profiles(&Harness::new(kind), &name, None) {
+                Ok(diff) => self.diff = Some(diff),
+                Err(e) => self.status_message = Some(format!("Diff failed: {}", e)),
+            }
+        }
+        self.detail_scroll = 0;
+        self.update_detail_content_height();
+    }
+
     fn switch_to_selected(&mut self) {
         let Some(kind) = self.selected_harness() else {
             return;
@@ -504,6 +590,19 @@ impl App {
             return;
         }
 
+        if self.drift.is_some() {
+            self.input_mode = InputMode::ResolvingDrift;
+            return;
+        }
+
+        self.perform_switch(kind, idx, &DriftAction::Keep);
+    }
+
+    fn perform_switch(&mut self, kind: HarnessKind, idx: usize, drift: &DriftAction) {
+        let Some(_lock) = self.lock_for_change() else {
+            return;
+        };
+        let profile = &self.profiles[idx];
         let harness = Harness::new(kind);
         let Ok(profile_name) = ProfileName::new(&profile.name) else {
             self.status_message = Some("Invalid profile name".to_string());
@@ -512,11 +611,21 @@ impl App {
 
         match self
             .manager
-            .switch_profile_with_resources(&harness, Some(&harness), &profile_name)
+            .switch_profile_with_drift(&harness, Some(&harness), &profile_name, drift)
         {
             Ok(_) => {
                 self.bridle_config = BridleConfig::load().unwrap_or_default();
-                self.status_message = Some(format!("Switched to '{}'", profile.name));
+                self.status_message = Some(match drift {
+                    DriftAction::Keep => format!("Switched to '{}'", profile.name),
+                    DriftAction::Discard => {
+                        format!("Switched to '{}', live changes discarded", profile.name)
+                    }
+                    DriftAction::Fork(fork) => format!(
+                        "Switched to '{}', live changes saved as '{}'",
+                        profile.name,
+                        fork.as_str()
+                    ),
+                });
                 let selected_idx = self.profile_state.selected();
                 self.refresh_profiles();
                 if let Some(idx) = selected_idx {
@@ -545,6 +654,8 @@ impl App {
             InputMode::Normal => self.handle_normal_key(key),
             InputMode::CreatingProfile => self.handle_input_key(key),
             InputMode::ConfirmingDelete => self.handle_confirm_delete_key(key),
+            InputMode::ResolvingDrift => self.handle_resolve_drift_key(key),
+            InputMode::NamingFork => self.handle_fork_name_key(key),
         }
     }
 
@@ -628,9 +739,11 @@ impl App {
                 }
             }
             KeyCode::Char('r') => {
-                self.sync_active_profiles();
+                let synced = self.sync_active_profiles();
                 self.refresh_profiles();
-                self.status_message = Some("Synced and refreshed".to_string());
+                if synced {
+                    self.status_message = Some("Synced and refreshed".to_string());
+                }
             }
             KeyCode::Char('n') => {
                 let Some(kind) = self.selected_harness() else {
@@ -666,8 +779,18 @@ impl App {
                     self.edit_selected();
                 }
             }
+            KeyCode::Char('c') if matches!(self.view_mode, ViewMode::Dashboard) => {
+                self.toggle_diff();
+            }
             KeyCode::Char('f') => {
                 if let Some(harness_kind) = self.selected_harness() {
+                    let Some(_lock) = self.lock_for_change() else {
+                        return;
+                    };
+                    // Another process may have changed config.toml since it was loaded
+                    if let Ok(config) = BridleConfig::load() {
+                        self.bridle_config = config;
+                    }
                     let id = harness_id(&harness_kind);
                     self.bridle_config.set_default_harness(Some(id));
                     if let Err(e) = self.bridle_config.save() {
@@ -722,6 +845,62 @@ impl App {
         }
     }
 
+    fn handle_resolve_drift_key(&mut self, key: KeyCode) {
+        let (Some(kind), Some(idx)) = (self.selected_harness(), self.profile_state.selected())
+        else {
+            self.input_mode = InputMode::Normal;
+            return;
+        };
+        match key {
+            KeyCode::Char('k') | KeyCode::Enter => {
+                self.input_mode = InputMode::Normal;
+                self.perform_switch(kind, idx, &DriftAction::Keep);
+            }
+            KeyCode::Char('d') => {
+                self.input_mode = InputMode::Normal;
+                self.perform_switch(kind, idx, &DriftAction::Discard);
+            }
+            KeyCode::Char('f') => {
+                let active = self.drift.as_ref().map(|d| d.profile.as_str());
+                self.input_buffer = format!("{}-local", active.unwrap_or("profile"));
+                self.input_mode = InputMode::NamingFork;
+            }
+            KeyCode::Esc | KeyCode::Char('q') => {
+                self.input_mode = InputMode::Normal;
+                self.status_message = Some("Switch cancelled".to_string());
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_fork_name_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Enter => {
+                let name = self.input_buffer.trim().to_string();
+                let Ok(fork) = ProfileName::new(&name) else {
+                    self.status_message = Some(format!("Invalid profile name: '{}'", name));
+                    return;
+                };
+                self.input_mode = InputMode::Normal;
+                self.input_buffer.clear();
+                if let (Some(kind), Some(idx)) =
+                    (self.selected_harness(), self.profile_state.selected())
+                {
+                    self.perform_switch(kind, idx, &DriftAction::Fork(fork));
+                }
+            }
+            KeyCode::Esc => {
+                self.input_mode = InputMode::ResolvingDrift;
+                self.input_buffer.clear();
+            }
+            KeyCode::Backspace => {
+                self.input_buffer.pop();
+            }
+            KeyCode::Char(c) => self.input_buffer.push(c),
+            _ => {}
+        }
+    }
+
     fn reset_create_profile_state(&mut self) {
         self.input_mode = InputMode::CreatingProfile;
         self.input_buffer.clear();
@@ -772,6 +951,10 @@ impl App {
             }
         };
 
+        let Some(_lock) = self.lock_for_change() else {
+            self.create_profile_error = self.status_message.take();
+            return;
+        };
         let result = if self.create_profile_copy_current {
             self.manager
                 .create_from_current_with_resources(&harness, Some(&harness), &profile_name)
@@ -891,6 +1074,65 @@ fn render_dashboard_view(frame: &mut Frame, app: &mut App) {
     if app.input_mode == InputMode::ConfirmingDelete {
         render_confirm_delete_popup(frame, app);
     }
+    if matches!(
+        app.input_mode,
+        InputMode::ResolvingDrift | InputMode::NamingFork
+    ) {
+        render_drift_popup(frame, app);
+    }
+}
+
+fn render_drift_popup(frame: &mut Frame, app: &App) {
+    let Some(drift) = &app.drift else {
+        return;
+    };
+
+    let mut lines = vec![Line::from(format!(
+        "'{}' changed since activation ({})",
+        drift.profile,
+        drift.summary()
+    ))];
+    let paths = drift
+        .modified
+        .iter()
+        .map(|p| format!("  ~ {}", p))
+        .chain(drift.added.iter().map(|p| format!("  + {}", p)))
+        .chain(drift.removed.iter().map(|p| format!("  - {}", p)));
+    lines.extend(
+        paths
+            .take(6)
+            .map(|p| Line::styled(p, Style::default().fg(Color::Gray))),
+    );
+    lines.push(Line::from(""));
+    if app.input_mode == InputMode::NamingFork {
+        lines.push(Line::from(format!("New profile: {}█", app.input_buffer)));
+        lines.push(Line::styled(
+            "Enter to fork, Esc to go back",
+            Style::default().fg(Color::DarkGray),
+        ));
+    } else {
+        lines.push(Line::from("[k]eep  [d]iscard  [f]ork  Esc cancel"));
+    }
+
+    let area = frame.area();
+    let popup_width = 60.min(area.width.saturating_sub(4));
+    let popup_height = (lines.len() as u16 + 2).min(area.height);
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup_area);
+
+    let popup = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Yellow))
+                .title(" Live Config Changed "),
+        )
+        .style(Style::default().fg(Color::White));
+
+    frame.render_widget(popup, popup_area);
 }
 
 fn render_confirm_delete_popup(frame: &mut Frame, app: &App) {
@@ -1066,7 +1308,9 @@ fn render_profile_table(frame: &mut Frame, app: &mut App, area: Rect) {
         return;
     }
 
-    let table = ProfileTable::new(&app.profiles).focused(app.active_pane == Pane::Profiles);
+    let table = ProfileTable::new(&app.profiles)
+        .focused(app.active_pane == Pane::Profiles)
+        .drifted(app.drift.is_some());
     frame.render_stateful_widget(table, area, &mut app.profile_table_state);
 }
 
@@ -1077,9 +1321,25 @@ fn render_detail_pane(frame: &mut Frame, app: &App, area: Rect) {
         .and_then(|i| app.profiles.get(i));
 
     let detail = DetailPane::new(selected_profile)
+        .diff(app.diff.as_ref())
         .focused(app.active_pane == Pane::Details)
         .scroll(app.detail_scroll);
-    frame.render_widget(detail, area);
+
+    let Some(project) = &app.project else {
+        frame.render_widget(detail, area);
+        return;
+    };
+
+    // Global profile and project-scope resources side by side
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    frame.render_widget(detail, chunks[0]);
+    frame.render_widget(
+        ProjectPane::new(project).scroll(app.detail_scroll),
+        chunks[1],
+    );
 }
 
 fn render_harness_tabs(frame: &mut Frame, app: &App, area: Rect) {
@@ -1308,6 +1568,7 @@ fn render_help_modal(frame: &mut Frame, area: Rect, view_mode: views::ViewMode)
         Line::from("  n         New profile"),
         Line::from("  d         Delete profile"),
         Line::from("  e         Edit profile"),
+        Line::from("  c         Compare with current config"),
         Line::from("  f         Set default harness"),
         Line::from("  r         Refresh"),
         Line::from(""),
@@ -1319,6 +1580,7 @@ fn render_help_modal(frame: &mut Frame, area: Rect, view_mode: views::ViewMode)
         Line::from("  +         Has config (not tracked)"),
         Line::from("  -         Binary only (no config)"),
         Line::from("  ○         Not installed"),
+        Line::from("  ● yellow  Edited since switch"),
         Line::from(""),
         Line::from(vec![Span::styled(
             "General",
@@ -1358,7 +1620,8 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
 
     let status_bar = StatusBar::new(app.view_mode)
         .message(app.status_message.as_deref())
-        .harness_status(harness_status);
+        .harness_status(harness_status)
+        .workspace(app.bridle_config.active_workspace());
     frame.render_widget(status_bar, area);
 }
 
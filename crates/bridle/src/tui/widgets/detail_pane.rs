@@ -7,7 +7,7 @@ use ratatui::{
 };
 
 use super::EmptyState;
-use crate::config::ProfileInfo;
+use crate::config::{ProfileDiff, ProfileInfo};
 
 pub fn render_profile_details(profile: &ProfileInfo) -> Vec<Line<'static>> {
     let nodes = crate::display::profile_to_nodes(profile);
@@ -16,6 +16,7 @@ pub fn render_profile_details(profile: &ProfileInfo) -> Vec<Line<'static>> {
 
 pub struct DetailPane<'a> {
     profile: Option<&'a ProfileInfo>,
+    diff: Option<&'a ProfileDiff>,
     is_focused: bool,
     scroll_offset: u16,
 }
@@ -24,6 +25,7 @@ impl<'a> DetailPane<'a> {
     pub fn new(profile: Option<&'a ProfileInfo>) -> Self {
         Self {
             profile,
+            diff: None,
             is_focused: false,
             scroll_offset: 0,
         }
@@ -38,6 +40,12 @@ impl<'a> DetailPane<'a> {
         self.scroll_offset = offset;
         self
     }
+
+    /// Show a profile diff instead of the profile details.
+    pub fn diff(mut self, diff: Option<&'a ProfileDiff>) -> Self {
+        self.diff = diff;
+        self
+    }
 }
 
 impl Widget for DetailPane<'_> {
@@ -59,13 +67,16 @@ impl Widget for DetailPane<'_> {
                     Style::default().fg(Color::DarkGray)
                 };
 
+                let (title, content) = match self.diff {
+                    Some(diff) => (" Diff ", crate::display::diff_to_lines(diff)),
+                    None => (" Details ", render_profile_details(profile)),
+                };
+
                 let block = Block::default()
-                    .title(" Details ")
+                    .title(title)
                     .borders(Borders::ALL)
                     .border_style(border_style);
 
-                let content = render_profile_details(profile);
-
                 Paragraph::new(content)
                     .block(block)
                     .scroll((self.scroll_offset, 0))
@@ -2,6 +2,7 @@ mod detail_pane;
 mod empty_state;
 mod harness_tabs;
 mod profile_table;
+mod project_pane;
 mod status_bar;
 
 #[cfg(feature = "tui-cards")]
@@ -13,6 +14,7 @@ pub use detail_pane::{DetailPane, render_profile_details};
 pub use empty_state::EmptyState;
 pub use harness_tabs::HarnessTabs;
 pub use profile_table::ProfileTable;
+pub use project_pane::ProjectPane;
 pub use status_bar::StatusBar;
 
 #[cfg(feature = "tui-cards")]
@@ -2,6 +2,7 @@ use ratatui::{
     buffer::Buffer,
     layout::{Constraint, Rect},
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Cell, Row, StatefulWidget, Table, TableState, Widget},
 };
 
@@ -12,6 +13,7 @@ pub struct ProfileTable<'a> {
     profiles: &'a [ProfileInfo],
     block: Option<Block<'a>>,
     focused: bool,
+    drifted: bool,
 }
 
 impl<'a> ProfileTable<'a> {
@@ -20,6 +22,7 @@ impl<'a> ProfileTable<'a> {
             profiles,
             block: None,
             focused: false,
+            drifted: false,
         }
     }
 
@@ -34,6 +37,12 @@ impl<'a> ProfileTable<'a> {
         self
     }
 
+    /// Mark the active profile as having live edits since activation.
+    pub fn drifted(mut self, drifted: bool) -> Self {
+        self.drifted = drifted;
+        self
+    }
+
     fn truncate_model(model: &str, max_len: usize) -> String {
         if model.len() <= max_len {
             model.to_string()
@@ -77,11 +86,22 @@ impl StatefulWidget for ProfileTable<'_> {
 
         let rows = self.profiles.iter().map(|profile| {
             let active = if profile.is_active { "●" } else { " " };
-            let active_style = if profile.is_active {
+            let drifted = profile.is_active && self.drifted;
+            let active_style = if drifted {
+                Style::default().fg(Color::Yellow)
+            } else if profile.is_active {
                 Style::default().fg(Color::Green)
             } else {
                 Style::default()
             };
+            let name = if drifted {
+                Line::from(vec![
+                    Span::raw(profile.name.clone()),
+                    Span::styled(" (drifted)", Style::default().fg(Color::Yellow)),
+                ])
+            } else {
+                Line::from(profile.name.as_str())
+            };
 
             let model = profile
                 .model
@@ -98,7 +118,7 @@ impl StatefulWidget for ProfileTable<'_> {
 
             Row::new(vec![
                 Cell::from(active).style(active_style),
-                Cell::from(profile.name.as_str()),
+                Cell::from(name),
                 Cell::from(model).style(Style::default().add_modifier(Modifier::DIM)),
                 Cell::from(mcp).style(Style::default().add_modifier(Modifier::DIM)),
             ])
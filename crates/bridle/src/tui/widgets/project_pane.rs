@@ -0,0 +1,94 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use crate::install::project::ProjectResources;
+
+pub fn render_project_details(resources: &ProjectResources) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            resources
+                .profile
+                .as_deref()
+                .unwrap_or("(no profile)")
+                .to_string(),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" {}", resources.root.display()),
+            Style::default().fg(Color::Gray),
+        ),
+    ])];
+
+    let groups = [
+        ("Skills", &resources.skills),
+        ("Agents", &resources.agents),
+        ("Commands", &resources.commands),
+        ("MCP Servers", &resources.mcp_servers),
+    ];
+    for (label, names) in groups {
+        if names.is_empty() {
+            continue;
+        }
+        lines.push(Line::styled(
+            format!("  {} ({})", label, names.len()),
+            Style::default().fg(Color::Cyan),
+        ));
+        for name in names {
+            lines.push(Line::styled(
+                format!("    {}", name),
+                Style::default().fg(Color::Gray),
+            ));
+        }
+    }
+
+    if resources.managed > 0 {
+        lines.push(Line::styled(
+            format!("  {} installed by bridle", resources.managed),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    lines
+}
+
+/// Project-scope resources of the selected harness, shown beside the global
+/// profile details.
+pub struct ProjectPane<'a> {
+    resources: &'a ProjectResources,
+    scroll_offset: u16,
+}
+
+impl<'a> ProjectPane<'a> {
+    pub fn new(resources: &'a ProjectResources) -> Self {
+        Self {
+            resources,
+            scroll_offset: 0,
+        }
+    }
+
+    pub fn scroll(mut self, offset: u16) -> Self {
+        self.scroll_offset = offset;
+        self
+    }
+}
+
+impl Widget for ProjectPane<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default()
+            .title(" Project ")
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::DarkGray));
+
+        Paragraph::new(render_project_details(self.resources))
+            .block(block)
+            .scroll((self.scroll_offset, 0))
+            .render(area, buf);
+    }
+}
@@ -12,6 +12,7 @@ pub struct StatusBar<'a> {
     view_mode: ViewMode,
     message: Option<&'a str>,
     harness_status: Option<&'a str>,
+    workspace: Option<&'a str>,
 }
 
 impl<'a> StatusBar<'a> {
@@ -20,6 +21,7 @@ impl<'a> StatusBar<'a> {
             view_mode,
             message: None,
             harness_status: None,
+            workspace: None,
         }
     }
 
@@ -33,6 +35,11 @@ impl<'a> StatusBar<'a> {
         self
     }
 
+    pub fn workspace(mut self, workspace: Option<&'a str>) -> Self {
+        self.workspace = workspace;
+        self
+    }
+
     pub fn installation_status_text(status: &InstallationStatus) -> &'static str {
         match status {
             InstallationStatus::FullyInstalled { .. } => "Installed",
@@ -77,6 +84,14 @@ impl Widget for StatusBar<'_> {
             Span::raw(" "),
         ];
 
+        if let Some(workspace) = self.workspace {
+            spans.push(Span::styled(
+                format!("[workspace: {}]", workspace),
+                Theme::tab_selected(),
+            ));
+            spans.push(Span::raw(" "));
+        }
+
         if let Some(status) = self.harness_status {
             spans.push(Span::styled(format!("[{}]", status), Theme::text_muted()));
             spans.push(Span::raw(" "));
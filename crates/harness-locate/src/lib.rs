@@ -37,6 +37,6 @@ pub use validation::{
     CODE_SKILL_NAME_DIRECTORY_MISMATCH, CODE_SKILL_NAME_FORMAT, CODE_SKILL_NAME_LENGTH,
     CODE_SKILL_PARSE_ERROR, CODE_SKILL_UNSUPPORTED, ColorFormat, NameFormat,
     SKILL_DESCRIPTION_MAX_LEN, SKILL_NAME_MAX_LEN, SKILL_NAME_REGEX, Severity, SkillCapabilities,
-    ToolsFormat, ValidationIssue, validate_agent_for_harness, validate_mcp_server,
-    validate_skill_for_harness,
+    ToolsFormat, ValidationIssue, validate_agent_for_harness, validate_environment,
+    validate_mcp_server, validate_skill_for_harness,
 };
@@ -67,6 +67,15 @@ pub const CODE_TOGGLE_UNSUPPORTED: &str = "harness.toggle.unsupported";
 /// SSE transport deprecated for this harness (prefer HTTP).
 pub const CODE_SSE_DEPRECATED: &str = "harness.transport.sse_deprecated";
 
+/// Referenced environment variable is not set.
+pub const CODE_ENV_MISSING: &str = "env.missing";
+
+/// Stdio command not found on PATH.
+pub const CODE_COMMAND_NOT_FOUND: &str = "stdio.command.not_found";
+
+/// MCP server entry could not be parsed.
+pub const CODE_MCP_PARSE_ERROR: &str = "mcp.parse_error";
+
 // Agent validation codes.
 
 /// Agent tools field has wrong type for harness.
@@ -426,6 +435,39 @@ pub fn validate_for_harness(server: &McpServer, kind: HarnessKind) -> Vec<Valida
     issues
 }
 
+/// Validates an MCP server against the current environment.
+///
+/// Unlike [`validate_mcp_server`], the result depends on the machine it runs
+/// on: it flags environment variables the server references that are unset,
+/// and stdio commands that cannot be found on `PATH`.
+#[must_use]
+pub fn validate_environment(server: &McpServer) -> Vec<ValidationIssue> {
+    let mut issues: Vec<ValidationIssue> = server
+        .missing_env_vars()
+        .into_iter()
+        .map(|name| {
+            ValidationIssue::warning(
+                format!("env.{name}"),
+                format!("Environment variable {name} is not set"),
+                Some(CODE_ENV_MISSING),
+            )
+        })
+        .collect();
+
+    if let McpServer::Stdio(s) = server
+        && !s.command.trim().is_empty()
+        && matches!(crate::detection::find_binary(&s.command), Ok(None))
+    {
+        issues.push(ValidationIssue::error(
+            "command",
+            format!("Command '{}' not found on PATH", s.command),
+            Some(CODE_COMMAND_NOT_FOUND),
+        ));
+    }
+
+    issues
+}
+
 /// Validates agent frontmatter content for a specific harness.
 ///
 /// Returns an empty vector if valid, or a list of issues found.
@@ -782,6 +824,31 @@ mod tests {
         })
     }
 
+    #[test]
+    fn validate_environment_flags_unset_env_and_missing_command() {
+        let mut env = HashMap::new();
+        env.insert(
+            "TOKEN".to_string(),
+            EnvValue::EnvRef {
+                env: "HARNESS_LOCATE_TEST_UNSET_VAR".to_string(),
+            },
+        );
+        let server = McpServer::Stdio(StdioMcpServer {
+            command: "nonexistent-binary-xyz-12345".to_string(),
+            args: vec![],
+            env,
+            cwd: None,
+            enabled: true,
+            timeout_ms: None,
+        });
+
+        let issues = validate_environment(&server);
+        let codes: Vec<_> = issues.iter().filter_map(|i| i.code).collect();
+        assert_eq!(codes, vec![CODE_ENV_MISSING, CODE_COMMAND_NOT_FOUND]);
+        assert_eq!(issues[0].field, "env.HARNESS_LOCATE_TEST_UNSET_VAR");
+        assert_eq!(issues[1].severity, Severity::Error);
+    }
+
     fn make_sse(url: &str) -> McpServer {
         McpServer::Sse(SseMcpServer {
             url: url.to_string(),
@@ -21,6 +21,14 @@ pub enum HookEvent {
     Stop,
     /// When a subagent stops.
     SubagentStop,
+    /// When the user submits a prompt.
+    UserPromptSubmit,
+    /// When a session starts or resumes.
+    SessionStart,
+    /// When a session ends.
+    SessionEnd,
+    /// Before the conversation is compacted.
+    PreCompact,
 }
 
 /// A hook action to execute.
@@ -34,7 +42,7 @@ pub enum HookAction {
     Extended {
         /// Command to execute.
         command: String,
-        /// Timeout in milliseconds.
+        /// Timeout in seconds.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         timeout: Option<u64>,
         /// Run in background.
@@ -57,8 +65,20 @@ pub struct HookGroup {
 /// Parsed hooks.json file structure.
 pub type HooksConfig = HashMap<HookEvent, Vec<HookGroup>>;
 
+/// Plugin hooks.json wrapper: `{"description": ..., "hooks": {...}}`.
+#[derive(Deserialize)]
+struct HooksWrapper {
+    hooks: HooksConfig,
+}
+
 /// Parse a hooks.json file content into a HooksConfig.
+///
+/// Accepts both the bare event map and the plugin wrapper format with a
+/// top-level `hooks` key.
 pub fn parse_hooks_json(content: &str) -> Result<HooksConfig> {
+    if let Ok(wrapper) = serde_json::from_str::<HooksWrapper>(content) {
+        return Ok(wrapper.hooks);
+    }
     serde_json::from_str(content).map_err(Error::JsonParse)
 }
 
@@ -130,6 +150,36 @@ mod tests {
         assert_eq!(config[&HookEvent::PreToolUse].len(), 1);
     }
 
+    #[test]
+    fn parse_plugin_wrapper_hooks_json() {
+        let content = r#"{
+            "description": "Format on save",
+            "hooks": {
+                "PostToolUse": [
+                    {
+                        "matcher": "Write|Edit",
+                        "hooks": [
+                            {"type": "command", "command": "fmt.sh", "timeout": 30}
+                        ]
+                    }
+                ],
+                "SessionStart": [{"hooks": ["echo ready"]}]
+            }
+        }"#;
+        let config = parse_hooks_json(content).unwrap();
+        let group = &config[&HookEvent::PostToolUse][0];
+        assert_eq!(group.matcher.as_deref(), Some("Write|Edit"));
+        assert_eq!(
+            group.hooks[0],
+            HookAction::Extended {
+                command: "fmt.sh".to_string(),
+                timeout: Some(30),
+                background: None,
+            }
+        );
+        assert!(config.contains_key(&HookEvent::SessionStart));
+    }
+
     #[test]
     fn parse_empty_hooks_json() {
         let content = "{}";
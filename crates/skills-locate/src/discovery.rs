@@ -138,7 +138,9 @@ fn discover_single_plugin(
     });
 
     let hooks_path = format!("{plugin_prefix}.claude-plugin/hooks.json");
+    let alt_hooks_path = format!("{plugin_prefix}hooks/hooks.json");
     let hooks = extract_file(archive, &hooks_path)
+        .or_else(|_| extract_file(archive, &alt_hooks_path))
         .ok()
         .and_then(|content| parse_hooks_json(&content).ok());
 
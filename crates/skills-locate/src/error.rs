@@ -38,6 +38,14 @@ pub enum Error {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    /// Git command failed.
+    #[error("git failed: {0}")]
+    Git(String),
+
+    /// Archive extraction (other than ZIP) failed.
+    #[error("archive extraction failed: {0}")]
+    Archive(String),
+
     /// Download size limit exceeded.
     #[error("size limit exceeded: {size} bytes > {limit} bytes")]
     SizeLimit {
@@ -88,6 +96,21 @@ mod tests {
         assert_eq!(err.to_string(), "file not found in archive: config.json");
     }
 
+    #[test]
+    fn error_display_git() {
+        let err = Error::Git("repository not found".to_string());
+        assert_eq!(err.to_string(), "git failed: repository not found");
+    }
+
+    #[test]
+    fn error_display_archive() {
+        let err = Error::Archive("unexpected end of file".to_string());
+        assert_eq!(
+            err.to_string(),
+            "archive extraction failed: unexpected end of file"
+        );
+    }
+
     #[test]
     fn error_display_size_limit() {
         let err = Error::SizeLimit {
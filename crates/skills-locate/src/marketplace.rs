@@ -5,12 +5,21 @@ use crate::PluginSource;
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct Marketplace {
+    /// Marketplace name, used to refer to it once subscribed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     pub plugins: Vec<MarketplaceEntry>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct MarketplaceEntry {
+    /// Plugin name as listed by the marketplace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Short description shown when browsing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
     pub source: PluginSource,
 }
 
@@ -50,6 +59,23 @@ mod tests {
         assert!(matches!(m.plugins[0].source, PluginSource::Url { .. }));
     }
 
+    #[test]
+    fn deserialize_marketplace_names_and_descriptions() {
+        let json = r#"{
+            "name": "team-tools",
+            "owner": {"name": "Team"},
+            "plugins": [
+                {"name": "review", "description": "Code review", "source": "./plugins/review"},
+                {"name": "remote", "source": {"source": "github", "repo": "owner/remote"}}
+            ]
+        }"#;
+        let m: Marketplace = serde_json::from_str(json).unwrap();
+        assert_eq!(m.name.as_deref(), Some("team-tools"));
+        assert_eq!(m.plugins[0].name.as_deref(), Some("review"));
+        assert_eq!(m.plugins[0].description.as_deref(), Some("Code review"));
+        assert!(matches!(m.plugins[1].source, PluginSource::GitHub { .. }));
+    }
+
     #[test]
     fn deserialize_marketplace_mixed_sources() {
         let json = r#"{
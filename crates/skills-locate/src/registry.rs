@@ -2,24 +2,26 @@
 
 use harness_locate::mcp::{HttpMcpServer, McpServer, SseMcpServer, StdioMcpServer};
 use harness_locate::types::EnvValue;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 use crate::error::Error;
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerEntry {
     pub id: String,
     pub name: String,
     #[serde(default)]
     pub description: Option<String>,
     #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
     pub packages: Vec<PackageEntry>,
     #[serde(default)]
     pub remotes: Vec<RemoteEntry>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PackageEntry {
     pub registry: String,
     pub name: String,
@@ -29,11 +31,12 @@ pub struct PackageEntry {
     pub runtime: Option<String>,
     #[serde(default)]
     pub arguments: Vec<String>,
+    /// Variables the package needs, mapped to a description of each.
     #[serde(default)]
     pub environment_variables: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RemoteEntry {
     pub transport_type: String,
     pub url: String,
@@ -107,10 +110,11 @@ impl PackageEntry {
         let mut args = base_args;
         args.extend(self.arguments.iter().cloned());
 
+        // Values are read from the user's environment, never stored.
         let env: HashMap<String, EnvValue> = self
             .environment_variables
-            .iter()
-            .map(|(k, v)| (k.clone(), EnvValue::plain(v)))
+            .keys()
+            .map(|k| (k.clone(), EnvValue::env(k)))
             .collect();
 
         Some(McpServer::Stdio(StdioMcpServer {
@@ -168,21 +172,61 @@ impl RegistryClient {
         }
     }
 
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     pub fn fetch_server(&self, id: &str) -> Result<ServerEntry, Error> {
-        let url = format!("{}/servers/{}", self.base_url, id);
-        let mut response = ureq::get(&url)
+        self.get_json(&format!("{}/servers/{}", self.base_url, id))
+    }
+
+    /// Fetches a specific published version of a server.
+    pub fn fetch_server_version(&self, id: &str, version: &str) -> Result<ServerEntry, Error> {
+        self.get_json(&format!(
+            "{}/servers/{}/versions/{}",
+            self.base_url, id, version
+        ))
+    }
+
+    /// Searches servers by name or description.
+    pub fn search(&self, query: &str) -> Result<Vec<ServerEntry>, Error> {
+        #[derive(Deserialize)]
+        struct SearchResponse {
+            #[serde(default)]
+            servers: Vec<ServerEntry>,
+        }
+
+        let url = format!("{}/servers?search={}", self.base_url, encode_query(query));
+        let response: SearchResponse = self.get_json(&url)?;
+        Ok(response.servers)
+    }
+
+    fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, Error> {
+        let mut response = ureq::get(url)
             .call()
             .map_err(|e| Error::Http(e.to_string()))?;
         let bytes = response
             .body_mut()
             .read_to_vec()
             .map_err(|e| Error::Http(format!("Failed to read registry response: {e}")))?;
-        let entry: ServerEntry = serde_json::from_slice(&bytes)
-            .map_err(|e| Error::Http(format!("Failed to parse registry response: {e}")))?;
-        Ok(entry)
+        serde_json::from_slice(&bytes)
+            .map_err(|e| Error::Http(format!("Failed to parse registry response: {e}")))
     }
 }
 
+/// Percent-encodes a query string value.
+fn encode_query(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,6 +319,7 @@ mod tests {
             id: "test-server".to_string(),
             name: "Test Server".to_string(),
             description: None,
+            version: None,
             packages: vec![PackageEntry {
                 registry: "npm".to_string(),
                 name: "test-pkg".to_string(),
@@ -294,4 +339,27 @@ mod tests {
         assert!(servers.contains_key("test-server"));
         assert!(servers.contains_key("test-server-remote-0"));
     }
+
+    #[test]
+    fn package_env_vars_become_env_refs() {
+        let pkg = PackageEntry {
+            registry: "npm".to_string(),
+            name: "github-mcp".to_string(),
+            version: None,
+            runtime: None,
+            arguments: vec![],
+            environment_variables: HashMap::from([(
+                "GITHUB_TOKEN".to_string(),
+                "Personal access token".to_string(),
+            )]),
+        };
+
+        match pkg.to_mcp_server().expect("should create server") {
+            McpServer::Stdio(s) => {
+                assert_eq!(s.env["GITHUB_TOKEN"], EnvValue::env("GITHUB_TOKEN"));
+            }
+            _ => panic!("expected Stdio"),
+        }
+        assert_eq!(encode_query("file system/ü"), "file%20system%2F%C3%BC");
+    }
 }
@@ -0,0 +1,1027 @@
+//! Source locations that components can be fetched from.
+//!
+//! A [`Source`] describes where a repository of skills, agents, commands and
+//! MCP servers lives. Fetching a source produces a [`SourceTree`], an
+//! in-memory snapshot of its files that discovery can scan regardless of
+//! whether the files came from a GitHub archive, a tarball, a git clone or a
+//! local checkout.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use zip::ZipArchive;
+
+use crate::fetch::fetch_bytes;
+use crate::github::GitHubRef;
+use crate::{Error, Result};
+
+/// Files larger than this are skipped when snapshotting a source.
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Directories that never contain installable components.
+const SKIPPED_DIRS: &[&str] = &[".git", "node_modules"];
+
+/// Archive formats supported for plain archive URLs and local archive files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// `.zip` archive.
+    Zip,
+    /// Gzip-compressed tarball (`.tar.gz` / `.tgz`).
+    TarGz,
+    /// Uncompressed tarball (`.tar`).
+    Tar,
+}
+
+impl ArchiveFormat {
+    /// Detects the archive format from a file name or URL path.
+    #[must_use]
+    pub fn from_path(path: &str) -> Option<Self> {
+        let path = path
+            .split(['?', '#'])
+            .next()
+            .unwrap_or(path)
+            .to_ascii_lowercase();
+        if path.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if path.ends_with(".tar.gz") || path.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if path.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// Location of a component repository.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Source {
+    /// GitHub repository, fetched as a zip archive.
+    GitHub(GitHubRef),
+    /// Local directory or archive file (plain path or `file://` URL).
+    Local(PathBuf),
+    /// Zip or tarball served over HTTP(S).
+    Archive {
+        /// Archive URL.
+        url: String,
+        /// Archive format, detected from the URL.
+        format: ArchiveFormat,
+    },
+    /// Generic git remote, cloned with the system `git`.
+    Git {
+        /// Remote URL as understood by `git clone`.
+        url: String,
+        /// Branch, tag or commit to check out (remote default when `None`).
+        git_ref: Option<String>,
+    },
+}
+
+impl Source {
+    /// Parses a source string.
+    ///
+    /// Accepted forms:
+    /// - `https://github.com/owner/repo[/tree/<ref>]`
+    /// - `git+https://host/group/repo.git[#<ref>]`, `git+ssh://...`, or
+    ///   scp-style `git@host:group/repo.git[#<ref>]`
+    /// - `https://host/path/archive.zip` (also `.tar.gz`, `.tgz`, `.tar`)
+    /// - `file:///path/to/dir`, `/abs/path`, `./rel/path`, `~/path`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidUrl`] if the string matches none of the forms,
+    /// or if it or its git ref starts with `-`.
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(Error::InvalidUrl("empty source".to_string()));
+        }
+        if input.starts_with('-') {
+            return Err(Error::InvalidUrl(format!(
+                "source starts with '-': {input}"
+            )));
+        }
+
+        if let Some(rest) = input.strip_prefix("git+") {
+            if !(rest.starts_with("https://")
+                || rest.starts_with("http://")
+                || rest.starts_with("ssh://")
+                || rest.starts_with("file://"))
+            {
+                return Err(Error::InvalidUrl(format!(
+                    "unsupported git transport: {input}"
+                )));
+            }
+            return git_remote(rest);
+        }
+
+        if is_scp_like(input) {
+            return git_remote(input);
+        }
+
+        if let Some(path) = input.strip_prefix("file://") {
+            if path.is_empty() {
+                return Err(Error::InvalidUrl(format!("missing path: {input}")));
+            }
+            return Ok(Self::Local(PathBuf::from(path)));
+        }
+
+        if input.starts_with("https://") || input.starts_with("http://") {
+            if let Some(format) = ArchiveFormat::from_path(input) {
+                return Ok(Self::Archive {
+                    url: input.to_string(),
+                    format,
+                });
+            }
+            if input.contains("://github.com/") {
+                return GitHubRef::parse(input).map(Self::GitHub);
+            }
+            if split_fragment(input).0.ends_with(".git") {
+                return git_remote(input);
+            }
+            return Err(Error::InvalidUrl(format!(
+                "not a GitHub repository, archive or git remote: {input} \
+                 (use git+https:// for other git hosts)"
+            )));
+        }
+
+        if input.contains("://") {
+            return Err(Error::InvalidUrl(format!("unsupported scheme: {input}")));
+        }
+
+        Ok(Self::Local(expand_home(input)))
+    }
+
+    /// Returns the repository or directory name of this source.
+    #[must_use]
+    pub fn name(&self) -> String {
+        match self {
+            Self::GitHub(gh) => gh.repo.clone(),
+            Self::Local(path) => path
+                .file_name()
+                .map(|n| archive_stem(&n.to_string_lossy()))
+                .unwrap_or_else(|| "local".to_string()),
+            Self::Archive { url, .. } => url
+                .split(['?', '#'])
+                .next()
+                .and_then(|u| u.rsplit('/').next())
+                .map(archive_stem)
+                .unwrap_or_else(|| "archive".to_string()),
+            Self::Git { url, .. } => last_segments(url)
+                .1
+                .unwrap_or_else(|| "repository".to_string()),
+        }
+    }
+
+    /// Returns the owning namespace of this source.
+    ///
+    /// This is the GitHub owner, the group of a git remote, the host of an
+    /// archive URL, or `"local"` for local paths.
+    #[must_use]
+    pub fn owner(&self) -> String {
+        match self {
+            Self::GitHub(gh) => gh.owner.clone(),
+            Self::Local(_) => "local".to_string(),
+            Self::Archive { url, .. } => url_host(url).unwrap_or_else(|| "archive".to_string()),
+            Self::Git { url, .. } => last_segments(url)
+                .0
+                .or_else(|| url_host(url))
+                .unwrap_or_else(|| "git".to_string()),
+        }
+    }
+
+    /// Returns the requested git ref, if the source has one.
+    #[must_use]
+    pub fn git_ref(&self) -> Option<&str> {
+        match self {
+            Self::GitHub(gh) => Some(&gh.git_ref),
+            Self::Git { git_ref, .. } => git_ref.as_deref(),
+            Self::Local(_) | Self::Archive { .. } => None,
+        }
+    }
+
+    /// Returns this source pinned to an exact commit.
+    ///
+    /// GitHub and git sources switch their ref to `commit`; local paths and
+    /// archives cannot be pinned and are returned unchanged.
+    #[must_use]
+    pub fn pinned(&self, commit: &str) -> Self {
+        match self {
+            Self::GitHub(gh) => Self::GitHub(GitHubRef {
+                git_ref: commit.to_string(),
+                ..gh.clone()
+            }),
+            Self::Git { url, .. } => Self::Git {
+                url: url.clone(),
+                git_ref: Some(commit.to_string()),
+            },
+            Self::Local(_) | Self::Archive { .. } => self.clone(),
+        }
+    }
+
+    /// Fetches the source and returns a snapshot of its files.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if downloading, cloning, extracting or reading fails.
+    pub fn fetch(&self) -> Result<SourceTree> {
+        match self {
+            Self::GitHub(gh) => {
+                let bytes = fetch_bytes(&gh.archive_url())?;
+                SourceTree::from_zip(&bytes, &gh.repo)
+            }
+            Self::Local(path) => {
+                if path.is_dir() {
+                    let mut tree = SourceTree::from_dir(path)?;
+                    if path.join(".git").exists() {
+                        tree.revision = git_head(path);
+                    }
+                    return Ok(tree);
+                }
+                let format =
+                    ArchiveFormat::from_path(&path.to_string_lossy()).ok_or_else(|| {
+                        Error::NotFound(format!("no such directory: {}", path.display()))
+                    })?;
+                let bytes = std::fs::read(path)?;
+                SourceTree::from_archive(&bytes, format, &self.name())
+            }
+            Self::Archive { url, format } => {
+                let bytes = fetch_bytes(url)?;
+                SourceTree::from_archive(&bytes, *format, &self.name())
+            }
+            Self::Git { url, git_ref } => {
+                let checkout = TempDir::new("git")?;
+                git_clone(url, git_ref.as_deref(), checkout.path())?;
+                let mut tree = SourceTree::from_dir_with_root(checkout.path(), &self.name())?;
+                tree.revision = git_head(checkout.path());
+                Ok(tree)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::GitHub(gh) => write!(
+                f,
+                "https://github.com/{}/{}/tree/{}",
+                gh.owner, gh.repo, gh.git_ref
+            ),
+            Self::Local(path) => write!(f, "{}", path.display()),
+            Self::Archive { url, .. } => write!(f, "{url}"),
+            Self::Git { url, git_ref } => {
+                if is_scp_like(url) {
+                    write!(f, "{url}")?;
+                } else {
+                    write!(f, "git+{url}")?;
+                }
+                if let Some(r) = git_ref {
+                    write!(f, "#{r}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// In-memory snapshot of the files in a source.
+///
+/// Paths are `/`-separated and rooted at a single top-level directory (for
+/// example `my-repo-main/skills/foo/SKILL.md`), mirroring the layout of a
+/// GitHub archive so that path-based heuristics behave the same for every
+/// kind of source.
+#[derive(Debug, Clone, Default)]
+pub struct SourceTree {
+    root: String,
+    files: BTreeMap<String, Vec<u8>>,
+    revision: Option<String>,
+}
+
+impl SourceTree {
+    /// Builds a tree from zip archive bytes.
+    ///
+    /// If every entry shares a single top-level directory it becomes the
+    /// root; otherwise `fallback_root` is used. Archives produced by
+    /// `git archive` (including GitHub downloads) carry the commit SHA in the
+    /// zip comment, which is exposed as the tree's [`revision`](Self::revision).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ZipExtract`] if the archive cannot be read.
+    pub fn from_zip(bytes: &[u8], fallback_root: &str) -> Result<Self> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes))
+            .map_err(|e| Error::ZipExtract(format!("invalid ZIP: {e}")))?;
+
+        let mut entries = Vec::new();
+        for i in 0..archive.len() {
+            let mut file = archive
+                .by_index(i)
+                .map_err(|e| Error::ZipExtract(format!("read entry: {e}")))?;
+            if file.is_dir() || file.size() > MAX_FILE_SIZE {
+                continue;
+            }
+            let name = file.name().trim_start_matches('/').to_string();
+            let mut content = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut content)
+                .map_err(|e| Error::ZipExtract(format!("read file: {e}")))?;
+            entries.push((name, content));
+        }
+
+        let mut tree = Self::from_entries(entries, fallback_root);
+        tree.revision = commit_from_comment(archive.comment());
+        Ok(tree)
+    }
+
+    /// Builds a tree from archive bytes of the given format.
+    ///
+    /// Tarballs are unpacked with the system `tar`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive cannot be unpacked.
+    pub fn from_archive(bytes: &[u8], format: ArchiveFormat, fallback_root: &str) -> Result<Self> {
+        if format == ArchiveFormat::Zip {
+            return Self::from_zip(bytes, fallback_root);
+        }
+
+        let work = TempDir::new("tar")?;
+        let archive_path = work.path().join("archive.tar");
+        let out_dir = work.path().join("out");
+        std::fs::write(&archive_path, bytes)?;
+        std::fs::create_dir_all(&out_dir)?;
+
+        let flags = if format == ArchiveFormat::TarGz {
+            "-xzf"
+        } else {
+            "-xf"
+        };
+        let output = Command::new("tar")
+            .arg(flags)
+            .arg(&archive_path)
+            .arg("-C")
+            .arg(&out_dir)
+            .output()
+            .map_err(|e| Error::Archive(format!("failed to run tar: {e}")))?;
+        if !output.status.success() {
+            return Err(Error::Archive(
+                String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ));
+        }
+
+        let mut entries = Vec::new();
+        collect_dir(&out_dir, "", &mut entries)?;
+        Ok(Self::from_entries(entries, fallback_root))
+    }
+
+    /// Builds a tree from a local directory, rooted at the directory's name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the directory cannot be read.
+    pub fn from_dir(dir: &Path) -> Result<Self> {
+        let root = dir
+            .canonicalize()
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "local".to_string());
+        Self::from_dir_with_root(dir, &root)
+    }
+
+    fn from_dir_with_root(dir: &Path, root: &str) -> Result<Self> {
+        if !dir.is_dir() {
+            return Err(Error::NotFound(format!(
+                "no such directory: {}",
+                dir.display()
+            )));
+        }
+        let mut entries = Vec::new();
+        collect_dir(dir, "", &mut entries)?;
+        Ok(Self {
+            root: root.to_string(),
+            files: entries.into_iter().collect(),
+            revision: None,
+        })
+    }
+
+    fn from_entries(entries: Vec<(String, Vec<u8>)>, fallback_root: &str) -> Self {
+        let common_root = entries
+            .first()
+            .and_then(|(name, _)| name.split_once('/').map(|(top, _)| top.to_string()))
+            .filter(|top| {
+                let prefix = format!("{top}/");
+                entries.iter().all(|(name, _)| name.starts_with(&prefix))
+            });
+
+        let (root, files) = match common_root {
+            Some(root) => {
+                let prefix = format!("{root}/");
+                let files = entries
+                    .into_iter()
+                    .filter_map(|(name, content)| {
+                        name.strip_prefix(&prefix).map(|n| (n.to_string(), content))
+                    })
+                    .collect();
+                (root, files)
+            }
+            None => (fallback_root.to_string(), entries.into_iter().collect()),
+        };
+
+        Self {
+            root,
+            files,
+            revision: None,
+        }
+    }
+
+    /// Returns the top-level directory name that all paths are rooted at.
+    #[must_use]
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    /// Returns the commit the tree was checked out at, when known.
+    #[must_use]
+    pub fn revision(&self) -> Option<&str> {
+        self.revision.as_deref()
+    }
+
+    /// Returns the number of files in the tree.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns `true` if the tree contains no files.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Lists rooted paths of all files whose name ends with `suffix`.
+    #[must_use]
+    pub fn list_files(&self, suffix: &str) -> Vec<String> {
+        self.files
+            .keys()
+            .filter(|name| name.ends_with(suffix))
+            .map(|name| format!("{}/{}", self.root, name))
+            .collect()
+    }
+
+    /// Reads a file as UTF-8 text. Accepts rooted or root-relative paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] if the file does not exist, or
+    /// [`Error::Io`] if it is not valid UTF-8.
+    pub fn read_file(&self, path: &str) -> Result<String> {
+        let bytes = self.read_bytes(path)?;
+        String::from_utf8(bytes.to_vec())
+            .map_err(|e| Error::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, e)))
+    }
+
+    /// Reads a file's raw bytes. Accepts rooted or root-relative paths.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::NotFound`] if the file does not exist.
+    pub fn read_bytes(&self, path: &str) -> Result<&[u8]> {
+        self.files
+            .get(self.relative_path(path))
+            .map(Vec::as_slice)
+            .ok_or_else(|| Error::NotFound(path.to_string()))
+    }
+
+    /// Strips the root directory from a rooted path.
+    #[must_use]
+    pub fn relative_path<'a>(&self, path: &'a str) -> &'a str {
+        let path = path.trim_start_matches('/');
+        path.strip_prefix(self.root.as_str())
+            .and_then(|p| p.strip_prefix('/'))
+            .unwrap_or(path)
+    }
+}
+
+fn commit_from_comment(comment: &[u8]) -> Option<String> {
+    let comment = std::str::from_utf8(comment).ok()?.trim();
+    (comment.len() == 40 && comment.bytes().all(|b| b.is_ascii_hexdigit()))
+        .then(|| comment.to_ascii_lowercase())
+}
+
+fn collect_dir(dir: &Path, prefix: &str, out: &mut Vec<(String, Vec<u8>)>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let rel = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{prefix}/{name}")
+        };
+
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if !SKIPPED_DIRS.contains(&name.as_str()) {
+                collect_dir(&entry.path(), &rel, out)?;
+            }
+        } else if file_type.is_file() && entry.metadata()?.len() <= MAX_FILE_SIZE {
+            out.push((rel, std::fs::read(entry.path())?));
+        }
+    }
+    Ok(())
+}
+
+fn git_clone(url: &str, git_ref: Option<&str>, dest: &Path) -> Result<()> {
+    // Both are passed to git as arguments; never let them read as options
+    if url.starts_with('-') || git_ref.is_some_and(|r| r.starts_with('-')) {
+        return Err(Error::InvalidUrl(format!(
+            "git remote or ref starts with '-': {url}"
+        )));
+    }
+
+    let mut shallow = Command::new("git");
+    shallow.args(["clone", "--quiet", "--depth", "1"]);
+    if let Some(r) = git_ref {
+        shallow.args(["--branch", r]);
+    }
+    shallow.arg("--").arg(url).arg(dest);
+    if run_git(shallow).is_ok() {
+        return Ok(());
+    }
+
+    // `--branch` only accepts branches and tags; fall back to a full clone
+    // so that commit SHAs can be checked out.
+    let _ = std::fs::remove_dir_all(dest);
+    let mut full = Command::new("git");
+    full.args(["clone", "--quiet", "--"]).arg(url).arg(dest);
+    run_git(full)?;
+
+    if let Some(r) = git_ref {
+        let mut checkout = Command::new("git");
+        checkout
+            .arg("-C")
+            .arg(dest)
+            .args(["checkout", "--quiet", r, "--"]);
+        run_git(checkout)?;
+    }
+    Ok(())
+}
+
+fn git_head(repo: &Path) -> Option<String> {
+    let mut cmd = Command::new("git");
+    cmd.arg("-C").arg(repo).args(["rev-parse", "HEAD"]);
+    run_git(cmd).ok().map(|out| out.trim().to_string())
+}
+
+fn run_git(mut cmd: Command) -> Result<String> {
+    let output = cmd
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|e| Error::Git(format!("failed to run git: {e}")))?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(Error::Git(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+}
+
+/// Scratch directory removed on drop.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(label: &str) -> Result<Self> {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        let path = std::env::temp_dir().join(format!(
+            "skills-locate-{label}-{}-{nanos}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(&path)?;
+        Ok(Self(path))
+    }
+
+    fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Parses a git remote with an optional `#<ref>` fragment. Neither part may
+/// start with `-`, which git would take for an option.
+fn git_remote(input: &str) -> Result<Source> {
+    let (url, git_ref) = split_fragment(input);
+    if url.starts_with('-') || git_ref.as_deref().is_some_and(|r| r.starts_with('-')) {
+        return Err(Error::InvalidUrl(format!(
+            "git remote or ref starts with '-': {input}"
+        )));
+    }
+    Ok(Source::Git { url, git_ref })
+}
+
+fn split_fragment(url: &str) -> (String, Option<String>) {
+    match url.split_once('#') {
+        Some((base, frag)) if !frag.is_empty() => (base.to_string(), Some(frag.to_string())),
+        Some((base, _)) => (base.to_string(), None),
+        None => (url.to_string(), None),
+    }
+}
+
+/// Matches scp-style git remotes such as `git@gitlab.com:group/repo.git`.
+fn is_scp_like(input: &str) -> bool {
+    if input.contains("://") {
+        return false;
+    }
+    let Some((user_host, path)) = input.split_once(':') else {
+        return false;
+    };
+    !user_host.starts_with('-')
+        && user_host.contains('@')
+        && !user_host.contains('/')
+        && !path.is_empty()
+}
+
+/// Returns the last two path segments of a remote URL (namespace, repo).
+fn last_segments(url: &str) -> (Option<String>, Option<String>) {
+    let path = url
+        .split_once("://")
+        .map(|(_, rest)| rest.split_once('/').map(|(_, p)| p).unwrap_or(""))
+        .or_else(|| url.split_once(':').map(|(_, p)| p))
+        .unwrap_or(url);
+    let mut segments = path
+        .trim_end_matches('/')
+        .rsplit('/')
+        .filter(|s| !s.is_empty());
+    let repo = segments
+        .next()
+        .map(|s| s.strip_suffix(".git").unwrap_or(s).to_string());
+    let owner = segments.next().map(String::from);
+    (owner, repo)
+}
+
+fn url_host(url: &str) -> Option<String> {
+    let rest = url.split_once("://")?.1;
+    let host = rest.split('/').next()?;
+    let host = host.rsplit('@').next().unwrap_or(host);
+    (!host.is_empty()).then(|| host.to_string())
+}
+
+fn archive_stem(name: &str) -> String {
+    [".tar.gz", ".tgz", ".tar", ".zip"]
+        .iter()
+        .find_map(|ext| name.strip_suffix(ext))
+        .unwrap_or(name)
+        .to_string()
+}
+
+fn expand_home(input: &str) -> PathBuf {
+    if let Some(rest) = input.strip_prefix("~/")
+        && let Ok(home) = harness_locate::platform::home_dir()
+    {
+        return home.join(rest);
+    }
+    PathBuf::from(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn create_test_zip(files: &[(&str, &str)]) -> Vec<u8> {
+        create_test_zip_with_comment(files, "")
+    }
+
+    fn create_test_zip_with_comment(files: &[(&str, &str)], comment: &str) -> Vec<u8> {
+        let mut buf = Cursor::new(Vec::new());
+        {
+            let mut zip = zip::ZipWriter::new(&mut buf);
+            zip.set_comment(comment);
+            let options = zip::write::SimpleFileOptions::default();
+            for (name, content) in files {
+                zip.start_file(*name, options).unwrap();
+                zip.write_all(content.as_bytes()).unwrap();
+            }
+            zip.finish().unwrap();
+        }
+        buf.into_inner()
+    }
+
+    #[test]
+    fn parse_github_url() {
+        let source = Source::parse("https://github.com/owner/repo/tree/dev").unwrap();
+        let Source::GitHub(gh) = &source else {
+            panic!("expected GitHub source, got {source:?}");
+        };
+        assert_eq!(gh.owner, "owner");
+        assert_eq!(gh.git_ref, "dev");
+        assert_eq!(source.owner(), "owner");
+        assert_eq!(source.name(), "repo");
+    }
+
+    #[test]
+    fn parse_git_https_with_ref() {
+        let source = Source::parse("git+https://gitlab.com/team/skills.git#v1.2").unwrap();
+        assert_eq!(
+            source,
+            Source::Git {
+                url: "https://gitlab.com/team/skills.git".into(),
+                git_ref: Some("v1.2".into()),
+            }
+        );
+        assert_eq!(source.owner(), "team");
+        assert_eq!(source.name(), "skills");
+        assert_eq!(
+            source.to_string(),
+            "git+https://gitlab.com/team/skills.git#v1.2"
+        );
+    }
+
+    #[test]
+    fn parse_git_ssh_and_scp_style() {
+        let ssh = Source::parse("git+ssh://git@gitlab.com/group/sub/repo.git").unwrap();
+        assert!(matches!(ssh, Source::Git { ref git_ref, .. } if git_ref.is_none()));
+        assert_eq!(ssh.owner(), "sub");
+
+        let scp = Source::parse("git@gitlab.com:group/repo.git#main").unwrap();
+        assert_eq!(
+            scp,
+            Source::Git {
+                url: "git@gitlab.com:group/repo.git".into(),
+                git_ref: Some("main".into()),
+            }
+        );
+        assert_eq!(scp.to_string(), "git@gitlab.com:group/repo.git#main");
+    }
+
+    #[test]
+    fn parse_https_dot_git_as_git_remote() {
+        let source = Source::parse("https://git.example.com/team/repo.git").unwrap();
+        assert!(matches!(source, Source::Git { .. }));
+    }
+
+    #[test]
+    fn pinned_replaces_ref_for_github_and_git() {
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+        let gh = Source::parse("https://github.com/owner/repo").unwrap();
+        assert_eq!(
+            gh.pinned(sha).to_string(),
+            format!("https://github.com/owner/repo/tree/{sha}")
+        );
+
+        let git = Source::parse("git+https://gitlab.com/team/skills.git#main").unwrap();
+        assert_eq!(git.pinned(sha).git_ref(), Some(sha));
+
+        let local = Source::parse("/srv/skills").unwrap();
+        assert_eq!(local.pinned(sha), local);
+    }
+
+    #[test]
+    fn parse_archive_urls() {
+        let zip = Source::parse("https://example.com/dl/skills-1.0.zip").unwrap();
+        assert!(matches!(
+            zip,
+            Source::Archive {
+                format: ArchiveFormat::Zip,
+                ..
+            }
+        ));
+        assert_eq!(zip.name(), "skills-1.0");
+        assert_eq!(zip.owner(), "example.com");
+
+        let tgz = Source::parse("https://example.com/skills.tgz?token=x").unwrap();
+        assert!(matches!(
+            tgz,
+            Source::Archive {
+                format: ArchiveFormat::TarGz,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn parse_local_paths() {
+        assert_eq!(
+            Source::parse("file:///srv/skills").unwrap(),
+            Source::Local(PathBuf::from("/srv/skills"))
+        );
+        assert_eq!(
+            Source::parse("./skills").unwrap(),
+            Source::Local(PathBuf::from("./skills"))
+        );
+        assert_eq!(Source::parse("/tmp/x").unwrap().owner(), "local");
+    }
+
+    #[test]
+    fn parse_rejects_unknown_hosts_and_schemes() {
+        assert!(matches!(
+            Source::parse("https://gitlab.com/owner/repo"),
+            Err(Error::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            Source::parse("ftp://example.com/x"),
+            Err(Error::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            Source::parse("git+ftp://example.com/x"),
+            Err(Error::InvalidUrl(_))
+        ));
+        assert!(Source::parse("  ").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_sources_and_refs_that_look_like_options() {
+        for input in [
+            "--upload-pack=touch /tmp/pwned",
+            "-oProxyCommand=x@host:repo.git",
+            "git+https://example.com/repo.git#--upload-pack=x",
+            "git@example.com:group/repo.git#-b",
+            "https://example.com/repo.git#--config=x",
+        ] {
+            assert!(
+                matches!(Source::parse(input), Err(Error::InvalidUrl(_))),
+                "{input}"
+            );
+        }
+        assert!(!is_scp_like("-oProxyCommand=x@host:repo.git"));
+    }
+
+    #[test]
+    fn git_clone_refuses_option_like_arguments() {
+        let dest = std::env::temp_dir().join("skills-locate-never-cloned");
+        assert!(matches!(
+            git_clone("--upload-pack=x", None, &dest),
+            Err(Error::InvalidUrl(_))
+        ));
+        assert!(matches!(
+            git_clone("https://example.com/repo.git", Some("--output=x"), &dest),
+            Err(Error::InvalidUrl(_))
+        ));
+        assert!(!dest.exists());
+    }
+
+    #[test]
+    fn tree_from_zip_detects_common_root() {
+        let zip = create_test_zip(&[
+            ("my-repo-main/skills/test/SKILL.md", "skill"),
+            ("my-repo-main/README.md", "readme"),
+        ]);
+        let tree = SourceTree::from_zip(&zip, "fallback").unwrap();
+        assert_eq!(tree.root(), "my-repo-main");
+        assert_eq!(
+            tree.list_files("SKILL.md"),
+            vec!["my-repo-main/skills/test/SKILL.md"]
+        );
+        assert_eq!(
+            tree.relative_path("my-repo-main/skills/test/SKILL.md"),
+            "skills/test/SKILL.md"
+        );
+        assert_eq!(
+            tree.read_file("my-repo-main/skills/test/SKILL.md").unwrap(),
+            "skill"
+        );
+        assert_eq!(tree.read_file("README.md").unwrap(), "readme");
+    }
+
+    #[test]
+    fn tree_from_zip_reads_commit_from_comment() {
+        let sha = "0123456789abcdef0123456789abcdef01234567";
+        let zip = create_test_zip_with_comment(&[("repo-main/README.md", "x")], sha);
+        let tree = SourceTree::from_zip(&zip, "repo").unwrap();
+        assert_eq!(tree.revision(), Some(sha));
+
+        let zip = create_test_zip_with_comment(&[("repo-main/README.md", "x")], "hello");
+        assert_eq!(SourceTree::from_zip(&zip, "repo").unwrap().revision(), None);
+    }
+
+    #[test]
+    fn tree_from_zip_without_common_root_uses_fallback() {
+        let zip = create_test_zip(&[("skills/a/SKILL.md", "a"), ("agents/b.md", "b")]);
+        let tree = SourceTree::from_zip(&zip, "bundle").unwrap();
+        assert_eq!(tree.root(), "bundle");
+        assert_eq!(tree.list_files(".md").len(), 2);
+        assert!(
+            tree.list_files(".md")
+                .contains(&"bundle/agents/b.md".to_string())
+        );
+    }
+
+    #[test]
+    fn relative_path_leaves_foreign_paths_alone() {
+        let tree = SourceTree {
+            root: "repo-main".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            tree.relative_path("other/skills/SKILL.md"),
+            "other/skills/SKILL.md"
+        );
+    }
+
+    #[test]
+    fn tree_from_dir_skips_git_and_node_modules() {
+        let dir = TempDir::new("test-dir").unwrap();
+        let root = dir.path().join("team-skills");
+        std::fs::create_dir_all(root.join("skills/demo")).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        std::fs::write(root.join("skills/demo/SKILL.md"), "demo").unwrap();
+        std::fs::write(root.join(".git/HEAD"), "ref").unwrap();
+        std::fs::write(root.join("node_modules/pkg/SKILL.md"), "dep").unwrap();
+
+        let tree = Source::Local(root).fetch().unwrap();
+        assert_eq!(tree.root(), "team-skills");
+        assert_eq!(
+            tree.list_files("SKILL.md"),
+            vec!["team-skills/skills/demo/SKILL.md"]
+        );
+        assert_eq!(tree.len(), 1);
+    }
+
+    #[test]
+    fn git_source_clones_local_remote() {
+        let dir = TempDir::new("test-git").unwrap();
+        let remote = dir.path().join("remote-skills");
+        std::fs::create_dir_all(remote.join("skills/demo")).unwrap();
+        std::fs::write(remote.join("skills/demo/SKILL.md"), "demo").unwrap();
+        for args in [
+            &["init", "--quiet", "--initial-branch", "main"][..],
+            &["add", "."],
+            &[
+                "-c",
+                "user.name=test",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "--quiet",
+                "-m",
+                "init",
+            ],
+        ] {
+            let mut cmd = Command::new("git");
+            cmd.arg("-C").arg(&remote).args(args);
+            if run_git(cmd).is_err() {
+                eprintln!("git unavailable, skipping");
+                return;
+            }
+        }
+
+        let source = Source::parse(&format!("git+file://{}#main", remote.display())).unwrap();
+        assert_eq!(source.name(), "remote-skills");
+        let tree = source.fetch().unwrap();
+        assert_eq!(tree.root(), "remote-skills");
+        assert_eq!(tree.read_file("skills/demo/SKILL.md").unwrap(), "demo");
+        assert_eq!(tree.revision().map(str::len), Some(40));
+    }
+
+    #[test]
+    fn local_tarball_is_unpacked() {
+        let dir = TempDir::new("test-tar").unwrap();
+        std::fs::create_dir_all(dir.path().join("pkg/agents")).unwrap();
+        std::fs::write(dir.path().join("pkg/agents/reviewer.md"), "agent").unwrap();
+        let tarball = dir.path().join("pkg.tar.gz");
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(&tarball)
+            .arg("-C")
+            .arg(dir.path())
+            .arg("pkg")
+            .status();
+        if !status.is_ok_and(|s| s.success()) {
+            eprintln!("tar unavailable, skipping");
+            return;
+        }
+
+        let tree = Source::Local(tarball).fetch().unwrap();
+        assert_eq!(tree.root(), "pkg");
+        assert_eq!(tree.list_files(".md"), vec!["pkg/agents/reviewer.md"]);
+    }
+
+    #[test]
+    fn local_missing_path_is_not_found() {
+        let result = Source::Local(PathBuf::from("/nonexistent/skills-locate")).fetch();
+        assert!(matches!(result, Err(Error::NotFound(_))));
+    }
+
+    #[test]
+    fn archive_format_detection() {
+        assert_eq!(ArchiveFormat::from_path("a.ZIP"), Some(ArchiveFormat::Zip));
+        assert_eq!(
+            ArchiveFormat::from_path("a.tar.gz"),
+            Some(ArchiveFormat::TarGz)
+        );
+        assert_eq!(ArchiveFormat::from_path("a.tar"), Some(ArchiveFormat::Tar));
+        assert_eq!(ArchiveFormat::from_path("a.txt"), None);
+    }
+}